@@ -1,13 +1,15 @@
-use std::fs;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use blake3::Hasher;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::config::config_directory;
 use crate::domain::branch::BranchCategory;
 use crate::domain::ticket::TicketDraft;
-use crate::error::{AppError, AppResult};
+use crate::error::AppResult;
+use crate::storage::{read_json_file, update_json_file};
 
 const CACHE_FILE_NAME: &str = "draft_cache.json";
 const CACHE_LIMIT: usize = 32;
@@ -24,35 +26,90 @@ struct CacheEntry {
     description: String,
     branch_category: String,
     branch_summary: String,
+    #[serde(default)]
+    acceptance_criteria: Vec<String>,
+    #[serde(default)]
+    estimate: Option<u32>,
+    #[serde(default)]
+    provider: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rationale: Option<String>,
+    /// Entries written before timestamps were recorded count as expired.
+    #[serde(default)]
+    created_at: Option<DateTime<Utc>>,
 }
 
+impl CacheEntry {
+    fn is_fresh(&self, ttl: Duration) -> bool {
+        self.created_at.is_some_and(|created| {
+            Utc::now()
+                .signed_duration_since(created)
+                .to_std()
+                .is_ok_and(|age| age <= ttl)
+        })
+    }
+}
+
+/// What `ugh cache list` shows about a stored draft.
+pub struct CachedDraftInfo {
+    pub key: String,
+    pub title: String,
+    pub provider: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub fresh: bool,
+}
+
+enum CacheOp {
+    Insert(CacheEntry),
+    Remove(String),
+    Clear,
+}
+
+impl CacheFile {
+    fn apply(&mut self, op: &CacheOp) {
+        match op {
+            CacheOp::Insert(entry) => {
+                self.entries.retain(|existing| existing.key != entry.key);
+                self.entries.push(entry.clone());
+                if self.entries.len() > CACHE_LIMIT {
+                    let overflow = self.entries.len() - CACHE_LIMIT;
+                    self.entries.drain(0..overflow);
+                }
+            }
+            CacheOp::Remove(key) => self.entries.retain(|entry| &entry.key != key),
+            CacheOp::Clear => self.entries.clear(),
+        }
+    }
+}
+
+/// Snapshot of the draft cache. Mutations are recorded and replayed onto the latest
+/// on-disk contents under a file lock when saved, so concurrent runs do not clobber
+/// each other.
 pub struct TicketDraftCache {
     file_path: PathBuf,
     file: CacheFile,
+    pending: Vec<CacheOp>,
 }
 
 impl TicketDraftCache {
     pub fn load() -> AppResult<Self> {
         let dir = config_directory()?;
         let path = dir.join(CACHE_FILE_NAME);
-        let file = match fs::read_to_string(&path) {
-            Ok(contents) => serde_json::from_str::<CacheFile>(&contents)
-                .map_err(|err| AppError::Configuration(format!("invalid cache file: {err}")))?,
-            Err(err) if err.kind() == std::io::ErrorKind::NotFound => CacheFile::default(),
-            Err(err) => return Err(AppError::Io(err)),
-        };
+        let file = read_json_file(&path, "cache")?;
 
         Ok(Self {
             file_path: path,
             file,
+            pending: Vec::new(),
         })
     }
 
-    pub fn get(&self, key: &str) -> Option<TicketDraft> {
+    /// The draft stored under `key`, unless it is older than `ttl`.
+    pub fn get(&self, key: &str, ttl: Duration) -> Option<TicketDraft> {
         self.file
             .entries
             .iter()
-            .find(|entry| entry.key == key)
+            .find(|entry| entry.key == key && entry.is_fresh(ttl))
             .map(|entry| {
                 let category = BranchCategory::from_str(&entry.branch_category)
                     .unwrap_or(BranchCategory::Feature);
@@ -61,38 +118,92 @@ impl TicketDraftCache {
                     description: entry.description.clone(),
                     branch_category: category,
                     branch_summary: entry.branch_summary.clone(),
+                    acceptance_criteria: entry.acceptance_criteria.clone(),
+                    estimate: entry.estimate,
+                    provider: entry.provider.clone(),
+                    rationale: entry.rationale.clone(),
                 }
             })
     }
 
     pub fn insert(&mut self, key: String, draft: &TicketDraft) {
-        self.file.entries.retain(|entry| entry.key != key);
-        self.file.entries.push(CacheEntry {
+        let op = CacheOp::Insert(CacheEntry {
             key,
             title: draft.title.clone(),
             description: draft.description.clone(),
             branch_category: draft.branch_category.as_str().to_string(),
             branch_summary: draft.branch_summary.clone(),
+            acceptance_criteria: draft.acceptance_criteria.clone(),
+            estimate: draft.estimate,
+            provider: draft.provider.clone(),
+            rationale: draft.rationale.clone(),
+            created_at: Some(Utc::now()),
         });
+        self.file.apply(&op);
+        self.pending.push(op);
+    }
 
-        if self.file.entries.len() > CACHE_LIMIT {
-            let overflow = self.file.entries.len() - CACHE_LIMIT;
-            self.file.entries.drain(0..overflow);
-        }
+    pub fn remove(&mut self, key: &str) -> bool {
+        let existed = self.file.entries.iter().any(|entry| entry.key == key);
+        let op = CacheOp::Remove(key.to_string());
+        self.file.apply(&op);
+        self.pending.push(op);
+        existed
     }
 
-    pub fn save(&self) -> AppResult<()> {
-        if let Some(parent) = self.file_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        let data = serde_json::to_string_pretty(&self.file)
-            .map_err(|err| AppError::Configuration(format!("failed to write cache: {err}")))?;
-        fs::write(&self.file_path, data)?;
+    pub fn clear(&mut self) -> usize {
+        let count = self.file.entries.len();
+        self.file.apply(&CacheOp::Clear);
+        self.pending.push(CacheOp::Clear);
+        count
+    }
+
+    /// Stored drafts, oldest first, with `fresh` judged against `ttl`.
+    pub fn entries(&self, ttl: Duration) -> Vec<CachedDraftInfo> {
+        self.file
+            .entries
+            .iter()
+            .map(|entry| CachedDraftInfo {
+                key: entry.key.clone(),
+                title: entry.title.clone(),
+                provider: entry.provider.clone(),
+                created_at: entry.created_at,
+                fresh: entry.is_fresh(ttl),
+            })
+            .collect()
+    }
+
+    /// Full keys starting with `prefix`, so short keys from `ugh cache list` can be used.
+    pub fn keys_matching(&self, prefix: &str) -> Vec<String> {
+        self.file
+            .entries
+            .iter()
+            .filter(|entry| entry.key.starts_with(prefix))
+            .map(|entry| entry.key.clone())
+            .collect()
+    }
+
+    pub fn save(&mut self) -> AppResult<()> {
+        let pending = std::mem::take(&mut self.pending);
+        self.file = update_json_file(&self.file_path, "cache", |file: &mut CacheFile| {
+            for op in &pending {
+                file.apply(op);
+            }
+        })?;
         Ok(())
     }
 
-    pub fn compute_key(summary: &str, files_changed: usize, board: Option<&str>) -> String {
+    /// `model` is the drafting model's fingerprint, so switching models or prompt
+    /// versions never serves a draft produced under the old configuration.
+    pub fn compute_key(
+        summary: &str,
+        files_changed: usize,
+        board: Option<&str>,
+        model: &str,
+    ) -> String {
         let mut hasher = Hasher::new();
+        hasher.update(model.as_bytes());
+        hasher.update(b"\0");
         hasher.update(summary.as_bytes());
         hasher.update(files_changed.to_string().as_bytes());
         if let Some(board) = board {
@@ -101,3 +212,36 @@ impl TicketDraftCache {
         hasher.finalize().to_hex().to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+
+    use super::*;
+
+    fn entry(created_at: Option<DateTime<Utc>>) -> CacheEntry {
+        CacheEntry {
+            key: "k".to_string(),
+            title: "Title".to_string(),
+            description: "Description".to_string(),
+            branch_category: "feature".to_string(),
+            branch_summary: "title".to_string(),
+            acceptance_criteria: Vec::new(),
+            estimate: None,
+            provider: None,
+            rationale: None,
+            created_at,
+        }
+    }
+
+    #[test]
+    fn entries_expire_after_ttl_and_untimestamped_ones_never_count() {
+        let ttl = Duration::from_secs(60 * 60);
+        assert!(entry(Some(Utc::now() - TimeDelta::minutes(30))).is_fresh(ttl));
+        assert!(!entry(Some(Utc::now() - TimeDelta::hours(2))).is_fresh(ttl));
+        assert!(!entry(None).is_fresh(ttl));
+
+        let key = |model| TicketDraftCache::compute_key("summary", 1, Some("PAY"), model);
+        assert_ne!(key("gemini:a:p1"), key("gemini:a:p2"));
+    }
+}
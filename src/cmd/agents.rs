@@ -0,0 +1,100 @@
+use clap::{Args, Subcommand};
+
+use crate::config::DraftingMode;
+use crate::context::AppContext;
+use crate::error::AppResult;
+
+#[derive(Args, Debug, Clone)]
+pub struct AgentsArgs {
+    #[command(subcommand)]
+    pub command: AgentsCommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum AgentsCommand {
+    /// List the agents in the order a ticket run calls them, as currently configured.
+    List,
+}
+
+pub fn run(ctx: &AppContext, args: AgentsArgs) -> AppResult<()> {
+    match args.command {
+        AgentsCommand::List => list(ctx),
+    }
+    Ok(())
+}
+
+fn list(ctx: &AppContext) {
+    let config = &ctx.config;
+    let mut agents: Vec<(String, String)> = Vec::new();
+
+    agents.push((
+        "changes".to_string(),
+        "reads the uncommitted changes from git and redacts secrets (skip with --no-redact)"
+            .to_string(),
+    ));
+    if config.drafting == DraftingMode::Template {
+        agents.push((
+            "template".to_string(),
+            "fills draft_template with change data; nothing generative runs".to_string(),
+        ));
+    } else if config.llm_disabled() {
+        agents.push((
+            "heuristic".to_string(),
+            "names and describes the work from the changed paths; no model is called".to_string(),
+        ));
+    } else {
+        let cache = if config.draft_cache_ttl.is_zero() {
+            "off".to_string()
+        } else {
+            format!("reused for {}h", config.draft_cache_ttl.as_secs() / 3600)
+        };
+        agents.push((
+            "cache".to_string(),
+            format!("reuses the draft for unchanged work ({cache})"),
+        ));
+        let providers: Vec<&str> = config
+            .llm_providers
+            .iter()
+            .map(|provider| provider.as_str())
+            .collect();
+        agents.push((
+            providers.join(", "),
+            "draft the title, description, branch, and acceptance criteria; providers \
+             are tried in order, then heuristics"
+                .to_string(),
+        ));
+    }
+    for step in ctx.workflow_steps.iter() {
+        agents.push((step.name().to_string(), step.description().to_string()));
+    }
+    let limits = ctx.issue_tracker.text_limits();
+    agents.push((
+        "limits".to_string(),
+        format!(
+            "fits the title to {} and the description to {} characters, attaching the rest",
+            limits.title_chars, limits.description_chars
+        ),
+    ));
+    let notifiers: Vec<&str> = ctx
+        .notifiers
+        .iter()
+        .map(|notifier| notifier.name())
+        .collect();
+    agents.push((
+        "notify".to_string(),
+        if notifiers.is_empty() {
+            "no Slack or Teams webhook configured".to_string()
+        } else {
+            format!("announces the ticket on {}", notifiers.join(" and "))
+        },
+    ));
+
+    let width = agents.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    println!("A ticket run passes through, in order:");
+    for (name, description) in agents {
+        println!("  {name:<width$}  {description}");
+    }
+    println!(
+        "\nRun `ugh ticket --explain` to see what each one changed, or -vv for their input and output."
+    );
+}
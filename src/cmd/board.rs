@@ -0,0 +1,95 @@
+use clap::{Args, Subcommand};
+
+use crate::context::AppContext;
+use crate::domain::board::Board;
+use crate::error::AppResult;
+
+#[derive(Args, Debug, Clone)]
+pub struct BoardArgs {
+    #[command(subcommand)]
+    pub command: BoardCommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum BoardCommand {
+    /// List projects you can file tickets on.
+    List(BoardListArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct BoardListArgs {
+    /// Only show boards whose key or name contains this text.
+    pub filter: Option<String>,
+    /// Only show boards of this kind (software, service_desk, business).
+    #[arg(long, value_name = "KIND")]
+    pub kind: Option<String>,
+}
+
+pub async fn run(ctx: &AppContext, args: BoardArgs) -> AppResult<()> {
+    match args.command {
+        BoardCommand::List(args) => list(ctx, args).await,
+    }
+}
+
+async fn list(ctx: &AppContext, args: BoardListArgs) -> AppResult<()> {
+    let filter = args
+        .filter
+        .as_deref()
+        .map(str::trim)
+        .filter(|f| !f.is_empty());
+    let boards: Vec<Board> = ctx
+        .issue_tracker
+        .list_boards(filter)
+        .await?
+        .into_iter()
+        .filter(|board| filter.is_none_or(|filter| board.matches(filter)))
+        .filter(|board| {
+            args.kind.as_deref().is_none_or(|kind| {
+                board
+                    .kind
+                    .as_deref()
+                    .is_some_and(|board_kind| board_kind.eq_ignore_ascii_case(kind))
+            })
+        })
+        .collect();
+
+    if boards.is_empty() {
+        println!("No boards match.");
+        return Ok(());
+    }
+
+    let key_width = boards
+        .iter()
+        .map(|board| board.key.len())
+        .max()
+        .unwrap_or(0);
+    for board in &boards {
+        let marker = if ctx.config.default_board.as_deref() == Some(board.key.as_str()) {
+            "*"
+        } else {
+            " "
+        };
+        let mut kind = board.kind.clone().unwrap_or_else(|| "unknown".to_string());
+        if board.team_managed {
+            kind.push_str(", team-managed");
+        }
+        if ctx.config.jira_service_desks.contains_key(&board.key) {
+            kind.push_str(", requests");
+        }
+        println!(
+            "{marker} {:<key_width$}  {}  ({kind})",
+            board.key, board.name
+        );
+        if !board.issue_types.is_empty() {
+            println!(
+                "  {:<key_width$}  issue types: {}",
+                "",
+                board.issue_types.join(", ")
+            );
+        }
+    }
+    if ctx.config.default_board.is_some() {
+        println!("\n* default board");
+    }
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use clap::Args;
+use tracing::warn;
+
+use crate::cmd::ticket::resolve_preset;
+use crate::context::AppContext;
+use crate::error::AppResult;
+use crate::workflow::scope::ChangeScope;
+use crate::workflow::ticket::{
+    CheckoutTarget, TicketWorkflowOptions, branch_name_for, prepare_ticket,
+};
+
+#[derive(Args, Debug, Clone)]
+pub struct BranchNameArgs {
+    /// Override the default board configured in the CLI.
+    #[arg(short, long)]
+    pub board: Option<String>,
+    /// Ticket key to put in the name; without it the key segment is left out.
+    #[arg(long, value_name = "KEY")]
+    pub key: Option<String>,
+    /// Use this preset's branch template.
+    #[arg(long, value_name = "NAME")]
+    pub preset: Option<String>,
+    /// Only consider changes under this directory (repeatable).
+    #[arg(long = "path", value_name = "DIR")]
+    pub paths: Vec<PathBuf>,
+    /// Send change details to the LLM without scrubbing secrets.
+    #[arg(long)]
+    pub no_redact: bool,
+}
+
+/// Drafts a ticket for the local changes and prints only the branch it would get. Nothing
+/// is filed or checked out; the draft is cached, so a following `ugh ticket` matches.
+pub async fn run(ctx: &AppContext, args: BranchNameArgs) -> AppResult<()> {
+    let repo_root = ctx.repo_root()?;
+    if args.no_redact {
+        warn!("secret redaction disabled; change details are sent to the LLM as-is.");
+    }
+    let options = TicketWorkflowOptions {
+        board_override: args.board,
+        redact: !args.no_redact,
+        epic: None,
+        assignee: None,
+        reporter: None,
+        due_date: None,
+        scope: ChangeScope::resolve(&ctx.config.working_dir, Some(repo_root), &args.paths),
+        custom_fields: BTreeMap::new(),
+        preset: resolve_preset(ctx, args.preset.as_deref())?,
+        placement: None,
+        checkout: CheckoutTarget::CurrentTree,
+        notify: false,
+    };
+
+    let prepared = prepare_ticket(ctx, &options).await?;
+    let branch = branch_name_for(
+        &options,
+        &prepared.draft,
+        args.key.as_deref().unwrap_or_default(),
+    )?;
+    println!("{}", branch.as_str());
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use chrono::{Local, Utc};
+use clap::{Args, Subcommand};
+
+use crate::cache::TicketDraftCache;
+use crate::config::AppConfig;
+use crate::error::{AppError, AppResult};
+
+/// Characters of a cache key shown by `list`; any unique prefix works for `rm`.
+const SHORT_KEY_LEN: usize = 12;
+
+#[derive(Args, Debug, Clone)]
+pub struct CacheArgs {
+    #[command(subcommand)]
+    pub command: CacheCommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum CacheCommand {
+    /// Show cached drafts, oldest first, and whether they are past the TTL.
+    List,
+    /// Remove every cached draft.
+    Clear,
+    /// Remove one cached draft by key (a unique prefix is enough).
+    Rm {
+        /// Key as printed by `ugh cache list`.
+        key: String,
+    },
+}
+
+pub fn run(config: &AppConfig, args: CacheArgs) -> AppResult<()> {
+    let mut cache = TicketDraftCache::load()?;
+    match args.command {
+        CacheCommand::List => {
+            let entries = cache.entries(config.draft_cache_ttl);
+            if entries.is_empty() {
+                println!("The draft cache is empty.");
+                return Ok(());
+            }
+            let ttl_hours = config.draft_cache_ttl.as_secs() / 3600;
+            println!("Drafts are reused for {ttl_hours}h (draft_cache_ttl_hours).");
+            for entry in entries {
+                let created = entry.created_at.map_or_else(
+                    || "unknown".to_string(),
+                    |created| {
+                        created
+                            .with_timezone(&Local)
+                            .format("%Y-%m-%d %H:%M")
+                            .to_string()
+                    },
+                );
+                let age = entry.created_at.map_or_else(String::new, |created| {
+                    format!(", {}h old", (Utc::now() - created).num_hours())
+                });
+                let status = if entry.fresh { "" } else { ", expired" };
+                println!(
+                    "{}  {created}  {}  ({}{age}{status})",
+                    &entry.key[..entry.key.len().min(SHORT_KEY_LEN)],
+                    entry.title,
+                    entry.provider.as_deref().unwrap_or("unknown"),
+                );
+            }
+        }
+        CacheCommand::Clear => {
+            let removed = cache.clear();
+            cache.save()?;
+            println!("Removed {removed} cached draft(s).");
+        }
+        CacheCommand::Rm { key } => {
+            let key = key.trim();
+            let matches = if key.is_empty() {
+                Vec::new()
+            } else {
+                cache.keys_matching(key)
+            };
+            match matches.as_slice() {
+                [full] => {
+                    cache.remove(full);
+                    cache.save()?;
+                    println!("Removed cached draft {full}.");
+                }
+                [] => {
+                    return Err(AppError::Configuration(format!(
+                        "no cached draft with key '{key}'"
+                    )));
+                }
+                _ => {
+                    return Err(AppError::Configuration(format!(
+                        "key '{key}' matches {} cached drafts; use more characters",
+                        matches.len()
+                    )));
+                }
+            }
+        }
+    }
+    Ok(())
+}
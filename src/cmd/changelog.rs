@@ -0,0 +1,59 @@
+use std::fs;
+use std::path::PathBuf;
+
+use clap::Args;
+use futures_util::stream::{self, StreamExt};
+use tracing::{info, warn};
+
+use crate::context::AppContext;
+use crate::domain::changelog::ChangelogFacts;
+use crate::error::AppResult;
+
+/// Tickets looked up in the tracker at the same time.
+const LOOKUP_CONCURRENCY: usize = 4;
+
+#[derive(Args, Debug, Clone)]
+pub struct ChangelogArgs {
+    /// Revision range to describe, e.g. `v1.2.0..HEAD` or `v1.1.0..v1.2.0`.
+    #[arg(value_name = "RANGE")]
+    pub range: String,
+    /// Write the Markdown to this file instead of printing it.
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+/// Groups the range's commits by ticket and category, looks the tickets up in the
+/// tracker, and has the language model write Markdown release notes.
+pub async fn run(ctx: &AppContext, args: ChangelogArgs) -> AppResult<()> {
+    let commits = ctx.version_control.commits_in_range(&args.range).await?;
+    info!(count = commits.len(), range = %args.range, "commits read");
+    let mut facts = ChangelogFacts::group(args.range.clone(), commits);
+
+    // The notes still work from commit subjects alone, so a ticket the tracker cannot
+    // find (deleted, another instance, a typo in a subject) only costs its title.
+    let lookups: Vec<_> = stream::iter(facts.ticket_keys())
+        .map(|key| async move {
+            let result = ctx.issue_tracker.get_ticket(&key).await;
+            (key, result)
+        })
+        .buffer_unordered(LOOKUP_CONCURRENCY)
+        .collect()
+        .await;
+    for (key, result) in lookups {
+        match result {
+            Ok(details) => facts.add_details(details),
+            Err(err) => warn!("could not look up {key} ({err}); using its commits instead."),
+        }
+    }
+
+    let notes = ctx.language_model.release_notes(&facts).await?;
+    let doc = facts.render(&notes);
+    match &args.output {
+        Some(path) => {
+            fs::write(path, &doc)?;
+            println!("Release notes written to {}.", path.display());
+        }
+        None => print!("{doc}"),
+    }
+    Ok(())
+}
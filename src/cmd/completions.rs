@@ -0,0 +1,29 @@
+use std::io::{self, Write};
+
+use clap::{Args, Command};
+use clap_complete::Shell;
+
+use crate::error::AppResult;
+
+#[derive(Args, Debug, Clone)]
+pub struct CompletionsArgs {
+    /// Shell to generate the completion script for.
+    #[arg(value_enum)]
+    pub shell: Shell,
+}
+
+/// Writes the completion script for `args.shell` to stdout.
+pub fn run(mut command: Command, args: CompletionsArgs) -> AppResult<()> {
+    let name = command.get_name().to_string();
+    // Rendered up front: the generator panics on write errors such as a closed pipe.
+    let mut script = Vec::new();
+    clap_complete::generate(args.shell, &mut command, name, &mut script);
+    io::stdout().write_all(&script)?;
+    Ok(())
+}
+
+/// Writes a roff man page for `command` to stdout, for packagers.
+pub fn man_page(command: Command) -> AppResult<()> {
+    clap_mangen::Man::new(command).render(&mut io::stdout())?;
+    Ok(())
+}
@@ -1,9 +1,22 @@
-use std::io::{self, Write};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use clap::{Args, Subcommand};
+use serde_json::Value;
 
-use crate::config::{StoredConfig, config_file_path};
-use crate::error::AppResult;
+use crate::cmd::login::{self, LoginArgs};
+use crate::config::{
+    DEFAULT_DRAFT_CACHE_TTL_HOURS, ENV_OVERRIDES, StoredConfig, TOML_CONFIG_FILE_NAME,
+    config_file_path,
+};
+use crate::config_file::{CONFIG_VERSION, ConfigFormat, setting_name};
+use crate::env_file::{self, VariableOrigin};
+use crate::error::{AppError, AppResult};
+use crate::repo_config::{REPO_CONFIG_FILE_NAME, RepoConfig};
+use crate::workflow::steps::StepRegistry;
 
 #[derive(Args, Debug, Clone)]
 pub struct ConfigArgs {
@@ -16,16 +29,179 @@ pub enum ConfigCommand {
     /// Run the interactive configuration wizard.
     Init,
     /// Show the stored configuration (secrets masked).
-    Show,
+    Show(ShowArgs),
+    /// Authenticate with a service through its browser login (OAuth).
+    Login(LoginArgs),
+    /// Change one stored setting, e.g. `ugh config set llm.temperature 0.2`.
+    Set(SetArgs),
+    /// Upgrade the config file to the current layout, optionally converting it to TOML.
+    Migrate(MigrateArgs),
 }
 
-pub fn run(command: ConfigCommand) -> AppResult<()> {
+#[derive(Args, Debug, Clone)]
+pub struct ShowArgs {
+    /// List every setting with its effective value and where it came from: an environment
+    /// variable, the repository's env file or `.ugh.toml`, the config file, or the default.
+    #[arg(long)]
+    pub sources: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct MigrateArgs {
+    /// Also convert `config.json` to `config.toml`.
+    #[arg(long)]
+    pub toml: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetArgs {
+    /// Setting name as in the config file; nested settings use dots (`llm.timeout_secs`).
+    pub key: String,
+    /// New value: JSON for numbers, booleans, and lists, plain text otherwise, or `-` to
+    /// clear the setting.
+    pub value: String,
+}
+
+static NON_INTERACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Turns every prompt into an error for the rest of the process (`--non-interactive`, `UGH_CI`).
+pub(crate) fn set_non_interactive(enabled: bool) {
+    NON_INTERACTIVE.store(enabled, Ordering::Relaxed);
+}
+
+pub(crate) fn is_non_interactive() -> bool {
+    NON_INTERACTIVE.load(Ordering::Relaxed)
+}
+
+/// Whether a question can be put to a person: prompts are enabled and stdin is a terminal.
+pub(crate) fn can_prompt() -> bool {
+    !is_non_interactive() && io::stdin().is_terminal()
+}
+
+fn ensure_interactive(question: &str) -> AppResult<()> {
+    if is_non_interactive() {
+        return Err(AppError::Configuration(format!(
+            "'{question}' needs an answer, but prompts are disabled (--non-interactive / UGH_CI)"
+        )));
+    }
+    Ok(())
+}
+
+pub async fn run(command: ConfigCommand) -> AppResult<()> {
     match command {
         ConfigCommand::Init => run_init(),
-        ConfigCommand::Show => run_show(),
+        ConfigCommand::Show(args) if args.sources => run_show_sources(),
+        ConfigCommand::Show(_) => run_show(),
+        ConfigCommand::Login(args) => login::run(args).await,
+        ConfigCommand::Set(args) => run_set(args),
+        ConfigCommand::Migrate(args) => run_migrate(args),
     }
 }
 
+fn run_migrate(args: MigrateArgs) -> AppResult<()> {
+    let path = config_file_path()?;
+    let Some((stored, version)) = StoredConfig::read(&path)? else {
+        println!("No configuration file at {} yet.", path.display());
+        return Ok(());
+    };
+    if version < CONFIG_VERSION {
+        let backup = stored.upgrade_file(&path, version)?;
+        println!(
+            "Upgraded {} from layout version {version} to {CONFIG_VERSION}; the old file is \
+             kept as {}.",
+            path.display(),
+            backup.display()
+        );
+    } else {
+        println!(
+            "{} already uses layout version {CONFIG_VERSION}.",
+            path.display()
+        );
+    }
+
+    if args.toml && ConfigFormat::of(&path) == ConfigFormat::Json {
+        let target = path.with_file_name(TOML_CONFIG_FILE_NAME);
+        stored.save_to(&target)?;
+        let mut backup = path.as_os_str().to_owned();
+        backup.push(".bak");
+        std::fs::rename(&path, &backup)?;
+        println!(
+            "Converted it to {}; the JSON file is kept as {}.",
+            target.display(),
+            std::path::Path::new(&backup).display()
+        );
+    }
+    Ok(())
+}
+
+fn run_set(args: SetArgs) -> AppResult<()> {
+    let updated = set_setting(&StoredConfig::load()?, &args.key, &args.value)?;
+    updated.llm.unwrap_or_default().validate()?;
+    updated.rate_limit.clone().unwrap_or_default().validate()?;
+    updated.save()?;
+    println!("Updated {} in {}.", args.key, config_file_path()?.display());
+    Ok(())
+}
+
+/// `stored` with the dotted `key` set to `raw`, read as JSON when it parses as a value of the
+/// setting's type and as text otherwise; `-` clears it.
+fn set_setting(stored: &StoredConfig, key: &str, raw: &str) -> AppResult<StoredConfig> {
+    let name = setting_name(key.trim());
+    let path: Vec<&str> = name.split('.').map(str::trim).collect();
+    if path.iter().any(|part| part.is_empty()) {
+        return Err(AppError::Configuration(format!(
+            "invalid setting name '{key}'"
+        )));
+    }
+    let candidates = if raw.trim() == "-" {
+        vec![Value::Null]
+    } else {
+        let text = Value::String(raw.to_string());
+        match serde_json::from_str::<Value>(raw) {
+            Ok(value) if value != text => vec![value, text],
+            _ => vec![text],
+        }
+    };
+
+    let mut last_error = None;
+    for value in candidates {
+        let mut root = serde_json::to_value(stored)
+            .map_err(|err| AppError::Configuration(format!("failed to serialize config: {err}")))?;
+        let mut slot = &mut root;
+        for part in &path {
+            if slot.is_null() {
+                *slot = Value::Object(Default::default());
+            }
+            let Value::Object(map) = slot else {
+                return Err(AppError::Configuration(format!("unknown setting '{key}'")));
+            };
+            slot = map.entry(part.to_string()).or_insert(Value::Null);
+        }
+        *slot = value;
+        match serde_json::from_value::<StoredConfig>(root) {
+            Ok(updated) => {
+                // Unknown names are dropped when a known table is read back, and set
+                // aside in the unknown settings of a section, so look for the key where
+                // ugh reads it.
+                let written = serde_json::to_value(&updated).unwrap_or_default();
+                let dotted = path.join(".");
+                let is_unknown = updated.unknown_settings().iter().any(|unknown| {
+                    dotted == *unknown || dotted.starts_with(&format!("{unknown}."))
+                });
+                if is_unknown || written.pointer(&format!("/{}", path.join("/"))).is_none() {
+                    return Err(AppError::Configuration(format!("unknown setting '{key}'")));
+                }
+                return Ok(updated);
+            }
+            Err(err) => last_error = Some(err),
+        }
+    }
+    Err(AppError::Configuration(format!(
+        "invalid value for {key}: {}",
+        last_error.map(|err| err.to_string()).unwrap_or_default()
+    )))
+}
+
 fn run_init() -> AppResult<()> {
     let mut cfg = StoredConfig::load()?;
 
@@ -36,21 +212,38 @@ fn run_init() -> AppResult<()> {
 
     apply_prompt(
         "Jira base URL (e.g., https://company.atlassian.net)",
-        &mut cfg.jira_base_url,
+        &mut cfg.jira.base_url,
         false,
     )?;
-    apply_prompt("Jira email", &mut cfg.jira_email, false)?;
-    apply_prompt("Jira API token", &mut cfg.jira_token, true)?;
+    apply_prompt("Jira email", &mut cfg.jira.email, false)?;
+    apply_prompt("Jira API token", &mut cfg.jira.token, true)?;
     apply_prompt(
         "Default Jira board/project key",
-        &mut cfg.default_board,
+        &mut cfg.behavior.default_board,
+        false,
+    )?;
+    apply_prompt("Default Jira issue type", &mut cfg.jira.issue_type, false)?;
+
+    apply_prompt(
+        "LLM provider (gemini/ollama)",
+        &mut cfg.behavior.llm_provider,
         false,
     )?;
-    apply_prompt("Default Jira issue type", &mut cfg.jira_issue_type, false)?;
+    apply_list_prompt(
+        "LLM provider fallback order (comma-separated, e.g. ollama,gemini)",
+        &mut cfg.behavior.llm_providers,
+    )?;
+    apply_prompt("Gemini API key", &mut cfg.gemini.api_key, true)?;
+    apply_prompt("Gemini model", &mut cfg.gemini.model, false)?;
+    apply_prompt("Ollama base URL", &mut cfg.ollama.base_url, false)?;
+    apply_prompt("Ollama model", &mut cfg.ollama.model, false)?;
 
-    apply_prompt("LLM provider (gemini/custom)", &mut cfg.llm_provider, false)?;
-    apply_prompt("Gemini API key", &mut cfg.gemini_api_key, true)?;
-    apply_prompt("Gemini model", &mut cfg.gemini_model, false)?;
+    apply_prompt("HTTP proxy URL (optional)", &mut cfg.http.proxy, false)?;
+    apply_prompt(
+        "Custom CA bundle path (optional, PEM)",
+        &mut cfg.http.ca_bundle,
+        false,
+    )?;
 
     cfg.save()?;
 
@@ -64,22 +257,334 @@ fn run_show() -> AppResult<()> {
     let path = config_file_path()?;
 
     println!("Configuration file: {}", path.display());
-    println!("Jira base URL: {}", display_value(&cfg.jira_base_url));
-    println!("Jira email: {}", display_value(&cfg.jira_email));
-    println!("Jira API token: {}", mask_secret(&cfg.jira_token));
-    println!("Default board: {}", display_value(&cfg.default_board));
+    println!(
+        "Issue tracker: {}",
+        cfg.behavior.tracker.as_deref().unwrap_or("jira")
+    );
+    println!("Jira base URL: {}", display_value(&cfg.jira.base_url));
+    println!("Jira email: {}", display_value(&cfg.jira.email));
+    println!("Jira API token: {}", mask_secret(&cfg.jira.token));
+    println!(
+        "Jira authentication: {}",
+        cfg.jira.auth.as_deref().unwrap_or("token")
+    );
+    println!(
+        "Jira OAuth client ID: {}",
+        display_value(&cfg.jira.oauth_client_id)
+    );
+    println!(
+        "Jira OAuth client secret: {}",
+        mask_secret(&cfg.jira.oauth_client_secret)
+    );
+    let azure = cfg.azure_devops.clone().unwrap_or_default();
+    println!(
+        "Azure DevOps organization: {}",
+        display_value(&azure.organization)
+    );
+    println!("Azure DevOps project: {}", display_value(&azure.project));
+    println!(
+        "Azure DevOps work item type: {}",
+        display_value(&azure.work_item_type)
+    );
+    println!("Azure DevOps PAT: {}", mask_secret(&azure.pat));
+    println!(
+        "Default board: {}",
+        display_value(&cfg.behavior.default_board)
+    );
     println!(
         "Default issue type: {}",
-        display_value(&cfg.jira_issue_type)
+        display_value(&cfg.jira.issue_type)
+    );
+    match cfg
+        .jira
+        .service_desks
+        .as_ref()
+        .filter(|desks| !desks.is_empty())
+    {
+        Some(desks) => {
+            println!("Service desk boards:");
+            for (board, desk) in desks {
+                println!(
+                    "  {board}: request type '{}'{}{}",
+                    desk.request_type,
+                    desk.service_desk_id
+                        .as_deref()
+                        .map(|id| format!(", service desk {id}"))
+                        .unwrap_or_default(),
+                    if desk.customer_visible {
+                        ""
+                    } else {
+                        ", internal description"
+                    }
+                );
+            }
+        }
+        None => println!("Service desk boards: <not set>"),
+    }
+    match cfg
+        .jira
+        .custom_fields
+        .as_ref()
+        .filter(|fields| !fields.is_empty())
+    {
+        Some(fields) => {
+            println!("Custom fields:");
+            for (field, value) in fields {
+                println!("  {field}: {value}");
+            }
+        }
+        None => println!("Custom fields: <not set>"),
+    }
+    println!(
+        "Story points field: {} (scale: {})",
+        display_value(&cfg.jira.story_points_field),
+        cfg.behavior
+            .estimate_scale
+            .as_deref()
+            .unwrap_or("fibonacci")
+    );
+    println!(
+        "Acceptance criteria field: {}",
+        cfg.jira
+            .acceptance_criteria_field
+            .as_deref()
+            .filter(|field| !field.is_empty())
+            .unwrap_or("<description>")
+    );
+    match cfg
+        .behavior
+        .path_boards
+        .as_ref()
+        .filter(|boards| !boards.is_empty())
+    {
+        Some(boards) => {
+            println!("Boards by path:");
+            for (prefix, board) in boards {
+                println!("  {prefix}: {board}");
+            }
+        }
+        None => println!("Boards by path: <not set>"),
+    }
+    match cfg.presets.as_ref().filter(|presets| !presets.is_empty()) {
+        Some(presets) => {
+            println!("Presets:");
+            for (name, preset) in presets {
+                let mut parts = Vec::new();
+                if let Some(issue_type) = &preset.issue_type {
+                    parts.push(format!("type {issue_type}"));
+                }
+                if let Some(priority) = &preset.priority {
+                    parts.push(format!("priority {priority}"));
+                }
+                if !preset.labels.is_empty() {
+                    parts.push(format!("labels {}", preset.labels.join(",")));
+                }
+                if let Some(template) = &preset.branch_template {
+                    parts.push(format!("branch {template}"));
+                }
+                if let Some(base) = &preset.base_branch {
+                    parts.push(format!("from {base}"));
+                }
+                println!("  {name}: {}", parts.join("; "));
+            }
+        }
+        None => println!("Presets: <not set>"),
+    }
+    match &cfg.ticket_templates {
+        Some(templates) if !templates.is_empty() => {
+            println!("Ticket templates:");
+            for template in templates {
+                let mut parts = vec![format!("sections {}", template.sections.join(" / "))];
+                if !template.labels.is_empty() {
+                    parts.push(format!("labels {}", template.labels.join(", ")));
+                }
+                if !template.components.is_empty() {
+                    parts.push(format!("components {}", template.components.join(", ")));
+                }
+                println!(
+                    "  {} {}: {}",
+                    template.board.as_deref().unwrap_or("any board"),
+                    template.issue_type.as_deref().unwrap_or("any type"),
+                    parts.join("; ")
+                );
+            }
+        }
+        _ => println!("Ticket templates: <not set>"),
+    }
+    println!("Drafting mode: {}", display_value(&cfg.behavior.drafting));
+    println!("Git backend: {}", display_value(&cfg.git.backend));
+    if let Some(template) = &cfg.draft_template {
+        println!("Draft title template: {}", template.title);
+        println!(
+            "Draft branch template: {}/{}",
+            template.branch_category, template.branch_summary
+        );
+    }
+    println!(
+        "Workflow steps: {} (available: {})",
+        display_value(
+            &cfg.behavior
+                .workflow_steps
+                .as_ref()
+                .map(|steps| steps.join(", "))
+        ),
+        StepRegistry::builtin()
+            .names()
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    println!(
+        "Slack webhook: {}",
+        mask_secret(&cfg.notifications.slack_webhook_url)
+    );
+    println!(
+        "Teams webhook: {}",
+        mask_secret(&cfg.notifications.teams_webhook_url)
+    );
+    println!("GitHub token: {}", mask_secret(&cfg.git.github_token));
+    println!(
+        "Ticket language: {}",
+        display_value(&cfg.behavior.ticket_language)
+    );
+    println!(
+        "Workspace repositories: {}",
+        display_value(
+            &cfg.git
+                .workspace_repos
+                .as_ref()
+                .map(|repos| repos.join(", "))
+        )
+    );
+    println!(
+        "LLM provider: {}",
+        display_value(&cfg.behavior.llm_provider)
+    );
+    println!(
+        "LLM provider fallback order: {}",
+        display_value(
+            &cfg.behavior
+                .llm_providers
+                .as_ref()
+                .map(|list| list.join(", "))
+        )
+    );
+    println!("Gemini API key: {}", mask_secret(&cfg.gemini.api_key));
+    println!("Gemini model: {}", display_value(&cfg.gemini.model));
+    println!("Ollama base URL: {}", display_value(&cfg.ollama.base_url));
+    println!("Ollama model: {}", display_value(&cfg.ollama.model));
+    let llm = cfg.llm.unwrap_or_default();
+    println!(
+        "LLM temperature: {}",
+        display_value(&llm.temperature.map(|value| value.to_string()))
+    );
+    println!(
+        "LLM top_p: {}",
+        display_value(&llm.top_p.map(|value| value.to_string()))
+    );
+    println!(
+        "LLM max output tokens: {}",
+        display_value(&llm.max_output_tokens.map(|value| value.to_string()))
+    );
+    println!(
+        "LLM timeout (seconds): {}",
+        display_value(&llm.timeout_secs.map(|value| value.to_string()))
+    );
+    println!(
+        "LLM streaming: {}",
+        display_value(&llm.stream.map(|value| value.to_string()))
+    );
+    println!(
+        "HTTP proxy: {}",
+        display_value(&cfg.http.proxy.as_deref().map(without_userinfo))
+    );
+    println!("CA bundle: {}", display_value(&cfg.http.ca_bundle));
+    println!(
+        "HTTP max attempts: {}",
+        display_value(&cfg.http.max_attempts.map(|value| value.to_string()))
+    );
+    println!(
+        "HTTP response cache: {}",
+        match cfg.http.cache {
+            Some(false) => "off",
+            _ => "on",
+        }
+    );
+    let gateway = cfg.gateway.clone().unwrap_or_default();
+    println!(
+        "Gateway hosts: {}",
+        display_value(&(!gateway.hosts.is_empty()).then(|| gateway.hosts.join(", ")))
+    );
+    println!(
+        "Gateway headers: {}",
+        display_value(&(!gateway.headers.is_empty()).then(|| {
+            gateway
+                .headers
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ")
+        }))
+    );
+    println!(
+        "Gateway signature header: {}",
+        display_value(&gateway.signature_header)
+    );
+    println!(
+        "Gateway signing secret: {}",
+        mask_secret(&gateway.signing_secret)
+    );
+    let rate_limit = cfg.rate_limit.clone().unwrap_or_default();
+    println!(
+        "Rate limit (req/s per host): {}",
+        display_value(
+            &rate_limit
+                .requests_per_second
+                .map(|value| value.to_string())
+        )
+    );
+    println!(
+        "Rate limit concurrency per host: {}",
+        display_value(&rate_limit.max_concurrent.map(|value| value.to_string()))
+    );
+    println!(
+        "Rate limit host overrides: {}",
+        display_value(&(!rate_limit.hosts.is_empty()).then(|| {
+            rate_limit
+                .hosts
+                .iter()
+                .map(|(host, per_second)| format!("{host}={per_second}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        }))
+    );
+    println!(
+        "Draft cache TTL (hours): {}",
+        display_value(
+            &cfg.behavior
+                .draft_cache_ttl_hours
+                .map(|value| value.to_string())
+                .or_else(|| Some(format!("{DEFAULT_DRAFT_CACHE_TTL_HOURS} (default)")))
+        )
+    );
+    println!(
+        "Default due dates: {}",
+        display_value(&cfg.behavior.due_lead_days.as_ref().map(|lead_days| {
+            lead_days
+                .iter()
+                .map(|(category, days)| format!("{category} +{days}d"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        }))
     );
-    println!("LLM provider: {}", display_value(&cfg.llm_provider));
-    println!("Gemini API key: {}", mask_secret(&cfg.gemini_api_key));
-    println!("Gemini model: {}", display_value(&cfg.gemini_model));
 
     Ok(())
 }
 
-fn apply_prompt(field: &str, target: &mut Option<String>, secret: bool) -> AppResult<()> {
+pub(crate) fn apply_prompt(
+    field: &str,
+    target: &mut Option<String>,
+    secret: bool,
+) -> AppResult<()> {
     match prompt(field, target.as_deref(), secret)? {
         PromptAction::Keep => {}
         PromptAction::Clear => *target = None,
@@ -88,7 +593,25 @@ fn apply_prompt(field: &str, target: &mut Option<String>, secret: bool) -> AppRe
     Ok(())
 }
 
+pub(crate) fn apply_list_prompt(field: &str, target: &mut Option<Vec<String>>) -> AppResult<()> {
+    let current = target.as_ref().map(|list| list.join(","));
+    match prompt(field, current.as_deref(), false)? {
+        PromptAction::Keep => {}
+        PromptAction::Clear => *target = None,
+        PromptAction::Set(value) => {
+            let items = value
+                .split(',')
+                .map(|item| item.trim().to_lowercase())
+                .filter(|item| !item.is_empty())
+                .collect::<Vec<_>>();
+            *target = if items.is_empty() { None } else { Some(items) };
+        }
+    }
+    Ok(())
+}
+
 fn prompt(field: &str, current: Option<&str>, secret: bool) -> AppResult<PromptAction> {
+    ensure_interactive(field)?;
     let mut stdout = io::stdout();
 
     match (current, secret) {
@@ -113,6 +636,259 @@ fn prompt(field: &str, current: Option<&str>, secret: bool) -> AppResult<PromptA
     }
 }
 
+/// Asks a yes/no question; an empty answer picks `default`.
+pub(crate) fn confirm(question: &str, default: bool) -> AppResult<bool> {
+    ensure_interactive(question)?;
+    let mut stdout = io::stdout();
+    let hint = if default { "[Y/n]" } else { "[y/N]" };
+    write!(stdout, "{question} {hint} ")?;
+    stdout.flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(match input.trim().to_lowercase().as_str() {
+        "" => default,
+        "y" | "yes" => true,
+        _ => false,
+    })
+}
+
+/// Lists `options` numbered from 1 and returns the index of the chosen one.
+pub(crate) fn choose(question: &str, options: &[String]) -> AppResult<usize> {
+    ensure_interactive(question)?;
+    let mut stdout = io::stdout();
+    writeln!(stdout, "{question}")?;
+    for (index, option) in options.iter().enumerate() {
+        writeln!(stdout, "  {}) {option}", index + 1)?;
+    }
+
+    loop {
+        write!(stdout, "Choose 1-{}: ", options.len())?;
+        stdout.flush()?;
+
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input)? == 0 {
+            return Err(AppError::Configuration("no selection made".to_string()));
+        }
+        match input.trim().parse::<usize>() {
+            Ok(choice) if (1..=options.len()).contains(&choice) => return Ok(choice - 1),
+            _ => writeln!(
+                stdout,
+                "Please enter a number between 1 and {}.",
+                options.len()
+            )?,
+        }
+    }
+}
+
+/// Reads a selection such as `1,3`, `2-4`, `all`, or `none` for items numbered from 1.
+pub(crate) fn choose_many(question: &str, count: usize) -> AppResult<Vec<usize>> {
+    ensure_interactive(question)?;
+    let mut stdout = io::stdout();
+    loop {
+        write!(
+            stdout,
+            "{question} (e.g. 1,3 or 1-{count}; Enter for all, 'none' to skip): "
+        )?;
+        stdout.flush()?;
+
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input)? == 0 {
+            return Ok(Vec::new());
+        }
+        match parse_selection(input.trim(), count) {
+            Some(selection) => return Ok(selection),
+            None => writeln!(stdout, "Please list numbers between 1 and {count}.")?,
+        }
+    }
+}
+
+fn parse_selection(input: &str, count: usize) -> Option<Vec<usize>> {
+    match input.to_lowercase().as_str() {
+        "" | "all" | "a" => return Some((0..count).collect()),
+        "none" | "n" => return Some(Vec::new()),
+        _ => {}
+    }
+
+    let mut selection = Vec::new();
+    for part in input
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+    {
+        let (start, end) = match part.split_once('-') {
+            Some((start, end)) => (start.trim().parse().ok()?, end.trim().parse().ok()?),
+            None => {
+                let single: usize = part.parse().ok()?;
+                (single, single)
+            }
+        };
+        if start == 0 || end > count || start > end {
+            return None;
+        }
+        selection.extend((start - 1)..end);
+    }
+    selection.sort_unstable();
+    selection.dedup();
+    Some(selection)
+}
+
+/// Where an effective setting came from, most specific first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SettingSource {
+    Environment(&'static str),
+    EnvFile(&'static str, PathBuf),
+    RepoConfig,
+    ConfigFile,
+    Default,
+}
+
+impl fmt::Display for SettingSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingSource::Environment(variable) => write!(f, "env {variable}"),
+            SettingSource::EnvFile(variable, path) => {
+                write!(f, "{variable} in {}", path.display())
+            }
+            SettingSource::RepoConfig => write!(f, "repo {REPO_CONFIG_FILE_NAME}"),
+            SettingSource::ConfigFile => write!(f, "config file"),
+            SettingSource::Default => write!(f, "default"),
+        }
+    }
+}
+
+fn run_show_sources() -> AppResult<()> {
+    let stored = StoredConfig::load()?;
+    let repo_config = match env_file::repo_root(&std::env::current_dir()?) {
+        Some(root) => RepoConfig::load_approved(&root)?,
+        None => RepoConfig::default(),
+    };
+    let settings = setting_sources(&stored, &repo_config, |variable| {
+        Some((env_file::var(variable)?, env_file::origin(variable)?))
+    })?;
+
+    println!("Configuration file: {}", config_file_path()?.display());
+    let width = settings
+        .iter()
+        .map(|(name, _, _)| name.len())
+        .max()
+        .unwrap_or_default();
+    for (name, value, source) in settings {
+        println!("{name:<width$}  {value}  ({source})");
+    }
+    Ok(())
+}
+
+/// Every setting under its sectioned name, with its effective value (secrets masked) and
+/// source: those with an environment variable first, then the rest of the config file.
+/// `env` looks a variable up in the environment and env files.
+fn setting_sources(
+    stored: &StoredConfig,
+    repo_config: &RepoConfig,
+    env: impl Fn(&str) -> Option<(String, VariableOrigin)>,
+) -> AppResult<Vec<(String, String, SettingSource)>> {
+    // Through text, so `f32` settings show their short form.
+    let root: Value = serde_json::to_string(stored)
+        .and_then(|json| serde_json::from_str(&json))
+        .map_err(|err| AppError::Configuration(format!("failed to serialize config: {err}")))?;
+    let mut leaves = BTreeMap::new();
+    collect_leaves(String::new(), &root, &mut leaves);
+    // Keys ugh does not know are kept in the file but set nothing.
+    let unknown = stored.unknown_settings();
+    leaves.retain(|name, _| {
+        !unknown
+            .iter()
+            .any(|key| name == key || name.starts_with(&format!("{key}.")))
+    });
+
+    let mut names: Vec<String> = ENV_OVERRIDES
+        .iter()
+        .map(|(name, _)| name.to_string())
+        .collect();
+    for name in leaves.keys() {
+        // An unset table is covered by the entries of its settings that have variables.
+        let covered = ENV_OVERRIDES
+            .iter()
+            .any(|(overridden, _)| overridden.starts_with(&format!("{name}.")));
+        if !covered && !names.contains(name) {
+            names.push(name.clone());
+        }
+    }
+
+    let notify = &repo_config.notify;
+    let notifications_off = !notify.enabled.unwrap_or(true);
+    let mut settings = Vec::new();
+    for name in names {
+        let variable = ENV_OVERRIDES
+            .iter()
+            .find(|(overridden, _)| *overridden == name)
+            .map(|(_, variable)| *variable);
+        let repo_value = match name.as_str() {
+            "notifications.slack_webhook_url" => notify.slack_webhook_url.clone(),
+            "notifications.teams_webhook_url" => notify.teams_webhook_url.clone(),
+            _ => None,
+        };
+        let stored_value = leaves
+            .get(&name)
+            .filter(|value| !value.is_null())
+            .map(|value| match value {
+                Value::String(text) => text.clone(),
+                other => other.to_string(),
+            });
+        let env_value = variable.and_then(|variable| Some((variable, env(variable)?)));
+        let (value, source) = if let Some(value) = repo_value {
+            (Some(value), SettingSource::RepoConfig)
+        } else if let Some((variable, (value, origin))) = env_value {
+            let source = match origin {
+                VariableOrigin::Environment => SettingSource::Environment(variable),
+                VariableOrigin::File(path) => SettingSource::EnvFile(variable, path),
+            };
+            (Some(value), source)
+        } else if let Some(value) = stored_value {
+            (Some(value), SettingSource::ConfigFile)
+        } else {
+            (None, SettingSource::Default)
+        };
+
+        let last = name.rsplit('.').next().unwrap_or_default();
+        let is_secret = name.starts_with("gateway.headers.")
+            || ["token", "secret", "api_key", "pat", "webhook_url"]
+                .iter()
+                .any(|suffix| last.ends_with(suffix));
+        let mut shown = if is_secret {
+            mask_secret(&value)
+        } else if name == "http.proxy" {
+            display_value(&value.as_deref().map(without_userinfo))
+        } else {
+            display_value(&value)
+        };
+        if notifications_off && name.ends_with("webhook_url") {
+            shown.push_str(&format!(", off in {REPO_CONFIG_FILE_NAME}"));
+        }
+        settings.push((name, shown, source));
+    }
+    Ok(settings)
+}
+
+/// Flattens `value` into dotted names of its scalars, lists, empty tables, and nulls.
+fn collect_leaves(prefix: String, value: &Value, leaves: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                let name = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                collect_leaves(name, value, leaves);
+            }
+        }
+        _ => {
+            leaves.insert(prefix, value.clone());
+        }
+    }
+}
+
 fn display_value(value: &Option<String>) -> String {
     value
         .as_deref()
@@ -122,10 +898,11 @@ fn display_value(value: &Option<String>) -> String {
 }
 
 fn mask_secret(value: &Option<String>) -> String {
+    let length = value.as_deref().map_or(0, |token| token.chars().count());
     match value {
-        Some(token) if token.len() > 6 => {
-            let prefix = &token[..3];
-            let suffix = &token[token.len() - 3..];
+        Some(token) if length > 6 => {
+            let prefix: String = token.chars().take(3).collect();
+            let suffix: String = token.chars().skip(length - 3).collect();
             format!("{prefix}***{suffix}")
         }
         Some(token) if !token.is_empty() => "***".to_string(),
@@ -133,8 +910,140 @@ fn mask_secret(value: &Option<String>) -> String {
     }
 }
 
+/// `url` with the `user:password@` before its host masked, e.g. `http://***@proxy:8080`.
+fn without_userinfo(url: &str) -> String {
+    let (scheme, rest) = match url.split_once("://") {
+        Some((scheme, rest)) => (format!("{scheme}://"), rest),
+        None => (String::new(), url),
+    };
+    let authority = &rest[..rest.find('/').unwrap_or(rest.len())];
+    match authority.rfind('@') {
+        Some(at) => format!("{scheme}***{}", &rest[at..]),
+        None => url.to_string(),
+    }
+}
+
 enum PromptAction {
     Keep,
     Clear,
     Set(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{BehaviorSection, GeminiSection, JiraSection};
+
+    #[test]
+    fn sets_nested_settings_by_dotted_name() {
+        let stored = StoredConfig::default();
+        let updated = set_setting(&stored, "llm.temperature", "0.2").unwrap();
+        assert_eq!(updated.llm.unwrap().temperature, Some(0.2));
+        let updated = set_setting(&updated, "default_board", "123").unwrap();
+        assert_eq!(updated.behavior.default_board.as_deref(), Some("123"));
+        let updated = set_setting(&updated, "behavior.default_board", "-").unwrap();
+        assert_eq!(updated.behavior.default_board, None);
+        assert!(set_setting(&stored, "llm.temprature", "0.2").is_err());
+        assert!(set_setting(&stored, "llm.max_output_tokens", "many").is_err());
+        assert!(set_setting(&stored, "default_bord", "PAY").is_err());
+        assert!(set_setting(&stored, "jira.base_urls", "https://acme.atlassian.net").is_err());
+        let updated = set_setting(&stored, "jira.base_url", "https://acme.atlassian.net").unwrap();
+        assert_eq!(
+            updated.jira.base_url.as_deref(),
+            Some("https://acme.atlassian.net")
+        );
+        let updated = set_setting(&updated, "http_max_attempts", "5").unwrap();
+        assert_eq!(updated.http.max_attempts, Some(5));
+        assert!(set_setting(&updated, "http.cache", "sometimes").is_err());
+    }
+
+    #[test]
+    fn reports_where_each_setting_comes_from() {
+        let stored = StoredConfig {
+            jira: JiraSection {
+                token: Some("secret-token".to_string()),
+                ..JiraSection::default()
+            },
+            gemini: GeminiSection {
+                model: Some("gemini-2.5-pro".to_string()),
+                ..GeminiSection::default()
+            },
+            behavior: BehaviorSection {
+                default_board: Some("OLD".to_string()),
+                path_boards: Some(BTreeMap::from([(
+                    "services/billing".to_string(),
+                    "BILL".to_string(),
+                )])),
+                ..BehaviorSection::default()
+            },
+            ..StoredConfig::default()
+        };
+        let repo_config: RepoConfig =
+            toml::from_str("[notify]\nslack_webhook_url = \"https://hooks.slack.com/x\"\n")
+                .unwrap();
+        let settings = setting_sources(&stored, &repo_config, |variable| {
+            (variable == "UGH_JIRA_DEFAULT_BOARD").then(|| {
+                (
+                    "NEW".to_string(),
+                    VariableOrigin::File(PathBuf::from("/repo/.ugh.env")),
+                )
+            })
+        })
+        .unwrap();
+        let find = |name: &str| {
+            settings
+                .iter()
+                .find(|(setting, _, _)| setting == name)
+                .map(|(_, value, source)| (value.as_str(), source.clone()))
+                .unwrap()
+        };
+
+        assert_eq!(
+            find("behavior.default_board"),
+            (
+                "NEW",
+                SettingSource::EnvFile("UGH_JIRA_DEFAULT_BOARD", PathBuf::from("/repo/.ugh.env"))
+            )
+        );
+        assert_eq!(
+            find("gemini.model"),
+            ("gemini-2.5-pro", SettingSource::ConfigFile)
+        );
+        assert_eq!(find("jira.token"), ("sec***ken", SettingSource::ConfigFile));
+        assert_eq!(
+            find("notifications.slack_webhook_url").1,
+            SettingSource::RepoConfig
+        );
+        assert_eq!(find("ollama.model"), ("<not set>", SettingSource::Default));
+        assert_eq!(
+            find("behavior.path_boards.services/billing"),
+            ("BILL", SettingSource::ConfigFile)
+        );
+        assert_eq!(find("presets"), ("<not set>", SettingSource::Default));
+    }
+
+    #[test]
+    fn masks_secrets_and_proxy_credentials() {
+        assert_eq!(mask_secret(&Some("secret-token".to_string())), "sec***ken");
+        assert_eq!(mask_secret(&Some("pässwörtchen".to_string())), "päs***hen");
+        assert_eq!(mask_secret(&Some("äöü".to_string())), "***");
+        assert_eq!(
+            without_userinfo("http://me:p@ss@proxy.corp:8080/path@x"),
+            "http://***@proxy.corp:8080/path@x"
+        );
+        assert_eq!(without_userinfo("me:pass@proxy:3128"), "***@proxy:3128");
+        assert_eq!(
+            without_userinfo("http://proxy.corp:8080"),
+            "http://proxy.corp:8080"
+        );
+    }
+
+    #[test]
+    fn parses_selection_lists_and_ranges() {
+        assert_eq!(parse_selection("", 3), Some(vec![0, 1, 2]));
+        assert_eq!(parse_selection("3, 1-2, 2", 4), Some(vec![0, 1, 2]));
+        assert_eq!(parse_selection("none", 3), Some(Vec::new()));
+        assert_eq!(parse_selection("0", 3), None);
+        assert_eq!(parse_selection("2-5", 3), None);
+    }
+}
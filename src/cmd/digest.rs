@@ -0,0 +1,75 @@
+use chrono::{Days, Local};
+use clap::Args;
+use tracing::warn;
+
+use crate::context::AppContext;
+use crate::domain::digest::{CreatedTicket, Digest};
+use crate::error::{AppError, AppResult};
+use crate::history::TicketHistory;
+
+#[derive(Args, Debug, Clone)]
+pub struct DigestArgs {
+    /// Also cover this many days before today.
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    pub days: u64,
+    /// Also post the digest to the configured Slack or Teams webhooks.
+    #[arg(long)]
+    pub post: bool,
+}
+
+/// Summarizes the tickets and branches ugh created today in every repository, plus the
+/// user's tickets still in progress, and prints the result.
+pub async fn run(ctx: &AppContext, args: DigestArgs) -> AppResult<()> {
+    let to = Local::now().date_naive();
+    let from = to.checked_sub_days(Days::new(args.days)).unwrap_or(to);
+
+    let created = TicketHistory::open()?
+        .entries()?
+        .into_iter()
+        .filter(|entry| {
+            let day = entry.created_at.with_timezone(&Local).date_naive();
+            from <= day && day <= to
+        })
+        .map(|entry| CreatedTicket {
+            key: entry.key,
+            url: entry.url,
+            branch: entry.branch,
+            repo: entry.repo,
+            created_at: entry.created_at,
+            undone: entry.undone_at.is_some(),
+        })
+        .collect();
+
+    // The ledger alone still makes a useful digest when the tracker is unreachable.
+    let in_progress = match ctx.issue_tracker.in_progress_tickets().await {
+        Ok(tickets) => Some(tickets),
+        Err(err) => {
+            warn!("could not list in-progress tickets ({err}).");
+            None
+        }
+    };
+
+    let digest = Digest {
+        from,
+        to,
+        created,
+        in_progress,
+    };
+    let summary = digest.render();
+    print!("{summary}");
+
+    if args.post {
+        if ctx.notifiers.is_empty() {
+            return Err(AppError::Configuration(
+                "--post needs slack_webhook_url or teams_webhook_url".to_string(),
+            ));
+        }
+        for notifier in ctx.notifiers.iter() {
+            match notifier.post_text(&summary).await {
+                Ok(()) => println!("Posted to {}.", notifier.name()),
+                Err(err) => warn!("could not post the digest to {} ({err}).", notifier.name()),
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,256 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Seek, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::cmd::config::{can_prompt, confirm};
+use crate::domain::branch::BranchCategory;
+use crate::domain::ticket::TicketDraft;
+use crate::error::{AppError, AppResult};
+use crate::workflow::ticket::PreparedTicket;
+
+const HELP: &str = "<!-- Save and close to file the ticket; clear the title to cancel. \
+                    category is feature, fix, or quality. -->";
+const CRITERIA_HEADING: &str = "## Acceptance criteria";
+
+#[derive(Serialize, Deserialize)]
+struct FrontMatter {
+    #[serde(default)]
+    title: String,
+    category: String,
+    #[serde(default)]
+    slug: String,
+}
+
+/// Fails early when `--edit` could not open an editor.
+pub fn ensure_terminal() -> AppResult<()> {
+    if can_prompt() {
+        Ok(())
+    } else {
+        Err(AppError::Configuration(
+            "--edit needs an interactive terminal".to_string(),
+        ))
+    }
+}
+
+/// Opens the draft in `$VISUAL`/`$EDITOR` as Markdown with a front-matter header and applies
+/// the saved result. Returns `false` when the user cleared the title to cancel.
+pub fn review(prepared: &mut PreparedTicket) -> AppResult<bool> {
+    let original = render(&prepared.draft);
+    let (path, mut file) = create_draft_file()?;
+    let mut text = original.clone();
+    let result = loop {
+        if let Err(err) = rewrite(&mut file, &text) {
+            break Err(err.into());
+        }
+        if let Err(err) = open_editor(&path) {
+            break Err(err);
+        }
+        text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(err) => break Err(err.into()),
+        };
+        match parse(&text, &prepared.draft) {
+            Ok(edited) => break Ok(edited),
+            Err(err) => {
+                warn!("could not read the edited draft: {err}");
+                if !confirm("Edit it again?", true)? {
+                    break Err(AppError::WorkflowStep(format!(
+                        "the edited draft is invalid: {err}"
+                    )));
+                }
+            }
+        }
+    };
+    let _ = fs::remove_file(&path);
+
+    match result? {
+        Some(edited) => {
+            if text != original {
+                prepared.edit_draft(edited);
+            }
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// A new file only this user can read, under a random name in the temp directory, so an
+/// existing file or symlink there is never written through.
+fn create_draft_file() -> AppResult<(PathBuf, File)> {
+    let random = SystemRandom::new();
+    loop {
+        let mut bytes = [0u8; 8];
+        random
+            .fill(&mut bytes)
+            .map_err(|_| AppError::WorkflowStep("could not name the draft file".to_string()))?;
+        let name: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+        let path = std::env::temp_dir().join(format!("ugh-draft-{name}.md"));
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        match options.open(&path) {
+            Ok(file) => return Ok((path, file)),
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
+
+/// Replaces the draft file's contents through the handle it was created with.
+fn rewrite(file: &mut File, text: &str) -> io::Result<()> {
+    file.set_len(0)?;
+    file.rewind()?;
+    file.write_all(text.as_bytes())?;
+    file.flush()
+}
+
+fn open_editor(path: &Path) -> AppResult<()> {
+    let editor = ["VISUAL", "EDITOR"]
+        .into_iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| if cfg!(windows) { "notepad" } else { "vi" }.to_string());
+    // Values such as `code --wait` carry their own arguments.
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+    let status = Command::new(program)
+        .args(words)
+        .arg(path)
+        .status()
+        .map_err(|err| {
+            AppError::Configuration(format!("could not start editor '{editor}': {err}"))
+        })?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(AppError::WorkflowStep(format!(
+            "editor '{editor}' exited with {status}; ticket not filed"
+        )))
+    }
+}
+
+fn render(draft: &TicketDraft) -> String {
+    let front_matter = FrontMatter {
+        title: draft.title.clone(),
+        category: draft.branch_category.as_str().to_string(),
+        slug: draft.branch_summary.clone(),
+    };
+    let header = serde_yaml::to_string(&front_matter).unwrap_or_default();
+    let mut text = format!("---\n{header}---\n{HELP}\n\n{}\n", draft.description.trim());
+    text.push_str(&format!("\n{CRITERIA_HEADING}\n\n"));
+    for criterion in &draft.acceptance_criteria {
+        text.push_str(&format!("- {criterion}\n"));
+    }
+    text
+}
+
+/// `original` with the edited fields applied, or `None` when the title was cleared.
+fn parse(text: &str, original: &TicketDraft) -> Result<Option<TicketDraft>, String> {
+    let text = text.replace("\r\n", "\n");
+    let rest = text
+        .strip_prefix("---\n")
+        .ok_or("the file must start with a `---` front-matter header")?;
+    let (header, body) = rest
+        .split_once("\n---\n")
+        .or_else(|| rest.strip_suffix("\n---").map(|header| (header, "")))
+        .ok_or("the front-matter header is not closed with `---`")?;
+    let front_matter: FrontMatter = serde_yaml::from_str(header).map_err(|err| err.to_string())?;
+    if front_matter.title.trim().is_empty() {
+        return Ok(None);
+    }
+    let branch_category = BranchCategory::from_str(&front_matter.category).ok_or_else(|| {
+        format!(
+            "unknown category '{}' (use feature, fix, or quality)",
+            front_matter.category
+        )
+    })?;
+
+    let body = body.replace(HELP, "");
+    let (description, criteria) = match body
+        .lines()
+        .position(|line| line.trim().eq_ignore_ascii_case(CRITERIA_HEADING))
+    {
+        Some(index) => {
+            let lines: Vec<&str> = body.lines().collect();
+            (lines[..index].join("\n"), lines[index + 1..].to_vec())
+        }
+        None => (body.clone(), Vec::new()),
+    };
+
+    Ok(Some(TicketDraft {
+        title: front_matter.title.trim().to_string(),
+        description: description.trim().to_string(),
+        branch_category,
+        branch_summary: front_matter.slug.trim().to_string(),
+        acceptance_criteria: criteria
+            .into_iter()
+            .map(|line| line.trim().trim_start_matches(['-', '*']).trim())
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect(),
+        ..original.clone()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draft_files_are_new_and_private() {
+        let (path, mut file) = create_draft_file().unwrap();
+        let (other, _) = create_draft_file().unwrap();
+        assert_ne!(path, other);
+        rewrite(&mut file, "a longer first text").unwrap();
+        rewrite(&mut file, "second").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(&other);
+    }
+
+    #[test]
+    fn edited_file_round_trips_into_the_draft() {
+        let draft = TicketDraft {
+            title: "Fix refunds: rounding".to_string(),
+            description: "Rounds refunds to cents.".to_string(),
+            branch_category: BranchCategory::Fix,
+            branch_summary: "refund-rounding".to_string(),
+            acceptance_criteria: vec!["Refunds post".to_string()],
+            estimate: Some(3),
+            provider: Some("gemini".to_string()),
+            rationale: None,
+        };
+        let unchanged = parse(&render(&draft), &draft).unwrap().unwrap();
+        assert_eq!(unchanged.title, draft.title);
+        assert_eq!(unchanged.description, draft.description);
+        assert_eq!(unchanged.branch_summary, draft.branch_summary);
+
+        let edited = render(&draft)
+            .replace("category: fix", "category: quality")
+            .replace("- Refunds post", "- Refunds post\n* Totals match");
+        let parsed = parse(&edited, &draft).unwrap().unwrap();
+        assert_eq!(parsed.branch_category, BranchCategory::Quality);
+        assert_eq!(parsed.acceptance_criteria, ["Refunds post", "Totals match"]);
+        assert_eq!(parsed.estimate, Some(3));
+
+        let cleared = render(&draft).replace("title: 'Fix refunds: rounding'", "title: ''");
+        assert!(parse(&cleared, &draft).unwrap().is_none());
+        assert!(parse("no header", &draft).is_err());
+    }
+}
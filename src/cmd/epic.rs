@@ -0,0 +1,42 @@
+use clap::Args;
+
+use crate::context::AppContext;
+use crate::domain::ticket::Ticket;
+use crate::error::{AppError, AppResult};
+use crate::repo_state::RepoStateStore;
+
+#[derive(Args, Debug, Clone)]
+pub struct EpicArgs {
+    /// Epic name, e.g. "Payments revamp".
+    pub name: String,
+    /// Override the default board configured in the CLI.
+    #[arg(short, long)]
+    pub board: Option<String>,
+    /// Optional epic description.
+    #[arg(short, long)]
+    pub description: Option<String>,
+    /// Create the epic without making it this repository's default.
+    #[arg(long)]
+    pub no_default: bool,
+}
+
+/// Creates the epic and, unless told otherwise, files future tickets from this repo under it.
+pub async fn run(ctx: &AppContext, args: EpicArgs) -> AppResult<Ticket> {
+    let board = args
+        .board
+        .or_else(|| ctx.config.default_board.clone())
+        .ok_or_else(|| AppError::Configuration("no board configured".to_string()))?;
+
+    let epic = ctx
+        .issue_tracker
+        .create_epic(&board, &args.name, args.description.as_deref())
+        .await?;
+
+    if !args.no_default {
+        RepoStateStore::open(ctx.repo_root()?)?.update(|state| {
+            state.default_epic = Some(epic.key.clone());
+        })?;
+    }
+
+    Ok(epic)
+}
@@ -0,0 +1,55 @@
+use clap::{Args, ValueEnum};
+use tracing::warn;
+
+use crate::cache::TicketDraftCache;
+use crate::error::AppResult;
+use crate::feedback::{FeedbackStore, Rating};
+
+#[derive(Args, Debug, Clone)]
+pub struct FeedbackArgs {
+    /// How the last draft turned out.
+    #[arg(value_enum)]
+    pub rating: FeedbackRating,
+    /// What was good or wrong about the draft; bad-draft notes steer future prompts.
+    #[arg(short, long)]
+    pub note: Option<String>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum FeedbackRating {
+    Good,
+    Bad,
+}
+
+pub fn run(args: FeedbackArgs) -> AppResult<()> {
+    let rating = match args.rating {
+        FeedbackRating::Good => Rating::Good,
+        FeedbackRating::Bad => Rating::Bad,
+    };
+
+    let mut store = FeedbackStore::load()?;
+    let title = store.last_draft_title().unwrap_or_default().to_string();
+    let key = store.rate_last(rating, args.note)?;
+    store.save()?;
+
+    if rating == Rating::Bad {
+        // A poorly rated draft must not be served from cache on the next run.
+        match TicketDraftCache::load() {
+            Ok(mut cache) => {
+                if cache.remove(&key)
+                    && let Err(err) = cache.save()
+                {
+                    warn!("failed to evict the rated draft from cache ({err}).");
+                }
+            }
+            Err(err) => warn!("could not load ticket draft cache ({err})."),
+        }
+    }
+
+    let label = match rating {
+        Rating::Good => "good",
+        Rating::Bad => "bad",
+    };
+    println!("Recorded '{label}' feedback for \"{title}\".");
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+
+use chrono::Local;
+use clap::Args;
+
+use crate::error::AppResult;
+use crate::history::{HistoryEntry, TicketHistory};
+use crate::infra::git2_repo::Git2Repo;
+use crate::repo_state::repo_identity;
+
+#[derive(Args, Debug, Clone)]
+pub struct HistoryArgs {
+    /// Only show tickets created from this repository (defaults to the current one).
+    #[arg(long, num_args = 0..=1, default_missing_value = ".", value_name = "PATH")]
+    pub repo: Option<PathBuf>,
+    /// Maximum number of tickets to list, newest first.
+    #[arg(short = 'n', long, default_value_t = 20)]
+    pub limit: usize,
+}
+
+pub fn run(args: HistoryArgs) -> AppResult<()> {
+    // Entries are keyed by repository toplevel, so a subdirectory selects its repository.
+    let repo_filter = args
+        .repo
+        .map(|path| repo_identity(&Git2Repo::toplevel(&path).unwrap_or(path)));
+    let entries: Vec<HistoryEntry> = TicketHistory::open()?
+        .entries()?
+        .into_iter()
+        .rev()
+        .filter(|entry| repo_filter.as_ref().is_none_or(|repo| &entry.repo == repo))
+        .take(args.limit)
+        .collect();
+
+    if entries.is_empty() {
+        println!("No tickets recorded yet.");
+        return Ok(());
+    }
+
+    for entry in entries {
+        let created = entry
+            .created_at
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M");
+        let mut source = entry.provider.unwrap_or_else(|| "unknown".to_string());
+        if entry.cache_hit {
+            source.push_str(", cached");
+        }
+        if entry.branch_pending {
+            source.push_str(", no branch");
+        }
+        if entry.undone_at.is_some() {
+            source.push_str(", undone");
+        }
+        println!("{created}  {:<10} {}  ({source})", entry.key, entry.branch);
+        match &entry.url {
+            Some(url) => println!("                  {url}  [{}]", entry.repo),
+            None => println!("                  [{}]", entry.repo),
+        }
+        if let Some(rationale) = &entry.rationale {
+            println!("                  Rationale: {rationale}");
+        }
+    }
+    Ok(())
+}
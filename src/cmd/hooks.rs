@@ -0,0 +1,73 @@
+use clap::{Args, Subcommand};
+use reqwest::Url;
+
+use crate::env_file::repo_root;
+use crate::error::{AppError, AppResult};
+use crate::repo_config::{REPO_CONFIG_FILE_NAME, RepoConfig};
+use crate::repo_state::RepoStateStore;
+
+#[derive(Args, Debug, Clone)]
+pub struct HooksArgs {
+    #[command(subcommand)]
+    pub command: HooksCommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum HooksCommand {
+    /// Approve the repository's current `.ugh.toml`, so its hooks run and its webhooks are
+    /// used until the file changes.
+    Allow,
+    /// Withdraw the approval; the file's hooks and webhooks are ignored again.
+    Revoke,
+}
+
+pub fn run(args: HooksArgs) -> AppResult<()> {
+    let root = repo_root(&std::env::current_dir()?).ok_or_else(|| {
+        AppError::Configuration("`ugh hooks` must run inside a git repository".to_string())
+    })?;
+    let store = RepoStateStore::open(&root)?;
+    match args.command {
+        HooksCommand::Allow => {
+            let Some(file) = RepoConfig::find(&root)? else {
+                println!("No {REPO_CONFIG_FILE_NAME} in {}.", root.display());
+                return Ok(());
+            };
+            let hooks = &file.config.hooks;
+            let notify = &file.config.notify;
+            for (name, command) in [
+                ("pre_draft", &hooks.pre_draft),
+                ("post_ticket", &hooks.post_ticket),
+                ("post_branch", &hooks.post_branch),
+            ] {
+                if let Some(command) = command {
+                    println!("  {name} hook: {command}");
+                }
+            }
+            for (name, url) in [
+                ("Slack", &notify.slack_webhook_url),
+                ("Teams", &notify.teams_webhook_url),
+            ] {
+                if let Some(url) = url {
+                    let host = Url::parse(url)
+                        .ok()
+                        .and_then(|url| url.host_str().map(str::to_string))
+                        .unwrap_or_else(|| "an invalid URL".to_string());
+                    println!("  {name} webhook at {host}");
+                }
+            }
+            store.update(|state| state.approved_repo_config = Some(file.digest.clone()))?;
+            println!(
+                "Approved {}; it needs approving again if it changes.",
+                file.path.display()
+            );
+        }
+        HooksCommand::Revoke => {
+            store.update(|state| state.approved_repo_config = None)?;
+            println!(
+                "Hooks and webhooks in {REPO_CONFIG_FILE_NAME} are ignored again for {}.",
+                root.display()
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use std::fs;
+use std::path::PathBuf;
+
+use clap::Args;
+use futures_util::stream::{self, StreamExt};
+use tracing::info;
+
+use crate::context::AppContext;
+use crate::domain::backlog::{self, BacklogEntry};
+use crate::domain::ticket::{Ticket, TicketOptions};
+use crate::error::{AppError, AppResult};
+use crate::workflow::agents::AgentTrail;
+use crate::workflow::scope::ChangeScope;
+use crate::workflow::steps;
+use crate::workflow::ticket::file_draft;
+
+#[derive(Args, Debug, Clone)]
+pub struct ImportArgs {
+    /// Backlog file: a YAML list of entries, or CSV (`.csv`) with a header row.
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+    /// Override the default board configured in the CLI.
+    #[arg(short, long)]
+    pub board: Option<String>,
+    /// Have the language model expand each entry's description and acceptance criteria.
+    #[arg(long)]
+    pub enrich: bool,
+    /// How many tickets are created at the same time.
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..=16))]
+    pub concurrency: u16,
+    /// Check the file and print what would be created without creating anything.
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Creates a ticket for each entry of a backlog file and prints which entry became which
+/// key. Entries run through the same review steps as `ugh ticket`, but get no branch.
+pub async fn run(ctx: &AppContext, args: ImportArgs) -> AppResult<()> {
+    let text = fs::read_to_string(&args.file).map_err(|err| {
+        AppError::Configuration(format!("could not read {}: {err}", args.file.display()))
+    })?;
+    let is_csv = args
+        .file
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+    let entries = if is_csv {
+        backlog::parse_csv(&text)
+    } else {
+        backlog::parse_yaml(&text)
+    }
+    .map_err(|err| AppError::Configuration(format!("{}: {err}", args.file.display())))?;
+    if entries.is_empty() {
+        println!("{} has no entries.", args.file.display());
+        return Ok(());
+    }
+
+    // Check every entry up front so a typo in row 40 does not leave 39 tickets behind.
+    let problems: Vec<String> = entries
+        .iter()
+        .enumerate()
+        .filter_map(|(index, entry)| {
+            entry
+                .to_draft()
+                .err()
+                .map(|err| format!("entry {}: {err}", index + 1))
+        })
+        .collect();
+    if !problems.is_empty() {
+        return Err(AppError::Configuration(format!(
+            "{} has invalid entries:\n  {}",
+            args.file.display(),
+            problems.join("\n  ")
+        )));
+    }
+
+    let board = args
+        .board
+        .clone()
+        .or_else(|| {
+            ChangeScope::resolve(&ctx.config.working_dir, ctx.repo_root().ok(), &[])
+                .board(&ctx.config.path_boards)
+        })
+        .or_else(|| ctx.config.default_board.clone())
+        .ok_or_else(|| AppError::Configuration("no board configured".to_string()))?;
+
+    if args.dry_run {
+        for (index, entry) in entries.iter().enumerate() {
+            println!("entry {} → {board}: {}", index + 1, entry.title.trim());
+        }
+        return Ok(());
+    }
+
+    info!(count = entries.len(), board = %board, "importing backlog");
+    let mut results: Vec<(usize, AppResult<Ticket>)> = stream::iter(entries.iter().enumerate())
+        .map(|(index, entry)| {
+            let board = board.as_str();
+            async move { (index, import_entry(ctx, board, entry, args.enrich).await) }
+        })
+        .buffer_unordered(usize::from(args.concurrency))
+        .collect()
+        .await;
+    results.sort_by_key(|(index, _)| *index);
+
+    let mut failed = 0;
+    for (index, result) in &results {
+        let title = entries[*index].title.trim();
+        match result {
+            Ok(ticket) => println!("entry {} → {}  {title}", index + 1, ticket.key),
+            Err(err) => {
+                failed += 1;
+                println!("entry {} ✗ {title}: {err}", index + 1);
+            }
+        }
+    }
+    if failed > 0 {
+        return Err(AppError::WorkflowStep(format!(
+            "{failed} of {} entries could not be created",
+            results.len()
+        )));
+    }
+    Ok(())
+}
+
+async fn import_entry(
+    ctx: &AppContext,
+    board: &str,
+    entry: &BacklogEntry,
+    enrich: bool,
+) -> AppResult<Ticket> {
+    let mut draft = entry.to_draft().map_err(AppError::Configuration)?;
+    if enrich {
+        draft = ctx.language_model.enrich_draft(&draft).await?;
+    }
+    let options = TicketOptions {
+        labels: entry.labels.clone(),
+        ..TicketOptions::default()
+    };
+    let (ticket, draft) =
+        file_draft(ctx, board, draft, options, &mut AgentTrail::default()).await?;
+    steps::after_ticket(ctx, board, &draft, &ticket).await?;
+    Ok(ticket)
+}
@@ -0,0 +1,252 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::process::{Command, Stdio};
+
+use clap::{Args, ValueEnum};
+use reqwest::Url;
+use ring::rand::{SecureRandom, SystemRandom};
+
+use crate::cmd::config::{apply_prompt, choose, is_non_interactive};
+use crate::config::{AppConfig, StoredConfig};
+use crate::context::http_client_factory;
+use crate::error::{AppError, AppResult};
+use crate::infra::atlassian_oauth::{
+    AccessibleResource, AtlassianOAuth, OAuthTokenStore, OAuthTokens,
+};
+
+#[derive(Args, Debug, Clone)]
+pub struct LoginArgs {
+    /// Service to log in to.
+    #[arg(value_enum)]
+    pub service: LoginService,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum LoginService {
+    /// Jira Cloud via OAuth 2.0 (3LO).
+    Jira,
+}
+
+pub async fn run(args: LoginArgs) -> AppResult<()> {
+    match args.service {
+        LoginService::Jira => login_jira().await,
+    }
+}
+
+/// Runs the 3LO browser flow: authorize in the browser, catch the redirect on a local
+/// port, exchange the code, and store the tokens for the chosen site.
+async fn login_jira() -> AppResult<()> {
+    if is_non_interactive() {
+        return Err(AppError::Configuration(
+            "browser login cannot run non-interactively; copy jira_oauth.json from a logged-in machine"
+                .to_string(),
+        ));
+    }
+    let mut stored = StoredConfig::load()?;
+    let cwd = std::env::current_dir()?;
+    let mut config = AppConfig::from_stored(&stored, &cwd)?;
+    if config.jira_oauth_client_id.is_none() || config.jira_oauth_client_secret.is_none() {
+        println!(
+            "Register an OAuth 2.0 (3LO) app at https://developer.atlassian.com/console/myapps/"
+        );
+        println!(
+            "with the callback URL http://localhost:{}/callback, then enter its credentials.",
+            config.jira_oauth_redirect_port
+        );
+        apply_prompt("OAuth client ID", &mut stored.jira.oauth_client_id, false)?;
+        apply_prompt(
+            "OAuth client secret",
+            &mut stored.jira.oauth_client_secret,
+            true,
+        )?;
+        config = AppConfig::from_stored(&stored, &cwd)?;
+    }
+    let (Some(client_id), Some(client_secret)) = (
+        config.jira_oauth_client_id.clone(),
+        config.jira_oauth_client_secret.clone(),
+    ) else {
+        return Err(AppError::Configuration(
+            "an OAuth client ID and secret are required to log in".to_string(),
+        ));
+    };
+
+    let oauth = AtlassianOAuth::new(&http_client_factory(&config)?, client_id, client_secret)?;
+    let port = config.jira_oauth_redirect_port;
+    let listener = TcpListener::bind(("127.0.0.1", port)).map_err(|err| {
+        AppError::Configuration(format!(
+            "could not listen on port {port} for the OAuth callback: {err}"
+        ))
+    })?;
+    let redirect_uri = format!("http://localhost:{port}/callback");
+    let state = random_state()?;
+    let authorize_url = oauth.authorize_url(&redirect_uri, &state)?;
+
+    println!("Opening your browser to authorize ugh with Atlassian.");
+    println!("If it does not open, visit:\n  {authorize_url}");
+    let _ = open_browser(&authorize_url);
+
+    let code = tokio::task::spawn_blocking(move || wait_for_callback(&listener, &state))
+        .await
+        .map_err(|err| AppError::Io(io::Error::other(err)))??;
+
+    let grant = oauth.exchange_code(&code, &redirect_uri).await?;
+    let resources = oauth.accessible_resources(&grant.access_token).await?;
+    let site = pick_site(&resources, config.jira_base_url.as_deref())?;
+
+    OAuthTokenStore::open()?.save(&OAuthTokens {
+        expires_at: grant.expires_at(),
+        access_token: grant.access_token,
+        refresh_token: grant.refresh_token,
+        cloud_id: site.id.clone(),
+        site_url: site.url.clone(),
+    })?;
+
+    stored.jira.auth = Some("oauth".to_string());
+    stored.jira.base_url = Some(site.url.clone());
+    stored.save()?;
+
+    println!(
+        "✓ Logged in to {} ({}). Jira requests now use OAuth.",
+        site.name, site.url
+    );
+    Ok(())
+}
+
+/// Prefers the configured site, then a lone grant, and otherwise asks.
+fn pick_site<'a>(
+    resources: &'a [AccessibleResource],
+    configured: Option<&str>,
+) -> AppResult<&'a AccessibleResource> {
+    let normalize = |url: &str| url.trim_end_matches('/').to_lowercase();
+    if let Some(configured) = configured
+        && let Some(site) = resources
+            .iter()
+            .find(|site| normalize(&site.url) == normalize(configured))
+    {
+        return Ok(site);
+    }
+    match resources {
+        [] => Err(AppError::IssueTracker(
+            "the authorization did not grant access to any Jira site".to_string(),
+        )),
+        [only] => Ok(only),
+        _ => {
+            let labels: Vec<String> = resources
+                .iter()
+                .map(|site| format!("{} ({})", site.name, site.url))
+                .collect();
+            Ok(&resources[choose("Which Jira site should ugh use?", &labels)?])
+        }
+    }
+}
+
+/// Serves the redirect until the callback arrives, answering stray requests with 404.
+fn wait_for_callback(listener: &TcpListener, state: &str) -> AppResult<String> {
+    for stream in listener.incoming() {
+        let mut stream = stream?;
+        let mut request_line = String::new();
+        BufReader::new(&stream).read_line(&mut request_line)?;
+
+        match parse_callback(&request_line, state) {
+            None => respond(&mut stream, "404 Not Found", "Not found.")?,
+            Some(Ok(code)) => {
+                respond(
+                    &mut stream,
+                    "200 OK",
+                    "ugh is authorized. You can close this tab and return to the terminal.",
+                )?;
+                return Ok(code);
+            }
+            Some(Err(err)) => {
+                respond(&mut stream, "400 Bad Request", &err.to_string())?;
+                return Err(err);
+            }
+        }
+    }
+    Err(AppError::Configuration(
+        "OAuth callback listener closed".to_string(),
+    ))
+}
+
+/// Extracts the authorization code from a `GET /callback?...` request line. `None` means
+/// the request was for some other path (e.g. a favicon).
+fn parse_callback(request_line: &str, expected_state: &str) -> Option<AppResult<String>> {
+    let target = request_line.split_whitespace().nth(1)?;
+    let url = Url::parse(&format!("http://localhost{target}")).ok()?;
+    if url.path() != "/callback" {
+        return None;
+    }
+
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+    if let Some(error) = param("error") {
+        let description = param("error_description").unwrap_or_default();
+        return Some(Err(AppError::Configuration(format!(
+            "authorization was declined: {error} {description}"
+        ))));
+    }
+    if param("state").as_deref() != Some(expected_state) {
+        return Some(Err(AppError::Configuration(
+            "OAuth state mismatch; restart `ugh config login jira`".to_string(),
+        )));
+    }
+    Some(param("code").ok_or_else(|| {
+        AppError::Configuration("OAuth callback did not include a code".to_string())
+    }))
+}
+
+fn respond(stream: &mut TcpStream, status: &str, message: &str) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{message}",
+        message.len()
+    )
+}
+
+/// 128 random bits from the OS, hex-encoded, for CSRF protection of a single login.
+fn random_state() -> AppResult<String> {
+    let mut bytes = [0u8; 16];
+    SystemRandom::new().fill(&mut bytes).map_err(|_| {
+        AppError::Configuration("could not generate a random OAuth state".to_string())
+    })?;
+    Ok(bytes.iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+/// Hands `url` to the desktop's default browser without waiting for it.
+pub(crate) fn open_browser(url: &str) -> std::io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        // `cmd /C start` would split the URL at its first `&`.
+        let mut command = Command::new("rundll32");
+        command.arg("url.dll,FileProtocolHandler");
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    command
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(drop)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_callback_code_and_checks_state() {
+        let line = "GET /callback?code=abc%3D&state=s1 HTTP/1.1\r\n";
+        assert_eq!(parse_callback(line, "s1").unwrap().unwrap(), "abc=");
+        assert!(parse_callback(line, "other").unwrap().is_err());
+        assert!(parse_callback("GET /favicon.ico HTTP/1.1", "s1").is_none());
+        let denied = "GET /callback?error=access_denied&state=s1 HTTP/1.1";
+        assert!(parse_callback(denied, "s1").unwrap().is_err());
+    }
+}
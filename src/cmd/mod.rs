@@ -1,2 +1,26 @@
+pub mod agents;
+pub mod board;
+pub mod branch_name;
+pub mod cache;
+pub mod changelog;
+pub mod completions;
 pub mod config;
+pub mod digest;
+pub mod editor;
+pub mod epic;
+pub mod feedback;
+pub mod history;
+pub mod hooks;
+pub mod import;
+pub mod login;
+pub mod onboarding;
+pub mod open;
+pub mod progress;
+pub mod retro;
+pub mod sprints;
+pub mod standup;
 pub mod ticket;
+pub mod tui;
+pub mod undo;
+pub mod users;
+pub mod worklog;
@@ -0,0 +1,197 @@
+use std::path::Path;
+
+use crate::cmd::config::{apply_list_prompt, apply_prompt, confirm};
+use crate::config::{AppConfig, LlmProvider, StoredConfig, config_file_path};
+use crate::context::{AppContext, http_client_factory};
+use crate::domain::branch::BranchName;
+use crate::domain::ticket::DraftGuidance;
+use crate::error::AppResult;
+use crate::infra::jira::{JiraAuth, JiraClient};
+use crate::infra::llm::GeminiClient;
+use crate::infra::ollama::OllamaClient;
+use crate::workflow::redact::Redactor;
+
+const INTRO: &str = "\
+Welcome to ugh!
+
+ugh turns your uncommitted git changes into a Jira ticket and a matching branch:
+  1. it summarizes `git status` / `git diff --stat` (secrets are scrubbed first),
+  2. asks an LLM to draft a title, description, and branch slug,
+  3. creates the Jira ticket and checks out `type/KEY-123/slug`.
+
+Let's connect Jira and an LLM provider. Each credential is checked as soon as you enter it.";
+
+/// Guided setup for the very first invocation: configure, verify, and preview a draft.
+pub async fn run(workspace_root: &Path) -> AppResult<()> {
+    println!("{INTRO}");
+    println!();
+
+    let mut cfg = StoredConfig::load()?;
+
+    configure_jira(&mut cfg, workspace_root).await?;
+    apply_prompt(
+        "Default Jira board/project key",
+        &mut cfg.behavior.default_board,
+        false,
+    )?;
+    apply_prompt("Default Jira issue type", &mut cfg.jira.issue_type, false)?;
+    configure_llm(&mut cfg, workspace_root).await?;
+
+    cfg.save()?;
+    println!("\nConfiguration saved to {}", config_file_path()?.display());
+
+    let config = AppConfig::from_stored(&cfg, workspace_root)?;
+    sample_draft(config).await?;
+
+    println!();
+    println!("You're all set. Run `ugh ticket` to create the ticket and branch for real,");
+    println!("or `ugh config init` to change any of these settings later.");
+    Ok(())
+}
+
+async fn configure_jira(cfg: &mut StoredConfig, workspace_root: &Path) -> AppResult<()> {
+    println!("== Jira ==");
+    loop {
+        apply_prompt(
+            "Jira base URL (e.g., https://company.atlassian.net)",
+            &mut cfg.jira.base_url,
+            false,
+        )?;
+        apply_prompt("Jira email", &mut cfg.jira.email, false)?;
+        apply_prompt("Jira API token", &mut cfg.jira.token, true)?;
+
+        let config = AppConfig::from_stored(cfg, workspace_root)?;
+        let client = JiraClient::new(
+            &http_client_factory(&config)?,
+            config.jira_base_url.clone(),
+            JiraAuth::ApiToken {
+                email: config.jira_email.clone(),
+                token: config.jira_token.clone(),
+            },
+            config.jira_issue_type.clone(),
+            config.jira_service_desks.clone(),
+            config.jira_fields.clone(),
+        )?;
+
+        match client.verify_credentials().await {
+            Ok(name) => {
+                println!("✓ Connected to Jira as {name}.");
+                return Ok(());
+            }
+            Err(err) => {
+                println!("✗ Could not verify Jira credentials: {err}");
+                if !confirm("Re-enter Jira credentials?", true)? {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+async fn configure_llm(cfg: &mut StoredConfig, workspace_root: &Path) -> AppResult<()> {
+    println!();
+    println!("== LLM provider ==");
+    apply_list_prompt(
+        "LLM providers in fallback order (gemini, ollama; default gemini)",
+        &mut cfg.behavior.llm_providers,
+    )?;
+
+    let providers = AppConfig::from_stored(cfg, workspace_root)?.llm_providers;
+    if providers.contains(&LlmProvider::Gemini) {
+        loop {
+            apply_prompt("Gemini API key", &mut cfg.gemini.api_key, true)?;
+            apply_prompt("Gemini model", &mut cfg.gemini.model, false)?;
+
+            let config = AppConfig::from_stored(cfg, workspace_root)?;
+            let client = GeminiClient::new(
+                &http_client_factory(&config)?,
+                config.gemini_api_key.clone(),
+                config.gemini_model.clone(),
+                &config.llm,
+            )?;
+            match client.verify_api_key().await {
+                Ok(()) => {
+                    println!("✓ Gemini key works with {}.", config.gemini_model);
+                    break;
+                }
+                Err(err) => {
+                    println!("✗ Could not verify Gemini: {err}");
+                    if !confirm("Re-enter Gemini settings?", true)? {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    if providers.contains(&LlmProvider::Ollama) {
+        loop {
+            apply_prompt("Ollama base URL", &mut cfg.ollama.base_url, false)?;
+            apply_prompt("Ollama model", &mut cfg.ollama.model, false)?;
+
+            let config = AppConfig::from_stored(cfg, workspace_root)?;
+            let client = OllamaClient::new(
+                &http_client_factory(&config)?,
+                config.ollama_base_url.clone(),
+                config.ollama_model.clone(),
+                &config.llm,
+            )?;
+            match client.verify_connection().await {
+                Ok(()) => {
+                    println!("✓ Ollama is reachable at {}.", config.ollama_base_url);
+                    break;
+                }
+                Err(err) => {
+                    println!("✗ Could not reach Ollama: {err}");
+                    if !confirm("Re-enter Ollama settings?", true)? {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Drafts a ticket for the current changes without creating anything.
+async fn sample_draft(config: AppConfig) -> AppResult<()> {
+    println!();
+    println!("== Sample draft (dry run, nothing is created) ==");
+
+    let board = config
+        .default_board
+        .clone()
+        .unwrap_or_else(|| "BOARD".to_string());
+    let excluded = config.redact_exclude_files.clone();
+    let ctx = AppContext::from_config(config)?;
+
+    let changes = match ctx.version_control.summarize_changes(&[]).await {
+        Ok(changes) if changes.files_changed > 0 => changes,
+        Ok(_) => {
+            println!("No uncommitted changes here, so there is nothing to draft yet.");
+            return Ok(());
+        }
+        Err(err) => {
+            println!("Skipping the sample draft: {err}");
+            return Ok(());
+        }
+    };
+    let changes = Redactor::new(excluded).redact(&changes);
+
+    let draft = ctx
+        .language_model
+        .draft_ticket(&changes, &DraftGuidance::default())
+        .await?;
+    let branch = BranchName::from_parts(
+        &draft.branch_category,
+        &format!("{board}-123"),
+        &draft.branch_summary,
+    );
+
+    println!("Title:  {}", draft.title);
+    println!("Branch: {}", branch.as_str());
+    println!();
+    println!("{}", draft.description);
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use clap::Args;
+use tracing::warn;
+
+use crate::cmd::config::is_non_interactive;
+use crate::cmd::login::open_browser;
+use crate::context::AppContext;
+use crate::error::{AppError, AppResult};
+use crate::history::TicketHistory;
+use crate::repo_state::repo_identity;
+use crate::workflow::scope::ChangeScope;
+
+#[derive(Args, Debug, Clone)]
+pub struct OpenArgs {
+    /// `board` for the board, a ticket key, or nothing for the current branch's ticket.
+    #[arg(value_name = "KEY|board")]
+    pub target: Option<String>,
+    /// Board `ugh open board` opens instead of the configured one.
+    #[arg(short, long)]
+    pub board: Option<String>,
+    /// Print the URL instead of opening a browser.
+    #[arg(long)]
+    pub print: bool,
+}
+
+/// Opens the current branch's ticket, a given ticket, or the board in the browser.
+pub async fn run(ctx: &AppContext, args: OpenArgs) -> AppResult<()> {
+    let url = match args.target.as_deref().map(str::trim) {
+        Some("board") => ctx.issue_tracker.board_url(&board(ctx, args.board)?)?,
+        Some(key) => ctx.issue_tracker.ticket_url(key)?,
+        None => ctx.issue_tracker.ticket_url(&branch_ticket(ctx).await?)?,
+    };
+
+    if args.print || is_non_interactive() {
+        println!("{url}");
+        return Ok(());
+    }
+    println!("Opening {url}");
+    if let Err(err) = open_browser(&url) {
+        warn!("could not start a browser ({err}); open the URL above yourself.");
+    }
+    Ok(())
+}
+
+/// `--board`, then the `path_boards` match for the current directory, then the default.
+fn board(ctx: &AppContext, requested: Option<String>) -> AppResult<String> {
+    let scope = ChangeScope::resolve(&ctx.config.working_dir, ctx.repo_root().ok(), &[]);
+    requested
+        .or_else(|| scope.board(&ctx.config.path_boards))
+        .or_else(|| ctx.config.default_board.clone())
+        .map(|board| board.trim().to_string())
+        .filter(|board| !board.is_empty())
+        .ok_or_else(|| AppError::Configuration("no board configured; pass --board".to_string()))
+}
+
+/// The ticket the checked-out branch was created for, from the history ledger, or else
+/// the key in the branch name.
+pub(crate) async fn branch_ticket(ctx: &AppContext) -> AppResult<String> {
+    let repo = repo_identity(ctx.repo_root()?);
+    let branch = ctx.version_control.current_branch().await?.ok_or_else(|| {
+        AppError::VersionControl(
+            "HEAD is detached, so no branch names a ticket; check out the ticket's branch"
+                .to_string(),
+        )
+    })?;
+
+    let recorded = TicketHistory::open()?
+        .entries()?
+        .into_iter()
+        .rev()
+        .find(|entry| entry.repo == repo && entry.branch == branch && entry.undone_at.is_none())
+        .map(|entry| entry.key);
+    recorded
+        .or_else(|| ctx.issue_tracker.ticket_key_in_branch(&branch))
+        .ok_or_else(|| {
+            AppError::VersionControl(format!(
+                "branch {branch} names no ticket and ugh did not create it for one"
+            ))
+        })
+}
@@ -0,0 +1,116 @@
+use std::io::{self, IsTerminal, Write};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+
+use crate::infra::stream::{StreamObserver, set_observer};
+
+const FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+const TICK: Duration = Duration::from_millis(100);
+/// Characters of the reply shown after the spinner.
+const PREVIEW_CHARS: usize = 32;
+
+#[derive(Default)]
+struct Line {
+    /// The provider answering right now; nothing is drawn between replies.
+    provider: Option<String>,
+    received: usize,
+    tail: String,
+    frame: usize,
+}
+
+/// A one-line spinner on stderr naming the model that is drafting and showing the end of
+/// its reply as it streams in. The line is cleared when this is dropped.
+pub struct DraftProgress {
+    line: Arc<Mutex<Line>>,
+    ticker: JoinHandle<()>,
+}
+
+impl DraftProgress {
+    /// `None` when stderr is not a terminal; replies are then fetched whole.
+    pub fn start() -> Option<Self> {
+        if !io::stderr().is_terminal() {
+            return None;
+        }
+        let line = Arc::new(Mutex::new(Line::default()));
+        set_observer(Some(Arc::new(Observer(line.clone()))));
+        let ticking = line.clone();
+        let ticker = tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(TICK).await;
+                if let Ok(mut line) = ticking.lock() {
+                    line.frame = line.frame.wrapping_add(1);
+                    draw(&line);
+                }
+            }
+        });
+        Some(Self { line, ticker })
+    }
+}
+
+impl Drop for DraftProgress {
+    fn drop(&mut self) {
+        set_observer(None);
+        self.ticker.abort();
+        if let Ok(mut line) = self.line.lock() {
+            line.provider = None;
+        }
+        clear();
+    }
+}
+
+struct Observer(Arc<Mutex<Line>>);
+
+impl StreamObserver for Observer {
+    fn started(&self, provider: &str) {
+        if let Ok(mut line) = self.0.lock() {
+            line.provider = Some(provider.to_string());
+            line.received = 0;
+            line.tail.clear();
+            draw(&line);
+        }
+    }
+
+    fn text(&self, chunk: &str) {
+        if let Ok(mut line) = self.0.lock() {
+            line.received += chunk.chars().count();
+            let joined: String = line.tail.chars().chain(chunk.chars()).collect();
+            let flat = joined.split_whitespace().collect::<Vec<_>>().join(" ");
+            let skip = flat.chars().count().saturating_sub(PREVIEW_CHARS);
+            line.tail = flat.chars().skip(skip).collect();
+            draw(&line);
+        }
+    }
+
+    fn finished(&self) {
+        if let Ok(mut line) = self.0.lock() {
+            line.provider = None;
+        }
+        clear();
+    }
+}
+
+fn draw(line: &Line) {
+    let Some(provider) = &line.provider else {
+        return;
+    };
+    let frame = FRAMES[line.frame % FRAMES.len()];
+    let message = if line.received == 0 {
+        format!("Waiting for {provider}…")
+    } else {
+        format!(
+            "{provider} is drafting ({} chars) …{}",
+            line.received, line.tail
+        )
+    };
+    let mut stderr = io::stderr();
+    let _ = write!(stderr, "\r\x1b[2K{frame} {message}");
+    let _ = stderr.flush();
+}
+
+fn clear() {
+    let mut stderr = io::stderr();
+    let _ = write!(stderr, "\r\x1b[2K");
+    let _ = stderr.flush();
+}
@@ -0,0 +1,100 @@
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{Days, Local, NaiveDate};
+use clap::Args;
+use tracing::{info, warn};
+
+use crate::cmd::sprints::find_sprint;
+use crate::context::AppContext;
+use crate::domain::retro::{RetroFacts, RetroScope};
+use crate::error::{AppError, AppResult};
+use crate::workflow::scope::ChangeScope;
+
+/// How far back a retrospective looks when neither a sprint nor dates are given.
+const DEFAULT_PERIOD_DAYS: u64 = 14;
+
+#[derive(Args, Debug, Clone)]
+pub struct RetroArgs {
+    /// Override the default board configured in the CLI.
+    #[arg(short, long)]
+    pub board: Option<String>,
+    /// Sprint to look back on: an ID, or a name matched loosely against the board's
+    /// sprints, closed ones included.
+    #[arg(long, value_name = "NAME|ID", conflicts_with_all = ["since", "until"])]
+    pub sprint: Option<String>,
+    /// First day of the period, e.g. 2025-03-01 (default: two weeks before --until).
+    #[arg(long, value_name = "DATE")]
+    pub since: Option<NaiveDate>,
+    /// Last day of the period (default: today).
+    #[arg(long, value_name = "DATE")]
+    pub until: Option<NaiveDate>,
+    /// Write the Markdown to this file instead of printing it.
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+/// Gathers the tickets finished in a sprint or period, their cycle times, and the reverts
+/// and hotfixes in git, then has the language model draft the retrospective.
+pub async fn run(ctx: &AppContext, args: RetroArgs) -> AppResult<()> {
+    let board = args
+        .board
+        .clone()
+        .or_else(|| {
+            ChangeScope::resolve(&ctx.config.working_dir, ctx.repo_root().ok(), &[])
+                .board(&ctx.config.path_boards)
+        })
+        .or_else(|| ctx.config.default_board.clone())
+        .ok_or_else(|| AppError::Configuration("no board configured".to_string()))?;
+
+    let scope = match &args.sprint {
+        Some(query) => RetroScope::Sprint(find_sprint(ctx, &board, query, true).await?),
+        None => {
+            let to = args.until.unwrap_or_else(|| Local::now().date_naive());
+            let from = args.since.unwrap_or_else(|| {
+                to.checked_sub_days(Days::new(DEFAULT_PERIOD_DAYS))
+                    .unwrap_or(to)
+            });
+            if from > to {
+                return Err(AppError::Configuration(format!(
+                    "--since {from} is after --until {to}"
+                )));
+            }
+            RetroScope::Period { from, to }
+        }
+    };
+
+    let tickets = ctx.issue_tracker.completed_tickets(&board, &scope).await?;
+    info!(count = tickets.len(), period = %scope.label(), "completed tickets fetched");
+
+    // The tracker data stands on its own; a missing or unreadable repository only costs
+    // the revert and hotfix signals from git.
+    let commits = match scope.window() {
+        Some((since, until)) => match ctx.version_control.commits_between(since, until).await {
+            Ok(commits) => Some(commits),
+            Err(err) => {
+                warn!("could not read the git history ({err}); leaving commits out.");
+                None
+            }
+        },
+        None => None,
+    };
+
+    let facts = RetroFacts {
+        board,
+        period: scope.label(),
+        tickets,
+        commits,
+    };
+    let notes = ctx.language_model.retrospective_notes(&facts).await?;
+    let document = facts.render(&notes);
+
+    match &args.output {
+        Some(path) => {
+            fs::write(path, &document)?;
+            println!("Retrospective written to {}.", path.display());
+        }
+        None => print!("{document}"),
+    }
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use crate::cmd::config::{can_prompt, choose};
+use crate::context::AppContext;
+use crate::domain::sprint::{Sprint, match_sprints};
+use crate::error::{AppError, AppResult};
+
+/// Resolves `--sprint` to a sprint ID: numbers are taken as IDs, names are matched
+/// against `board`'s active and future sprints, asking when several match equally well.
+pub(crate) async fn resolve_sprint(ctx: &AppContext, board: &str, query: &str) -> AppResult<u64> {
+    if let Ok(id) = query.trim().parse::<u64>() {
+        return Ok(id);
+    }
+    Ok(find_sprint(ctx, board, query, false).await?.id)
+}
+
+/// Looks up one of `board`'s sprints by ID or loosely by name, asking when several names
+/// match equally well. Closed sprints are candidates only with `include_closed`.
+pub(crate) async fn find_sprint(
+    ctx: &AppContext,
+    board: &str,
+    query: &str,
+    include_closed: bool,
+) -> AppResult<Sprint> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Err(AppError::IssueTracker(
+            "sprint must not be empty".to_string(),
+        ));
+    }
+
+    let sprints = ctx
+        .issue_tracker
+        .list_sprints(board, include_closed)
+        .await?;
+    if let Ok(id) = query.parse::<u64>() {
+        return sprints
+            .into_iter()
+            .find(|sprint| sprint.id == id)
+            .ok_or_else(|| AppError::IssueTracker(format!("no sprint {id} on {board}")));
+    }
+
+    let candidates = match_sprints(&sprints, query);
+    match candidates.as_slice() {
+        [only] => return Ok((*only).clone()),
+        [] => {
+            let known: Vec<&str> = sprints.iter().map(|sprint| sprint.name.as_str()).collect();
+            let searched = if include_closed {
+                "sprint"
+            } else {
+                "active or future sprint"
+            };
+            return Err(AppError::IssueTracker(format!(
+                "no {searched} on {board} matches '{query}' (known sprints: {})",
+                if known.is_empty() {
+                    "none".to_string()
+                } else {
+                    known.join(", ")
+                }
+            )));
+        }
+        _ => {}
+    }
+
+    let labels: Vec<String> = candidates.iter().map(|sprint| sprint.label()).collect();
+    if !can_prompt() {
+        return Err(AppError::IssueTracker(format!(
+            "sprint '{query}' is ambiguous: {}",
+            labels.join(", ")
+        )));
+    }
+    let index = choose(&format!("Several sprints match '{query}':"), &labels)?;
+    Ok(candidates[index].clone())
+}
@@ -0,0 +1,68 @@
+use chrono::{Datelike, Days, Local, TimeDelta, Utc, Weekday};
+use clap::Args;
+use tracing::{info, warn};
+
+use crate::context::AppContext;
+use crate::domain::standup::StandupFacts;
+use crate::error::{AppError, AppResult};
+
+#[derive(Args, Debug, Clone)]
+pub struct StandupArgs {
+    /// Look back this many days (default: since the previous working day's morning).
+    #[arg(long, value_name = "N")]
+    pub days: Option<u64>,
+    /// Also post the summary to the configured Slack or Teams webhooks.
+    #[arg(long)]
+    pub post: bool,
+}
+
+/// Collects the user's commits across local branches, has the language model summarize
+/// them per ticket, and prints the result.
+pub async fn run(ctx: &AppContext, args: StandupArgs) -> AppResult<()> {
+    ctx.repo_root()?;
+    let since = match args.days {
+        Some(days) => Utc::now() - TimeDelta::days(days as i64),
+        None => previous_working_day(),
+    };
+
+    let commits = ctx.version_control.recent_commits(since).await?;
+    info!(count = commits.len(), "recent commits collected");
+    let facts = StandupFacts::group(since, commits);
+    let notes = if facts.groups.is_empty() {
+        Default::default()
+    } else {
+        ctx.language_model.standup_notes(&facts).await?
+    };
+    let summary = facts.render(&notes);
+    print!("{summary}");
+
+    if args.post {
+        if ctx.notifiers.is_empty() {
+            return Err(AppError::Configuration(
+                "--post needs slack_webhook_url or teams_webhook_url".to_string(),
+            ));
+        }
+        for notifier in ctx.notifiers.iter() {
+            match notifier.post_text(&summary).await {
+                Ok(()) => println!("Posted to {}.", notifier.name()),
+                Err(err) => warn!("could not post the standup to {} ({err}).", notifier.name()),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Midnight at the start of the previous working day, so Monday covers Friday's work.
+fn previous_working_day() -> chrono::DateTime<Utc> {
+    let today = Local::now().date_naive();
+    let back = match today.weekday() {
+        Weekday::Mon => 3,
+        Weekday::Sun => 2,
+        _ => 1,
+    };
+    let day = today.checked_sub_days(Days::new(back)).unwrap_or(today);
+    day.and_time(Default::default())
+        .and_local_timezone(Local)
+        .earliest()
+        .map_or_else(Utc::now, |start| start.to_utc())
+}
@@ -1,12 +1,401 @@
-use crate::context::AppContext;
-use crate::error::AppResult;
-use crate::workflow::ticket::{TicketWorkflowOutcome, create_ticket_from_changes};
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+use clap::ValueEnum;
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::cmd::config::{can_prompt, choose_many, confirm};
+use crate::cmd::editor;
+use crate::cmd::open::branch_ticket;
+use crate::cmd::progress::DraftProgress;
+use crate::cmd::sprints::resolve_sprint;
+use crate::cmd::tui;
+use crate::cmd::users::resolve_user;
+use crate::config::TicketPreset;
+use crate::context::{AppContext, http_client_factory};
+use crate::domain::sprint::Placement;
+use crate::domain::ticket::TicketDetails;
+use crate::error::{AppError, AppResult};
+use crate::infra::change_source::change_source;
+use crate::repo_state::RepoStateStore;
+use crate::workflow::amend::{AmendTarget, PreparedAmendment, apply_amendment, prepare_amendment};
+use crate::workflow::crosscheck::{ClaimCheck, cross_check};
+use crate::workflow::scope::ChangeScope;
+use crate::workflow::ticket::{
+    CheckoutTarget, PreparedTicket, SplitPlan, TicketWorkflowOptions, TicketWorkflowOutcome,
+    create_split_tickets, plan_split, prepare_ticket, resolve_board, submit_ticket,
+    submit_workspace_ticket,
+};
+use crate::workflow::workspace::{changed_repositories, prepare_workspace_ticket};
+
+/// The value `--print` writes to stdout in place of the summary.
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum PrintField {
+    Key,
+    Url,
+    Branch,
+}
+
+impl PrintField {
+    /// `None` for a URL the tracker did not return.
+    pub fn value(self, outcome: &TicketWorkflowOutcome) -> Option<String> {
+        match self {
+            PrintField::Key => Some(outcome.ticket.key.clone()),
+            PrintField::Url => outcome.ticket.url.clone(),
+            PrintField::Branch => Some(outcome.branch.as_str().to_string()),
+        }
+    }
+}
+
+/// What `--amend` changes on the branch's ticket.
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum AmendField {
+    Description,
+    Comment,
+}
+
+/// Width of the claims column in the draft preview.
+const CLAIM_COLUMN: usize = 52;
 
 #[derive(Debug, Clone)]
 pub struct TicketCommandArgs {
     pub board: Option<String>,
+    pub no_redact: bool,
+    /// Epic to file the ticket under; falls back to the repository's default epic.
+    pub epic: Option<String>,
+    pub no_epic: bool,
+    /// Free-text user queries resolved against the tracker's user search.
+    pub assignee: Option<String>,
+    pub reporter: Option<String>,
+    pub due_date: Option<NaiveDate>,
+    /// Cluster the changes into several tickets and pick which to create.
+    pub split: bool,
+    /// Directories to restrict the change summary to (monorepo packages).
+    pub paths: Vec<PathBuf>,
+    /// Custom field values given on the command line, by field ID.
+    pub fields: Vec<(String, Value)>,
+    /// Name of a configured preset bundling issue type, labels, priority, and branching.
+    pub preset: Option<String>,
+    /// Move the new ticket to the backlog.
+    pub backlog: bool,
+    /// Sprint name (matched loosely) or ID to move the new ticket into.
+    pub sprint: Option<String>,
+    /// Check the branch out in a new worktree; `Some(None)` picks a sibling directory.
+    pub worktree: Option<Option<PathBuf>>,
+    /// Show the draft next to the changed files and ask before filing it.
+    pub preview: bool,
+    /// Skip the Slack/Teams notifications.
+    pub no_notify: bool,
+    /// Review and edit the draft in a full-screen terminal UI before filing it.
+    pub tui: bool,
+    /// Open the draft in `$VISUAL`/`$EDITOR` before filing it.
+    pub edit: bool,
+    /// Draft from a patch file, pull request URL, or editor buffer dump instead of the
+    /// working tree.
+    pub from: Option<String>,
+    /// Show a spinner and the model's reply as it streams in while drafting.
+    pub progress: bool,
+    /// Cover the changes in every configured workspace repository with one ticket.
+    pub workspace: bool,
+}
+
+pub async fn run(
+    ctx: &AppContext,
+    args: TicketCommandArgs,
+) -> AppResult<Vec<TicketWorkflowOutcome>> {
+    let workspace = if args.workspace {
+        if matches!(args.worktree, Some(Some(_))) {
+            return Err(AppError::Configuration(
+                "--worktree PATH cannot be used with --workspace; leave the path out to get a \
+                 worktree beside each repository"
+                    .to_string(),
+            ));
+        }
+        Some(changed_repositories(ctx).await?)
+    } else {
+        None
+    };
+    // A workspace run takes its per-repository defaults from its leading repository.
+    let repo_root = match &workspace {
+        Some(repos) => repos[0].ctx.repo_root()?,
+        None => ctx.repo_root()?,
+    };
+    if args.tui {
+        tui::ensure_terminal()?;
+    }
+    if args.edit {
+        editor::ensure_terminal()?;
+    }
+    if args.no_redact {
+        warn!("secret redaction disabled; change details are sent to the LLM as-is.");
+    }
+
+    let epic = if args.no_epic {
+        None
+    } else {
+        match args.epic {
+            Some(epic) => Some(epic),
+            None => RepoStateStore::open(repo_root)?
+                .load()?
+                .default_epic
+                .inspect(|epic| info!(%epic, "filing under the repository's default epic")),
+        }
+    };
+
+    let assignee = match args.assignee.as_deref() {
+        Some(query) => Some(resolve_user(ctx, query, "assignee").await?),
+        None => None,
+    };
+    let reporter = match args.reporter.as_deref() {
+        Some(query) => Some(resolve_user(ctx, query, "reporter").await?),
+        None => None,
+    };
+
+    let sourced;
+    let ctx = match args.from.as_deref() {
+        Some(spec) => {
+            let source = change_source(
+                spec,
+                repo_root,
+                &http_client_factory(&ctx.config)?,
+                ctx.config.github_token.clone(),
+            )?;
+            info!(source = %source.describe(), "drafting from changes outside the working tree");
+            sourced = ctx.clone().with_change_source(source);
+            &sourced
+        }
+        None => ctx,
+    };
+
+    let scope = if workspace.is_some() {
+        ChangeScope::default()
+    } else {
+        ChangeScope::resolve(&ctx.config.working_dir, Some(repo_root), &args.paths)
+    };
+    if !scope.is_whole_repo() {
+        info!(paths = ?scope.repo_relative, "limiting changes to scoped paths");
+    }
+
+    let preset = resolve_preset(ctx, args.preset.as_deref())?;
+
+    let mut options = TicketWorkflowOptions {
+        board_override: args.board,
+        redact: !args.no_redact,
+        epic,
+        assignee: assignee.map(|user| user.account_id),
+        reporter: reporter.map(|user| user.account_id),
+        due_date: args.due_date,
+        scope,
+        custom_fields: args.fields.into_iter().collect(),
+        preset,
+        placement: args.backlog.then_some(Placement::Backlog),
+        checkout: match args.worktree {
+            Some(path) => CheckoutTarget::Worktree(path),
+            None => CheckoutTarget::CurrentTree,
+        },
+        notify: !args.no_notify,
+    };
+    if let Some(query) = args.sprint.as_deref() {
+        let board = resolve_board(ctx, &options)?;
+        let sprint = resolve_sprint(ctx, &board, query).await?;
+        info!(sprint, "filing into sprint");
+        options.placement = Some(Placement::Sprint(sprint));
+    }
+
+    if !args.split {
+        let progress = args.progress.then(DraftProgress::start).flatten();
+        let mut prepared = match &workspace {
+            Some(repos) => prepare_workspace_ticket(ctx, &options, repos).await?,
+            None => prepare_ticket(ctx, &options).await?,
+        };
+        drop(progress);
+        if args.tui && !tui::review(ctx, &mut prepared).await? {
+            println!("Ticket not filed.");
+            return Ok(Vec::new());
+        }
+        if args.edit && !editor::review(&mut prepared)? {
+            println!("Ticket not filed.");
+            return Ok(Vec::new());
+        }
+        let checks = cross_check(&prepared.draft.description, &prepared.changes.files);
+        let unverified = checks.iter().filter(|check| check.is_unverified()).count();
+        if unverified > 0 {
+            warn!(
+                "{unverified} draft claim(s) match no changed file; review the description for invented work."
+            );
+        }
+        if args.preview {
+            print_preview(&prepared, &checks);
+            if can_prompt() && !confirm("File this ticket?", unverified == 0)? {
+                println!("Ticket not filed.");
+                return Ok(Vec::new());
+            }
+        }
+        return match &workspace {
+            Some(repos) => submit_workspace_ticket(ctx, &options, prepared, repos).await,
+            None => Ok(vec![submit_ticket(ctx, &options, prepared).await?]),
+        };
+    }
+
+    let progress = args.progress.then(DraftProgress::start).flatten();
+    let plan = plan_split(ctx, &options).await?;
+    drop(progress);
+    let selected = select_split_drafts(&plan)?;
+    if selected.is_empty() {
+        println!("No tickets selected; nothing created.");
+        return Ok(Vec::new());
+    }
+    create_split_tickets(ctx, &options, plan, &selected).await
+}
+
+/// Brings the checked-out branch's ticket up to date with the changes. Returns the
+/// ticket, or `None` when the user declined at the preview.
+pub async fn amend(
+    ctx: &AppContext,
+    args: TicketCommandArgs,
+    field: AmendField,
+) -> AppResult<Option<TicketDetails>> {
+    let target = match field {
+        AmendField::Description => AmendTarget::Description,
+        AmendField::Comment => AmendTarget::Comment,
+    };
+    if args.no_redact {
+        warn!("secret redaction disabled; change details are sent to the LLM as-is.");
+    }
+    let key = branch_ticket(ctx).await?;
+    let scope = ChangeScope::resolve(&ctx.config.working_dir, Some(ctx.repo_root()?), &args.paths);
+    if !scope.is_whole_repo() {
+        info!(paths = ?scope.repo_relative, "limiting changes to scoped paths");
+    }
+
+    let progress = args.progress.then(DraftProgress::start).flatten();
+    let prepared = prepare_amendment(ctx, &key, &scope, !args.no_redact).await?;
+    drop(progress);
+    if args.preview {
+        print_amendment(&prepared, target);
+        if can_prompt() && !confirm(&format!("Update {key}?"), true)? {
+            println!("{key} left unchanged.");
+            return Ok(None);
+        }
+    }
+    apply_amendment(ctx, &prepared, target).await?;
+    Ok(Some(prepared.ticket))
+}
+
+/// The configured preset called `name`, or no preset at all.
+pub(crate) fn resolve_preset(ctx: &AppContext, name: Option<&str>) -> AppResult<TicketPreset> {
+    let Some(name) = name else {
+        return Ok(TicketPreset::default());
+    };
+    ctx.config.presets.get(name).cloned().ok_or_else(|| {
+        let known: Vec<&str> = ctx.config.presets.keys().map(String::as_str).collect();
+        AppError::Configuration(format!(
+            "unknown preset '{name}' (configured: {})",
+            if known.is_empty() {
+                "none".to_string()
+            } else {
+                known.join(", ")
+            }
+        ))
+    })
+}
+
+fn select_split_drafts(plan: &SplitPlan) -> AppResult<Vec<usize>> {
+    println!("Proposed tickets for board {}:", plan.board);
+    for (index, split) in plan.drafts.iter().enumerate() {
+        println!(
+            "\n  {}) [{}] {}",
+            index + 1,
+            split.draft.branch_category.as_str(),
+            split.draft.title
+        );
+        for file in &split.files {
+            println!("       {file}");
+        }
+    }
+    println!();
+
+    if !can_prompt() {
+        return Ok((0..plan.drafts.len()).collect());
+    }
+    choose_many("Create which tickets?", plan.drafts.len())
+}
+
+/// Draft claims on the left, changed paths on the right; `?` marks claims no file backs up.
+fn print_preview(prepared: &PreparedTicket, checks: &[ClaimCheck]) {
+    let draft = &prepared.draft;
+    println!(
+        "Draft for board {}: [{}] {}",
+        prepared.board,
+        draft.branch_category.as_str(),
+        draft.title
+    );
+    if let Some(rationale) = &draft.rationale {
+        println!("Model's rationale: {rationale}");
+    }
+    println!();
+    println!("  {:<CLAIM_COLUMN$}  Changed files", "Claims");
+    let rows = checks.len().max(prepared.changes.files.len());
+    for row in 0..rows {
+        let claim = checks
+            .get(row)
+            .map(|check| {
+                let mark = if check.is_unverified() { '?' } else { '✓' };
+                format!("{mark} {}", truncate(&check.claim, CLAIM_COLUMN - 2))
+            })
+            .unwrap_or_default();
+        let file = prepared.changes.files.get(row).map_or("", String::as_str);
+        println!("  {claim:<CLAIM_COLUMN$}  {file}");
+    }
+    if !draft.acceptance_criteria.is_empty() {
+        println!();
+        println!("  Acceptance criteria:");
+        for criterion in &draft.acceptance_criteria {
+            println!("    - {criterion}");
+        }
+    }
+    println!();
+}
+
+fn print_amendment(prepared: &PreparedAmendment, target: AmendTarget) {
+    let amendment = &prepared.amendment;
+    match target {
+        AmendTarget::Description => {
+            println!(
+                "New description for {}: {}\n",
+                prepared.ticket.key, prepared.ticket.title
+            );
+            println!("{}", amendment.description);
+            if !amendment.acceptance_criteria.is_empty() {
+                println!();
+                println!("  Acceptance criteria:");
+                for criterion in &amendment.acceptance_criteria {
+                    println!("    - {criterion}");
+                }
+            }
+        }
+        AmendTarget::Comment => {
+            println!(
+                "Comment for {}: {}\n",
+                prepared.ticket.key, prepared.ticket.title
+            );
+            println!("{}", amendment.comment);
+        }
+    }
+    println!();
+    println!("  Changed files:");
+    for file in &prepared.changes.files {
+        println!("    {file}");
+    }
+    println!();
 }
 
-pub async fn run(ctx: &AppContext, args: TicketCommandArgs) -> AppResult<TicketWorkflowOutcome> {
-    create_ticket_from_changes(ctx, args.board).await
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let mut short: String = text.chars().take(max - 1).collect();
+    short.push('…');
+    short
 }
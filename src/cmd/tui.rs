@@ -0,0 +1,529 @@
+use std::io;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Position, Rect};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::context::AppContext;
+use crate::domain::branch::BranchCategory;
+use crate::domain::ticket::TicketDraft;
+use crate::error::{AppError, AppResult};
+use crate::workflow::ticket::{PreparedTicket, redraft};
+
+const HELP: &str = "Tab next field · ←/→ category · Ctrl-R regenerate · PgUp/PgDn scroll changes \
+                    · Ctrl-S file ticket · Esc cancel";
+
+const CATEGORIES: [BranchCategory; 3] = [
+    BranchCategory::Feature,
+    BranchCategory::Fix,
+    BranchCategory::Quality,
+];
+
+/// Shows the change summary beside an editable draft until the user files or cancels.
+/// Returns whether to file; `prepared` then holds the edited draft.
+pub async fn review(ctx: &AppContext, prepared: &mut PreparedTicket) -> AppResult<bool> {
+    let mut terminal = ratatui::try_init()?;
+    let result = run_loop(ctx, &mut terminal, prepared).await;
+    ratatui::try_restore()?;
+    result
+}
+
+async fn run_loop(
+    ctx: &AppContext,
+    terminal: &mut DefaultTerminal,
+    prepared: &mut PreparedTicket,
+) -> AppResult<bool> {
+    let mut editor = Editor::from_draft(&prepared.draft);
+    loop {
+        terminal.draw(|frame| draw(frame, prepared, &editor))?;
+        let event = tokio::task::spawn_blocking(event::read)
+            .await
+            .map_err(io::Error::other)??;
+        let Event::Key(key) = event else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match editor.handle_key(key) {
+            Action::Continue => {}
+            Action::Cancel => return Ok(false),
+            Action::File => {
+                if editor.title.text.trim().is_empty() {
+                    editor.status = Some("The title is empty.".to_string());
+                    continue;
+                }
+                if editor.dirty {
+                    let edited = editor.to_draft(&prepared.draft);
+                    prepared.edit_draft(edited);
+                }
+                return Ok(true);
+            }
+            Action::Regenerate => {
+                editor.status = Some("Asking the language model for a new draft…".to_string());
+                terminal.draw(|frame| draw(frame, prepared, &editor))?;
+                let result = redraft(ctx, prepared).await;
+                // Provider warnings are logged to stderr and scribble over the screen.
+                terminal.clear()?;
+                editor = Editor::from_draft(&prepared.draft);
+                editor.status = Some(match result {
+                    Ok(()) => format!(
+                        "New draft from {}.",
+                        prepared.draft.provider.as_deref().unwrap_or("the drafter")
+                    ),
+                    Err(err) => format!("Regenerating failed: {err}"),
+                });
+            }
+        }
+    }
+}
+
+fn draw(frame: &mut Frame, prepared: &PreparedTicket, editor: &Editor) {
+    let [main, help] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+    let [changes_area, draft_area] =
+        Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(main);
+
+    let changes = &prepared.changes;
+    let mut lines = Vec::new();
+    if let Some(rationale) = &prepared.draft.rationale {
+        lines.push(Line::from("Model's rationale").bold());
+        lines.push(Line::from(rationale.as_str()));
+        lines.push(Line::default());
+    }
+    lines.push(Line::from(format!("{} file(s) changed", changes.files_changed)).bold());
+    lines.extend(
+        changes
+            .files
+            .iter()
+            .map(|file| Line::from(format!("  {file}"))),
+    );
+    lines.push(Line::default());
+    lines.extend(changes.summary.lines().map(Line::from));
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(Block::bordered().title(format!(" Changes → {} ", prepared.board)))
+            .wrap(Wrap { trim: false })
+            .scroll((editor.changes_scroll, 0)),
+        changes_area,
+    );
+
+    let [title_area, branch_area, description_area, criteria_area] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Length(3),
+        Constraint::Min(5),
+        Constraint::Length(8),
+    ])
+    .areas(draft_area);
+    let [category_area, slug_area] =
+        Layout::horizontal([Constraint::Length(16), Constraint::Min(0)]).areas(branch_area);
+
+    draw_text(frame, title_area, editor, Field::Title, &editor.title);
+    frame.render_widget(
+        Paragraph::new(format!("◀ {} ▶", editor.category.as_str()))
+            .block(field_block(Field::Category, editor.focus)),
+        category_area,
+    );
+    draw_text(frame, slug_area, editor, Field::Slug, &editor.slug);
+    draw_text(
+        frame,
+        description_area,
+        editor,
+        Field::Description,
+        &editor.description,
+    );
+    draw_text(
+        frame,
+        criteria_area,
+        editor,
+        Field::Criteria,
+        &editor.criteria,
+    );
+
+    let status = editor.status.as_deref().unwrap_or(HELP);
+    frame.render_widget(Paragraph::new(status).reversed(), help);
+}
+
+/// Renders a text field scrolled so its cursor stays visible, placing the terminal cursor
+/// when the field has focus.
+fn draw_text(frame: &mut Frame, area: Rect, editor: &Editor, field: Field, text: &TextField) {
+    let block = field_block(field, editor.focus);
+    let inner = block.inner(area);
+    let (row, col) = text.cursor_position();
+    let scroll = (
+        row.saturating_sub(inner.height.saturating_sub(1)),
+        col.saturating_sub(inner.width.saturating_sub(1)),
+    );
+    frame.render_widget(
+        Paragraph::new(text.text.as_str())
+            .block(block)
+            .scroll(scroll),
+        area,
+    );
+    if editor.focus == field {
+        frame.set_cursor_position(Position::new(
+            inner.x + col - scroll.1,
+            inner.y + row - scroll.0,
+        ));
+    }
+}
+
+fn field_block(field: Field, focus: Field) -> Block<'static> {
+    let block = Block::bordered().title(format!(" {} ", field.label()));
+    if field == focus {
+        block.border_style(Style::new().fg(Color::Cyan))
+    } else {
+        block
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Title,
+    Category,
+    Slug,
+    Description,
+    Criteria,
+}
+
+impl Field {
+    const ORDER: [Field; 5] = [
+        Field::Title,
+        Field::Category,
+        Field::Slug,
+        Field::Description,
+        Field::Criteria,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Field::Title => "Title",
+            Field::Category => "Category",
+            Field::Slug => "Branch slug",
+            Field::Description => "Description",
+            Field::Criteria => "Acceptance criteria (one per line)",
+        }
+    }
+
+    fn multiline(self) -> bool {
+        matches!(self, Field::Description | Field::Criteria)
+    }
+
+    fn step(self, forward: bool) -> Field {
+        let index = Self::ORDER
+            .iter()
+            .position(|field| *field == self)
+            .unwrap_or(0);
+        let len = Self::ORDER.len();
+        Self::ORDER[if forward {
+            (index + 1) % len
+        } else {
+            (index + len - 1) % len
+        }]
+    }
+}
+
+enum Action {
+    Continue,
+    Regenerate,
+    File,
+    Cancel,
+}
+
+/// The draft's editable fields and what the screen shows around them.
+struct Editor {
+    title: TextField,
+    category: BranchCategory,
+    slug: TextField,
+    description: TextField,
+    criteria: TextField,
+    focus: Field,
+    /// Whether anything was edited since the draft was loaded.
+    dirty: bool,
+    changes_scroll: u16,
+    /// Replaces the key help until the next key press.
+    status: Option<String>,
+}
+
+impl Editor {
+    fn from_draft(draft: &TicketDraft) -> Self {
+        Self {
+            title: TextField::new(&draft.title),
+            category: draft.branch_category.clone(),
+            slug: TextField::new(&draft.branch_summary),
+            description: TextField::new(&draft.description),
+            criteria: TextField::new(&draft.acceptance_criteria.join("\n")),
+            focus: Field::Title,
+            dirty: false,
+            changes_scroll: 0,
+            status: None,
+        }
+    }
+
+    /// `original` with the edited fields applied.
+    fn to_draft(&self, original: &TicketDraft) -> TicketDraft {
+        TicketDraft {
+            title: self.title.text.trim().to_string(),
+            description: self.description.text.trim().to_string(),
+            branch_category: self.category.clone(),
+            branch_summary: self.slug.text.trim().to_string(),
+            acceptance_criteria: self
+                .criteria
+                .text
+                .lines()
+                .map(|line| line.trim().trim_start_matches(['-', '*']).trim())
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect(),
+            ..original.clone()
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        self.status = None;
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return Action::Cancel,
+            KeyCode::Char('c') if control => return Action::Cancel,
+            KeyCode::Char('s') if control => return Action::File,
+            KeyCode::Char('r') if control => return Action::Regenerate,
+            KeyCode::Tab => self.focus = self.focus.step(true),
+            KeyCode::BackTab => self.focus = self.focus.step(false),
+            KeyCode::PageDown => self.changes_scroll = self.changes_scroll.saturating_add(10),
+            KeyCode::PageUp => self.changes_scroll = self.changes_scroll.saturating_sub(10),
+            code if self.focus == Field::Category => self.edit_category(code),
+            code => self.edit_text(code, control),
+        }
+        Action::Continue
+    }
+
+    fn edit_category(&mut self, code: KeyCode) {
+        let forward = match code {
+            KeyCode::Right | KeyCode::Char(' ') => true,
+            KeyCode::Left => false,
+            KeyCode::Enter | KeyCode::Down => {
+                self.focus = self.focus.step(true);
+                return;
+            }
+            KeyCode::Up => {
+                self.focus = self.focus.step(false);
+                return;
+            }
+            _ => return,
+        };
+        let index = CATEGORIES
+            .iter()
+            .position(|category| *category == self.category)
+            .unwrap_or(0);
+        let len = CATEGORIES.len();
+        let next = if forward {
+            (index + 1) % len
+        } else {
+            (index + len - 1) % len
+        };
+        self.category = CATEGORIES[next].clone();
+        self.dirty = true;
+    }
+
+    fn edit_text(&mut self, code: KeyCode, control: bool) {
+        let multiline = self.focus.multiline();
+        let field = match self.focus {
+            Field::Title => &mut self.title,
+            Field::Slug => &mut self.slug,
+            Field::Description => &mut self.description,
+            Field::Criteria => &mut self.criteria,
+            Field::Category => return,
+        };
+        match code {
+            KeyCode::Char(ch) if !control => field.insert(ch),
+            KeyCode::Enter if multiline => field.insert('\n'),
+            KeyCode::Backspace => field.backspace(),
+            KeyCode::Delete => field.delete(),
+            KeyCode::Left => field.left(),
+            KeyCode::Right => field.right(),
+            KeyCode::Home => field.home(),
+            KeyCode::End => field.end(),
+            KeyCode::Up if multiline => field.up(),
+            KeyCode::Down if multiline => field.down(),
+            KeyCode::Up => self.focus = self.focus.step(false),
+            KeyCode::Enter | KeyCode::Down => self.focus = self.focus.step(true),
+            _ => {}
+        }
+        if matches!(
+            code,
+            KeyCode::Char(_) | KeyCode::Enter | KeyCode::Backspace | KeyCode::Delete
+        ) && (multiline || code != KeyCode::Enter)
+        {
+            self.dirty = true;
+        }
+    }
+}
+
+/// Text with a cursor, kept on a char boundary as a byte offset.
+#[derive(Debug, Clone, Default)]
+struct TextField {
+    text: String,
+    cursor: usize,
+}
+
+impl TextField {
+    fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            cursor: text.len(),
+        }
+    }
+
+    fn insert(&mut self, ch: char) {
+        self.text.insert(self.cursor, ch);
+        self.cursor += ch.len_utf8();
+    }
+
+    fn backspace(&mut self) {
+        if let Some(ch) = self.text[..self.cursor].chars().next_back() {
+            self.cursor -= ch.len_utf8();
+            self.text.remove(self.cursor);
+        }
+    }
+
+    fn delete(&mut self) {
+        if self.cursor < self.text.len() {
+            self.text.remove(self.cursor);
+        }
+    }
+
+    fn left(&mut self) {
+        if let Some(ch) = self.text[..self.cursor].chars().next_back() {
+            self.cursor -= ch.len_utf8();
+        }
+    }
+
+    fn right(&mut self) {
+        if let Some(ch) = self.text[self.cursor..].chars().next() {
+            self.cursor += ch.len_utf8();
+        }
+    }
+
+    fn line_start(&self, offset: usize) -> usize {
+        self.text[..offset]
+            .rfind('\n')
+            .map_or(0, |newline| newline + 1)
+    }
+
+    fn line_end(&self, offset: usize) -> usize {
+        self.text[offset..]
+            .find('\n')
+            .map_or(self.text.len(), |newline| offset + newline)
+    }
+
+    fn home(&mut self) {
+        self.cursor = self.line_start(self.cursor);
+    }
+
+    fn end(&mut self) {
+        self.cursor = self.line_end(self.cursor);
+    }
+
+    fn up(&mut self) {
+        let start = self.line_start(self.cursor);
+        if start > 0 {
+            let column = self.text[start..self.cursor].chars().count();
+            self.cursor = self.offset_in_line(self.line_start(start - 1), column);
+        }
+    }
+
+    fn down(&mut self) {
+        let end = self.line_end(self.cursor);
+        if end < self.text.len() {
+            let column = self.text[self.line_start(self.cursor)..self.cursor]
+                .chars()
+                .count();
+            self.cursor = self.offset_in_line(end + 1, column);
+        }
+    }
+
+    /// Offset of `column` chars into the line starting at `start`, clamped to its end.
+    fn offset_in_line(&self, start: usize, column: usize) -> usize {
+        let end = self.line_end(start);
+        self.text[start..end]
+            .char_indices()
+            .nth(column)
+            .map_or(end, |(offset, _)| start + offset)
+    }
+
+    /// Row and column of the cursor, counted in lines and chars.
+    fn cursor_position(&self) -> (u16, u16) {
+        let before = &self.text[..self.cursor];
+        let row = before.matches('\n').count();
+        let column = before[self.line_start(self.cursor)..].chars().count();
+        (
+            u16::try_from(row).unwrap_or(u16::MAX),
+            u16::try_from(column).unwrap_or(u16::MAX),
+        )
+    }
+}
+
+/// Fails early when `--tui` cannot take over the terminal.
+pub fn ensure_terminal() -> AppResult<()> {
+    if crate::cmd::config::can_prompt() {
+        Ok(())
+    } else {
+        Err(AppError::Configuration(
+            "--tui needs an interactive terminal".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edits_move_through_lines_and_end_up_in_the_draft() {
+        let draft = TicketDraft {
+            title: "Add refunds".to_string(),
+            description: "Adds refunds.\nPartial ones too.".to_string(),
+            branch_category: BranchCategory::Feature,
+            branch_summary: "add-refunds".to_string(),
+            acceptance_criteria: vec!["Refunds post".to_string()],
+            estimate: Some(3),
+            provider: Some("gemini".to_string()),
+            rationale: None,
+        };
+        let mut editor = Editor::from_draft(&draft);
+        let press = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        editor.handle_key(press(KeyCode::Tab));
+        editor.handle_key(press(KeyCode::Left));
+        assert_eq!(editor.category, BranchCategory::Quality);
+
+        editor.focus = Field::Description;
+        let field = &mut editor.description;
+        field.up();
+        assert_eq!(field.cursor_position(), (0, 13));
+        field.down();
+        field.home();
+        field.insert('é');
+        assert_eq!(field.cursor_position(), (1, 1));
+        field.backspace();
+
+        editor.focus = Field::Criteria;
+        for code in [KeyCode::Enter, KeyCode::Char('-'), KeyCode::Char('X')] {
+            editor.handle_key(press(code));
+        }
+        assert!(matches!(
+            editor.handle_key(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL)),
+            Action::File
+        ));
+
+        let edited = editor.to_draft(&draft);
+        assert_eq!(edited.branch_category, BranchCategory::Quality);
+        assert_eq!(edited.description, draft.description);
+        assert_eq!(edited.acceptance_criteria, ["Refunds post", "X"]);
+        assert_eq!(edited.estimate, Some(3));
+    }
+}
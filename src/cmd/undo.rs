@@ -0,0 +1,49 @@
+use clap::Args;
+
+use crate::cmd::config::confirm;
+use crate::context::AppContext;
+use crate::domain::ticket::TicketRemoval;
+use crate::error::AppResult;
+use crate::workflow::undo::{last_undoable_run, undo_run};
+
+#[derive(Args, Debug, Clone)]
+pub struct UndoArgs {
+    /// Skip the confirmation prompt (required with --non-interactive).
+    #[arg(short, long)]
+    pub yes: bool,
+}
+
+pub async fn run(ctx: &AppContext, args: UndoArgs) -> AppResult<()> {
+    let Some(entry) = last_undoable_run(ctx)? else {
+        println!("Nothing to undo: no tickets recorded for this repository.");
+        return Ok(());
+    };
+
+    if entry.branch_pending {
+        println!("Last run created {} but no branch for it.", entry.key);
+    } else {
+        println!("Last run created {} on branch {}.", entry.key, entry.branch);
+    }
+    if !args.yes && !confirm("Delete the ticket and the branch?", false)? {
+        println!("Nothing changed.");
+        return Ok(());
+    }
+
+    let outcome = undo_run(ctx, &entry).await?;
+    match &outcome.removal {
+        TicketRemoval::Deleted => println!("Deleted {}.", entry.key),
+        TicketRemoval::Closed(status) => {
+            println!(
+                "Closed {} as '{status}' (deleting is not permitted).",
+                entry.key
+            )
+        }
+    }
+    if let Some(branch) = &outcome.switched_to {
+        println!("Switched back to {branch}.");
+    }
+    if outcome.branch_deleted {
+        println!("Deleted local branch {}.", entry.branch);
+    }
+    Ok(())
+}
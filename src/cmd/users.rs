@@ -0,0 +1,126 @@
+use std::time::Duration;
+
+use tracing::{debug, warn};
+
+use crate::cmd::config::{can_prompt, choose};
+use crate::context::AppContext;
+use crate::domain::user::TrackerUser;
+use crate::error::{AppError, AppResult};
+use crate::metadata::MetadataCache;
+
+const USER_SEARCH_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Resolves a free-text `query` (name, email, account ID, or `me`) to a single tracker
+/// user, asking the user to pick when the search is ambiguous. `role` labels the prompt.
+pub(crate) async fn resolve_user(
+    ctx: &AppContext,
+    query: &str,
+    role: &str,
+) -> AppResult<TrackerUser> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Err(AppError::IssueTracker(format!("{role} must not be empty")));
+    }
+    if query.eq_ignore_ascii_case("me") || query == "@me" {
+        return ctx.current_user().await;
+    }
+
+    let candidates = search_users_cached(ctx, query).await?;
+    if let Some(user) = pick_obvious(&candidates, query) {
+        return Ok(user.clone());
+    }
+    if candidates.is_empty() {
+        return Err(AppError::IssueTracker(format!(
+            "no {} user matches {role} '{query}'",
+            ctx.config.tracker.name()
+        )));
+    }
+
+    let labels: Vec<String> = candidates.iter().map(TrackerUser::label).collect();
+    if !can_prompt() {
+        return Err(AppError::IssueTracker(format!(
+            "{role} '{query}' is ambiguous: {}",
+            labels.join(", ")
+        )));
+    }
+    let index = choose(&format!("Several users match {role} '{query}':"), &labels)?;
+    Ok(candidates[index].clone())
+}
+
+async fn search_users_cached(ctx: &AppContext, query: &str) -> AppResult<Vec<TrackerUser>> {
+    let key = format!(
+        "users:{}:{}",
+        ctx.config.tracker_url().unwrap_or_default(),
+        query.to_lowercase()
+    );
+
+    let mut cache = match MetadataCache::load() {
+        Ok(cache) => Some(cache),
+        Err(err) => {
+            warn!(
+                "could not load metadata cache ({err}). Searching {} directly.",
+                ctx.config.tracker.name()
+            );
+            None
+        }
+    };
+    if let Some(users) = cache
+        .as_ref()
+        .and_then(|cache| cache.get::<Vec<TrackerUser>>(&key, USER_SEARCH_TTL))
+    {
+        debug!(%query, "user search served from cache");
+        return Ok(users);
+    }
+
+    let users = ctx.issue_tracker.search_users(query).await?;
+    if let Some(cache) = cache.as_mut() {
+        cache.insert(&key, &users);
+        if let Err(err) = cache.save() {
+            warn!("failed to persist metadata cache ({err}).");
+        }
+    }
+    Ok(users)
+}
+
+/// A lone result, or an exact email/name/account ID match, needs no prompt.
+fn pick_obvious<'a>(candidates: &'a [TrackerUser], query: &str) -> Option<&'a TrackerUser> {
+    if let [only] = candidates {
+        return Some(only);
+    }
+    let mut exact = candidates.iter().filter(|user| {
+        user.account_id == query
+            || user.display_name.eq_ignore_ascii_case(query)
+            || user
+                .email
+                .as_deref()
+                .is_some_and(|email| email.eq_ignore_ascii_case(query))
+    });
+    match (exact.next(), exact.next()) {
+        (Some(user), None) => Some(user),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(id: &str, name: &str, email: Option<&str>) -> TrackerUser {
+        TrackerUser {
+            account_id: id.to_string(),
+            display_name: name.to_string(),
+            email: email.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn exact_email_match_skips_prompt() {
+        let users = vec![
+            user("1", "Sam Lee", Some("sam.lee@example.com")),
+            user("2", "Sam Leeds", Some("sleeds@example.com")),
+        ];
+        let picked = pick_obvious(&users, "SAM.LEE@example.com").unwrap();
+        assert_eq!(picked.account_id, "1");
+        assert!(pick_obvious(&users, "sam").is_none());
+    }
+}
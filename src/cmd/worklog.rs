@@ -0,0 +1,72 @@
+use chrono::{Local, TimeDelta, Utc};
+use clap::Args;
+
+use crate::cmd::config::confirm;
+use crate::cmd::open::branch_ticket;
+use crate::context::AppContext;
+use crate::domain::worklog::{elapsed_since, format_time_spent, parse_time_spent};
+use crate::error::{AppError, AppResult};
+
+#[derive(Args, Debug, Clone)]
+pub struct WorklogArgs {
+    /// Time spent, e.g. `2h`, `1h 30m`, `45m`, or `1d` (8 hours). Defaults to the time
+    /// since the branch's first commit.
+    #[arg(short, long, value_parser = parse_time_spent)]
+    pub time: Option<TimeDelta>,
+    /// What the time went into; added to the worklog entry.
+    #[arg(short, long)]
+    pub message: Option<String>,
+    /// Log to this ticket instead of the current branch's.
+    #[arg(long)]
+    pub key: Option<String>,
+    /// Log an estimated time without asking (required with --non-interactive).
+    #[arg(short, long)]
+    pub yes: bool,
+}
+
+/// Logs work on the current branch's ticket, estimating the time from the branch's first
+/// commit when none is given.
+pub async fn run(ctx: &AppContext, args: WorklogArgs) -> AppResult<()> {
+    let key = match args.key.map(|key| key.trim().to_string()) {
+        Some(key) if !key.is_empty() => key,
+        _ => branch_ticket(ctx).await?,
+    };
+
+    let now = Utc::now();
+    let (time_spent, started) = match args.time {
+        Some(time) => (time, now - time),
+        None => {
+            let branch = ctx.version_control.current_branch().await?.ok_or_else(|| {
+                AppError::VersionControl(
+                    "HEAD is detached, so there is no branch to time; pass --time".to_string(),
+                )
+            })?;
+            let started = ctx
+                .version_control
+                .branch_started_at(&branch)
+                .await?
+                .ok_or_else(|| {
+                    AppError::VersionControl(format!(
+                        "{branch} has no commits of its own yet; pass --time"
+                    ))
+                })?;
+            let time = elapsed_since(started, now);
+            println!(
+                "{branch} started {} ({} ago).",
+                started.with_timezone(&Local).format("%Y-%m-%d %H:%M"),
+                format_time_spent(time)
+            );
+            if !args.yes && !confirm(&format!("Log {} to {key}?", format_time_spent(time)), true)? {
+                println!("Nothing logged.");
+                return Ok(());
+            }
+            (time, started)
+        }
+    };
+
+    ctx.issue_tracker
+        .log_work(&key, time_spent, started, args.message.as_deref())
+        .await?;
+    println!("Logged {} to {key}.", format_time_spent(time_spent));
+    Ok(())
+}
@@ -1,65 +1,686 @@
+use std::collections::BTreeMap;
 use std::env;
+use std::fmt;
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
 
+use chrono::{Days, Local, NaiveDate};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{info, warn};
 
+use crate::config_file::{self, CONFIG_VERSION, ConfigFormat};
+use crate::domain::estimate::{FIBONACCI_SCALE, parse_estimate_scale};
+use crate::env_file;
 use crate::error::{AppError, AppResult};
+use crate::storage::{FileLock, write_atomic};
+use crate::workflow::redact::DEFAULT_EXCLUDED_FILES;
 
 const CONFIG_FILE_NAME: &str = "config.json";
+/// Read instead of `config.json` when it exists.
+pub const TOML_CONFIG_FILE_NAME: &str = "config.toml";
+/// One week: long enough to survive re-runs, short enough to pick up prompt tweaks.
+pub const DEFAULT_DRAFT_CACHE_TTL_HOURS: u64 = 7 * 24;
+
+/// Stored settings (version 1 names, dotted inside tables) and the environment variable
+/// that overrides each one in [`AppConfig::from_stored`].
+pub const ENV_OVERRIDES: &[(&str, &str)] = &[
+    ("behavior.tracker", "UGH_TRACKER"),
+    ("jira.base_url", "UGH_JIRA_BASE_URL"),
+    ("jira.email", "UGH_JIRA_EMAIL"),
+    ("jira.token", "UGH_JIRA_TOKEN"),
+    ("jira.auth", "UGH_JIRA_AUTH"),
+    ("jira.oauth_client_id", "UGH_JIRA_OAUTH_CLIENT_ID"),
+    ("jira.oauth_client_secret", "UGH_JIRA_OAUTH_CLIENT_SECRET"),
+    ("behavior.default_board", "UGH_JIRA_DEFAULT_BOARD"),
+    ("jira.issue_type", "UGH_JIRA_ISSUE_TYPE"),
+    (
+        "jira.acceptance_criteria_field",
+        "UGH_JIRA_ACCEPTANCE_CRITERIA_FIELD",
+    ),
+    ("jira.story_points_field", "UGH_JIRA_STORY_POINTS_FIELD"),
+    ("azure_devops.organization", "UGH_AZURE_DEVOPS_ORGANIZATION"),
+    ("azure_devops.project", "UGH_AZURE_DEVOPS_PROJECT"),
+    (
+        "azure_devops.work_item_type",
+        "UGH_AZURE_DEVOPS_WORK_ITEM_TYPE",
+    ),
+    ("azure_devops.pat", "UGH_AZURE_DEVOPS_PAT"),
+    ("behavior.llm_provider", "UGH_LLM_PROVIDER"),
+    ("behavior.llm_providers", "UGH_LLM_PROVIDERS"),
+    ("behavior.drafting", "UGH_DRAFTING"),
+    ("behavior.ticket_language", "UGH_TICKET_LANGUAGE"),
+    ("gemini.api_key", "UGH_GEMINI_API_KEY"),
+    ("gemini.model", "UGH_GEMINI_MODEL"),
+    ("ollama.base_url", "UGH_OLLAMA_BASE_URL"),
+    ("ollama.model", "UGH_OLLAMA_MODEL"),
+    ("llm.temperature", "UGH_LLM_TEMPERATURE"),
+    ("llm.top_p", "UGH_LLM_TOP_P"),
+    ("llm.max_output_tokens", "UGH_LLM_MAX_OUTPUT_TOKENS"),
+    ("llm.timeout_secs", "UGH_LLM_TIMEOUT_SECS"),
+    ("llm.stream", "UGH_LLM_STREAM"),
+    ("rate_limit.requests_per_second", "UGH_RATE_LIMIT_RPS"),
+    ("rate_limit.max_concurrent", "UGH_RATE_LIMIT_CONCURRENCY"),
+    ("http.proxy", "UGH_HTTP_PROXY"),
+    ("http.ca_bundle", "UGH_CA_BUNDLE"),
+    ("http.max_attempts", "UGH_HTTP_MAX_ATTEMPTS"),
+    ("http.cache", "UGH_HTTP_CACHE"),
+    (
+        "behavior.draft_cache_ttl_hours",
+        "UGH_DRAFT_CACHE_TTL_HOURS",
+    ),
+    ("git.backend", "UGH_GIT_BACKEND"),
+    ("git.workspace_repos", "UGH_WORKSPACE_REPOS"),
+    ("git.github_token", "UGH_GITHUB_TOKEN"),
+    ("behavior.workflow_steps", "UGH_WORKFLOW_STEPS"),
+    ("notifications.slack_webhook_url", "UGH_SLACK_WEBHOOK_URL"),
+    ("notifications.teams_webhook_url", "UGH_TEAMS_WEBHOOK_URL"),
+    ("gateway.signing_secret", "UGH_GATEWAY_SIGNING_SECRET"),
+];
 
 #[derive(Debug, Clone)]
 pub struct AppConfig {
+    /// Which issue tracker tickets are filed in.
+    pub tracker: TrackerKind,
     pub jira_base_url: Option<String>,
     pub jira_token: Option<String>,
     pub jira_email: Option<String>,
+    pub jira_auth: JiraAuthMode,
+    pub jira_oauth_client_id: Option<String>,
+    pub jira_oauth_client_secret: Option<String>,
+    /// Local port for the OAuth callback; must match the app's registered redirect URI.
+    pub jira_oauth_redirect_port: u16,
     pub default_board: Option<String>,
-    pub llm_provider: LlmProvider,
-    pub workspace_root: PathBuf,
+    pub llm_providers: Vec<LlmProvider>,
+    /// Directory ugh was started in. The enclosing repository's toplevel is
+    /// `AppContext::repo_root`.
+    pub working_dir: PathBuf,
     pub gemini_api_key: Option<String>,
     pub gemini_model: String,
+    pub ollama_base_url: String,
+    pub ollama_model: String,
     pub jira_issue_type: String,
+    pub jira_service_desks: BTreeMap<String, ServiceDeskBoard>,
+    pub jira_fields: JiraFields,
+    /// Story point values drafts are estimated on.
+    pub estimate_scale: Vec<u32>,
+    pub redact_exclude_files: Vec<String>,
+    pub http_proxy: Option<String>,
+    pub ca_bundle: Option<PathBuf>,
+    pub http_max_attempts: u32,
+    /// Revalidate repeated tracker GETs with their ETag instead of refetching them.
+    pub http_cache: bool,
+    /// How long a cached draft may be reused; zero disables the draft cache.
+    pub draft_cache_ttl: Duration,
+    /// Days from creation until a ticket is due, keyed by branch category (`fix`, ...).
+    pub due_lead_days: BTreeMap<String, u32>,
+    /// Board per repository-relative path prefix, for monorepos.
+    pub path_boards: BTreeMap<String, String>,
+    pub drafting: DraftingMode,
+    pub git_backend: GitBackend,
+    pub draft_template: DraftTemplate,
+    /// Registered workflow steps to run, in order.
+    pub workflow_steps: Vec<String>,
+    /// Named bundles of ticket settings, applied with `ugh ticket --preset`.
+    pub presets: BTreeMap<String, TicketPreset>,
+    /// Description sections and default labels/components per board and issue type.
+    pub ticket_templates: Vec<TicketTemplate>,
+    /// Incoming webhooks told about each new ticket; a repository's `.ugh.toml` may
+    /// override them.
+    pub slack_webhook_url: Option<String>,
+    pub teams_webhook_url: Option<String>,
+    /// Token for reading pull requests given to `ugh ticket --from`.
+    pub github_token: Option<String>,
+    /// Language drafts are written in (e.g. `Japanese`); branch slugs stay English ASCII.
+    pub ticket_language: Option<String>,
+    /// Extra headers for an API gateway in front of the tracker and model providers.
+    pub gateway: GatewaySettings,
+    /// Pace and concurrency limits for outbound requests, per host.
+    pub rate_limit: RateLimitSettings,
+    /// Sampling, output length, and timeout for language model requests.
+    pub llm: LlmSettings,
+    /// Organization, project, and credentials for Azure DevOps Boards.
+    pub azure_devops: AzureDevOpsSettings,
+    /// Repositories `ugh ticket --workspace` covers, in order.
+    pub workspace_repos: Vec<PathBuf>,
 }
 
+/// The config file's settings, one field per section of the current layout. Every setting
+/// is optional; [`AppConfig::from_stored`] fills in defaults and environment overrides.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
 pub struct StoredConfig {
-    pub jira_base_url: Option<String>,
-    pub jira_token: Option<String>,
-    pub jira_email: Option<String>,
+    pub jira: JiraSection,
+    pub gemini: GeminiSection,
+    pub ollama: OllamaSection,
+    pub git: GitSection,
+    pub http: HttpSection,
+    pub notifications: NotificationSection,
+    pub behavior: BehaviorSection,
+    pub draft_template: Option<DraftTemplate>,
+    pub presets: Option<BTreeMap<String, TicketPreset>>,
+    pub ticket_templates: Option<Vec<TicketTemplate>>,
+    pub gateway: Option<GatewaySettings>,
+    pub rate_limit: Option<RateLimitSettings>,
+    pub llm: Option<LlmSettings>,
+    pub azure_devops: Option<AzureDevOpsSettings>,
+    /// Settings this version does not know, written back unchanged.
+    #[serde(flatten)]
+    pub unknown: BTreeMap<String, Value>,
+}
+
+/// `[jira]`: the site, how to sign in, and how tickets map onto its fields.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct JiraSection {
+    pub base_url: Option<String>,
+    pub email: Option<String>,
+    pub token: Option<String>,
+    /// `token` or `oauth`.
+    pub auth: Option<String>,
+    pub oauth_client_id: Option<String>,
+    pub oauth_client_secret: Option<String>,
+    pub oauth_redirect_port: Option<u16>,
+    pub issue_type: Option<String>,
+    pub service_desks: Option<BTreeMap<String, ServiceDeskBoard>>,
+    pub acceptance_criteria_field: Option<String>,
+    pub story_points_field: Option<String>,
+    pub custom_fields: Option<BTreeMap<String, Value>>,
+    #[serde(flatten)]
+    pub unknown: BTreeMap<String, Value>,
+}
+
+/// `[gemini]`
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct GeminiSection {
+    pub api_key: Option<String>,
+    pub model: Option<String>,
+    #[serde(flatten)]
+    pub unknown: BTreeMap<String, Value>,
+}
+
+/// `[ollama]`
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct OllamaSection {
+    pub base_url: Option<String>,
+    pub model: Option<String>,
+    #[serde(flatten)]
+    pub unknown: BTreeMap<String, Value>,
+}
+
+/// `[git]`: how ugh talks to the repositories it works in.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct GitSection {
+    /// `auto`, `libgit2`, or `cli`.
+    pub backend: Option<String>,
+    pub workspace_repos: Option<Vec<String>>,
+    pub github_token: Option<String>,
+    #[serde(flatten)]
+    pub unknown: BTreeMap<String, Value>,
+}
+
+/// `[http]`: outbound connections.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct HttpSection {
+    pub proxy: Option<String>,
+    pub ca_bundle: Option<String>,
+    pub max_attempts: Option<u32>,
+    pub cache: Option<bool>,
+    #[serde(flatten)]
+    pub unknown: BTreeMap<String, Value>,
+}
+
+/// `[notifications]`: where new tickets are announced.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct NotificationSection {
+    pub slack_webhook_url: Option<String>,
+    pub teams_webhook_url: Option<String>,
+    #[serde(flatten)]
+    pub unknown: BTreeMap<String, Value>,
+}
+
+/// `[behavior]`: which tracker and board tickets go to and how they are drafted.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct BehaviorSection {
+    /// `jira` or `azure_devops`.
+    pub tracker: Option<String>,
     pub default_board: Option<String>,
     pub llm_provider: Option<String>,
-    pub gemini_api_key: Option<String>,
-    pub gemini_model: Option<String>,
-    pub jira_issue_type: Option<String>,
+    pub llm_providers: Option<Vec<String>>,
+    pub drafting: Option<String>,
+    pub ticket_language: Option<String>,
+    pub estimate_scale: Option<String>,
+    pub redact_exclude_files: Option<Vec<String>>,
+    pub draft_cache_ttl_hours: Option<u64>,
+    pub due_lead_days: Option<BTreeMap<String, u32>>,
+    pub path_boards: Option<BTreeMap<String, String>>,
+    pub workflow_steps: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub unknown: BTreeMap<String, Value>,
+}
+
+/// Marks a board as a Jira Service Management project whose tickets are created as requests.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceDeskBoard {
+    /// Service desk ID; resolved from the project key when omitted.
+    pub service_desk_id: Option<String>,
+    /// Request type name or numeric ID.
+    pub request_type: String,
+    /// When false, the drafted description is posted as an internal comment instead.
+    #[serde(default = "default_customer_visible")]
+    pub customer_visible: bool,
+}
+
+fn default_customer_visible() -> bool {
+    true
+}
+
+/// Settings for a recurring kind of ticket, e.g. a hotfix branched from `main`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TicketPreset {
+    pub issue_type: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub priority: Option<String>,
+    /// Branch name layout with `{category}`, `{key}`, and `{slug}` placeholders.
+    pub branch_template: Option<String>,
+    /// Branch the ticket branch starts from instead of the current commit.
+    pub base_branch: Option<String>,
+}
+
+/// Structure and defaults for tickets on a board and/or of an issue type. A template naming
+/// both beats one naming the board, which beats one naming the issue type; one naming
+/// neither applies everywhere else.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct TicketTemplate {
+    /// Board (project key) the template is for, matched case-insensitively.
+    pub board: Option<String>,
+    /// Issue type name the template is for, e.g. `Story`.
+    pub issue_type: Option<String>,
+    /// Description headings the drafter fills in order, e.g. `Background`, `Scope`.
+    pub sections: Vec<String>,
+    /// Added to every ticket, after any preset labels.
+    pub labels: Vec<String>,
+    /// Jira component names set on every ticket.
+    pub components: Vec<String>,
+}
+
+/// Headers a corporate API gateway expects on every outbound request.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct GatewaySettings {
+    /// Hosts that get the headers: exact names, or `.corp.example` for every subdomain.
+    /// Required whenever headers are configured.
+    pub hosts: Vec<String>,
+    /// Sent as-is, e.g. `{"X-Team": "payments"}`.
+    pub headers: BTreeMap<String, String>,
+    /// Header carrying a fresh random ID for each request, e.g. `X-Request-Id`.
+    pub request_id_header: Option<String>,
+    /// Header carrying the hex HMAC-SHA256 of the request; needs `signing_secret`.
+    pub signature_header: Option<String>,
+    /// Header carrying the Unix time the signature covers (default `X-Timestamp`).
+    pub timestamp_header: Option<String>,
+    /// HMAC key; `UGH_GATEWAY_SIGNING_SECRET` takes precedence.
+    pub signing_secret: Option<String>,
+}
+
+impl GatewaySettings {
+    pub fn is_empty(&self) -> bool {
+        self.headers.is_empty()
+            && self.request_id_header.is_none()
+            && self.signature_header.is_none()
+    }
+}
+
+/// Limits on outbound requests, applied to each host separately so a slow tracker does not
+/// hold up the model provider.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(default)]
+pub struct RateLimitSettings {
+    /// Requests per second to any one host; unset means unlimited.
+    pub requests_per_second: Option<f64>,
+    /// Requests in flight to any one host at once; unset means unlimited.
+    pub max_concurrent: Option<usize>,
+    /// Requests per second for particular hosts, overriding `requests_per_second`. A
+    /// leading dot (`.corp.example`) covers a domain and its subdomains.
+    pub hosts: BTreeMap<String, f64>,
+}
+
+impl RateLimitSettings {
+    pub fn validate(&self) -> AppResult<()> {
+        let invalid =
+            |message: String| Err(AppError::Configuration(format!("rate_limit.{message}")));
+        if let Some(rate) = self.requests_per_second
+            && !(rate > 0.0 && rate.is_finite())
+        {
+            return invalid(format!("requests_per_second must be above 0, not {rate}"));
+        }
+        if self.max_concurrent == Some(0) {
+            return invalid("max_concurrent must be at least 1".to_string());
+        }
+        for (host, rate) in &self.hosts {
+            if !(*rate > 0.0 && rate.is_finite()) {
+                return invalid(format!("hosts.{host} must be above 0, not {rate}"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Generation parameters passed to every language model provider; unset values keep the
+/// provider's defaults.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+#[serde(default)]
+pub struct LlmSettings {
+    /// 0.0 to 2.0; lower is more deterministic.
+    pub temperature: Option<f32>,
+    /// Nucleus sampling cutoff, 0.0 to 1.0.
+    pub top_p: Option<f32>,
+    /// Longest reply, in tokens.
+    pub max_output_tokens: Option<u32>,
+    /// Seconds to wait for one reply; Gemini defaults to 20 and Ollama to 60.
+    pub timeout_secs: Option<u64>,
+    /// Stream replies so progress shows while they arrive; on unless set to `false`.
+    pub stream: Option<bool>,
+}
+
+impl LlmSettings {
+    pub fn validate(&self) -> AppResult<()> {
+        let invalid = |message: &str| Err(AppError::Configuration(format!("llm.{message}")));
+        if self
+            .temperature
+            .is_some_and(|value| !(0.0..=2.0).contains(&value))
+        {
+            return invalid("temperature must be between 0.0 and 2.0");
+        }
+        if self
+            .top_p
+            .is_some_and(|value| !(0.0..=1.0).contains(&value))
+        {
+            return invalid("top_p must be between 0.0 and 1.0");
+        }
+        if self.max_output_tokens == Some(0) {
+            return invalid("max_output_tokens must be at least 1");
+        }
+        if self.timeout_secs == Some(0) {
+            return invalid("timeout_secs must be at least 1");
+        }
+        Ok(())
+    }
+
+    pub fn streaming(&self) -> bool {
+        self.stream.unwrap_or(true)
+    }
+
+    /// The configured timeout, or `default` when none is set.
+    pub fn timeout_or(&self, default: Duration) -> Duration {
+        self.timeout_secs.map_or(default, Duration::from_secs)
+    }
+}
+
+/// Azure DevOps Boards settings; the project doubles as the default board.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct AzureDevOpsSettings {
+    /// Organization name (`contoso`) or the collection URL of an Azure DevOps Server.
+    pub organization: Option<String>,
+    /// Project new work items are created in unless `--board` names another.
+    pub project: Option<String>,
+    /// Work item type for new tickets (default `Task`).
+    pub work_item_type: Option<String>,
+    /// Personal access token with Work Items read and write scope; `UGH_AZURE_DEVOPS_PAT`
+    /// takes precedence.
+    pub pat: Option<String>,
+}
+
+impl AzureDevOpsSettings {
+    /// `https://dev.azure.com/<organization>`, or the collection URL as configured.
+    pub fn organization_url(&self) -> Option<String> {
+        let organization = self.organization.as_deref()?.trim().trim_end_matches('/');
+        if organization.is_empty() {
+            None
+        } else if organization.starts_with("https://") || organization.starts_with("http://") {
+            Some(organization.to_string())
+        } else {
+            Some(format!("https://dev.azure.com/{organization}"))
+        }
+    }
+
+    pub fn work_item_type(&self) -> &str {
+        self.work_item_type.as_deref().unwrap_or("Task")
+    }
+}
+
+/// Site-specific custom fields (`customfield_*`): where draft data Jira has no system field
+/// for goes, and values every new ticket must carry.
+#[derive(Debug, Clone, Default)]
+pub struct JiraFields {
+    /// Receives acceptance criteria instead of the description.
+    pub acceptance_criteria: Option<String>,
+    /// Receives the draft's estimate; estimates are only requested when this is set.
+    pub story_points: Option<String>,
+    /// Field ID to value for every ticket. Strings may use `{board}`, `{title}`,
+    /// `{category}`, and `{date}` placeholders.
+    pub values: BTreeMap<String, Value>,
 }
 
 impl StoredConfig {
+    /// Reads the config file, upgrading a file in an older layout in place.
     pub fn load() -> AppResult<Self> {
         let path = config_file_path()?;
-        match fs::read_to_string(&path) {
-            Ok(contents) => serde_json::from_str(&contents)
-                .map_err(|err| AppError::Configuration(format!("invalid config file: {err}"))),
-            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
-            Err(err) => Err(AppError::Io(err)),
+        let Some((stored, version)) = Self::read(&path)? else {
+            return Ok(Self::default());
+        };
+        if version < CONFIG_VERSION {
+            match stored.upgrade_file(&path, version) {
+                Ok(backup) => info!(
+                    "upgraded {} to config layout version {CONFIG_VERSION}; the old file is kept \
+                     as {}",
+                    path.display(),
+                    backup.display()
+                ),
+                Err(err) => warn!(
+                    "could not upgrade {} to config layout version {CONFIG_VERSION} ({err}); \
+                     reading it as it is.",
+                    path.display()
+                ),
+            }
         }
+        Ok(stored)
+    }
+
+    /// The settings in `path` and the layout version they were written in, or `None`
+    /// when there is no such file.
+    pub fn read(path: &Path) -> AppResult<Option<(Self, u64)>> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(AppError::Io(err)),
+        };
+        let document = config_file::parse(&contents, ConfigFormat::of(path))?;
+        let stored = serde_json::from_value(document.settings)
+            .map_err(|err| AppError::Configuration(format!("invalid config file: {err}")))?;
+        Ok(Some((stored, document.version)))
+    }
+
+    /// Keeps `path` as `<name>.v<version>` and rewrites it in the current layout. Returns
+    /// where the old file went.
+    pub fn upgrade_file(&self, path: &Path, version: u64) -> AppResult<PathBuf> {
+        let mut backup = path.as_os_str().to_owned();
+        backup.push(format!(".v{version}"));
+        let backup = PathBuf::from(backup);
+        fs::copy(path, &backup)?;
+        self.save_to(path)?;
+        Ok(backup)
     }
 
     pub fn save(&self) -> AppResult<()> {
-        let path = config_file_path()?;
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)?;
+        self.save_to(&config_file_path()?)
+    }
+
+    /// Dotted names of the settings this version does not know, at the top level and
+    /// within the sections.
+    pub fn unknown_settings(&self) -> Vec<String> {
+        let sections = [
+            ("jira", &self.jira.unknown),
+            ("gemini", &self.gemini.unknown),
+            ("ollama", &self.ollama.unknown),
+            ("git", &self.git.unknown),
+            ("http", &self.http.unknown),
+            ("notifications", &self.notifications.unknown),
+            ("behavior", &self.behavior.unknown),
+        ];
+        let mut names: Vec<String> = self.unknown.keys().cloned().collect();
+        for (section, unknown) in sections {
+            names.extend(unknown.keys().map(|key| format!("{section}.{key}")));
         }
-        let json = serde_json::to_string_pretty(self)
+        names
+    }
+
+    /// Writes the settings to `path` in the current layout, as TOML for a `.toml` path.
+    pub fn save_to(&self, path: &Path) -> AppResult<()> {
+        // Through text, so `f32` settings keep their short form (0.2, not 0.200000003).
+        let settings = serde_json::to_string(self)
+            .and_then(|json| serde_json::from_str(&json))
             .map_err(|err| AppError::Configuration(format!("failed to serialize config: {err}")))?;
-        fs::write(path, json)?;
+        let contents = config_file::render(settings, ConfigFormat::of(path))?;
+        let _lock = FileLock::acquire(path)?;
+        write_atomic(path, contents.as_bytes())?;
         Ok(())
     }
 }
 
-#[derive(Debug, Clone)]
+/// How `ugh` authenticates to Jira.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JiraAuthMode {
+    /// Email and API token (basic auth).
+    ApiToken,
+    /// OAuth 2.0 (3LO) tokens from `ugh config login jira`.
+    OAuth,
+}
+
+impl JiraAuthMode {
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "token" | "api_token" | "basic" => Some(JiraAuthMode::ApiToken),
+            "oauth" => Some(JiraAuthMode::OAuth),
+            _ => None,
+        }
+    }
+}
+
+/// Where tickets are filed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrackerKind {
+    Jira,
+    /// Azure DevOps Boards work items.
+    AzureDevOps,
+}
+
+impl TrackerKind {
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value
+            .trim()
+            .to_lowercase()
+            .replace(['-', ' '], "_")
+            .as_str()
+        {
+            "jira" => Some(TrackerKind::Jira),
+            "azure_devops" | "azure" | "ado" => Some(TrackerKind::AzureDevOps),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TrackerKind::Jira => "Jira",
+            TrackerKind::AzureDevOps => "Azure DevOps",
+        }
+    }
+}
+
+/// Which implementation talks to the repository.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitBackend {
+    /// The `git` binary when one is on `PATH`, libgit2 otherwise.
+    Auto,
+    /// Shells out to the `git` binary.
+    Cli,
+    /// Links libgit2; works without git installed and independent of the locale.
+    Libgit2,
+}
+
+impl GitBackend {
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "auto" => Some(GitBackend::Auto),
+            "cli" | "git" => Some(GitBackend::Cli),
+            "libgit2" | "git2" => Some(GitBackend::Libgit2),
+            _ => None,
+        }
+    }
+}
+
+/// How ticket text is produced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DraftingMode {
+    /// Language model providers, falling back to heuristics.
+    Llm,
+    /// Configured templates filled with change data only; nothing generative runs.
+    Template,
+}
+
+impl DraftingMode {
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "llm" => Some(DraftingMode::Llm),
+            "template" => Some(DraftingMode::Template),
+            _ => None,
+        }
+    }
+}
+
+/// Templates for `drafting = "template"`. Placeholders: `{files}` (bullet list),
+/// `{files_changed}`, `{directories}`, `{summary}` (git status/diff stat), `{date}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DraftTemplate {
+    pub title: String,
+    pub description: String,
+    pub branch_category: String,
+    pub branch_summary: String,
+    /// One template per acceptance criterion; none by default.
+    pub acceptance_criteria: Vec<String>,
+}
+
+impl Default for DraftTemplate {
+    fn default() -> Self {
+        Self {
+            title: "Update {directories} ({files_changed} files)".to_string(),
+            description: "Changed files:\n{files}\n\n{summary}".to_string(),
+            branch_category: "feature".to_string(),
+            branch_summary: "update-{directories}".to_string(),
+            acceptance_criteria: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum LlmProvider {
     Gemini,
+    Ollama,
+    /// `none`: no model is called and drafts come from heuristics alone.
+    Disabled,
     Custom(String),
 }
 
@@ -67,63 +688,370 @@ impl LlmProvider {
     pub fn from_str(value: &str) -> Option<Self> {
         match value.trim().to_lowercase().as_str() {
             "gemini" => Some(LlmProvider::Gemini),
+            "ollama" => Some(LlmProvider::Ollama),
+            "none" => Some(LlmProvider::Disabled),
             other if !other.is_empty() => Some(LlmProvider::Custom(other.to_string())),
             _ => None,
         }
     }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            LlmProvider::Gemini => "gemini",
+            LlmProvider::Ollama => "ollama",
+            LlmProvider::Disabled => "none",
+            LlmProvider::Custom(name) => name,
+        }
+    }
+
+    /// Parses an ordered provider list, skipping blanks and duplicates. A list naming
+    /// `none` turns language models off altogether.
+    pub fn parse_list<I, S>(values: I) -> Vec<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut providers = Vec::new();
+        for value in values {
+            if let Some(provider) = LlmProvider::from_str(value.as_ref())
+                && !providers.contains(&provider)
+            {
+                providers.push(provider);
+            }
+        }
+        if providers.contains(&LlmProvider::Disabled) {
+            return vec![LlmProvider::Disabled];
+        }
+        providers
+    }
 }
 
 impl AppConfig {
+    /// The most specific ticket template for `board` and `issue_type`, if any applies.
+    pub fn ticket_template(&self, board: &str, issue_type: &str) -> Option<&TicketTemplate> {
+        let matches = |wanted: &Option<String>, actual: &str| {
+            wanted
+                .as_deref()
+                .is_none_or(|wanted| wanted.trim().eq_ignore_ascii_case(actual.trim()))
+        };
+        // On a tie the template listed first wins.
+        self.ticket_templates
+            .iter()
+            .rev()
+            .filter(|template| {
+                matches(&template.board, board) && matches(&template.issue_type, issue_type)
+            })
+            .max_by_key(|template| (template.board.is_some(), template.issue_type.is_some()))
+    }
+
+    /// Issue type new tickets get unless a preset names one.
+    pub fn default_issue_type(&self) -> &str {
+        match self.tracker {
+            TrackerKind::Jira => &self.jira_issue_type,
+            TrackerKind::AzureDevOps => self.azure_devops.work_item_type(),
+        }
+    }
+
+    /// Where the configured tracker lives, for scoping cached tracker data.
+    pub fn tracker_url(&self) -> Option<String> {
+        match self.tracker {
+            TrackerKind::Jira => self.jira_base_url.clone(),
+            TrackerKind::AzureDevOps => self.azure_devops.organization_url(),
+        }
+    }
+
+    /// Today plus the configured lead time for tickets of `category`, if it has one.
+    pub fn default_due_date(&self, category: &str) -> Option<NaiveDate> {
+        let lead_days = self.due_lead_days.get(category)?;
+        Local::now()
+            .date_naive()
+            .checked_add_days(Days::new(u64::from(*lead_days)))
+    }
+
+    /// True with `--no-llm` or `llm_provider = "none"`.
+    pub fn llm_disabled(&self) -> bool {
+        self.llm_providers.contains(&LlmProvider::Disabled)
+    }
+
+    /// What `--no-llm` does: drafts come from heuristics and no model is called.
+    pub fn disable_llm(&mut self) {
+        self.llm_providers = vec![LlmProvider::Disabled];
+    }
+
     pub fn load(workspace_hint: &Path) -> AppResult<Self> {
         let stored = StoredConfig::load()?;
+        Self::from_stored(&stored, workspace_hint)
+    }
 
-        let jira_base_url = env::var("UGH_JIRA_BASE_URL")
-            .ok()
-            .or(stored.jira_base_url.clone());
-        let jira_token = env::var("UGH_JIRA_TOKEN")
-            .ok()
-            .or(stored.jira_token.clone());
-        let jira_email = env::var("UGH_JIRA_EMAIL")
-            .ok()
-            .or(stored.jira_email.clone());
-        let default_board = env::var("UGH_JIRA_DEFAULT_BOARD")
-            .ok()
-            .or(stored.default_board.clone());
-
-        let llm_provider = env::var("UGH_LLM_PROVIDER")
-            .ok()
-            .or(stored.llm_provider.clone())
-            .and_then(|value| LlmProvider::from_str(&value))
-            .unwrap_or(LlmProvider::Gemini);
-
-        let gemini_api_key = env::var("UGH_GEMINI_API_KEY")
-            .ok()
-            .or(stored.gemini_api_key.clone());
-        let gemini_model = env::var("UGH_GEMINI_MODEL")
-            .ok()
-            .or(stored.gemini_model.clone())
+    /// Resolves effective settings from stored values, letting environment variables (or
+    /// the repository's env file, see [`env_file`]) win. A numeric or on/off variable that
+    /// does not parse is an error naming it.
+    pub fn from_stored(stored: &StoredConfig, workspace_hint: &Path) -> AppResult<Self> {
+        let jira_base_url = env_file::var("UGH_JIRA_BASE_URL").or(stored.jira.base_url.clone());
+        let jira_token = env_file::var("UGH_JIRA_TOKEN").or(stored.jira.token.clone());
+        let jira_email = env_file::var("UGH_JIRA_EMAIL").or(stored.jira.email.clone());
+        let jira_auth = env_file::var("UGH_JIRA_AUTH")
+            .or(stored.jira.auth.clone())
+            .and_then(|value| JiraAuthMode::from_str(&value))
+            .unwrap_or(JiraAuthMode::ApiToken);
+        let jira_oauth_client_id =
+            env_file::var("UGH_JIRA_OAUTH_CLIENT_ID").or(stored.jira.oauth_client_id.clone());
+        let jira_oauth_client_secret = env_file::var("UGH_JIRA_OAUTH_CLIENT_SECRET")
+            .or(stored.jira.oauth_client_secret.clone());
+        let tracker = env_file::var("UGH_TRACKER")
+            .or(stored.behavior.tracker.clone())
+            .and_then(|value| TrackerKind::from_str(&value))
+            .unwrap_or(TrackerKind::Jira);
+        let default_board =
+            env_file::var("UGH_JIRA_DEFAULT_BOARD").or(stored.behavior.default_board.clone());
+
+        let provider_list = env_file::var("UGH_LLM_PROVIDERS")
+            .map(|value| value.split(',').map(str::to_string).collect::<Vec<_>>())
+            .or(stored.behavior.llm_providers.clone())
+            .map(LlmProvider::parse_list)
+            .unwrap_or_default();
+        let llm_providers = if provider_list.is_empty() {
+            let single = env_file::var("UGH_LLM_PROVIDER")
+                .or(stored.behavior.llm_provider.clone())
+                .and_then(|value| LlmProvider::from_str(&value))
+                .unwrap_or(LlmProvider::Gemini);
+            vec![single]
+        } else {
+            provider_list
+        };
+
+        let gemini_api_key = env_file::var("UGH_GEMINI_API_KEY").or(stored.gemini.api_key.clone());
+        let gemini_model = env_file::var("UGH_GEMINI_MODEL")
+            .or(stored.gemini.model.clone())
             .unwrap_or_else(|| "gemini-2.5-flash".to_string());
-        let jira_issue_type = env::var("UGH_JIRA_ISSUE_TYPE")
-            .ok()
-            .or(stored.jira_issue_type.clone())
+        let ollama_base_url = env_file::var("UGH_OLLAMA_BASE_URL")
+            .or(stored.ollama.base_url.clone())
+            .unwrap_or_else(|| "http://localhost:11434".to_string());
+        let ollama_model = env_file::var("UGH_OLLAMA_MODEL")
+            .or(stored.ollama.model.clone())
+            .unwrap_or_else(|| "llama3.1".to_string());
+        let http_proxy = env_file::var("UGH_HTTP_PROXY")
+            .or(stored.http.proxy.clone())
+            .filter(|value| !value.trim().is_empty());
+        let ca_bundle = env_file::var("UGH_CA_BUNDLE")
+            .or(stored.http.ca_bundle.clone())
+            .filter(|value| !value.trim().is_empty())
+            .map(PathBuf::from);
+        let http_max_attempts = parsed_var("UGH_HTTP_MAX_ATTEMPTS")?
+            .or(stored.http.max_attempts)
+            .unwrap_or(3)
+            .max(1);
+        let http_cache = env_file::var("UGH_HTTP_CACHE")
+            .map(|value| !matches!(value.trim().to_lowercase().as_str(), "0" | "false" | "no"))
+            .or(stored.http.cache)
+            .unwrap_or(true);
+        let draft_cache_ttl_hours = parsed_var("UGH_DRAFT_CACHE_TTL_HOURS")?
+            .or(stored.behavior.draft_cache_ttl_hours)
+            .unwrap_or(DEFAULT_DRAFT_CACHE_TTL_HOURS);
+        let jira_issue_type = env_file::var("UGH_JIRA_ISSUE_TYPE")
+            .or(stored.jira.issue_type.clone())
             .unwrap_or_else(|| "Task".to_string());
 
+        let trimmed_setting = |name: &str, stored: &Option<String>| {
+            env_file::var(name)
+                .or(stored.clone())
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        let jira_fields = JiraFields {
+            acceptance_criteria: trimmed_setting(
+                "UGH_JIRA_ACCEPTANCE_CRITERIA_FIELD",
+                &stored.jira.acceptance_criteria_field,
+            ),
+            story_points: trimmed_setting(
+                "UGH_JIRA_STORY_POINTS_FIELD",
+                &stored.jira.story_points_field,
+            ),
+            values: stored.jira.custom_fields.clone().unwrap_or_default(),
+        };
+        let estimate_scale = stored
+            .behavior
+            .estimate_scale
+            .as_deref()
+            .and_then(parse_estimate_scale)
+            .unwrap_or_else(|| FIBONACCI_SCALE.to_vec());
+
+        let drafting = env_file::var("UGH_DRAFTING")
+            .or(stored.behavior.drafting.clone())
+            .and_then(|value| DraftingMode::from_str(&value))
+            .unwrap_or(DraftingMode::Llm);
+        let git_backend = env_file::var("UGH_GIT_BACKEND")
+            .or(stored.git.backend.clone())
+            .and_then(|value| GitBackend::from_str(&value))
+            .unwrap_or(GitBackend::Auto);
+
+        let gateway = stored.gateway.clone().unwrap_or_default();
+        let stored_llm = stored.llm.unwrap_or_default();
+        let llm = LlmSettings {
+            temperature: parsed_var("UGH_LLM_TEMPERATURE")?.or(stored_llm.temperature),
+            top_p: parsed_var("UGH_LLM_TOP_P")?.or(stored_llm.top_p),
+            max_output_tokens: parsed_var("UGH_LLM_MAX_OUTPUT_TOKENS")?
+                .or(stored_llm.max_output_tokens),
+            timeout_secs: parsed_var("UGH_LLM_TIMEOUT_SECS")?.or(stored_llm.timeout_secs),
+            stream: parsed_var("UGH_LLM_STREAM")?.or(stored_llm.stream),
+        };
+
+        let stored_rate_limit = stored.rate_limit.clone().unwrap_or_default();
+        let rate_limit = RateLimitSettings {
+            requests_per_second: parsed_var("UGH_RATE_LIMIT_RPS")?
+                .or(stored_rate_limit.requests_per_second),
+            max_concurrent: parsed_var("UGH_RATE_LIMIT_CONCURRENCY")?
+                .or(stored_rate_limit.max_concurrent),
+            ..stored_rate_limit
+        };
+
+        let stored_azure = stored.azure_devops.clone().unwrap_or_default();
+        let azure_devops = AzureDevOpsSettings {
+            organization: trimmed_setting(
+                "UGH_AZURE_DEVOPS_ORGANIZATION",
+                &stored_azure.organization,
+            ),
+            project: trimmed_setting("UGH_AZURE_DEVOPS_PROJECT", &stored_azure.project),
+            work_item_type: trimmed_setting(
+                "UGH_AZURE_DEVOPS_WORK_ITEM_TYPE",
+                &stored_azure.work_item_type,
+            ),
+            pat: trimmed_setting("UGH_AZURE_DEVOPS_PAT", &stored_azure.pat),
+        };
+        // The Azure DevOps project is the default board when none is set.
+        let default_board = match tracker {
+            TrackerKind::AzureDevOps => default_board.or(azure_devops.project.clone()),
+            TrackerKind::Jira => default_board,
+        };
+
+        let workflow_steps = env_file::var("UGH_WORKFLOW_STEPS")
+            .map(|value| value.split(',').map(str::to_string).collect::<Vec<_>>())
+            .or(stored.behavior.workflow_steps.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|name| name.trim().to_lowercase())
+            .filter(|name| !name.is_empty())
+            .collect();
+
+        // Like PATH: `:`-separated (`;` on Windows).
+        let workspace_repos = env_file::var("UGH_WORKSPACE_REPOS")
+            .map(|value| {
+                env::split_paths(&value)
+                    .map(|path| path.to_string_lossy().into_owned())
+                    .collect::<Vec<_>>()
+            })
+            .or(stored.git.workspace_repos.clone())
+            .unwrap_or_default()
+            .iter()
+            .map(|path| path.trim())
+            .filter(|path| !path.is_empty())
+            .map(expand_home)
+            .collect();
+
         Ok(Self {
+            tracker,
             jira_base_url,
             jira_token,
             jira_email,
+            jira_auth,
+            jira_oauth_client_id,
+            jira_oauth_client_secret,
+            jira_oauth_redirect_port: stored.jira.oauth_redirect_port.unwrap_or(8723),
             default_board,
-            llm_provider,
-            workspace_root: workspace_hint.to_path_buf(),
+            llm_providers,
+            working_dir: workspace_hint.to_path_buf(),
             gemini_api_key,
             gemini_model,
+            ollama_base_url,
+            ollama_model,
             jira_issue_type,
+            jira_service_desks: stored.jira.service_desks.clone().unwrap_or_default(),
+            jira_fields,
+            estimate_scale,
+            redact_exclude_files: stored.behavior.redact_exclude_files.clone().unwrap_or_else(
+                || {
+                    DEFAULT_EXCLUDED_FILES
+                        .iter()
+                        .map(|pattern| pattern.to_string())
+                        .collect()
+                },
+            ),
+            http_proxy,
+            ca_bundle,
+            http_max_attempts,
+            http_cache,
+            draft_cache_ttl: Duration::from_secs(draft_cache_ttl_hours.saturating_mul(60 * 60)),
+            due_lead_days: stored
+                .behavior
+                .due_lead_days
+                .iter()
+                .flatten()
+                .map(|(category, days)| (category.trim().to_lowercase(), *days))
+                .collect(),
+            path_boards: stored.behavior.path_boards.clone().unwrap_or_default(),
+            drafting,
+            git_backend,
+            draft_template: stored.draft_template.clone().unwrap_or_default(),
+            workflow_steps,
+            presets: stored.presets.clone().unwrap_or_default(),
+            ticket_templates: stored.ticket_templates.clone().unwrap_or_default(),
+            slack_webhook_url: trimmed_setting(
+                "UGH_SLACK_WEBHOOK_URL",
+                &stored.notifications.slack_webhook_url,
+            ),
+            teams_webhook_url: trimmed_setting(
+                "UGH_TEAMS_WEBHOOK_URL",
+                &stored.notifications.teams_webhook_url,
+            ),
+            github_token: trimmed_setting("UGH_GITHUB_TOKEN", &stored.git.github_token),
+            ticket_language: trimmed_setting(
+                "UGH_TICKET_LANGUAGE",
+                &stored.behavior.ticket_language,
+            )
+            .filter(|language| !language.eq_ignore_ascii_case("english")),
+            gateway: GatewaySettings {
+                signing_secret: trimmed_setting(
+                    "UGH_GATEWAY_SIGNING_SECRET",
+                    &gateway.signing_secret,
+                ),
+                ..gateway
+            },
+            rate_limit,
+            llm,
+            azure_devops,
+            workspace_repos,
         })
     }
 }
 
+/// The value of the variable `name` read as a `T`, or `None` when it is unset.
+fn parsed_var<T>(name: &str) -> AppResult<Option<T>>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let Some(value) = env_file::var(name) else {
+        return Ok(None);
+    };
+    value.trim().parse().map(Some).map_err(|err| {
+        AppError::Configuration(format!(
+            "{name} is set to '{value}', which is invalid ({err})"
+        ))
+    })
+}
+
+/// True until a configuration file has been written for the first time.
+pub fn is_first_run() -> AppResult<bool> {
+    Ok(!config_file_path()?.exists())
+}
+
+/// `config.toml` in the config directory when it exists, `config.json` otherwise.
 pub fn config_file_path() -> AppResult<PathBuf> {
     let dir = config_directory()?;
+    let toml = dir.join(TOML_CONFIG_FILE_NAME);
+    if toml.exists() {
+        return Ok(toml);
+    }
     Ok(dir.join(CONFIG_FILE_NAME))
 }
 
@@ -142,10 +1070,10 @@ pub fn config_directory() -> AppResult<PathBuf> {
 }
 
 fn platform_config_dir() -> Option<PathBuf> {
-    if let Ok(path) = env::var("XDG_CONFIG_HOME") {
-        if !path.trim().is_empty() {
-            return Some(PathBuf::from(path).join("ugh"));
-        }
+    if let Ok(path) = env::var("XDG_CONFIG_HOME")
+        && !path.trim().is_empty()
+    {
+        return Some(PathBuf::from(path).join("ugh"));
     }
 
     if cfg!(target_os = "macos") {
@@ -153,10 +1081,10 @@ fn platform_config_dir() -> Option<PathBuf> {
             return Some(home.join("Library").join("Application Support").join("ugh"));
         }
     } else if cfg!(target_os = "windows") {
-        if let Ok(appdata) = env::var("APPDATA") {
-            if !appdata.trim().is_empty() {
-                return Some(PathBuf::from(appdata).join("ugh"));
-            }
+        if let Ok(appdata) = env::var("APPDATA")
+            && !appdata.trim().is_empty()
+        {
+            return Some(PathBuf::from(appdata).join("ugh"));
         }
     } else if let Some(home) = home_dir() {
         return Some(home.join(".config").join("ugh"));
@@ -165,11 +1093,19 @@ fn platform_config_dir() -> Option<PathBuf> {
     home_dir().map(|home| home.join(".ugh"))
 }
 
+/// `path` with a leading `~/` replaced by the home directory.
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
 fn home_dir() -> Option<PathBuf> {
-    if let Ok(path) = env::var("HOME") {
-        if !path.trim().is_empty() {
-            return Some(PathBuf::from(path));
-        }
+    if let Ok(path) = env::var("HOME")
+        && !path.trim().is_empty()
+    {
+        return Some(PathBuf::from(path));
     }
 
     #[cfg(windows)]
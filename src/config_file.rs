@@ -0,0 +1,228 @@
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+use crate::error::{AppError, AppResult};
+
+/// Layout of the config file written by this version of ugh. Files without a `version`
+/// are version 1, where every setting sat at the top level.
+pub const CONFIG_VERSION: u64 = 2;
+
+/// Where each top-level setting of version 1 lives from version 2 on: its version 1 name,
+/// the section, and its name within the section. Settings that were tables already
+/// (`llm`, `gateway`, `azure_devops`, ...) keep their place.
+const SECTIONED: &[(&str, &str, &str)] = &[
+    ("tracker", "behavior", "tracker"),
+    ("default_board", "behavior", "default_board"),
+    ("jira_base_url", "jira", "base_url"),
+    ("jira_email", "jira", "email"),
+    ("jira_token", "jira", "token"),
+    ("jira_auth", "jira", "auth"),
+    ("jira_oauth_client_id", "jira", "oauth_client_id"),
+    ("jira_oauth_client_secret", "jira", "oauth_client_secret"),
+    ("jira_oauth_redirect_port", "jira", "oauth_redirect_port"),
+    ("jira_issue_type", "jira", "issue_type"),
+    ("jira_service_desks", "jira", "service_desks"),
+    (
+        "jira_acceptance_criteria_field",
+        "jira",
+        "acceptance_criteria_field",
+    ),
+    ("jira_story_points_field", "jira", "story_points_field"),
+    ("jira_custom_fields", "jira", "custom_fields"),
+    ("gemini_api_key", "gemini", "api_key"),
+    ("gemini_model", "gemini", "model"),
+    ("ollama_base_url", "ollama", "base_url"),
+    ("ollama_model", "ollama", "model"),
+    ("git_backend", "git", "backend"),
+    ("workspace_repos", "git", "workspace_repos"),
+    ("github_token", "git", "github_token"),
+    ("http_proxy", "http", "proxy"),
+    ("ca_bundle", "http", "ca_bundle"),
+    ("http_max_attempts", "http", "max_attempts"),
+    ("http_cache", "http", "cache"),
+    ("slack_webhook_url", "notifications", "slack_webhook_url"),
+    ("teams_webhook_url", "notifications", "teams_webhook_url"),
+    ("llm_provider", "behavior", "llm_provider"),
+    ("llm_providers", "behavior", "llm_providers"),
+    ("drafting", "behavior", "drafting"),
+    ("ticket_language", "behavior", "ticket_language"),
+    ("estimate_scale", "behavior", "estimate_scale"),
+    ("redact_exclude_files", "behavior", "redact_exclude_files"),
+    ("draft_cache_ttl_hours", "behavior", "draft_cache_ttl_hours"),
+    ("due_lead_days", "behavior", "due_lead_days"),
+    ("path_boards", "behavior", "path_boards"),
+    ("workflow_steps", "behavior", "workflow_steps"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+}
+
+impl ConfigFormat {
+    /// TOML for `.toml` files, JSON otherwise.
+    pub fn of(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => ConfigFormat::Toml,
+            _ => ConfigFormat::Json,
+        }
+    }
+}
+
+/// A config file's settings in the current layout, which `StoredConfig` reads, and the
+/// layout version the file was written in.
+pub struct ConfigDocument {
+    pub settings: Value,
+    pub version: u64,
+}
+
+/// Reads a config file of any version, moving version 1 settings into their sections. A
+/// section entry wins over a top-level setting of the same name; keys ugh does not know
+/// stay where they are.
+pub fn parse(text: &str, format: ConfigFormat) -> AppResult<ConfigDocument> {
+    let invalid = |err: String| AppError::Configuration(format!("invalid config file: {err}"));
+    let value: Value = match format {
+        ConfigFormat::Json => serde_json::from_str(text).map_err(|err| invalid(err.to_string()))?,
+        ConfigFormat::Toml => toml::from_str(text).map_err(|err| invalid(err.to_string()))?,
+    };
+    let Value::Object(mut document) = without_nulls(value) else {
+        return Err(invalid("expected a table of settings".to_string()));
+    };
+
+    let version = match document.remove("version") {
+        None => 1,
+        Some(version) => version
+            .as_u64()
+            .filter(|version| *version >= 1)
+            .ok_or_else(|| invalid(format!("version must be a positive number, not {version}")))?,
+    };
+    if version > CONFIG_VERSION {
+        return Err(AppError::Configuration(format!(
+            "the config file uses layout version {version}, but this ugh only knows up to \
+             {CONFIG_VERSION}; upgrade ugh"
+        )));
+    }
+
+    for (flat, section, name) in SECTIONED {
+        let Some(value) = document.remove(*flat) else {
+            continue;
+        };
+        match document
+            .entry(section.to_string())
+            .or_insert_with(|| Value::Object(Map::new()))
+        {
+            Value::Object(table) => {
+                table.entry(name.to_string()).or_insert(value);
+            }
+            other => {
+                return Err(invalid(format!(
+                    "{section} must be a table of settings, not {other}"
+                )));
+            }
+        }
+    }
+    Ok(ConfigDocument {
+        settings: Value::Object(document),
+        version,
+    })
+}
+
+/// Writes `settings` (as `StoredConfig` serializes them) in the current layout. Unset
+/// settings and empty sections are left out.
+pub fn render(settings: Value, format: ConfigFormat) -> AppResult<String> {
+    let Value::Object(mut document) = without_nulls(settings) else {
+        return Err(AppError::Configuration(
+            "settings must serialize to a table".to_string(),
+        ));
+    };
+    document
+        .retain(|key, value| !(is_section(key) && value.as_object().is_some_and(Map::is_empty)));
+    document.insert("version".to_string(), Value::from(CONFIG_VERSION));
+
+    let failed =
+        |err: String| AppError::Configuration(format!("failed to serialize config: {err}"));
+    match format {
+        ConfigFormat::Json => serde_json::to_string_pretty(&Value::Object(document))
+            .map_err(|err| failed(err.to_string())),
+        ConfigFormat::Toml => {
+            toml::to_string(&Value::Object(document)).map_err(|err| failed(err.to_string()))
+        }
+    }
+}
+
+/// A dotted setting name in the current layout: a version 1 name at its start is replaced
+/// by its section (`jira_base_url` becomes `jira.base_url`, `jira_custom_fields.x` becomes
+/// `jira.custom_fields.x`); other names are returned as-is.
+pub fn setting_name(key: &str) -> String {
+    let (head, rest) = match key.split_once('.') {
+        Some((head, rest)) => (head, Some(rest)),
+        None => (key, None),
+    };
+    let Some((_, section, name)) = SECTIONED.iter().find(|(flat, _, _)| *flat == head) else {
+        return key.to_string();
+    };
+    match rest {
+        Some(rest) => format!("{section}.{name}.{rest}"),
+        None => format!("{section}.{name}"),
+    }
+}
+
+fn is_section(key: &str) -> bool {
+    SECTIONED.iter().any(|(_, section, _)| *section == key)
+}
+
+/// TOML has no null, and an unset setting means the same as a missing one.
+fn without_nulls(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| (key, without_nulls(value)))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(without_nulls).collect()),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upgrades_flat_files_into_sections_keeping_unknown_keys() {
+        let old = r#"{"jira_base_url": "https://acme.atlassian.net", "gemini_model": "m",
+            "jira_token": null, "http_cache": false, "llm": {"temperature": 0.2},
+            "gemini": {"model": "kept"}, "team_note": "keep me"}"#;
+        let document = parse(old, ConfigFormat::Json).unwrap();
+        assert_eq!(document.version, 1);
+        assert_eq!(document.settings["gemini"]["model"], "kept");
+
+        let toml = render(document.settings.clone(), ConfigFormat::Toml).unwrap();
+        let upgraded: Value = toml::from_str(&toml).unwrap();
+        assert_eq!(upgraded["version"], 2);
+        assert_eq!(upgraded["jira"]["base_url"], "https://acme.atlassian.net");
+        assert_eq!(upgraded["http"]["cache"], false);
+        assert_eq!(upgraded["llm"]["temperature"], 0.2);
+        assert_eq!(upgraded["team_note"], "keep me");
+        assert!(upgraded.get("jira_base_url").is_none());
+        assert!(upgraded["jira"].get("token").is_none());
+
+        let reread = parse(&toml, ConfigFormat::Toml).unwrap();
+        assert_eq!(reread.version, 2);
+        assert_eq!(reread.settings, document.settings);
+        assert!(parse(r#"{"version": 9}"#, ConfigFormat::Json).is_err());
+        assert!(parse(r#"{"jira": "x", "jira_token": "t"}"#, ConfigFormat::Json).is_err());
+
+        assert_eq!(setting_name("jira_base_url"), "jira.base_url");
+        assert_eq!(
+            setting_name("jira_custom_fields.customfield_1"),
+            "jira.custom_fields.customfield_1"
+        );
+        assert_eq!(setting_name("default_board"), "behavior.default_board");
+        assert_eq!(setting_name("jira.base_url"), "jira.base_url");
+        assert_eq!(setting_name("llm.temperature"), "llm.temperature");
+    }
+}
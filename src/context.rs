@@ -1,7 +1,34 @@
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 
-use crate::config::AppConfig;
-use crate::services::{IssueTrackerService, LanguageModelService, VersionControlService};
+use ring::digest;
+use tracing::{debug, warn};
+
+use crate::config::{AppConfig, DraftingMode, GitBackend, JiraAuthMode, LlmProvider, TrackerKind};
+use crate::domain::user::TrackerUser;
+use crate::error::{AppError, AppResult};
+use crate::infra::atlassian_oauth::{AtlassianOAuth, OAuthSession, OAuthTokenStore};
+use crate::infra::azure_devops::AzureDevOpsClient;
+use crate::infra::change_source::WorkingTree;
+use crate::infra::fallback::FallbackLanguageModel;
+use crate::infra::git::GitCli;
+use crate::infra::git2_repo::Git2Repo;
+use crate::infra::heuristic::HeuristicDrafter;
+use crate::infra::http::{HttpClientFactory, HttpSettings, RetryPolicy};
+use crate::infra::jira::{JiraAuth, JiraClient};
+use crate::infra::llm::GeminiClient;
+use crate::infra::ollama::OllamaClient;
+use crate::infra::template::TemplateDrafter;
+use crate::infra::webhook::{SlackWebhook, TeamsWebhook};
+use crate::metadata::MetadataCache;
+use crate::repo_config::RepoConfig;
+use crate::services::{
+    ChangeSource, IssueTrackerService, LanguageModelService, NotificationService,
+    VersionControlService,
+};
+use crate::workflow::hooks::HookRunner;
+use crate::workflow::steps::{StepRegistry, WorkflowStep};
 
 #[derive(Clone)]
 pub struct AppContext {
@@ -9,8 +36,22 @@ pub struct AppContext {
     pub version_control: Arc<dyn VersionControlService>,
     pub issue_tracker: Arc<dyn IssueTrackerService>,
     pub language_model: Arc<dyn LanguageModelService>,
+    /// Where ticket drafts take their changes from; the working tree unless `--from` says
+    /// otherwise.
+    pub change_source: Arc<dyn ChangeSource>,
+    /// Custom pipeline steps enabled through `workflow_steps`.
+    pub workflow_steps: Arc<[Arc<dyn WorkflowStep>]>,
+    /// Channels told about each new ticket.
+    pub notifiers: Arc<[Arc<dyn NotificationService>]>,
+    /// Toplevel of the repository ugh was started in, when it was started in one.
+    repo_root: Option<PathBuf>,
+    current_user: Arc<OnceLock<TrackerUser>>,
 }
 
+/// The authenticated account only changes with the credentials, and the key names them: the
+/// Jira email, or a fingerprint of the OAuth grant or Azure DevOps PAT.
+const CURRENT_USER_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
 impl AppContext {
     pub fn new(
         config: AppConfig,
@@ -20,9 +61,357 @@ impl AppContext {
     ) -> Self {
         Self {
             config,
+            change_source: Arc::new(WorkingTree::new(version_control.clone())),
             version_control,
             issue_tracker,
             language_model,
+            workflow_steps: Arc::new([]),
+            notifiers: Arc::new([]),
+            repo_root: None,
+            current_user: Arc::new(OnceLock::new()),
         }
     }
+
+    pub fn with_workflow_steps(mut self, steps: Vec<Arc<dyn WorkflowStep>>) -> Self {
+        self.workflow_steps = steps.into();
+        self
+    }
+
+    pub fn with_change_source(mut self, change_source: Arc<dyn ChangeSource>) -> Self {
+        self.change_source = change_source;
+        self
+    }
+
+    pub fn with_notifiers(mut self, notifiers: Vec<Arc<dyn NotificationService>>) -> Self {
+        self.notifiers = notifiers.into();
+        self
+    }
+
+    pub fn with_repo_root(mut self, repo_root: Option<PathBuf>) -> Self {
+        self.repo_root = repo_root;
+        self
+    }
+
+    /// Toplevel of the current git repository. Repository-bound commands call this first,
+    /// so running them outside a repository fails with a clear message.
+    pub fn repo_root(&self) -> AppResult<&Path> {
+        self.repo_root.as_deref().ok_or_else(|| {
+            AppError::VersionControl(format!(
+                "{} is not inside a git repository; run ugh from a checkout",
+                self.config.working_dir.display()
+            ))
+        })
+    }
+
+    /// The same tracker and model working on the repository containing `dir`, with its
+    /// working tree as the change source and the steps and hooks its own approved
+    /// `.ugh.toml` enables.
+    pub fn for_repository(&self, dir: &Path) -> AppResult<Self> {
+        let (git, repo_root) = version_control(&self.config, dir);
+        let Some(root) = &repo_root else {
+            return Err(AppError::VersionControl(format!(
+                "{} is not inside a git repository",
+                dir.display()
+            )));
+        };
+        let repo_config = RepoConfig::load_approved(root)?;
+        Ok(Self {
+            change_source: Arc::new(WorkingTree::new(git.clone())),
+            version_control: git,
+            workflow_steps: workflow_steps(&self.config, root, &repo_config)?.into(),
+            repo_root,
+            ..self.clone()
+        })
+    }
+
+    /// The tracker account the configured credentials belong to. Fetched at most once per
+    /// run and kept in the metadata cache, so features needing the `accountId` (auto-assign,
+    /// watchers, worklogs) can call this freely.
+    pub async fn current_user(&self) -> AppResult<TrackerUser> {
+        if let Some(user) = self.current_user.get() {
+            return Ok(user.clone());
+        }
+
+        let account = match (self.config.tracker, self.config.jira_auth) {
+            (TrackerKind::Jira, JiraAuthMode::ApiToken) => self
+                .config
+                .jira_email
+                .as_deref()
+                .unwrap_or_default()
+                .to_lowercase(),
+            // An OAuth login names no account either, so the key holds the site it is bound
+            // to and a fingerprint of the grant. A new login gets a fresh entry.
+            (TrackerKind::Jira, JiraAuthMode::OAuth) => match OAuthTokenStore::open()?.load()? {
+                Some(tokens) => format!(
+                    "{}:{}",
+                    tokens.cloud_id,
+                    fingerprint(
+                        tokens
+                            .refresh_token
+                            .as_deref()
+                            .unwrap_or(&tokens.access_token)
+                    )
+                ),
+                None => String::new(),
+            },
+            // A PAT names no account, so the key holds a fingerprint of the token itself.
+            (TrackerKind::AzureDevOps, _) => {
+                fingerprint(self.config.azure_devops.pat.as_deref().unwrap_or_default())
+            }
+        };
+        let key = format!(
+            "myself:{}:{account}",
+            self.config.tracker_url().unwrap_or_default()
+        );
+        let mut cache = match MetadataCache::load() {
+            Ok(cache) => Some(cache),
+            Err(err) => {
+                warn!(
+                    "could not load metadata cache ({err}). Asking {} directly.",
+                    self.config.tracker.name()
+                );
+                None
+            }
+        };
+
+        let user = match cache
+            .as_ref()
+            .and_then(|cache| cache.get::<TrackerUser>(&key, CURRENT_USER_TTL))
+        {
+            Some(user) => {
+                debug!(account_id = %user.account_id, "current user served from cache");
+                user
+            }
+            None => {
+                let user = self.issue_tracker.current_user().await?;
+                if let Some(cache) = cache.as_mut() {
+                    cache.insert(&key, &user);
+                    if let Err(err) = cache.save() {
+                        warn!("failed to persist metadata cache ({err}).");
+                    }
+                }
+                user
+            }
+        };
+        Ok(self.current_user.get_or_init(|| user).clone())
+    }
+
+    /// Wires the git, tracker, and LLM implementations from the loaded configuration.
+    pub fn from_config(config: AppConfig) -> AppResult<Self> {
+        let http = http_client_factory(&config)?;
+        let language_model = build_language_model(&config, &http)?;
+        let (git, repo_root) = version_control(&config, &config.working_dir);
+        let issue_tracker: Arc<dyn IssueTrackerService> = match config.tracker {
+            TrackerKind::Jira => Arc::new(JiraClient::new(
+                &http,
+                config.jira_base_url.clone(),
+                jira_auth(&config, &http)?,
+                config.jira_issue_type.clone(),
+                config.jira_service_desks.clone(),
+                config.jira_fields.clone(),
+            )?),
+            TrackerKind::AzureDevOps => Arc::new(AzureDevOpsClient::new(
+                &http,
+                config.azure_devops.organization_url(),
+                config.azure_devops.pat.clone(),
+                config.azure_devops.work_item_type().to_string(),
+                config.jira_fields.clone(),
+            )?),
+        };
+
+        let repo_config = match &repo_root {
+            Some(root) => RepoConfig::load_approved(root)?,
+            None => RepoConfig::default(),
+        };
+        let steps = match &repo_root {
+            Some(root) => workflow_steps(&config, root, &repo_config)?,
+            None => StepRegistry::builtin().build(&config)?,
+        };
+        let notifiers = build_notifiers(&config, &repo_config, &http)?;
+
+        Ok(Self::new(config, git, issue_tracker, language_model)
+            .with_workflow_steps(steps)
+            .with_notifiers(notifiers)
+            .with_repo_root(repo_root))
+    }
+}
+
+/// The configured steps, followed by the hooks of the repository at `root` when it has any.
+fn workflow_steps(
+    config: &AppConfig,
+    root: &Path,
+    repo_config: &RepoConfig,
+) -> AppResult<Vec<Arc<dyn WorkflowStep>>> {
+    let mut steps = StepRegistry::builtin().build(config)?;
+    if !repo_config.hooks.is_empty() {
+        steps.push(Arc::new(HookRunner::new(
+            repo_config.hooks.clone(),
+            root.to_path_buf(),
+        )));
+    }
+    Ok(steps)
+}
+
+/// Slack and Teams webhooks, each taken from `.ugh.toml` when the repository sets one and
+/// from the global config otherwise.
+fn build_notifiers(
+    config: &AppConfig,
+    repo_config: &RepoConfig,
+    http: &HttpClientFactory,
+) -> AppResult<Vec<Arc<dyn NotificationService>>> {
+    let notify = &repo_config.notify;
+    let mut notifiers: Vec<Arc<dyn NotificationService>> = Vec::new();
+    if !notify.enabled.unwrap_or(true) {
+        return Ok(notifiers);
+    }
+    if let Some(url) = notify
+        .slack_webhook_url
+        .as_ref()
+        .or(config.slack_webhook_url.as_ref())
+    {
+        notifiers.push(Arc::new(SlackWebhook::new(http, url.clone())?));
+    }
+    if let Some(url) = notify
+        .teams_webhook_url
+        .as_ref()
+        .or(config.teams_webhook_url.as_ref())
+    {
+        notifiers.push(Arc::new(TeamsWebhook::new(http, url.clone())?));
+    }
+    Ok(notifiers)
+}
+
+/// The configured git backend, rooted at the toplevel of the repository containing `dir`.
+/// Outside a repository it falls back to `dir` and no root is reported.
+fn version_control(
+    config: &AppConfig,
+    dir: &Path,
+) -> (Arc<dyn VersionControlService>, Option<PathBuf>) {
+    let use_cli = match config.git_backend {
+        GitBackend::Cli => true,
+        GitBackend::Libgit2 => false,
+        GitBackend::Auto => git_on_path(),
+    };
+    let toplevel = if use_cli {
+        GitCli::toplevel(dir)
+    } else {
+        Git2Repo::toplevel(dir)
+    };
+    let repo_root = match toplevel {
+        Ok(root) => Some(root),
+        Err(err) => {
+            debug!("no repository root detected ({err})");
+            None
+        }
+    };
+
+    let root = repo_root.clone().unwrap_or_else(|| dir.to_path_buf());
+    let git: Arc<dyn VersionControlService> = if use_cli {
+        Arc::new(GitCli::new(root))
+    } else {
+        Arc::new(Git2Repo::new(root))
+    };
+    (git, repo_root)
+}
+
+fn git_on_path() -> bool {
+    let names: &[&str] = if cfg!(windows) {
+        &["git.exe", "git.cmd"]
+    } else {
+        &["git"]
+    };
+    std::env::var_os("PATH").is_some_and(|path| {
+        std::env::split_paths(&path).any(|dir| names.iter().any(|name| dir.join(name).is_file()))
+    })
+}
+
+pub fn http_client_factory(config: &AppConfig) -> AppResult<HttpClientFactory> {
+    HttpClientFactory::new(HttpSettings {
+        proxy: config.http_proxy.clone(),
+        ca_bundle: config.ca_bundle.clone(),
+        retry: RetryPolicy {
+            max_attempts: config.http_max_attempts,
+            ..RetryPolicy::default()
+        },
+        response_cache: config.http_cache,
+        gateway: config.gateway.clone(),
+        rate_limit: config.rate_limit.clone(),
+    })
+}
+
+/// The first bytes of the SHA-256 of `secret`, hex-encoded, for cache keys that must tell
+/// credentials apart without storing them.
+fn fingerprint(secret: &str) -> String {
+    let hash = digest::digest(&digest::SHA256, secret.as_bytes());
+    hash.as_ref()[..8]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn jira_auth(config: &AppConfig, http: &HttpClientFactory) -> AppResult<JiraAuth> {
+    match config.jira_auth {
+        JiraAuthMode::ApiToken => Ok(JiraAuth::ApiToken {
+            email: config.jira_email.clone(),
+            token: config.jira_token.clone(),
+        }),
+        JiraAuthMode::OAuth => {
+            let store = OAuthTokenStore::open()?;
+            let (Some(tokens), Some(client_id), Some(client_secret)) = (
+                store.load()?,
+                config.jira_oauth_client_id.clone(),
+                config.jira_oauth_client_secret.clone(),
+            ) else {
+                return Ok(JiraAuth::OAuthLoggedOut);
+            };
+            let oauth = AtlassianOAuth::new(http, client_id, client_secret)?;
+            Ok(JiraAuth::OAuth(Box::new(OAuthSession::new(
+                oauth, store, tokens,
+            ))))
+        }
+    }
+}
+
+fn build_language_model(
+    config: &AppConfig,
+    http: &HttpClientFactory,
+) -> AppResult<Arc<dyn LanguageModelService>> {
+    if config.drafting == DraftingMode::Template {
+        return Ok(Arc::new(TemplateDrafter::new(
+            config.draft_template.clone(),
+        )?));
+    }
+    if config.llm_disabled() {
+        return Ok(Arc::new(HeuristicDrafter));
+    }
+
+    let mut providers: Vec<(String, Arc<dyn LanguageModelService>)> = Vec::new();
+    for provider in &config.llm_providers {
+        match provider {
+            LlmProvider::Gemini => providers.push((
+                provider.as_str().to_string(),
+                Arc::new(GeminiClient::new(
+                    http,
+                    config.gemini_api_key.clone(),
+                    config.gemini_model.clone(),
+                    &config.llm,
+                )?),
+            )),
+            LlmProvider::Ollama => providers.push((
+                provider.as_str().to_string(),
+                Arc::new(OllamaClient::new(
+                    http,
+                    config.ollama_base_url.clone(),
+                    config.ollama_model.clone(),
+                    &config.llm,
+                )?),
+            )),
+            LlmProvider::Disabled => {}
+            LlmProvider::Custom(name) => {
+                warn!("custom LLM provider '{name}' not yet implemented, skipping it.");
+            }
+        }
+    }
+
+    Ok(Arc::new(FallbackLanguageModel::new(providers)))
 }
@@ -0,0 +1,159 @@
+use serde::Deserialize;
+
+use crate::domain::branch::BranchCategory;
+use crate::domain::ticket::TicketDraft;
+
+/// One ticket to create from a backlog file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BacklogEntry {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    /// `feature`, `fix`, or `quality`; `feature` when left out.
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    #[serde(default)]
+    pub acceptance_criteria: Vec<String>,
+}
+
+impl BacklogEntry {
+    pub fn to_draft(&self) -> Result<TicketDraft, String> {
+        let title = self.title.trim();
+        if title.is_empty() {
+            return Err("title is empty".to_string());
+        }
+        let branch_category = match self.category.as_deref().map(str::trim) {
+            None | Some("") => BranchCategory::Feature,
+            Some(category) => BranchCategory::from_str(category)
+                .ok_or_else(|| format!("unknown category '{category}'"))?,
+        };
+        Ok(TicketDraft {
+            title: title.to_string(),
+            description: self.description.trim().to_string(),
+            branch_category,
+            branch_summary: title.to_lowercase(),
+            acceptance_criteria: self.acceptance_criteria.clone(),
+            estimate: None,
+            provider: Some("import".to_string()),
+            rationale: None,
+        })
+    }
+}
+
+/// Reads a YAML list of entries.
+pub fn parse_yaml(text: &str) -> Result<Vec<BacklogEntry>, String> {
+    serde_yaml::from_str(text).map_err(|err| err.to_string())
+}
+
+/// Reads CSV with a header row naming the columns (`title`, `description`, `category`,
+/// `labels`, `acceptance_criteria`). List columns separate their items with `;`.
+pub fn parse_csv(text: &str) -> Result<Vec<BacklogEntry>, String> {
+    let mut rows = csv_rows(text)?.into_iter();
+    let header: Vec<String> = rows
+        .next()
+        .ok_or("the file is empty")?
+        .into_iter()
+        .map(|column| column.trim().to_lowercase())
+        .collect();
+    if !header.iter().any(|column| column == "title") {
+        return Err("the header row has no title column".to_string());
+    }
+
+    let list = |cell: &str| -> Vec<String> {
+        cell.split(';')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    let mut entries = Vec::new();
+    for (index, row) in rows.enumerate() {
+        if row.iter().all(|cell| cell.trim().is_empty()) {
+            continue;
+        }
+        let mut entry = BacklogEntry::default();
+        for (column, cell) in header.iter().zip(&row) {
+            match column.as_str() {
+                "title" => entry.title = cell.clone(),
+                "description" => entry.description = cell.clone(),
+                "category" => entry.category = Some(cell.clone()),
+                "labels" => entry.labels = list(cell),
+                "acceptance_criteria" => entry.acceptance_criteria = list(cell),
+                other => {
+                    return Err(format!("row {}: unknown column '{other}'", index + 1));
+                }
+            }
+        }
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// Splits RFC 4180 CSV into rows of cells: quoted cells may hold commas, newlines, and
+/// doubled quotes.
+fn csv_rows(text: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match (ch, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                cell.push('"');
+            }
+            ('"', true) => quoted = false,
+            ('"', false) if cell.is_empty() => quoted = true,
+            (',', false) => row.push(std::mem::take(&mut cell)),
+            ('\r', false) => {}
+            ('\n', false) => {
+                row.push(std::mem::take(&mut cell));
+                rows.push(std::mem::take(&mut row));
+            }
+            (ch, _) => cell.push(ch),
+        }
+    }
+    if quoted {
+        return Err("a quoted cell is never closed".to_string());
+    }
+    if !cell.is_empty() || !row.is_empty() {
+        row.push(cell);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_and_yaml_read_the_same_entries() {
+        let csv = "title,description,category,labels\n\
+                   Add refunds,\"Refunds, partial or full.\nLogged.\",feature,payments;api\n\
+                   \"Fix \"\"rounding\"\"\",,fix,\n";
+        let entries = parse_csv(csv).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].description, "Refunds, partial or full.\nLogged.");
+        assert_eq!(entries[0].labels, ["payments", "api"]);
+        assert_eq!(entries[1].title, "Fix \"rounding\"");
+        assert!(matches!(
+            entries[1].to_draft().unwrap().branch_category,
+            BranchCategory::Fix
+        ));
+
+        let yaml =
+            "- title: Add refunds\n  labels: [payments]\n- title: Tidy logs\n  category: chore\n";
+        let entries = parse_yaml(yaml).unwrap();
+        assert_eq!(entries[0].labels, ["payments"]);
+        assert_eq!(
+            entries[1].to_draft().unwrap_err(),
+            "unknown category 'chore'"
+        );
+        assert!(parse_csv("name\nx\n").is_err());
+    }
+}
@@ -0,0 +1,20 @@
+/// A tracker project tickets can be filed on (what `--board` names).
+#[derive(Debug, Clone)]
+pub struct Board {
+    pub key: String,
+    pub name: String,
+    /// Tracker-specific kind, e.g. `software`, `service_desk`, or `business` in Jira.
+    pub kind: Option<String>,
+    /// Whether the project is team-managed rather than company-managed.
+    pub team_managed: bool,
+    /// Issue types available on the board, excluding sub-task types.
+    pub issue_types: Vec<String>,
+}
+
+impl Board {
+    /// Case-insensitive match against the key or name.
+    pub fn matches(&self, filter: &str) -> bool {
+        let filter = filter.to_lowercase();
+        self.key.to_lowercase().contains(&filter) || self.name.to_lowercase().contains(&filter)
+    }
+}
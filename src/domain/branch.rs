@@ -1,3 +1,5 @@
+use crate::domain::romanize::romanize;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BranchCategory {
     Feature,
@@ -24,6 +26,12 @@ impl BranchCategory {
     }
 }
 
+/// Branch layout used unless a preset overrides it.
+pub const DEFAULT_BRANCH_TEMPLATE: &str = "{category}/{key}/{slug}";
+
+/// Longest branch name [`BranchName::sanitized`] keeps, well under file name limits.
+const MAX_BRANCH_BYTES: usize = 200;
+
 #[derive(Debug, Clone)]
 pub struct BranchName(pub String);
 
@@ -33,20 +41,160 @@ impl BranchName {
     }
 
     pub fn from_parts(category: &BranchCategory, ticket_key: &str, summary: &str) -> Self {
-        let clean_ticket = ticket_key.trim();
-        let slug = slugify(summary);
-        Self(format!("{}/{}/{}", category.as_str(), clean_ticket, slug))
+        Self::from_template(DEFAULT_BRANCH_TEMPLATE, category, ticket_key, summary)
+    }
+
+    /// Fills `{category}`, `{key}`, and `{slug}` in `template`, e.g. `hotfix/{key}-{slug}`.
+    /// Separators left dangling by an empty key are dropped (`feature//x` → `feature/x`).
+    pub fn from_template(
+        template: &str,
+        category: &BranchCategory,
+        ticket_key: &str,
+        summary: &str,
+    ) -> Self {
+        let filled = template
+            .replace("{category}", category.as_str())
+            .replace("{key}", ticket_key.trim())
+            .replace("{slug}", &slugify(summary));
+        Self(
+            filled
+                .split('/')
+                .map(|segment| segment.trim_matches(['-', '_']))
+                .filter(|segment| !segment.is_empty())
+                .collect::<Vec<_>>()
+                .join("/"),
+        )
+    }
+
+    /// A version of this name that `git check-ref-format --branch` accepts, with what had to
+    /// change, or `None` when nothing needed changing (or nothing usable is left).
+    pub fn sanitized(&self) -> Option<(Self, Vec<&'static str>)> {
+        let mut fixes = Vec::new();
+        let mut note = |fix: &'static str| {
+            if !fixes.contains(&fix) {
+                fixes.push(fix);
+            }
+        };
+
+        let mut name: String = self
+            .0
+            .chars()
+            .map(|c| {
+                if c.is_control() || matches!(c, ' ' | '~' | '^' | ':' | '?' | '*' | '[' | '\\') {
+                    note("replaced characters git does not allow");
+                    '-'
+                } else {
+                    c
+                }
+            })
+            .collect();
+        if name.contains("@{") {
+            note("replaced '@{'");
+            name = name.replace("@{", "-");
+        }
+        while name.contains("..") {
+            note("collapsed '..'");
+            name = name.replace("..", ".");
+        }
+
+        let mut segments = Vec::new();
+        for segment in name.split('/') {
+            let mut segment = segment.to_string();
+            if segment.starts_with('.') {
+                note("dropped leading dots");
+                segment = segment.trim_start_matches('.').to_string();
+            }
+            if let Some(stem) = segment.strip_suffix(".lock") {
+                note("renamed a '.lock' ending");
+                segment = format!("{stem}-lock");
+            }
+            if segment.is_empty() {
+                note("removed empty path segments");
+            } else {
+                segments.push(segment);
+            }
+        }
+        let mut name = segments.join("/");
+        if name.starts_with('-') {
+            note("dropped leading dashes");
+            name = name.trim_start_matches('-').to_string();
+        }
+
+        if name.len() > MAX_BRANCH_BYTES {
+            note("shortened to 200 characters");
+            let mut end = MAX_BRANCH_BYTES;
+            while !name.is_char_boundary(end) {
+                end -= 1;
+            }
+            name.truncate(end);
+            name = name.trim_end_matches(['/', '-']).to_string();
+        }
+        if name.ends_with('.') {
+            note("removed the trailing dot");
+            name = name.trim_end_matches('.').to_string();
+        }
+        if !fixes.is_empty() {
+            while name.contains("--") {
+                name = name.replace("--", "-");
+            }
+        }
+
+        (name != self.0 && !name.is_empty() && name != "@").then_some((Self(name), fixes))
+    }
+}
+
+/// The first Jira-style key (`PAY-123`) in `text`.
+pub fn ticket_key_in(text: &str) -> Option<String> {
+    let bytes = text.as_bytes();
+    for start in 0..bytes.len() {
+        if !bytes[start].is_ascii_uppercase()
+            || (start > 0 && bytes[start - 1].is_ascii_alphanumeric())
+        {
+            continue;
+        }
+        let project = bytes[start..]
+            .iter()
+            .take_while(|byte| byte.is_ascii_uppercase() || byte.is_ascii_digit())
+            .count();
+        let dash = start + project;
+        if project < 2 || bytes.get(dash) != Some(&b'-') {
+            continue;
+        }
+        let number = bytes[dash + 1..]
+            .iter()
+            .take_while(|byte| byte.is_ascii_digit())
+            .count();
+        let end = dash + 1 + number;
+        if number > 0 && !bytes.get(end).is_some_and(u8::is_ascii_alphanumeric) {
+            return Some(text[start..end].to_string());
+        }
     }
+    None
+}
+
+/// The first all-digit ID in `branch` that starts a path segment, as Azure DevOps work
+/// item IDs do under the default template (`feature/4821/add-refunds`, `fix/4821-refunds`).
+pub fn work_item_id_in(branch: &str) -> Option<String> {
+    branch.split('/').find_map(|segment| {
+        let digits = segment
+            .bytes()
+            .take_while(|byte| byte.is_ascii_digit())
+            .count();
+        let rest = &segment[digits..];
+        (digits > 0 && (rest.is_empty() || rest.starts_with('-')))
+            .then(|| segment[..digits].to_string())
+    })
 }
 
 fn slugify(input: &str) -> String {
-    let clean = input
+    let romanized = romanize(input);
+    let clean = romanized
+        .as_deref()
+        .unwrap_or(input)
         .chars()
         .map(|c| {
             if c.is_ascii_alphanumeric() {
                 c.to_ascii_lowercase()
-            } else if c.is_whitespace() || c == '-' || c == '_' || c == '/' {
-                '-'
             } else {
                 '-'
             }
@@ -91,6 +239,78 @@ mod tests {
         );
     }
 
+    #[test]
+    fn romanizes_summaries_in_other_scripts() {
+        let name = BranchName::from_parts(&BranchCategory::Fix, "TCK-13", "Исправить возвраты");
+        assert_eq!(name.as_str(), "fix/TCK-13/ispravit-vozvraty");
+    }
+
+    #[test]
+    fn sanitizes_names_git_rejects() {
+        let (name, fixes) = BranchName("hotfix/PAY 9: refunds../.hidden.lock".to_string())
+            .sanitized()
+            .unwrap();
+        assert_eq!(name.as_str(), "hotfix/PAY-9-refunds./hidden-lock");
+        assert_eq!(
+            fixes,
+            [
+                "replaced characters git does not allow",
+                "collapsed '..'",
+                "dropped leading dots",
+                "renamed a '.lock' ending"
+            ]
+        );
+
+        let long = BranchName(format!("feature/{}", "a".repeat(300)));
+        assert_eq!(long.sanitized().unwrap().0.as_str().len(), MAX_BRANCH_BYTES);
+        assert!(
+            BranchName("feature/PAY-9/refunds".to_string())
+                .sanitized()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn fills_branch_template() {
+        let name = BranchName::from_template(
+            "hotfix/{key}-{slug}",
+            &BranchCategory::Fix,
+            "TCK-7",
+            "Stop double charges",
+        );
+        assert_eq!(name.as_str(), "hotfix/TCK-7-stop-double-charges");
+
+        let unkeyed =
+            BranchName::from_template("hotfix/{key}-{slug}", &BranchCategory::Fix, "", "Stop");
+        assert_eq!(unkeyed.as_str(), "hotfix/stop");
+    }
+
+    #[test]
+    fn default_layout_drops_a_missing_key() {
+        // `ugh branch-name` names a branch before the ticket exists, so an empty key
+        // leaves no `//` behind.
+        let name = BranchName::from_parts(&BranchCategory::Feature, "", "Add refunds");
+        assert_eq!(name.as_str(), "feature/add-refunds");
+    }
+
+    #[test]
+    fn finds_ticket_keys_in_branch_names() {
+        assert_eq!(
+            ticket_key_in("feature/PAY-123/add-refunds").as_deref(),
+            Some("PAY-123")
+        );
+        assert_eq!(ticket_key_in("main"), None);
+        assert_eq!(
+            work_item_id_in("feature/4821/fix-404-page").as_deref(),
+            Some("4821")
+        );
+        assert_eq!(
+            work_item_id_in("hotfix/4821-refunds").as_deref(),
+            Some("4821")
+        );
+        assert_eq!(work_item_id_in("fix/fix-404-page"), None);
+    }
+
     #[test]
     fn parses_branch_category() {
         assert_eq!(
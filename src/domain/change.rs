@@ -1,5 +1,288 @@
+use std::collections::BTreeMap;
+
 #[derive(Debug, Clone)]
 pub struct ChangeSummary {
     pub files_changed: usize,
     pub summary: String,
+    /// Paths of every changed file, relative to the repository root.
+    pub files: Vec<String>,
+}
+
+/// One file's line counts in changes that did not come from the working tree, such as a
+/// patch or a pull request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDiffStat {
+    /// Path relative to the repository root.
+    pub path: String,
+    /// Git's status letter: `A`, `M`, `D`, or `R`.
+    pub status: char,
+    pub added: usize,
+    pub removed: usize,
+}
+
+impl ChangeSummary {
+    /// A summary laid out like the one for the working tree, for the changes `origin`
+    /// describes, e.g. `Patch fix.diff`.
+    pub fn from_diff_stats(origin: &str, stats: &[FileDiffStat]) -> Self {
+        if stats.is_empty() {
+            return Self {
+                files_changed: 0,
+                summary: format!("{origin} has no changes."),
+                files: Vec::new(),
+            };
+        }
+
+        let mut lines = vec![format!("{origin} changes {} file(s).", stats.len())];
+        for stat in stats.iter().take(8) {
+            lines.push(format!("  {} {}", stat.status, stat.path));
+        }
+        if stats.len() > 8 {
+            lines.push("  …".to_string());
+        }
+        lines.push(String::new());
+        lines.push("Diff summary:".to_string());
+        for stat in stats.iter().take(8) {
+            lines.push(format!(
+                "  {} | +{} -{}",
+                stat.path, stat.added, stat.removed
+            ));
+        }
+        lines.push(format!(
+            "  {} file(s) changed, {} insertion(s)(+), {} deletion(s)(-)",
+            stats.len(),
+            stats.iter().map(|stat| stat.added).sum::<usize>(),
+            stats.iter().map(|stat| stat.removed).sum::<usize>()
+        ));
+
+        Self {
+            files_changed: stats.len(),
+            summary: lines.join("\n"),
+            files: stats.iter().map(|stat| stat.path.clone()).collect(),
+        }
+    }
+
+    /// One summary for changes spread over several repositories, each labeled with the
+    /// repository's name and its files prefixed with it (`api/src/lib.rs`).
+    pub fn combine(repos: &[(&str, &ChangeSummary)]) -> Self {
+        let mut lines = vec![format!(
+            "Changes across {} repositories: {}.",
+            repos.len(),
+            repos
+                .iter()
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )];
+        for (name, changes) in repos {
+            lines.push(String::new());
+            lines.push(format!("Repository {name}:"));
+            lines.push(changes.summary.clone());
+        }
+
+        Self {
+            files_changed: repos.iter().map(|(_, changes)| changes.files_changed).sum(),
+            summary: lines.join("\n"),
+            files: repos
+                .iter()
+                .flat_map(|(name, changes)| {
+                    changes
+                        .files
+                        .iter()
+                        .map(move |file| format!("{name}/{file}"))
+                })
+                .collect(),
+        }
+    }
+
+    /// Every changed path with its detected kind.
+    pub fn tagged_files(&self) -> impl Iterator<Item = (&str, FileKind)> {
+        self.files
+            .iter()
+            .map(|file| (file.as_str(), FileKind::detect(file)))
+    }
+
+    /// How many changed files there are of each kind, in `FileKind` order.
+    pub fn kind_counts(&self) -> BTreeMap<FileKind, usize> {
+        let mut counts = BTreeMap::new();
+        for (_, kind) in self.tagged_files() {
+            *counts.entry(kind).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// True when anything besides tests changed, i.e. tests are incidental to the work.
+    pub fn has_non_test_changes(&self) -> bool {
+        self.tagged_files().any(|(_, kind)| kind != FileKind::Test)
+    }
+}
+
+/// What a changed file is for, detected from its path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FileKind {
+    Source,
+    Test,
+    Config,
+    Docs,
+    Ci,
+    Generated,
+}
+
+impl FileKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FileKind::Source => "source",
+            FileKind::Test => "test",
+            FileKind::Config => "config",
+            FileKind::Docs => "docs",
+            FileKind::Ci => "ci",
+            FileKind::Generated => "generated",
+        }
+    }
+
+    /// Checks run most specific first: lockfiles are generated before they are config,
+    /// and a workflow YAML is CI before it is config.
+    pub fn detect(path: &str) -> Self {
+        let path = path.trim().trim_end_matches('/').to_lowercase();
+        let name = path.rsplit('/').next().unwrap_or(&path);
+        let segments: Vec<&str> = path.split('/').collect();
+        let dirs = &segments[..segments.len().saturating_sub(1)];
+        let extension = name.rsplit_once('.').map(|(_, ext)| ext).unwrap_or("");
+
+        if is_generated(name, dirs) {
+            FileKind::Generated
+        } else if is_ci(&path, name) {
+            FileKind::Ci
+        } else if is_test(name, dirs) {
+            FileKind::Test
+        } else if is_docs(name, dirs, extension) {
+            FileKind::Docs
+        } else if is_config(name, extension) {
+            FileKind::Config
+        } else {
+            FileKind::Source
+        }
+    }
+}
+
+fn is_generated(name: &str, dirs: &[&str]) -> bool {
+    const LOCKFILES: &[&str] = &[
+        "cargo.lock",
+        "package-lock.json",
+        "yarn.lock",
+        "pnpm-lock.yaml",
+        "poetry.lock",
+        "gemfile.lock",
+        "go.sum",
+        "composer.lock",
+    ];
+    const OUTPUT_DIRS: &[&str] = &["dist", "build", "target", "vendor", "node_modules"];
+    LOCKFILES.contains(&name)
+        || name.contains(".generated.")
+        || name.ends_with(".min.js")
+        || name.ends_with(".pb.go")
+        || name.ends_with(".snap")
+        || dirs.iter().any(|dir| OUTPUT_DIRS.contains(dir))
+}
+
+fn is_ci(path: &str, name: &str) -> bool {
+    path.starts_with(".github/workflows/")
+        || path.starts_with(".circleci/")
+        || path.starts_with(".buildkite/")
+        || matches!(
+            name,
+            ".gitlab-ci.yml" | "jenkinsfile" | "azure-pipelines.yml" | ".travis.yml"
+        )
+}
+
+fn is_test(name: &str, dirs: &[&str]) -> bool {
+    let stem = name.split('.').next().unwrap_or(name);
+    dirs.iter()
+        .any(|dir| matches!(*dir, "test" | "tests" | "spec" | "__tests__"))
+        || stem.starts_with("test_")
+        || stem.ends_with("_test")
+        || stem.ends_with("_spec")
+        || name.contains(".test.")
+        || name.contains(".spec.")
+}
+
+fn is_docs(name: &str, dirs: &[&str], extension: &str) -> bool {
+    matches!(extension, "md" | "mdx" | "rst" | "adoc")
+        || dirs
+            .first()
+            .is_some_and(|dir| matches!(*dir, "docs" | "doc"))
+        || name.starts_with("license")
+        || name.starts_with("changelog")
+}
+
+fn is_config(name: &str, extension: &str) -> bool {
+    matches!(
+        extension,
+        "toml" | "yaml" | "yml" | "ini" | "cfg" | "conf" | "properties"
+    ) || name.starts_with(".env")
+        || name.starts_with('.') && name.ends_with("rc")
+        || matches!(
+            name,
+            "package.json" | "tsconfig.json" | "dockerfile" | "makefile" | ".gitignore"
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_file_kinds_from_paths() {
+        let cases = [
+            ("src/workflow/ticket.rs", FileKind::Source),
+            ("tests/cli.rs", FileKind::Test),
+            ("web/src/cart.test.ts", FileKind::Test),
+            ("pkg/api/handler_test.go", FileKind::Test),
+            ("Cargo.toml", FileKind::Config),
+            (".eslintrc", FileKind::Config),
+            ("README.md", FileKind::Docs),
+            ("docs/setup.txt", FileKind::Docs),
+            (".github/workflows/release.yml", FileKind::Ci),
+            ("Cargo.lock", FileKind::Generated),
+            ("api/gen/user.pb.go", FileKind::Generated),
+        ];
+        for (path, kind) in cases {
+            assert_eq!(FileKind::detect(path), kind, "{path}");
+        }
+    }
+
+    #[test]
+    fn combines_repositories_under_their_names() {
+        let api = ChangeSummary::from_diff_stats(
+            "Patch",
+            &[FileDiffStat {
+                path: "src/refunds.rs".to_string(),
+                status: 'M',
+                added: 4,
+                removed: 1,
+            }],
+        );
+        let web = ChangeSummary::from_diff_stats(
+            "Patch",
+            &[FileDiffStat {
+                path: "app/refund.tsx".to_string(),
+                status: 'A',
+                added: 20,
+                removed: 0,
+            }],
+        );
+
+        let combined = ChangeSummary::combine(&[("api", &api), ("web", &web)]);
+        assert_eq!(combined.files_changed, 2);
+        assert_eq!(combined.files, ["api/src/refunds.rs", "web/app/refund.tsx"]);
+        assert!(
+            combined
+                .summary
+                .starts_with("Changes across 2 repositories: api, web.\n\nRepository api:\n")
+        );
+        assert!(
+            combined
+                .summary
+                .contains("\nRepository web:\nPatch changes 1 file(s).")
+        );
+    }
 }
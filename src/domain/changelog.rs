@@ -0,0 +1,274 @@
+use crate::domain::branch::{BranchCategory, ticket_key_in};
+use crate::domain::retro::CommitSummary;
+use crate::domain::ticket::TicketDetails;
+
+/// The work of one ticket in a release, or ticketless commits of one category.
+#[derive(Debug, Clone)]
+pub struct ChangelogEntry {
+    pub ticket: Option<String>,
+    /// The ticket's details from the tracker, once fetched.
+    pub details: Option<TicketDetails>,
+    pub category: Option<BranchCategory>,
+    /// Newest first, merges left out.
+    pub commits: Vec<CommitSummary>,
+}
+
+impl ChangelogEntry {
+    /// `PAY-12 Add refunds`, with the key linked when the tracker gave a URL and the
+    /// newest commit subject standing in for a missing title.
+    pub fn headline(&self) -> String {
+        let subject = self.commits.first().map(|commit| {
+            let subject = commit.subject.trim();
+            // Drop a leading `PAY-12:` so the key is not shown twice.
+            self.ticket
+                .as_deref()
+                .and_then(|key| subject.strip_prefix(key))
+                .map_or(subject, |rest| rest.trim_start_matches([':', ' ', '-']))
+        });
+        let title = self
+            .details
+            .as_ref()
+            .map(|details| details.title.trim())
+            .filter(|title| !title.is_empty())
+            .or(subject)
+            .unwrap_or_default();
+        let url = self
+            .details
+            .as_ref()
+            .and_then(|details| details.url.as_deref());
+        match (&self.ticket, url) {
+            (Some(key), Some(url)) => format!("[{key}]({url}) {title}"),
+            (Some(key), None) => format!("{key} {title}"),
+            (None, _) => title.to_string(),
+        }
+    }
+}
+
+/// Everything release notes are written from.
+#[derive(Debug, Clone)]
+pub struct ChangelogFacts {
+    /// The revision range as given, e.g. `v1.2.0..HEAD`.
+    pub range: String,
+    /// Ticket entries in order of their newest commit, then ticketless ones.
+    pub entries: Vec<ChangelogEntry>,
+}
+
+/// Release notes by section, written by a model or derived from the entries.
+#[derive(Debug, Clone, Default)]
+pub struct ReleaseNotes {
+    /// A sentence or two on the release as a whole; may be empty.
+    pub summary: String,
+    pub features: Vec<String>,
+    pub fixes: Vec<String>,
+    pub quality: Vec<String>,
+    pub other: Vec<String>,
+}
+
+impl ChangelogFacts {
+    /// Groups commits by the ticket key in their subject. Merge commits are not listed,
+    /// but the branch they name (`Merge branch 'fix/PAY-9/…'`) gives its ticket a key and
+    /// a category. Ticketless commits are grouped by the category their subject suggests.
+    pub fn group(range: String, commits: Vec<CommitSummary>) -> Self {
+        let mut entries: Vec<ChangelogEntry> = Vec::new();
+        for commit in commits {
+            let ticket = ticket_key_in(&commit.subject);
+            let category = category_in(&commit.subject);
+            let is_merge = commit.subject.starts_with("Merge ");
+            if is_merge && ticket.is_none() {
+                continue;
+            }
+            let index = entries.iter().position(|entry| {
+                entry.ticket == ticket && (ticket.is_some() || entry.category == category)
+            });
+            let entry = match index {
+                Some(index) => &mut entries[index],
+                None => {
+                    entries.push(ChangelogEntry {
+                        ticket,
+                        details: None,
+                        category: None,
+                        commits: Vec::new(),
+                    });
+                    entries.last_mut().expect("entry was just pushed")
+                }
+            };
+            // The merged branch names the category most reliably.
+            if let Some(category) = category
+                && (is_merge || entry.category.is_none())
+            {
+                entry.category = Some(category);
+            }
+            if !is_merge {
+                entry.commits.push(commit);
+            }
+        }
+        entries.sort_by_key(|entry| entry.ticket.is_none());
+        Self { range, entries }
+    }
+
+    /// Keys of the tickets in the range, in entry order.
+    pub fn ticket_keys(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .filter_map(|entry| entry.ticket.clone())
+            .collect()
+    }
+
+    /// Stores a ticket's tracker details; tickets without a category from git take one
+    /// from the issue type.
+    pub fn add_details(&mut self, details: TicketDetails) {
+        let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.ticket.as_deref() == Some(details.key.as_str()))
+        else {
+            return;
+        };
+        if entry.category.is_none() {
+            entry.category = details
+                .issue_type
+                .as_deref()
+                .and_then(category_of_issue_type);
+        }
+        entry.details = Some(details);
+    }
+
+    /// One line per entry under its category, for when no model is available.
+    pub fn heuristic_notes(&self) -> ReleaseNotes {
+        let mut notes = ReleaseNotes::default();
+        for entry in &self.entries {
+            let line = match &entry.ticket {
+                Some(_) => entry.headline(),
+                None => entry
+                    .commits
+                    .iter()
+                    .map(|commit| commit.subject.trim())
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            };
+            let section = match entry.category {
+                Some(BranchCategory::Feature) => &mut notes.features,
+                Some(BranchCategory::Fix) => &mut notes.fixes,
+                Some(BranchCategory::Quality) => &mut notes.quality,
+                None => &mut notes.other,
+            };
+            section.push(line);
+        }
+        notes
+    }
+
+    /// The Markdown release notes; empty sections are left out.
+    pub fn render(&self, notes: &ReleaseNotes) -> String {
+        let mut doc = format!("# Release notes: {}\n", self.range);
+        if self.entries.is_empty() {
+            doc.push_str("\nNo changes in this range.\n");
+            return doc;
+        }
+        if !notes.summary.trim().is_empty() {
+            doc.push_str(&format!("\n{}\n", notes.summary.trim()));
+        }
+        for (heading, items) in [
+            ("Features", &notes.features),
+            ("Fixes", &notes.fixes),
+            ("Quality", &notes.quality),
+            ("Other changes", &notes.other),
+        ] {
+            if items.is_empty() {
+                continue;
+            }
+            doc.push_str(&format!("\n## {heading}\n\n"));
+            for item in items {
+                doc.push_str(&format!("- {}\n", item.trim()));
+            }
+        }
+        doc
+    }
+}
+
+/// The branch category named in a branch path (`fix/PAY-9/…`) or a conventional commit
+/// prefix (`fix:`, `feat(api):`, `refactor:`).
+pub fn category_in(subject: &str) -> Option<BranchCategory> {
+    let lower = subject.to_lowercase();
+    for category in [
+        BranchCategory::Feature,
+        BranchCategory::Fix,
+        BranchCategory::Quality,
+    ] {
+        if lower.contains(&format!("{}/", category.as_str())) {
+            return Some(category);
+        }
+    }
+    let prefix = lower
+        .split([':', '(', '!'])
+        .next()
+        .filter(|prefix| prefix.len() < lower.len())?;
+    match prefix.trim() {
+        "feat" | "feature" => Some(BranchCategory::Feature),
+        "fix" | "hotfix" => Some(BranchCategory::Fix),
+        "refactor" | "perf" | "test" | "tests" | "chore" | "build" | "ci" | "style" => {
+            Some(BranchCategory::Quality)
+        }
+        _ => None,
+    }
+}
+
+fn category_of_issue_type(issue_type: &str) -> Option<BranchCategory> {
+    let issue_type = issue_type.to_lowercase();
+    if issue_type.contains("bug") || issue_type.contains("incident") {
+        Some(BranchCategory::Fix)
+    } else if issue_type.contains("story") || issue_type.contains("feature") {
+        Some(BranchCategory::Feature)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(id: &str, subject: &str) -> CommitSummary {
+        CommitSummary {
+            id: id.to_string(),
+            subject: subject.to_string(),
+        }
+    }
+
+    #[test]
+    fn groups_commits_by_ticket_and_files_them_under_categories() {
+        let mut facts = ChangelogFacts::group(
+            "v1.2.0..HEAD".to_string(),
+            vec![
+                commit("a1", "Merge branch 'fix/PAY-9/rounding'"),
+                commit("b2", "PAY-9 round half to even"),
+                commit("c3", "docs: typo"),
+                commit("d4", "refactor: split ledger module"),
+                commit("e5", "PAY-12 add refund endpoint"),
+                commit("f6", "Merge pull request #4 from org/main"),
+            ],
+        );
+        assert_eq!(facts.ticket_keys(), ["PAY-9", "PAY-12"]);
+        assert_eq!(facts.entries[0].commits.len(), 1);
+
+        facts.add_details(TicketDetails {
+            key: "PAY-12".to_string(),
+            title: "Add refunds".to_string(),
+            issue_type: Some("Story".to_string()),
+            status: None,
+            url: Some("https://example.atlassian.net/browse/PAY-12".to_string()),
+            description: None,
+        });
+        let notes = facts.heuristic_notes();
+        assert_eq!(
+            notes.features,
+            ["[PAY-12](https://example.atlassian.net/browse/PAY-12) Add refunds"]
+        );
+        assert_eq!(notes.fixes, ["PAY-9 round half to even"]);
+        assert_eq!(notes.quality, ["refactor: split ledger module"]);
+        assert_eq!(notes.other, ["docs: typo"]);
+
+        let rendered = facts.render(&notes);
+        assert!(rendered.starts_with("# Release notes: v1.2.0..HEAD\n"));
+        assert!(rendered.contains("\n## Fixes\n\n- PAY-9 round half to even\n"));
+    }
+}
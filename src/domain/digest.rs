@@ -0,0 +1,140 @@
+use chrono::{DateTime, Local, NaiveDate, Utc};
+
+use crate::domain::ticket::TicketDetails;
+
+/// A ticket ugh created during the digest's period, with the branch made for it.
+#[derive(Debug, Clone)]
+pub struct CreatedTicket {
+    pub key: String,
+    pub url: Option<String>,
+    pub branch: String,
+    /// Path of the repository the ticket was created from.
+    pub repo: String,
+    pub created_at: DateTime<Utc>,
+    /// Rolled back with `ugh undo` since.
+    pub undone: bool,
+}
+
+/// What ugh did over a period, across repositories, and the work still in progress.
+#[derive(Debug, Clone)]
+pub struct Digest {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// Oldest first.
+    pub created: Vec<CreatedTicket>,
+    /// `None` when the tracker could not be asked.
+    pub in_progress: Option<Vec<TicketDetails>>,
+}
+
+impl Digest {
+    /// The printable digest, with created tickets grouped by repository.
+    pub fn render(&self) -> String {
+        let mut text = if self.from == self.to {
+            format!("Digest for {}\n", self.from.format("%a %d %b %Y"))
+        } else {
+            format!(
+                "Digest for {} to {}\n",
+                self.from.format("%a %d %b"),
+                self.to.format("%a %d %b %Y")
+            )
+        };
+
+        if self.created.is_empty() {
+            text.push_str("\nNo tickets created.\n");
+        } else {
+            let mut repos: Vec<&str> = Vec::new();
+            for ticket in &self.created {
+                if !repos.contains(&ticket.repo.as_str()) {
+                    repos.push(&ticket.repo);
+                }
+            }
+            text.push_str(&format!(
+                "\nCreated {} ticket(s) in {} repositor{}:\n",
+                self.created.len(),
+                repos.len(),
+                if repos.len() == 1 { "y" } else { "ies" }
+            ));
+            for repo in repos {
+                text.push_str(&format!("{}\n", repo_name(repo)));
+                for ticket in self.created.iter().filter(|ticket| ticket.repo == repo) {
+                    let time = ticket.created_at.with_timezone(&Local).format("%H:%M");
+                    let undone = if ticket.undone { " (undone)" } else { "" };
+                    text.push_str(&format!(
+                        "- {time} {} on branch {}{undone}",
+                        ticket.key, ticket.branch
+                    ));
+                    if let Some(url) = &ticket.url {
+                        text.push_str(&format!(" {url}"));
+                    }
+                    text.push('\n');
+                }
+            }
+        }
+
+        match &self.in_progress {
+            Some(tickets) if tickets.is_empty() => {
+                text.push_str("\nNothing in progress.\n");
+            }
+            Some(tickets) => {
+                text.push_str(&format!("\nIn progress ({}):\n", tickets.len()));
+                for ticket in tickets {
+                    let status = ticket.status.as_deref().unwrap_or("In Progress");
+                    text.push_str(&format!("- {} {} [{status}]\n", ticket.key, ticket.title));
+                }
+            }
+            None => text.push_str("\nIn-progress tickets are unavailable.\n"),
+        }
+        text
+    }
+}
+
+/// The last component of a repository path, which is how people name their checkouts.
+fn repo_name(repo: &str) -> &str {
+    repo.trim_end_matches(['/', '\\'])
+        .rsplit(['/', '\\'])
+        .next()
+        .filter(|name| !name.is_empty())
+        .unwrap_or(repo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_created_tickets_by_repository() {
+        let day = NaiveDate::from_ymd_opt(2025, 3, 14).unwrap();
+        let created = |key: &str, repo: &str, undone: bool| CreatedTicket {
+            key: key.to_string(),
+            url: None,
+            branch: format!("fix/{key}-refunds"),
+            repo: repo.to_string(),
+            created_at: Utc::now(),
+            undone,
+        };
+        let digest = Digest {
+            from: day,
+            to: day,
+            created: vec![
+                created("PAY-1", "/src/shop", false),
+                created("OPS-2", "/src/infra", false),
+                created("PAY-3", "/src/shop", true),
+            ],
+            in_progress: Some(vec![TicketDetails {
+                key: "PAY-0".to_string(),
+                title: "Ledger export".to_string(),
+                issue_type: None,
+                status: Some("In Review".to_string()),
+                url: None,
+                description: None,
+            }]),
+        };
+
+        let text = digest.render();
+        assert!(text.starts_with("Digest for Fri 14 Mar 2025\n"));
+        assert!(text.contains("Created 3 ticket(s) in 2 repositories:\nshop\n- "));
+        assert!(text.contains("PAY-3 on branch fix/PAY-3-refunds (undone)"));
+        assert!(text.contains("In progress (1):\n- PAY-0 Ledger export [In Review]\n"));
+        assert_eq!(repo_name("/src/shop/"), "shop");
+    }
+}
@@ -0,0 +1,127 @@
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+
+/// Parses a human due date relative to `today`.
+///
+/// Accepts ISO dates (`2025-03-14`), `today`, `tomorrow`, `in 3 days` / `3d` /
+/// `in 2 weeks` / `1 month`, `next week`, `end of week` / `eow`, `end of month` / `eom`,
+/// and weekdays (`friday`, `this fri`, `next friday`). A bare or `this`/`next` weekday
+/// means its next occurrence after today; `next week` is seven days out.
+pub fn parse_due_date(input: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    let normalized = input.trim().to_lowercase();
+    let text = normalized.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() {
+        return Err("due date must not be empty".to_string());
+    }
+
+    if let Ok(date) = NaiveDate::parse_from_str(&text, "%Y-%m-%d") {
+        return Ok(date);
+    }
+
+    let date = match text.as_str() {
+        "today" => Some(today),
+        "tomorrow" => today.checked_add_days(Days::new(1)),
+        "next week" => today.checked_add_days(Days::new(7)),
+        "end of week" | "eow" => Some(next_weekday(today, Weekday::Fri, true)),
+        "end of month" | "eom" => end_of_month(today),
+        _ => None,
+    };
+    if let Some(date) = date {
+        return Ok(date);
+    }
+
+    let weekday_text = text
+        .strip_prefix("next ")
+        .or_else(|| text.strip_prefix("this "))
+        .unwrap_or(&text);
+    if let Some(weekday) = parse_weekday(weekday_text) {
+        return Ok(next_weekday(today, weekday, false));
+    }
+
+    let offset_text = text.strip_prefix("in ").unwrap_or(&text);
+    if let Some(date) = parse_offset(offset_text, today) {
+        return Ok(date);
+    }
+
+    Err(format!(
+        "could not understand due date '{}' (try 2025-03-14, tomorrow, friday, or in 3 days)",
+        input.trim()
+    ))
+}
+
+/// Next `weekday` strictly after `today`, or `today` itself when `inclusive` and it matches.
+fn next_weekday(today: NaiveDate, weekday: Weekday, inclusive: bool) -> NaiveDate {
+    let current = today.weekday().num_days_from_monday();
+    let target = weekday.num_days_from_monday();
+    let mut ahead = (7 + target - current) % 7;
+    if ahead == 0 && !inclusive {
+        ahead = 7;
+    }
+    today + Days::new(u64::from(ahead))
+}
+
+fn end_of_month(today: NaiveDate) -> Option<NaiveDate> {
+    let first = today.with_day(1)?;
+    first.checked_add_months(Months::new(1))?.pred_opt()
+}
+
+fn parse_weekday(text: &str) -> Option<Weekday> {
+    match text {
+        "monday" | "mon" => Some(Weekday::Mon),
+        "tuesday" | "tue" | "tues" => Some(Weekday::Tue),
+        "wednesday" | "wed" => Some(Weekday::Wed),
+        "thursday" | "thu" | "thurs" => Some(Weekday::Thu),
+        "friday" | "fri" => Some(Weekday::Fri),
+        "saturday" | "sat" => Some(Weekday::Sat),
+        "sunday" | "sun" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// `3 days`, `3d`, `2 weeks`, `2w`, `1 month`, `1mo`.
+fn parse_offset(text: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let digits_end = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    let amount: u32 = text[..digits_end].parse().ok()?;
+    match text[digits_end..].trim() {
+        "d" | "day" | "days" => today.checked_add_days(Days::new(u64::from(amount))),
+        "w" | "week" | "weeks" => today.checked_add_days(Days::new(u64::from(amount) * 7)),
+        "mo" | "month" | "months" => today.checked_add_months(Months::new(amount)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A Wednesday.
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, 12).unwrap()
+    }
+
+    fn parse(input: &str) -> NaiveDate {
+        parse_due_date(input, today()).unwrap()
+    }
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+    }
+
+    #[test]
+    fn parses_relative_phrases() {
+        assert_eq!(parse("2025-04-01"), date(4, 1));
+        assert_eq!(parse("Tomorrow"), date(3, 13));
+        assert_eq!(parse("next friday"), date(3, 14));
+        assert_eq!(parse("wed"), date(3, 19));
+        assert_eq!(parse("in 3 days"), date(3, 15));
+        assert_eq!(parse("2w"), date(3, 26));
+        assert_eq!(parse("end of month"), date(3, 31));
+    }
+
+    #[test]
+    fn rejects_unknown_phrases() {
+        assert!(parse_due_date("whenever", today()).is_err());
+        assert!(parse_due_date("  ", today()).is_err());
+    }
+}
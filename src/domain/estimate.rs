@@ -0,0 +1,49 @@
+/// Story point values a team estimates with.
+pub const FIBONACCI_SCALE: &[u32] = &[1, 2, 3, 5, 8, 13, 21];
+
+/// Parses a scale name (`fibonacci`, `linear`, `powers-of-two`) or an explicit
+/// comma-separated list such as `1,2,4,8`. Returns the values sorted and deduplicated.
+pub fn parse_estimate_scale(input: &str) -> Option<Vec<u32>> {
+    let mut scale = match input.trim().to_lowercase().as_str() {
+        "fibonacci" | "fib" => FIBONACCI_SCALE.to_vec(),
+        "linear" => (1..=10).collect(),
+        "powers-of-two" | "powers" => vec![1, 2, 4, 8, 16],
+        list => list
+            .split(',')
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse().ok())
+            .collect::<Option<Vec<u32>>>()?,
+    };
+    scale.sort_unstable();
+    scale.dedup();
+    (!scale.is_empty()).then_some(scale)
+}
+
+/// Rounds a model's raw estimate to the nearest value on `scale`, preferring the larger
+/// value on ties (underestimates hurt more than overestimates).
+pub fn snap_to_scale(estimate: f64, scale: &[u32]) -> Option<u32> {
+    if !estimate.is_finite() || estimate <= 0.0 {
+        return None;
+    }
+    scale.iter().copied().min_by(|a, b| {
+        let distance_a = (f64::from(*a) - estimate).abs();
+        let distance_b = (f64::from(*b) - estimate).abs();
+        distance_a.total_cmp(&distance_b).then(b.cmp(a))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snaps_to_nearest_scale_value() {
+        let scale = parse_estimate_scale("fibonacci").unwrap();
+        assert_eq!(snap_to_scale(4.0, &scale), Some(5));
+        assert_eq!(snap_to_scale(10.0, &scale), Some(8));
+        assert_eq!(snap_to_scale(0.0, &scale), None);
+        assert_eq!(parse_estimate_scale("8, 2,4"), Some(vec![2, 4, 8]));
+        assert_eq!(parse_estimate_scale("small"), None);
+    }
+}
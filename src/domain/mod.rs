@@ -1,3 +1,15 @@
+pub mod backlog;
+pub mod board;
 pub mod branch;
 pub mod change;
+pub mod changelog;
+pub mod digest;
+pub mod due;
+pub mod estimate;
+pub mod retro;
+pub mod romanize;
+pub mod sprint;
+pub mod standup;
 pub mod ticket;
+pub mod user;
+pub mod worklog;
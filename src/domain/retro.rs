@@ -0,0 +1,325 @@
+use chrono::{DateTime, Days, NaiveDate, TimeDelta, Utc};
+
+use crate::domain::sprint::Sprint;
+
+/// Tickets finishing faster than this count as quick turnarounds in the fallback notes.
+const QUICK_TURNAROUND_HOURS: i64 = 24;
+
+/// The stretch of work a retrospective looks back on.
+#[derive(Debug, Clone)]
+pub enum RetroScope {
+    Sprint(Sprint),
+    /// Tickets resolved from `from` through `to`, both inclusive.
+    Period {
+        from: NaiveDate,
+        to: NaiveDate,
+    },
+}
+
+impl RetroScope {
+    pub fn label(&self) -> String {
+        match self {
+            RetroScope::Sprint(sprint) => sprint.name.clone(),
+            RetroScope::Period { from, to } => format!("{from} to {to}"),
+        }
+    }
+
+    /// The time range commits are read from; `None` for a sprint that never started.
+    pub fn window(&self) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        match self {
+            RetroScope::Sprint(sprint) => {
+                Some((sprint.start?, sprint.end.unwrap_or_else(Utc::now)))
+            }
+            RetroScope::Period { from, to } => Some((
+                from.and_time(Default::default()).and_utc(),
+                to.checked_add_days(Days::new(1))?
+                    .and_time(Default::default())
+                    .and_utc(),
+            )),
+        }
+    }
+}
+
+/// A ticket that reached a done status within the retrospective's scope.
+#[derive(Debug, Clone)]
+pub struct CompletedTicket {
+    pub key: String,
+    pub summary: String,
+    pub issue_type: Option<String>,
+    pub labels: Vec<String>,
+    pub created: DateTime<Utc>,
+    /// When the ticket last moved into its done status.
+    pub done: DateTime<Utc>,
+}
+
+impl CompletedTicket {
+    /// Created to done.
+    pub fn cycle_time(&self) -> TimeDelta {
+        (self.done - self.created).max(TimeDelta::zero())
+    }
+
+    pub fn is_revert_or_hotfix(&self) -> bool {
+        mentions_revert_or_hotfix(&self.summary)
+            || self
+                .issue_type
+                .as_deref()
+                .is_some_and(mentions_revert_or_hotfix)
+            || self
+                .labels
+                .iter()
+                .any(|label| mentions_revert_or_hotfix(label))
+    }
+}
+
+/// One commit from the repository's history.
+#[derive(Debug, Clone)]
+pub struct CommitSummary {
+    /// Abbreviated hash.
+    pub id: String,
+    pub subject: String,
+}
+
+/// Revert or hotfix wording as teams commonly write it in titles, labels, and commits.
+pub fn mentions_revert_or_hotfix(text: &str) -> bool {
+    let text = text.to_lowercase();
+    [
+        "revert",
+        "hotfix",
+        "hot fix",
+        "hot-fix",
+        "rollback",
+        "roll back",
+    ]
+    .iter()
+    .any(|word| text.contains(word))
+}
+
+/// Everything a retrospective is drafted from.
+#[derive(Debug, Clone)]
+pub struct RetroFacts {
+    pub board: String,
+    pub period: String,
+    pub tickets: Vec<CompletedTicket>,
+    /// Commits made during the period, or `None` when the history was not available.
+    pub commits: Option<Vec<CommitSummary>>,
+}
+
+impl RetroFacts {
+    pub fn median_cycle_time(&self) -> Option<TimeDelta> {
+        let mut times: Vec<TimeDelta> = self
+            .tickets
+            .iter()
+            .map(CompletedTicket::cycle_time)
+            .collect();
+        times.sort();
+        let middle = times.len() / 2;
+        match times.len() {
+            0 => None,
+            len if len % 2 == 1 => Some(times[middle]),
+            _ => Some((times[middle - 1] + times[middle]) / 2),
+        }
+    }
+
+    pub fn notable_tickets(&self) -> impl Iterator<Item = &CompletedTicket> {
+        self.tickets
+            .iter()
+            .filter(|ticket| ticket.is_revert_or_hotfix())
+    }
+
+    pub fn notable_commits(&self) -> impl Iterator<Item = &CommitSummary> {
+        self.commits
+            .iter()
+            .flatten()
+            .filter(|commit| mentions_revert_or_hotfix(&commit.subject))
+    }
+
+    /// Tickets that took more than twice the median, slowest first.
+    pub fn slow_tickets(&self) -> Vec<&CompletedTicket> {
+        let Some(median) = self.median_cycle_time() else {
+            return Vec::new();
+        };
+        let mut slow: Vec<&CompletedTicket> = self
+            .tickets
+            .iter()
+            .filter(|ticket| ticket.cycle_time() > median * 2)
+            .collect();
+        slow.sort_by_key(|ticket| std::cmp::Reverse(ticket.cycle_time()));
+        slow
+    }
+
+    /// Notes derived from the numbers alone, for when no model is available.
+    pub fn heuristic_notes(&self) -> RetroNotes {
+        let mut notes = RetroNotes::default();
+
+        if let Some(median) = self.median_cycle_time() {
+            notes.went_well.push(format!(
+                "{} ticket(s) completed with a median cycle time of {}.",
+                self.tickets.len(),
+                format_duration(median)
+            ));
+        } else {
+            notes
+                .went_poorly
+                .push("No tickets were completed in this period.".to_string());
+        }
+        let quick: Vec<&str> = self
+            .tickets
+            .iter()
+            .filter(|ticket| ticket.cycle_time() < TimeDelta::hours(QUICK_TURNAROUND_HOURS))
+            .map(|ticket| ticket.key.as_str())
+            .collect();
+        if !quick.is_empty() {
+            notes
+                .went_well
+                .push(format!("Finished within a day: {}.", quick.join(", ")));
+        }
+
+        let slow = self.slow_tickets();
+        if !slow.is_empty() {
+            let listed: Vec<String> = slow
+                .iter()
+                .map(|ticket| format!("{} ({})", ticket.key, format_duration(ticket.cycle_time())))
+                .collect();
+            notes.went_poorly.push(format!(
+                "Took more than twice the median: {}.",
+                listed.join(", ")
+            ));
+        }
+        let notable: Vec<String> = self
+            .notable_tickets()
+            .map(|ticket| ticket.key.clone())
+            .chain(self.notable_commits().map(|commit| commit.id.clone()))
+            .collect();
+        if !notable.is_empty() {
+            notes
+                .went_poorly
+                .push(format!("Reverts or hotfixes: {}.", notable.join(", ")));
+            notes
+                .action_items
+                .push("Look at what let the reverted or hotfixed changes through.".to_string());
+        }
+        if !slow.is_empty() {
+            notes
+                .action_items
+                .push("Check whether the slowest tickets could have been split.".to_string());
+        }
+        notes
+    }
+
+    /// The finished Markdown document: title, the notes, then the supporting data.
+    pub fn render(&self, notes: &RetroNotes) -> String {
+        let mut doc = format!("# Retrospective: {}, {}\n", self.board, self.period);
+        for (heading, items) in [
+            ("What went well", &notes.went_well),
+            ("What didn't go well", &notes.went_poorly),
+            ("Action items", &notes.action_items),
+        ] {
+            doc.push_str(&format!("\n## {heading}\n\n"));
+            if items.is_empty() {
+                doc.push_str("- Nothing noted.\n");
+            }
+            for item in items {
+                doc.push_str(&format!("- {}\n", item.trim()));
+            }
+        }
+
+        doc.push_str(&format!(
+            "\n## Completed tickets ({})\n\n",
+            self.tickets.len()
+        ));
+        if let Some(median) = self.median_cycle_time() {
+            doc.push_str(&format!(
+                "Median cycle time (created to done): {}.\n\n",
+                format_duration(median)
+            ));
+        }
+        if self.tickets.is_empty() {
+            doc.push_str("None.\n");
+        } else {
+            doc.push_str("| Ticket | Type | Summary | Cycle time |\n|---|---|---|---|\n");
+            for ticket in &self.tickets {
+                doc.push_str(&format!(
+                    "| {} | {} | {} | {} |\n",
+                    ticket.key,
+                    ticket.issue_type.as_deref().unwrap_or("-"),
+                    ticket.summary.replace('|', "\\|"),
+                    format_duration(ticket.cycle_time())
+                ));
+            }
+        }
+
+        let notable_commits: Vec<&CommitSummary> = self.notable_commits().collect();
+        if !notable_commits.is_empty() {
+            doc.push_str("\n## Reverts and hotfixes in git\n\n");
+            for commit in notable_commits {
+                doc.push_str(&format!("- `{}` {}\n", commit.id, commit.subject));
+            }
+        }
+        doc
+    }
+}
+
+/// The judgement part of a retrospective, written by a model or derived heuristically.
+#[derive(Debug, Clone, Default)]
+pub struct RetroNotes {
+    pub went_well: Vec<String>,
+    pub went_poorly: Vec<String>,
+    pub action_items: Vec<String>,
+}
+
+/// Compact duration such as `45m`, `5h`, or `3d 4h`.
+pub fn format_duration(duration: TimeDelta) -> String {
+    let days = duration.num_days();
+    let hours = duration.num_hours() % 24;
+    match (days, hours) {
+        (0, 0) => format!("{}m", duration.num_minutes().max(0)),
+        (0, hours) => format!("{hours}h"),
+        (days, 0) => format!("{days}d"),
+        (days, hours) => format!("{days}d {hours}h"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticket(key: &str, summary: &str, hours: i64) -> CompletedTicket {
+        let created = DateTime::parse_from_rfc3339("2025-03-03T09:00:00Z")
+            .unwrap()
+            .to_utc();
+        CompletedTicket {
+            key: key.to_string(),
+            summary: summary.to_string(),
+            issue_type: None,
+            labels: Vec::new(),
+            created,
+            done: created + TimeDelta::hours(hours),
+        }
+    }
+
+    #[test]
+    fn heuristic_notes_flag_slow_tickets_and_reverts() {
+        let facts = RetroFacts {
+            board: "PAY".to_string(),
+            period: "Sprint 24".to_string(),
+            tickets: vec![
+                ticket("PAY-1", "Add refunds", 10),
+                ticket("PAY-2", "Hotfix rounding in invoices", 30),
+                ticket("PAY-3", "Rework ledger", 200),
+            ],
+            commits: Some(vec![CommitSummary {
+                id: "abc1234".to_string(),
+                subject: "Revert \"Cache exchange rates\"".to_string(),
+            }]),
+        };
+
+        assert_eq!(facts.median_cycle_time(), Some(TimeDelta::hours(30)));
+        assert_eq!(format_duration(TimeDelta::hours(200)), "8d 8h");
+
+        let notes = facts.heuristic_notes();
+        assert!(notes.went_well[1].contains("PAY-1"));
+        assert!(notes.went_poorly[0].contains("PAY-3 (8d 8h)"));
+        assert!(notes.went_poorly[1].contains("PAY-2, abc1234"));
+        assert_eq!(notes.action_items.len(), 2);
+    }
+}
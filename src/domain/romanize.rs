@@ -0,0 +1,260 @@
+/// Spells `text` in ASCII so branch slugs stay ASCII when tickets are written in another
+/// language: Latin diacritics are dropped, Cyrillic, Greek, Japanese kana, and Hangul are
+/// transliterated, and everything else that is not a letter or digit becomes a space.
+///
+/// Returns `None` when some letters have no spelling here, such as Chinese characters or
+/// Japanese kanji, which need a dictionary to read.
+pub fn romanize(text: &str) -> Option<String> {
+    let mut out = String::with_capacity(text.len());
+    let mut complete = true;
+    // A small っ/ッ doubles the consonant of the kana after it.
+    let mut double_next = false;
+
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if c.is_ascii() {
+            out.push(c);
+        } else if let Some(kana) = kana(c) {
+            match kana {
+                Kana::Syllable(romaji) => {
+                    if double_next && let Some(first) = romaji.chars().next() {
+                        if romaji.starts_with("ch") {
+                            out.push('t');
+                        } else if !"aeiou".contains(first) {
+                            out.push(first);
+                        }
+                    }
+                    out.push_str(romaji);
+                }
+                Kana::Small(vowel) => {
+                    // きゃ → kya, しゃ → sha, ちゃ → cha, じゃ → ja
+                    if out.ends_with("shi") || out.ends_with("chi") || out.ends_with("ji") {
+                        out.pop();
+                    } else if out.ends_with('i') {
+                        out.pop();
+                        out.push('y');
+                    } else {
+                        out.push('y');
+                    }
+                    out.push(vowel);
+                }
+                Kana::SmallVowel(vowel) => {
+                    // チェ → che, ファ → fa, ティ → ti, ウィ → wi
+                    if out.ends_with(['a', 'i', 'u', 'e', 'o']) {
+                        let previous = out.pop();
+                        if out.is_empty() || out.ends_with(|c: char| !c.is_ascii_alphabetic()) {
+                            out.push(if previous == Some('u') { 'w' } else { 'y' });
+                        }
+                    }
+                    out.push(vowel);
+                }
+                Kana::Sokuon => {
+                    double_next = true;
+                    continue;
+                }
+                Kana::Skip => {}
+            }
+        } else if let Some(syllable) = hangul(c) {
+            out.push_str(&syllable);
+        } else if let Some(spelled) = letter(c) {
+            out.push_str(spelled);
+        } else if c.is_alphanumeric() {
+            complete = false;
+            out.push(' ');
+        } else {
+            out.push(' ');
+        }
+        double_next = false;
+    }
+    complete.then_some(out)
+}
+
+enum Kana {
+    Syllable(&'static str),
+    /// Small ゃ/ゅ/ょ, which merge with the kana before them.
+    Small(char),
+    /// Small ぁ/ぃ/ぅ/ぇ/ぉ, which replace the vowel of the kana before them.
+    SmallVowel(char),
+    /// Small っ.
+    Sokuon,
+    /// The long vowel mark; Hepburn slugs leave it out.
+    Skip,
+}
+
+#[rustfmt::skip]
+const HIRAGANA: [(char, &str); 75] = [
+    ('あ', "a"), ('い', "i"), ('う', "u"), ('え', "e"), ('お', "o"),
+    ('か', "ka"), ('き', "ki"), ('く', "ku"), ('け', "ke"), ('こ', "ko"),
+    ('が', "ga"), ('ぎ', "gi"), ('ぐ', "gu"), ('げ', "ge"), ('ご', "go"),
+    ('さ', "sa"), ('し', "shi"), ('す', "su"), ('せ', "se"), ('そ', "so"),
+    ('ざ', "za"), ('じ', "ji"), ('ず', "zu"), ('ぜ', "ze"), ('ぞ', "zo"),
+    ('た', "ta"), ('ち', "chi"), ('つ', "tsu"), ('て', "te"), ('と', "to"),
+    ('だ', "da"), ('ぢ', "ji"), ('づ', "zu"), ('で', "de"), ('ど', "do"),
+    ('な', "na"), ('に', "ni"), ('ぬ', "nu"), ('ね', "ne"), ('の', "no"),
+    ('は', "ha"), ('ひ', "hi"), ('ふ', "fu"), ('へ', "he"), ('ほ', "ho"),
+    ('ば', "ba"), ('び', "bi"), ('ぶ', "bu"), ('べ', "be"), ('ぼ', "bo"),
+    ('ぱ', "pa"), ('ぴ', "pi"), ('ぷ', "pu"), ('ぺ', "pe"), ('ぽ', "po"),
+    ('ま', "ma"), ('み', "mi"), ('む', "mu"), ('め', "me"), ('も', "mo"),
+    ('や', "ya"), ('ゆ', "yu"), ('よ', "yo"), ('ら', "ra"), ('り', "ri"),
+    ('る', "ru"), ('れ', "re"), ('ろ', "ro"), ('わ', "wa"), ('ゐ', "i"),
+    ('ゑ', "e"), ('を', "o"), ('ん', "n"), ('ゔ', "vu"), ('ゎ', "wa"),
+];
+
+fn kana(c: char) -> Option<Kana> {
+    // Katakana sit 0x60 above their hiragana.
+    let c = match c {
+        'ァ'..='ヶ' => char::from_u32(c as u32 - 0x60)?,
+        'ー' => return Some(Kana::Skip),
+        _ => c,
+    };
+    match c {
+        'ゃ' => Some(Kana::Small('a')),
+        'ゅ' => Some(Kana::Small('u')),
+        'ょ' => Some(Kana::Small('o')),
+        'っ' => Some(Kana::Sokuon),
+        'ぁ' => Some(Kana::SmallVowel('a')),
+        'ぃ' => Some(Kana::SmallVowel('i')),
+        'ぅ' => Some(Kana::SmallVowel('u')),
+        'ぇ' => Some(Kana::SmallVowel('e')),
+        'ぉ' => Some(Kana::SmallVowel('o')),
+        'ゕ' => Some(Kana::Syllable("ka")),
+        'ゖ' => Some(Kana::Syllable("ke")),
+        _ => HIRAGANA
+            .iter()
+            .find(|(kana, _)| *kana == c)
+            .map(|(_, romaji)| Kana::Syllable(romaji)),
+    }
+}
+
+/// Revised Romanization of a precomposed Hangul syllable, with final consonants as
+/// pronounced at the end of a word.
+fn hangul(c: char) -> Option<String> {
+    const INITIALS: [&str; 19] = [
+        "g", "kk", "n", "d", "tt", "r", "m", "b", "pp", "s", "ss", "", "j", "jj", "ch", "k", "t",
+        "p", "h",
+    ];
+    const MEDIALS: [&str; 21] = [
+        "a", "ae", "ya", "yae", "eo", "e", "yeo", "ye", "o", "wa", "wae", "oe", "yo", "u", "wo",
+        "we", "wi", "yu", "eu", "ui", "i",
+    ];
+    const FINALS: [&str; 28] = [
+        "", "k", "k", "k", "n", "n", "n", "t", "l", "k", "m", "l", "l", "l", "p", "l", "m", "p",
+        "p", "t", "t", "ng", "t", "t", "k", "t", "p", "t",
+    ];
+    let index = (c as u32).checked_sub(0xAC00)? as usize;
+    if index >= 19 * 21 * 28 {
+        return None;
+    }
+    Some(format!(
+        "{}{}{}",
+        INITIALS[index / (21 * 28)],
+        MEDIALS[index % (21 * 28) / 28],
+        FINALS[index % 28]
+    ))
+}
+
+/// Latin letters with diacritics, Cyrillic, and Greek, already lower-cased.
+fn letter(c: char) -> Option<&'static str> {
+    let spelled = match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ĵ' => "j",
+        'ķ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'œ' => "oe",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' | 'ș' => "s",
+        'ß' => "ss",
+        'ţ' | 'ť' | 'ŧ' | 'ț' => "t",
+        'þ' => "th",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        // Cyrillic (Russian and Ukrainian)
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' | 'ґ' => "g",
+        'д' => "d",
+        'е' | 'э' => "e",
+        'ё' => "yo",
+        'є' => "ye",
+        'ж' => "zh",
+        'з' => "z",
+        'и' | 'і' => "i",
+        'ї' => "yi",
+        'й' | 'ы' => "y",
+        'к' => "k",
+        'л' => "l",
+        'м' => "m",
+        'н' => "n",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'у' => "u",
+        'ф' => "f",
+        'х' => "kh",
+        'ц' => "ts",
+        'ч' => "ch",
+        'ш' => "sh",
+        'щ' => "shch",
+        'ъ' | 'ь' => "",
+        'ю' => "yu",
+        'я' => "ya",
+        // Greek
+        'α' | 'ά' => "a",
+        'β' => "v",
+        'γ' => "g",
+        'δ' => "d",
+        'ε' | 'έ' => "e",
+        'ζ' => "z",
+        'η' | 'ή' | 'ι' | 'ί' | 'ϊ' | 'ΐ' => "i",
+        'θ' => "th",
+        'κ' => "k",
+        'λ' => "l",
+        'μ' => "m",
+        'ν' => "n",
+        'ξ' => "x",
+        'ο' | 'ό' | 'ω' | 'ώ' => "o",
+        'π' => "p",
+        'ρ' => "r",
+        'σ' | 'ς' => "s",
+        'τ' => "t",
+        'υ' | 'ύ' | 'ϋ' | 'ΰ' => "y",
+        'φ' => "f",
+        'χ' => "ch",
+        'ψ' => "ps",
+        _ => return None,
+    };
+    Some(spelled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spells_other_scripts_in_ascii() {
+        assert_eq!(romanize("Café Größe").unwrap(), "cafe grosse");
+        assert_eq!(romanize("Исправить возвраты").unwrap(), "ispravit vozvraty");
+        assert_eq!(romanize("チェックアウト").unwrap(), "chekkuauto");
+        assert_eq!(romanize("ファイル").unwrap(), "fairu");
+        assert_eq!(
+            romanize("きょう の しゅっぱつ").unwrap(),
+            "kyou no shuppatsu"
+        );
+        assert_eq!(romanize("결제 환불").unwrap(), "gyeolje hwanbul");
+        assert_eq!(romanize("add-refunds").unwrap(), "add-refunds");
+        assert!(romanize("返金を修正").is_none());
+    }
+}
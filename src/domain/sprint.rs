@@ -0,0 +1,85 @@
+use chrono::{DateTime, Utc};
+
+/// A sprint on one of a board's scrum boards.
+#[derive(Debug, Clone)]
+pub struct Sprint {
+    pub id: u64,
+    pub name: String,
+    /// `active`, `future`, or `closed`.
+    pub state: String,
+    /// Unset until the sprint is started.
+    pub start: Option<DateTime<Utc>>,
+    /// When a closed sprint was completed, else its planned end.
+    pub end: Option<DateTime<Utc>>,
+}
+
+impl Sprint {
+    pub fn label(&self) -> String {
+        format!("{} ({}, id {})", self.name, self.state, self.id)
+    }
+}
+
+/// Where a new ticket goes on the board's planning views.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placement {
+    Backlog,
+    Sprint(u64),
+}
+
+/// Sprints matching `query`, from the best tier only: exact name, then name containing
+/// the query, then names containing every word of it (so "24 payments" finds
+/// "Payments Sprint 24"). Matching ignores case.
+pub fn match_sprints<'a>(sprints: &'a [Sprint], query: &str) -> Vec<&'a Sprint> {
+    let query = query.trim().to_lowercase();
+    let words: Vec<&str> = query.split_whitespace().collect();
+    let tiers: [&dyn Fn(&str) -> bool; 3] = [
+        &|name| name == query,
+        &|name| name.contains(&query),
+        &|name| !words.is_empty() && words.iter().all(|word| name.contains(word)),
+    ];
+    for matches in tiers {
+        let found: Vec<&Sprint> = sprints
+            .iter()
+            .filter(|sprint| matches(&sprint.name.to_lowercase()))
+            .collect();
+        if !found.is_empty() {
+            return found;
+        }
+    }
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sprint(id: u64, name: &str) -> Sprint {
+        Sprint {
+            id,
+            name: name.to_string(),
+            state: "future".to_string(),
+            start: None,
+            end: None,
+        }
+    }
+
+    #[test]
+    fn prefers_exact_then_substring_then_word_matches() {
+        let sprints = vec![
+            sprint(1, "Payments Sprint 24"),
+            sprint(2, "Payments Sprint 24b"),
+            sprint(3, "Core Sprint 24"),
+        ];
+
+        let exact = match_sprints(&sprints, "payments sprint 24");
+        assert_eq!(exact.iter().map(|s| s.id).collect::<Vec<_>>(), vec![1]);
+
+        let partial = match_sprints(&sprints, "sprint 24");
+        assert_eq!(partial.len(), 3);
+
+        let words = match_sprints(&sprints, "24 core");
+        assert_eq!(words.iter().map(|s| s.id).collect::<Vec<_>>(), vec![3]);
+
+        assert!(match_sprints(&sprints, "billing").is_empty());
+    }
+}
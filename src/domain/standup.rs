@@ -0,0 +1,189 @@
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::{DateTime, Local, Utc};
+
+use crate::domain::branch::ticket_key_in;
+
+/// Label of the group holding commits no ticket key could be found for.
+pub const OTHER_WORK: &str = "other";
+
+/// Commit subjects quoted per group in the fallback summary.
+const SUBJECTS_PER_GROUP: usize = 5;
+
+/// A commit on one of the user's local branches.
+#[derive(Debug, Clone)]
+pub struct BranchCommit {
+    pub branch: String,
+    /// Abbreviated hash.
+    pub id: String,
+    pub subject: String,
+    pub committed: DateTime<Utc>,
+}
+
+/// Commits that belong to one ticket, or to no ticket at all.
+#[derive(Debug, Clone)]
+pub struct WorkGroup {
+    pub ticket: Option<String>,
+    pub branches: Vec<String>,
+    /// Newest first.
+    pub commits: Vec<BranchCommit>,
+}
+
+impl WorkGroup {
+    /// The ticket key, or [`OTHER_WORK`].
+    pub fn label(&self) -> &str {
+        self.ticket.as_deref().unwrap_or(OTHER_WORK)
+    }
+}
+
+/// Everything a standup summary is written from.
+#[derive(Debug, Clone)]
+pub struct StandupFacts {
+    pub since: DateTime<Utc>,
+    /// Most recently touched first, with [`OTHER_WORK`] last.
+    pub groups: Vec<WorkGroup>,
+}
+
+/// One summary per group label, written by a model or taken from the commit subjects.
+#[derive(Debug, Clone, Default)]
+pub struct StandupNotes {
+    pub summaries: BTreeMap<String, String>,
+}
+
+impl StandupFacts {
+    /// Groups commits by the ticket key in their branch name, or in their subject when the
+    /// branch has none. A commit reachable from several branches counts once, under a
+    /// ticket branch when one of them is.
+    pub fn group(since: DateTime<Utc>, mut commits: Vec<BranchCommit>) -> Self {
+        commits.sort_by_key(|commit| ticket_key_in(&commit.branch).is_none());
+        let mut seen = HashSet::new();
+        commits.retain(|commit| seen.insert(commit.id.clone()));
+        commits.sort_by_key(|commit| std::cmp::Reverse(commit.committed));
+
+        let mut groups: Vec<WorkGroup> = Vec::new();
+        for commit in commits {
+            let ticket = ticket_key_in(&commit.branch).or_else(|| ticket_key_in(&commit.subject));
+            let group = match groups.iter().position(|group| group.ticket == ticket) {
+                Some(index) => &mut groups[index],
+                None => {
+                    groups.push(WorkGroup {
+                        ticket,
+                        branches: Vec::new(),
+                        commits: Vec::new(),
+                    });
+                    groups.last_mut().expect("group was just pushed")
+                }
+            };
+            if !group.branches.contains(&commit.branch) {
+                group.branches.push(commit.branch.clone());
+            }
+            group.commits.push(commit);
+        }
+        groups.sort_by_key(|group| group.ticket.is_none());
+        Self { since, groups }
+    }
+
+    /// Summaries made of the commit subjects, for when no model is available.
+    pub fn heuristic_notes(&self) -> StandupNotes {
+        let summaries = self
+            .groups
+            .iter()
+            .map(|group| {
+                let mut subjects: Vec<&str> = group
+                    .commits
+                    .iter()
+                    .take(SUBJECTS_PER_GROUP)
+                    .map(|commit| commit.subject.as_str())
+                    .collect();
+                let more = group.commits.len().saturating_sub(SUBJECTS_PER_GROUP);
+                let tail = format!("and {more} more commit(s)");
+                if more > 0 {
+                    subjects.push(&tail);
+                }
+                (group.label().to_string(), subjects.join("; "))
+            })
+            .collect();
+        StandupNotes { summaries }
+    }
+
+    /// The printable standup; groups the notes skip fall back to their commit subjects.
+    pub fn render(&self, notes: &StandupNotes) -> String {
+        let since = self.since.with_timezone(&Local).format("%a %d %b %H:%M");
+        if self.groups.is_empty() {
+            return format!("No commits on your local branches since {since}.\n");
+        }
+
+        let fallback = self.heuristic_notes();
+        let mut text = format!("Standup: work since {since}\n");
+        for group in &self.groups {
+            let summary = notes
+                .summaries
+                .get(group.label())
+                .filter(|summary| !summary.trim().is_empty())
+                .or_else(|| fallback.summaries.get(group.label()))
+                .map_or("", |summary| summary.trim());
+            let name = group.ticket.as_deref().unwrap_or("Other work");
+            text.push_str(&format!(
+                "- {name} ({}): {summary}\n",
+                group.branches.join(", ")
+            ));
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(branch: &str, id: &str, subject: &str, hour: u32) -> BranchCommit {
+        BranchCommit {
+            branch: branch.to_string(),
+            id: id.to_string(),
+            subject: subject.to_string(),
+            committed: DateTime::parse_from_rfc3339(&format!("2025-03-10T{hour:02}:00:00Z"))
+                .unwrap()
+                .to_utc(),
+        }
+    }
+
+    #[test]
+    fn groups_commits_by_ticket_key_and_counts_each_commit_once() {
+        assert_eq!(
+            ticket_key_in("feature/PAY-123/add-refunds").as_deref(),
+            Some("PAY-123")
+        );
+        assert_eq!(ticket_key_in("hotfix/pay-1-UTF-8x"), None);
+
+        let facts = StandupFacts::group(
+            Utc::now(),
+            vec![
+                commit("main", "a1", "Add refund endpoint", 9),
+                commit("main", "b2", "Bump dependencies", 10),
+                commit(
+                    "feature/PAY-123/add-refunds",
+                    "a1",
+                    "Add refund endpoint",
+                    9,
+                ),
+                commit("main", "c3", "OPS-7: rotate keys", 11),
+            ],
+        );
+
+        let labels: Vec<&str> = facts.groups.iter().map(WorkGroup::label).collect();
+        assert_eq!(labels, ["OPS-7", "PAY-123", OTHER_WORK]);
+        assert_eq!(facts.groups[1].branches, ["feature/PAY-123/add-refunds"]);
+
+        let mut notes = StandupNotes::default();
+        notes.summaries.insert(
+            "PAY-123".to_string(),
+            "Built the refund endpoint.".to_string(),
+        );
+        let rendered = facts.render(&notes);
+        assert!(
+            rendered
+                .contains("- PAY-123 (feature/PAY-123/add-refunds): Built the refund endpoint.\n")
+        );
+        assert!(rendered.contains("- Other work (main): Bump dependencies\n"));
+    }
+}
@@ -1,4 +1,11 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use serde_json::Value;
+
 use crate::domain::branch::BranchCategory;
+use crate::domain::change::ChangeSummary;
+use crate::domain::sprint::Placement;
 
 #[derive(Debug, Clone)]
 pub struct TicketDraft {
@@ -6,6 +13,136 @@ pub struct TicketDraft {
     pub description: String,
     pub branch_category: BranchCategory,
     pub branch_summary: String,
+    /// Verifiable conditions for the work to count as done; may be empty.
+    pub acceptance_criteria: Vec<String>,
+    /// Story points on the team's configured scale, when estimates are enabled.
+    pub estimate: Option<u32>,
+    /// Which language model (or `heuristic`) produced the draft, when known.
+    pub provider: Option<String>,
+    /// The model's own account of what the changes do and why it drafted them this way.
+    /// Shown before filing and kept in the history, never sent to the tracker.
+    pub rationale: Option<String>,
+}
+
+/// File name a truncated description is attached under in full.
+pub const OVERFLOW_ATTACHMENT_NAME: &str = "ticket-details.md";
+
+const TRUNCATION_NOTE: &str =
+    "(Truncated; see the attached ticket-details.md for the full details.)";
+
+/// What a tracker accepts in a ticket's text fields, counted in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextLimits {
+    pub title_chars: usize,
+    pub description_chars: usize,
+}
+
+impl TicketDraft {
+    /// Shortens the draft to fit `limits`. The title becomes one line cut at a word; a
+    /// description that is too long is cut at a paragraph, line, or word and ends with a
+    /// pointer to the attachment. Returns the full description when it was cut.
+    pub fn fit_to(&mut self, limits: &TextLimits) -> Option<String> {
+        let title = self.title.split_whitespace().collect::<Vec<_>>().join(" ");
+        self.title = if title.chars().count() > limits.title_chars {
+            let head = cut_at_boundary(&title, limits.title_chars.saturating_sub(1));
+            format!("{}…", head.trim_end())
+        } else {
+            title
+        };
+
+        // Trackers without a criteria field render them into the description, so they
+        // share its budget.
+        let criteria_chars: usize = self
+            .acceptance_criteria
+            .iter()
+            .map(|criterion| criterion.chars().count() + 4)
+            .sum();
+        let budget = limits.description_chars.saturating_sub(criteria_chars);
+        if self.description.chars().count() <= budget {
+            return None;
+        }
+
+        let full = self.description.clone();
+        let keep = budget.saturating_sub(TRUNCATION_NOTE.chars().count() + 2);
+        let head = cut_at_boundary(&full, keep).trim_end();
+        self.description = if head.is_empty() {
+            TRUNCATION_NOTE.to_string()
+        } else {
+            format!("{head}\n\n{TRUNCATION_NOTE}")
+        };
+        Some(full)
+    }
+}
+
+/// The longest prefix of `text` within `max_chars`, ending at a paragraph break, line
+/// break, or space when one falls in its second half.
+fn cut_at_boundary(text: &str, max_chars: usize) -> &str {
+    let end = text
+        .char_indices()
+        .nth(max_chars)
+        .map_or(text.len(), |(index, _)| index);
+    let head = &text[..end];
+    if end == text.len() {
+        return head;
+    }
+    ["\n\n", "\n", " "]
+        .iter()
+        .filter_map(|separator| head.rfind(separator))
+        .find(|&index| index >= end / 2)
+        .map_or(head, |index| &head[..index])
+}
+
+/// One ticket proposed when a mixed changeset is split, with the files it covers.
+#[derive(Debug, Clone)]
+pub struct SplitDraft {
+    pub draft: TicketDraft,
+    pub files: Vec<String>,
+}
+
+/// Extra prompt context: past feedback on drafts and team conventions.
+#[derive(Debug, Clone, Default)]
+pub struct DraftGuidance {
+    /// Highly rated drafts, used as few-shot examples.
+    pub examples: Vec<DraftExample>,
+    /// Notes from poorly rated drafts that the next draft should address.
+    pub corrections: Vec<String>,
+    /// Story point values to estimate with; empty means no estimate is requested.
+    pub estimate_scale: Vec<u32>,
+    /// Language to write tickets in, e.g. `Japanese`; unset means English. Branch slugs
+    /// stay English either way.
+    pub language: Option<String>,
+    /// Headings the description must be organized under, in order; empty leaves the
+    /// structure to the drafter.
+    pub sections: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DraftExample {
+    pub change_summary: String,
+    pub draft: TicketDraft,
+}
+
+/// Tracker-side placement for a new ticket, independent of the drafted content.
+#[derive(Debug, Clone, Default)]
+pub struct TicketOptions {
+    /// Key of the epic the ticket is filed under.
+    pub epic: Option<String>,
+    /// Tracker account ID of the assignee; unset leaves the project default.
+    pub assignee: Option<String>,
+    /// Tracker account ID of the reporter; unset means the authenticated user.
+    pub reporter: Option<String>,
+    pub due_date: Option<NaiveDate>,
+    /// Per-run custom field values, overriding configured ones with the same ID.
+    pub custom_fields: BTreeMap<String, Value>,
+    /// Issue type name; unset uses the configured default.
+    pub issue_type: Option<String>,
+    pub labels: Vec<String>,
+    /// Component names, e.g. `Checkout`.
+    pub components: Vec<String>,
+    /// Priority name, e.g. `Highest`.
+    pub priority: Option<String>,
+    /// Sprint or backlog to move the ticket to once created; unset leaves Jira's default.
+    pub placement: Option<Placement>,
 }
 
 #[derive(Debug, Clone)]
@@ -13,3 +150,150 @@ pub struct Ticket {
     pub key: String,
     pub url: Option<String>,
 }
+
+/// An existing ticket as the tracker reports it.
+#[derive(Debug, Clone)]
+pub struct TicketDetails {
+    pub key: String,
+    pub title: String,
+    pub issue_type: Option<String>,
+    pub status: Option<String>,
+    pub url: Option<String>,
+    /// The description as plain Markdown-like text; only `get_ticket` fills it in.
+    pub description: Option<String>,
+}
+
+/// How `ugh ticket --amend` brings an existing ticket up to date with new changes.
+#[derive(Debug, Clone)]
+pub struct TicketAmendment {
+    /// The whole description, rewritten to also cover the new changes.
+    pub description: String,
+    /// Replacement acceptance criteria; empty keeps the ticket's own.
+    pub acceptance_criteria: Vec<String>,
+    /// A follow-up comment on what the new changes add.
+    pub comment: String,
+    /// Which language model (or `heuristic`) wrote the amendment.
+    pub provider: Option<String>,
+}
+
+impl TicketAmendment {
+    /// Keeps the description and notes the changed files under a "Follow-up changes"
+    /// heading; the comment lists the same files.
+    pub fn heuristic(ticket: &TicketDetails, changes: &ChangeSummary) -> Self {
+        let files = changes
+            .files
+            .iter()
+            .map(|file| format!("- {file}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let existing = ticket.description.as_deref().unwrap_or_default().trim();
+        let description = if existing.is_empty() {
+            format!("## Follow-up changes\n{files}")
+        } else {
+            format!("{existing}\n\n## Follow-up changes\n{files}")
+        };
+        Self {
+            description,
+            acceptance_criteria: Vec::new(),
+            comment: format!(
+                "Follow-up changes to {} file(s):\n{files}",
+                changes.files.len()
+            ),
+            provider: Some("heuristic".to_string()),
+        }
+    }
+}
+
+/// What notifiers announce after a successful run.
+#[derive(Debug, Clone)]
+pub struct TicketNotice {
+    pub key: String,
+    pub url: Option<String>,
+    pub title: String,
+    pub branch: String,
+}
+
+impl TicketNotice {
+    /// One line such as `PAY-123 created, branch feature/PAY-123/add-refunds ready`.
+    pub fn summary(&self) -> String {
+        format!("{} created, branch {} ready", self.key, self.branch)
+    }
+}
+
+/// How a ticket was taken back by `ugh undo`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TicketRemoval {
+    Deleted,
+    /// Deletion was not permitted, so the ticket was transitioned to this status instead.
+    Closed(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_to_cuts_at_a_boundary_and_returns_the_full_description() {
+        let description = format!("{}\n\n{}", "First paragraph.", "word ".repeat(40));
+        let mut draft = TicketDraft {
+            title: "Add refunds\nto the   ledger".to_string(),
+            description: description.clone(),
+            branch_category: BranchCategory::Feature,
+            branch_summary: "add refunds".to_string(),
+            acceptance_criteria: Vec::new(),
+            estimate: None,
+            provider: None,
+            rationale: None,
+        };
+
+        let overflow = draft.fit_to(&TextLimits {
+            title_chars: 14,
+            description_chars: 100,
+        });
+
+        assert_eq!(draft.title, "Add refunds…");
+        assert_eq!(overflow.as_deref(), Some(description.as_str()));
+        assert!(
+            draft
+                .description
+                .starts_with("First paragraph.\n\n(Truncated;")
+        );
+        assert!(draft.description.chars().count() <= 100);
+
+        let mut short = draft.clone();
+        short.description = "Short.".to_string();
+        assert_eq!(
+            short.fit_to(&TextLimits {
+                title_chars: 255,
+                description_chars: 100,
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn heuristic_amendment_appends_the_changed_files() {
+        let ticket = TicketDetails {
+            key: "PAY-1".to_string(),
+            title: "Add refunds".to_string(),
+            issue_type: None,
+            status: None,
+            url: None,
+            description: Some("Refund card payments.\n".to_string()),
+        };
+        let changes = ChangeSummary {
+            files_changed: 1,
+            summary: String::new(),
+            files: vec!["src/refunds.rs".to_string()],
+        };
+
+        let amendment = TicketAmendment::heuristic(&ticket, &changes);
+
+        assert_eq!(
+            amendment.description,
+            "Refund card payments.\n\n## Follow-up changes\n- src/refunds.rs"
+        );
+        assert!(amendment.comment.ends_with("- src/refunds.rs"));
+        assert!(amendment.acceptance_criteria.is_empty());
+    }
+}
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+/// A person known to the issue tracker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackerUser {
+    /// Tracker-specific identifier (the Jira `accountId`).
+    pub account_id: String,
+    pub display_name: String,
+    #[serde(default)]
+    pub email: Option<String>,
+}
+
+impl TrackerUser {
+    /// `Name <email>` when the email is visible, otherwise just the name.
+    pub fn label(&self) -> String {
+        match &self.email {
+            Some(email) => format!("{} <{email}>", self.display_name),
+            None => self.display_name.clone(),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use chrono::{DateTime, TimeDelta, Utc};
+
+/// Jira's default working day and week, which `d` and `w` stand for.
+const DAY_MINUTES: f64 = 8.0 * 60.0;
+const WEEK_MINUTES: f64 = 5.0 * DAY_MINUTES;
+
+/// Parses time spent in Jira's notation: `2h`, `1h 30m`, `45m`, `1.5h`, `1d` (8 hours),
+/// `1w` (5 days). Every number needs a unit.
+pub fn parse_time_spent(input: &str) -> Result<TimeDelta, String> {
+    let invalid = || {
+        format!(
+            "could not understand time '{}' (try 2h, 1h 30m, 45m, or 1d)",
+            input.trim()
+        )
+    };
+    let mut minutes = 0.0;
+    let mut number = String::new();
+    let mut units = 0;
+    for ch in input.trim().to_lowercase().chars() {
+        let unit = match ch {
+            '0'..='9' | '.' => {
+                number.push(ch);
+                continue;
+            }
+            ' ' if number.is_empty() => continue,
+            'w' => WEEK_MINUTES,
+            'd' => DAY_MINUTES,
+            'h' => 60.0,
+            'm' => 1.0,
+            _ => return Err(invalid()),
+        };
+        let value: f64 = number.parse().map_err(|_| invalid())?;
+        minutes += value * unit;
+        number.clear();
+        units += 1;
+    }
+    if units == 0 || !number.is_empty() {
+        return Err(invalid());
+    }
+    match minutes.round() as i64 {
+        0 => Err("time spent must be at least a minute".to_string()),
+        minutes => Ok(TimeDelta::minutes(minutes)),
+    }
+}
+
+/// `1h 30m`, `2h`, or `45m`; days are left as hours since their length is a convention.
+pub fn format_time_spent(time: TimeDelta) -> String {
+    let minutes = time.num_minutes().max(0);
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{minutes}m"),
+        (hours, 0) => format!("{hours}h"),
+        (hours, minutes) => format!("{hours}h {minutes}m"),
+    }
+}
+
+/// Time from `started` to `now`, rounded up to whole minutes and at least one.
+pub fn elapsed_since(started: DateTime<Utc>, now: DateTime<Utc>) -> TimeDelta {
+    let seconds = (now - started).num_seconds().max(1);
+    TimeDelta::minutes((seconds + 59) / 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_formats_jira_durations() {
+        let minutes = |input| parse_time_spent(input).unwrap().num_minutes();
+        assert_eq!(minutes("2h"), 120);
+        assert_eq!(minutes("1h 30m"), 90);
+        assert_eq!(minutes("1.5H"), 90);
+        assert_eq!(minutes("1d 1w"), 6 * 8 * 60);
+        assert!(parse_time_spent("90").is_err());
+        assert!(parse_time_spent("2 hours").is_err());
+        assert!(parse_time_spent("0m").is_err());
+
+        assert_eq!(format_time_spent(TimeDelta::minutes(90)), "1h 30m");
+        assert_eq!(format_time_spent(TimeDelta::minutes(600)), "10h");
+        assert_eq!(format_time_spent(TimeDelta::minutes(5)), "5m");
+    }
+}
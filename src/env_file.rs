@@ -0,0 +1,228 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use git2::Repository;
+use tracing::warn;
+
+use crate::error::AppResult;
+use crate::repo_state::main_worktree;
+
+/// Untracked files in the repository root that may set `UGH_*` variables, the first one
+/// winning where both set the same variable. A file git tracks is ignored: it arrives with
+/// a clone, so it must not steer where the user's credentials go.
+pub const ENV_FILE_NAMES: [&str; 2] = [".ugh.env", ".env"];
+
+/// Only variables with this prefix are taken from an env file; a shared `.env` holds the
+/// application's own settings too.
+const VARIABLE_PREFIX: &str = "UGH_";
+
+/// The variables an env file may set: drafting and filing choices, never a secret or an
+/// endpoint (tracker and model URLs, proxies, CA bundles, webhooks) or which provider sees
+/// the changes.
+pub const FILE_VARIABLES: &[&str] = &[
+    "UGH_JIRA_DEFAULT_BOARD",
+    "UGH_JIRA_ISSUE_TYPE",
+    "UGH_JIRA_ACCEPTANCE_CRITERIA_FIELD",
+    "UGH_JIRA_STORY_POINTS_FIELD",
+    "UGH_AZURE_DEVOPS_PROJECT",
+    "UGH_AZURE_DEVOPS_WORK_ITEM_TYPE",
+    "UGH_TICKET_LANGUAGE",
+    "UGH_DRAFTING",
+    "UGH_GEMINI_MODEL",
+    "UGH_OLLAMA_MODEL",
+    "UGH_LLM_TEMPERATURE",
+    "UGH_LLM_TOP_P",
+    "UGH_LLM_MAX_OUTPUT_TOKENS",
+    "UGH_LLM_TIMEOUT_SECS",
+    "UGH_LLM_STREAM",
+    "UGH_DRAFT_CACHE_TTL_HOURS",
+    "UGH_WORKFLOW_STEPS",
+    "UGH_CI",
+];
+
+/// Variables read from the env files, with the file each came from.
+static LOADED: OnceLock<BTreeMap<String, (String, PathBuf)>> = OnceLock::new();
+
+/// Where an effective `UGH_*` value came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VariableOrigin {
+    Environment,
+    File(PathBuf),
+}
+
+/// Reads the env files of the repository containing `dir`, or of its main worktree when
+/// a linked worktree has none. Only the first call has an effect.
+pub fn load(dir: &Path) -> AppResult<()> {
+    let mut variables = BTreeMap::new();
+    if let Some(root) = repo_root(dir) {
+        let mut roots = vec![root.clone()];
+        roots.extend(main_worktree(&root));
+        for name in ENV_FILE_NAMES {
+            let Some((file_root, path)) = roots
+                .iter()
+                .map(|root| (root, root.join(name)))
+                .find(|(_, path)| path.is_file())
+            else {
+                continue;
+            };
+            if is_tracked(file_root, name) {
+                warn!(
+                    "ignoring {}: git tracks it, and only an untracked env file may set ugh \
+                     variables.",
+                    path.display()
+                );
+                continue;
+            }
+            let contents = match fs::read_to_string(&path) {
+                Ok(contents) => contents,
+                Err(err) if err.kind() == ErrorKind::NotFound => continue,
+                Err(err) => return Err(err.into()),
+            };
+            for (key, value) in parse(&contents) {
+                if !FILE_VARIABLES.contains(&key.as_str()) {
+                    warn!(
+                        "ignoring {key} in {}: set it in the environment or the config file.",
+                        path.display()
+                    );
+                    continue;
+                }
+                variables
+                    .entry(key)
+                    .or_insert_with(|| (value, path.clone()));
+            }
+        }
+    }
+    let _ = LOADED.set(variables);
+    Ok(())
+}
+
+/// The value of `name`: the process environment wins over the env files.
+pub fn var(name: &str) -> Option<String> {
+    env::var(name).ok().or_else(|| {
+        LOADED
+            .get()
+            .and_then(|variables| variables.get(name))
+            .map(|(value, _)| value.clone())
+    })
+}
+
+/// Where [`var`] finds `name`, or `None` when it is unset.
+pub fn origin(name: &str) -> Option<VariableOrigin> {
+    if env::var_os(name).is_some() {
+        return Some(VariableOrigin::Environment);
+    }
+    LOADED
+        .get()
+        .and_then(|variables| variables.get(name))
+        .map(|(_, path)| VariableOrigin::File(path.clone()))
+}
+
+/// Whether `name` is in the index of the repository at `root`. A repository git2 cannot
+/// read counts as tracking it, so the file is left alone.
+fn is_tracked(root: &Path, name: &str) -> bool {
+    let Ok(repo) = Repository::open(root) else {
+        return true;
+    };
+    repo.index()
+        .map(|index| index.get_path(Path::new(name), 0).is_some())
+        .unwrap_or(true)
+}
+
+/// The nearest directory at or above `dir` holding a `.git` directory or file.
+pub fn repo_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|candidate| candidate.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+/// The `UGH_*` assignments in an env file: `NAME=value` lines, optionally prefixed with
+/// `export`, with `#` comments and single- or double-quoted values. Other lines are
+/// skipped.
+fn parse(contents: &str) -> Vec<(String, String)> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (key, value) = line.split_once('=')?;
+            let key = key.trim();
+            if !key.starts_with(VARIABLE_PREFIX)
+                || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            {
+                return None;
+            }
+            Some((key.to_string(), unquote(value.trim())))
+        })
+        .collect()
+}
+
+fn unquote(value: &str) -> String {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|rest| rest.split_once(quote))
+            .map(|(inner, _)| inner)
+        {
+            return if quote == '"' {
+                inner.replace("\\n", "\n")
+            } else {
+                inner.to_string()
+            };
+        }
+    }
+    // Unquoted values end at a comment.
+    match value.find(" #") {
+        Some(index) => value[..index].trim_end().to_string(),
+        None => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_ugh_assignments_and_skips_everything_else() {
+        let parsed = parse(
+            "# local overrides\n\
+             UGH_JIRA_DEFAULT_BOARD=PAY # the new board\n\
+             export UGH_GEMINI_MODEL=\"gemini-2.5-pro\"\n\
+             UGH_TICKET_LANGUAGE='German # not a comment'\n\
+             DATABASE_URL=postgres://localhost\n\
+             not an assignment\n",
+        );
+        assert_eq!(
+            parsed,
+            vec![
+                ("UGH_JIRA_DEFAULT_BOARD".to_string(), "PAY".to_string()),
+                ("UGH_GEMINI_MODEL".to_string(), "gemini-2.5-pro".to_string()),
+                (
+                    "UGH_TICKET_LANGUAGE".to_string(),
+                    "German # not a comment".to_string()
+                ),
+            ]
+        );
+        assert!(FILE_VARIABLES.contains(&"UGH_JIRA_DEFAULT_BOARD"));
+        assert!(!FILE_VARIABLES.contains(&"UGH_JIRA_BASE_URL"));
+    }
+
+    #[test]
+    fn committed_env_files_count_as_tracked() {
+        let dir = std::env::temp_dir().join(format!("ugh-env-file-{}", std::process::id()));
+        let repo = Repository::init(&dir).unwrap();
+        fs::write(dir.join(".env"), "UGH_JIRA_DEFAULT_BOARD=PAY\n").unwrap();
+        fs::write(dir.join(".ugh.env"), "UGH_JIRA_DEFAULT_BOARD=OPS\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(".env")).unwrap();
+        index.write().unwrap();
+
+        assert!(is_tracked(&dir, ".env"));
+        assert!(!is_tracked(&dir, ".ugh.env"));
+        assert_eq!(repo_root(&dir.join("src")), Some(dir.clone()));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -12,6 +12,10 @@ pub enum AppError {
     IssueTracker(String),
     #[error("language model error: {0}")]
     LanguageModel(String),
+    #[error("notification error: {0}")]
+    Notification(String),
+    #[error("workflow step error: {0}")]
+    WorkflowStep(String),
     #[error(transparent)]
     Io(#[from] io::Error),
 }
@@ -0,0 +1,203 @@
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::config_directory;
+use crate::domain::branch::BranchCategory;
+use crate::domain::ticket::{DraftExample, DraftGuidance, TicketDraft};
+use crate::error::{AppError, AppResult};
+use crate::storage::{read_json_file, update_json_file};
+
+const FEEDBACK_FILE_NAME: &str = "feedback.json";
+const FEEDBACK_LIMIT: usize = 100;
+const EXAMPLE_LIMIT: usize = 3;
+const CORRECTION_LIMIT: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Rating {
+    Good,
+    Bad,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct FeedbackFile {
+    last_draft: Option<DraftRecord>,
+    #[serde(default)]
+    ratings: Vec<RatedDraft>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct DraftRecord {
+    key: String,
+    change_summary: String,
+    title: String,
+    description: String,
+    branch_category: String,
+    branch_summary: String,
+    #[serde(default)]
+    acceptance_criteria: Vec<String>,
+    recorded_at: u64,
+}
+
+impl DraftRecord {
+    fn to_draft(&self) -> TicketDraft {
+        TicketDraft {
+            title: self.title.clone(),
+            description: self.description.clone(),
+            branch_category: BranchCategory::from_str(&self.branch_category)
+                .unwrap_or(BranchCategory::Feature),
+            branch_summary: self.branch_summary.clone(),
+            acceptance_criteria: self.acceptance_criteria.clone(),
+            estimate: None,
+            provider: None,
+            rationale: None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct RatedDraft {
+    #[serde(flatten)]
+    draft: DraftRecord,
+    rating: Rating,
+    note: Option<String>,
+    rated_at: u64,
+}
+
+enum FeedbackOp {
+    RecordDraft(DraftRecord),
+    Rate(RatedDraft),
+}
+
+impl FeedbackFile {
+    fn apply(&mut self, op: &FeedbackOp) {
+        match op {
+            FeedbackOp::RecordDraft(record) => self.last_draft = Some(record.clone()),
+            FeedbackOp::Rate(rated) => {
+                self.ratings
+                    .retain(|entry| entry.draft.key != rated.draft.key);
+                self.ratings.push(rated.clone());
+                if self.ratings.len() > FEEDBACK_LIMIT {
+                    let overflow = self.ratings.len() - FEEDBACK_LIMIT;
+                    self.ratings.drain(0..overflow);
+                }
+            }
+        }
+    }
+}
+
+/// Ratings for past drafts, keyed by the same diff signature as the draft cache.
+pub struct FeedbackStore {
+    file_path: PathBuf,
+    file: FeedbackFile,
+    pending: Vec<FeedbackOp>,
+}
+
+impl FeedbackStore {
+    pub fn load() -> AppResult<Self> {
+        let dir = config_directory()?;
+        let path = dir.join(FEEDBACK_FILE_NAME);
+        let file = read_json_file(&path, "feedback")?;
+
+        Ok(Self {
+            file_path: path,
+            file,
+            pending: Vec::new(),
+        })
+    }
+
+    /// Remembers the most recent draft so `ugh feedback` can rate it.
+    pub fn record_draft(&mut self, key: &str, change_summary: &str, draft: &TicketDraft) {
+        let op = FeedbackOp::RecordDraft(DraftRecord {
+            key: key.to_string(),
+            change_summary: change_summary.to_string(),
+            title: draft.title.clone(),
+            description: draft.description.clone(),
+            branch_category: draft.branch_category.as_str().to_string(),
+            branch_summary: draft.branch_summary.clone(),
+            acceptance_criteria: draft.acceptance_criteria.clone(),
+            recorded_at: unix_now(),
+        });
+        self.file.apply(&op);
+        self.pending.push(op);
+    }
+
+    /// Rates the most recent draft and returns its diff signature.
+    pub fn rate_last(&mut self, rating: Rating, note: Option<String>) -> AppResult<String> {
+        let draft = self.file.last_draft.clone().ok_or_else(|| {
+            AppError::Configuration("no draft to rate yet; run `ugh ticket` first".to_string())
+        })?;
+        let key = draft.key.clone();
+
+        let op = FeedbackOp::Rate(RatedDraft {
+            draft,
+            rating,
+            note: note
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty()),
+            rated_at: unix_now(),
+        });
+        self.file.apply(&op);
+        self.pending.push(op);
+
+        Ok(key)
+    }
+
+    pub fn last_draft_title(&self) -> Option<&str> {
+        self.file
+            .last_draft
+            .as_ref()
+            .map(|draft| draft.title.as_str())
+    }
+
+    /// Builds prompt guidance from the most recent ratings.
+    pub fn guidance(&self) -> DraftGuidance {
+        let examples = self
+            .file
+            .ratings
+            .iter()
+            .rev()
+            .filter(|entry| entry.rating == Rating::Good)
+            .take(EXAMPLE_LIMIT)
+            .map(|entry| DraftExample {
+                change_summary: entry.draft.change_summary.clone(),
+                draft: entry.draft.to_draft(),
+            })
+            .collect();
+
+        let corrections = self
+            .file
+            .ratings
+            .iter()
+            .rev()
+            .filter(|entry| entry.rating == Rating::Bad)
+            .filter_map(|entry| entry.note.clone())
+            .take(CORRECTION_LIMIT)
+            .collect();
+
+        DraftGuidance {
+            examples,
+            corrections,
+            ..DraftGuidance::default()
+        }
+    }
+
+    pub fn save(&mut self) -> AppResult<()> {
+        let pending = std::mem::take(&mut self.pending);
+        self.file = update_json_file(&self.file_path, "feedback", |file: &mut FeedbackFile| {
+            for op in &pending {
+                file.apply(op);
+            }
+        })?;
+        Ok(())
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
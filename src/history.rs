@@ -0,0 +1,140 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::config::config_directory;
+use crate::error::{AppError, AppResult};
+use crate::storage::{FileLock, write_atomic};
+
+const HISTORY_FILE_NAME: &str = "history.jsonl";
+
+/// One created ticket, as recorded in the append-only history ledger.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub key: String,
+    #[serde(default)]
+    pub url: Option<String>,
+    pub branch: String,
+    /// Set while the ticket exists but its branch does not yet; left set when creating the
+    /// branch failed.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub branch_pending: bool,
+    /// Canonical path of the repository the ticket was created from.
+    pub repo: String,
+    pub board: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub provider: Option<String>,
+    #[serde(default)]
+    pub cache_hit: bool,
+    /// Why the model drafted the ticket as it did, in its own words.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rationale: Option<String>,
+    /// Branch that was checked out before the ticket branch was created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_branch: Option<String>,
+    /// Worktree the branch was checked out in instead of the repository's own tree.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktree: Option<PathBuf>,
+    /// Set once `ugh undo` has rolled the run back.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub undone_at: Option<DateTime<Utc>>,
+}
+
+/// JSON-lines ledger of every ticket created, stored next to the config.
+pub struct TicketHistory {
+    file_path: PathBuf,
+}
+
+impl TicketHistory {
+    pub fn open() -> AppResult<Self> {
+        Ok(Self {
+            file_path: config_directory()?.join(HISTORY_FILE_NAME),
+        })
+    }
+
+    pub fn append(&self, entry: &HistoryEntry) -> AppResult<()> {
+        let mut line = serde_json::to_string(entry).map_err(|err| {
+            AppError::Configuration(format!("failed to serialize history entry: {err}"))
+        })?;
+        line.push('\n');
+
+        let _lock = FileLock::acquire(&self.file_path)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.file_path)?;
+        file.write_all(line.as_bytes())?;
+        Ok(())
+    }
+
+    /// Flags the entry for `key` as rolled back so it is not undone twice.
+    pub fn mark_undone(&self, key: &str) -> AppResult<()> {
+        let now = Utc::now();
+        self.update(|entry| {
+            if entry.key == key && entry.undone_at.is_none() {
+                entry.undone_at = Some(now);
+            }
+        })
+    }
+
+    /// Fills in the branch of the pending entry for `key` in `repo` once it was created.
+    pub fn record_branch(
+        &self,
+        key: &str,
+        repo: &str,
+        branch: &str,
+        worktree: Option<PathBuf>,
+    ) -> AppResult<()> {
+        self.update(|entry| {
+            if entry.key == key && entry.repo == repo && entry.branch_pending {
+                entry.branch = branch.to_string();
+                entry.branch_pending = false;
+                entry.worktree = worktree.clone();
+            }
+        })
+    }
+
+    /// Rewrites the ledger with `change` applied to every entry.
+    fn update(&self, mut change: impl FnMut(&mut HistoryEntry)) -> AppResult<()> {
+        let _lock = FileLock::acquire(&self.file_path)?;
+        let mut entries = self.entries()?;
+        entries.iter_mut().for_each(&mut change);
+
+        let mut contents = String::new();
+        for entry in &entries {
+            let line = serde_json::to_string(entry).map_err(|err| {
+                AppError::Configuration(format!("failed to serialize history entry: {err}"))
+            })?;
+            contents.push_str(&line);
+            contents.push('\n');
+        }
+        write_atomic(&self.file_path, contents.as_bytes())?;
+        Ok(())
+    }
+
+    /// All recorded entries, oldest first. Unreadable lines are skipped with a warning.
+    pub fn entries(&self) -> AppResult<Vec<HistoryEntry>> {
+        let contents = match fs::read_to_string(&self.file_path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(AppError::Io(err)),
+        };
+
+        let mut entries = Vec::new();
+        for (index, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(line) {
+                Ok(entry) => entries.push(entry),
+                Err(err) => warn!("skipping unreadable history line {} ({err}).", index + 1),
+            }
+        }
+        Ok(entries)
+    }
+}
@@ -0,0 +1,265 @@
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use reqwest::{Client, Url};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument};
+
+use crate::config::config_directory;
+use crate::error::{AppError, AppResult};
+use crate::infra::http::{HttpClientFactory, RetryPolicy, send_with_retry};
+use crate::storage::{FileLock, read_json_file, write_atomic_private};
+
+const AUTHORIZE_URL: &str = "https://auth.atlassian.com/authorize";
+const TOKEN_URL: &str = "https://auth.atlassian.com/oauth/token";
+const RESOURCES_URL: &str = "https://api.atlassian.com/oauth/token/accessible-resources";
+const TOKEN_FILE_NAME: &str = "jira_oauth.json";
+
+/// Everything `ugh` does in Jira and Jira Service Management, plus `offline_access` so the
+/// session can be refreshed without another browser round trip.
+pub const JIRA_SCOPES: &str = "read:jira-work write:jira-work read:jira-user \
+     read:servicedesk-request write:servicedesk-request read:board-scope:jira-software \
+     read:sprint:jira-software write:sprint:jira-software read:issue:jira-software \
+     write:board-scope:jira-software offline_access";
+
+/// Access tokens are refreshed this long before they actually expire.
+const REFRESH_MARGIN: TimeDelta = TimeDelta::seconds(60);
+
+/// Tokens from a completed 3LO login, bound to one Jira Cloud site.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthTokens {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub cloud_id: String,
+    pub site_url: String,
+}
+
+impl OAuthTokens {
+    fn is_fresh(&self) -> bool {
+        self.expires_at - REFRESH_MARGIN > Utc::now()
+    }
+}
+
+/// `jira_oauth.json` in the config directory, readable only by the current user.
+pub struct OAuthTokenStore {
+    path: PathBuf,
+}
+
+impl OAuthTokenStore {
+    pub fn open() -> AppResult<Self> {
+        Ok(Self {
+            path: config_directory()?.join(TOKEN_FILE_NAME),
+        })
+    }
+
+    pub fn load(&self) -> AppResult<Option<OAuthTokens>> {
+        read_json_file(&self.path, "Jira OAuth token")
+    }
+
+    pub fn save(&self, tokens: &OAuthTokens) -> AppResult<()> {
+        let json = serde_json::to_vec_pretty(&Some(tokens)).map_err(|err| {
+            AppError::Configuration(format!("failed to serialize Jira OAuth tokens: {err}"))
+        })?;
+        let _lock = FileLock::acquire(&self.path)?;
+        write_atomic_private(&self.path, &json)?;
+        Ok(())
+    }
+}
+
+/// A site the authorizing user granted access to.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AccessibleResource {
+    pub id: String,
+    pub url: String,
+    pub name: String,
+}
+
+/// Token grant returned by the authorization-code and refresh-token exchanges.
+#[derive(Debug, Deserialize)]
+pub struct TokenGrant {
+    pub access_token: String,
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+    pub expires_in: i64,
+}
+
+impl TokenGrant {
+    pub fn expires_at(&self) -> DateTime<Utc> {
+        Utc::now() + TimeDelta::seconds(self.expires_in)
+    }
+}
+
+/// Client for Atlassian's OAuth 2.0 (3LO) endpoints, registered in the developer console.
+pub struct AtlassianOAuth {
+    http: Client,
+    retry: RetryPolicy,
+    client_id: String,
+    client_secret: String,
+}
+
+impl AtlassianOAuth {
+    pub fn new(
+        http: &HttpClientFactory,
+        client_id: String,
+        client_secret: String,
+    ) -> AppResult<Self> {
+        Ok(Self {
+            http: http.build(Some(Duration::from_secs(20)))?,
+            retry: http.retry_policy(),
+            client_id,
+            client_secret,
+        })
+    }
+
+    pub fn authorize_url(&self, redirect_uri: &str, state: &str) -> AppResult<String> {
+        let url = Url::parse_with_params(
+            AUTHORIZE_URL,
+            &[
+                ("audience", "api.atlassian.com"),
+                ("client_id", self.client_id.as_str()),
+                ("scope", JIRA_SCOPES),
+                ("redirect_uri", redirect_uri),
+                ("state", state),
+                ("response_type", "code"),
+                ("prompt", "consent"),
+            ],
+        )
+        .map_err(|err| AppError::Configuration(format!("invalid OAuth redirect URI: {err}")))?;
+        Ok(url.into())
+    }
+
+    #[instrument(level = "info", name = "oauth.exchange_code", skip_all)]
+    pub async fn exchange_code(&self, code: &str, redirect_uri: &str) -> AppResult<TokenGrant> {
+        self.request_token(serde_json::json!({
+            "grant_type": "authorization_code",
+            "client_id": self.client_id,
+            "client_secret": self.client_secret,
+            "code": code,
+            "redirect_uri": redirect_uri,
+        }))
+        .await
+    }
+
+    #[instrument(level = "info", name = "oauth.refresh", skip_all)]
+    pub async fn refresh(&self, refresh_token: &str) -> AppResult<TokenGrant> {
+        self.request_token(serde_json::json!({
+            "grant_type": "refresh_token",
+            "client_id": self.client_id,
+            "client_secret": self.client_secret,
+            "refresh_token": refresh_token,
+        }))
+        .await
+    }
+
+    pub async fn accessible_resources(
+        &self,
+        access_token: &str,
+    ) -> AppResult<Vec<AccessibleResource>> {
+        let response = send_with_retry(&self.retry, "Atlassian", || {
+            self.http.get(RESOURCES_URL).bearer_auth(access_token)
+        })
+        .await
+        .map_err(|err| AppError::IssueTracker(err.to_string()))?;
+        parse_json(response).await
+    }
+
+    async fn request_token(&self, body: serde_json::Value) -> AppResult<TokenGrant> {
+        let response = send_with_retry(&self.retry, "Atlassian", || {
+            self.http.post(TOKEN_URL).json(&body)
+        })
+        .await
+        .map_err(|err| AppError::IssueTracker(err.to_string()))?;
+        parse_json(response).await
+    }
+}
+
+async fn parse_json<T: for<'de> Deserialize<'de>>(response: reqwest::Response) -> AppResult<T> {
+    let status = response.status();
+    if !status.is_success() {
+        let body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "<unable to read response>".to_string());
+        return Err(AppError::IssueTracker(format!(
+            "Atlassian OAuth responded with {status}: {body}"
+        )));
+    }
+    response.json().await.map_err(|err| {
+        AppError::IssueTracker(format!("failed to parse Atlassian OAuth response: {err}"))
+    })
+}
+
+/// A logged-in 3LO session that hands out bearer tokens, refreshing them as they expire.
+pub struct OAuthSession {
+    oauth: AtlassianOAuth,
+    store: OAuthTokenStore,
+    api_base: String,
+    site_url: String,
+    tokens: Mutex<OAuthTokens>,
+}
+
+impl OAuthSession {
+    pub fn new(oauth: AtlassianOAuth, store: OAuthTokenStore, tokens: OAuthTokens) -> Self {
+        Self {
+            oauth,
+            store,
+            api_base: format!("https://api.atlassian.com/ex/jira/{}", tokens.cloud_id),
+            site_url: tokens.site_url.clone(),
+            tokens: Mutex::new(tokens),
+        }
+    }
+
+    /// REST API root for the site; 3LO requests go through the API gateway, not the site.
+    pub fn api_base(&self) -> &str {
+        &self.api_base
+    }
+
+    /// The site's own URL, used for browse links.
+    pub fn site_url(&self) -> &str {
+        &self.site_url
+    }
+
+    /// `Authorization` header value, refreshing the access token when it is about to expire.
+    pub async fn authorization(&self) -> AppResult<String> {
+        let current = self.lock_tokens().clone();
+        if current.is_fresh() {
+            return Ok(format!("Bearer {}", current.access_token));
+        }
+
+        // Refresh tokens rotate, so another process may already have used ours.
+        if let Some(stored) = self.store.load()?
+            && stored.is_fresh()
+        {
+            debug!("using Jira OAuth token refreshed by another process");
+            let header = format!("Bearer {}", stored.access_token);
+            *self.lock_tokens() = stored;
+            return Ok(header);
+        }
+
+        let refresh_token = current.refresh_token.as_deref().ok_or_else(|| {
+            AppError::Configuration(
+                "Jira OAuth session expired; run `ugh config login jira`".to_string(),
+            )
+        })?;
+        let grant = self.oauth.refresh(refresh_token).await?;
+        let refreshed = OAuthTokens {
+            expires_at: grant.expires_at(),
+            refresh_token: grant.refresh_token.or(current.refresh_token),
+            access_token: grant.access_token,
+            ..current
+        };
+        self.store.save(&refreshed)?;
+        let header = format!("Bearer {}", refreshed.access_token);
+        *self.lock_tokens() = refreshed;
+        Ok(header)
+    }
+
+    fn lock_tokens(&self) -> std::sync::MutexGuard<'_, OAuthTokens> {
+        self.tokens
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
@@ -0,0 +1,1067 @@
+use async_trait::async_trait;
+use base64::prelude::{BASE64_STANDARD, Engine as _};
+use chrono::{DateTime, Local, TimeDelta, Utc};
+use reqwest::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE};
+use reqwest::{Client, Method, Response, StatusCode, Url};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value, json};
+use tracing::{instrument, warn};
+
+use crate::config::JiraFields;
+use crate::domain::board::Board;
+use crate::domain::branch::work_item_id_in;
+use crate::domain::retro::{CompletedTicket, RetroScope};
+use crate::domain::sprint::Sprint;
+use crate::domain::ticket::{
+    TextLimits, Ticket, TicketDetails, TicketDraft, TicketOptions, TicketRemoval,
+};
+use crate::domain::user::TrackerUser;
+use crate::domain::worklog::format_time_spent;
+use crate::error::{AppError, AppResult};
+use crate::infra::http::{HttpClientFactory, RetryPolicy, send_with_retry};
+use crate::infra::jira::{fill_placeholders, markdown_heading};
+use crate::services::IssueTrackerService;
+
+const API_VERSION: &str = "7.1";
+const EPIC_WORK_ITEM_TYPE: &str = "Epic";
+/// The work items batch endpoint takes at most 200 IDs per call.
+const BATCH_SIZE: usize = 200;
+/// "In Progress" states of the Agile, Scrum, CMMI, and Basic processes; WIQL cannot
+/// filter on a state's category.
+const IN_PROGRESS_STATES: [&str; 4] = ["Active", "In Progress", "Committed", "Doing"];
+
+const TITLE: &str = "System.Title";
+const DESCRIPTION: &str = "System.Description";
+/// Writing to it adds a discussion comment.
+const HISTORY: &str = "System.History";
+const WORK_ITEM_TYPE: &str = "System.WorkItemType";
+const STATE: &str = "System.State";
+const TAGS: &str = "System.Tags";
+const TEAM_PROJECT: &str = "System.TeamProject";
+const CREATED_DATE: &str = "System.CreatedDate";
+const CLOSED_DATE: &str = "Microsoft.VSTS.Common.ClosedDate";
+const PRIORITY: &str = "Microsoft.VSTS.Common.Priority";
+/// Hours, in the Agile and Scrum processes' task fields.
+const COMPLETED_WORK: &str = "Microsoft.VSTS.Scheduling.CompletedWork";
+const REMAINING_WORK: &str = "Microsoft.VSTS.Scheduling.RemainingWork";
+
+/// Azure DevOps Boards through the Work Items REST API. Projects play the part of boards,
+/// and a work item's numeric ID is its ticket key.
+pub struct AzureDevOpsClient {
+    http: Client,
+    retry: RetryPolicy,
+    organization_url: Option<String>,
+    pat: Option<String>,
+    work_item_type: String,
+    fields: JiraFields,
+}
+
+#[derive(Deserialize)]
+struct WorkItem {
+    id: u64,
+    #[serde(default)]
+    fields: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+struct ValueList<T> {
+    #[serde(default = "Vec::new")]
+    value: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct WiqlResult {
+    #[serde(rename = "workItems", default)]
+    work_items: Vec<WorkItemRef>,
+}
+
+#[derive(Deserialize)]
+struct WorkItemRef {
+    id: u64,
+}
+
+#[derive(Deserialize)]
+struct Project {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Attachment {
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct WorkItemState {
+    name: String,
+    #[serde(default)]
+    category: String,
+}
+
+#[derive(Deserialize)]
+struct ConnectionData {
+    #[serde(rename = "authenticatedUser")]
+    authenticated_user: Identity,
+}
+
+#[derive(Deserialize)]
+struct Identity {
+    #[serde(rename = "providerDisplayName", default)]
+    display_name: String,
+    #[serde(rename = "isActive", default = "default_true")]
+    active: bool,
+    #[serde(default)]
+    properties: Map<String, Value>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Identity {
+    fn property(&self, name: &str) -> Option<String> {
+        self.properties
+            .get(name)
+            .and_then(|property| property.get("$value"))
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    }
+
+    /// The unique name (usually the sign-in email), which is what `System.AssignedTo`
+    /// accepts.
+    fn into_user(self) -> Option<TrackerUser> {
+        let email = self.property("Mail");
+        let account_id = self.property("Account").or(email.clone())?;
+        Some(TrackerUser {
+            account_id,
+            display_name: self.display_name,
+            email,
+        })
+    }
+}
+
+impl AzureDevOpsClient {
+    pub fn new(
+        http: &HttpClientFactory,
+        organization_url: Option<String>,
+        pat: Option<String>,
+        work_item_type: String,
+        fields: JiraFields,
+    ) -> AppResult<Self> {
+        Ok(Self {
+            http: http.build(None)?,
+            retry: http.retry_policy(),
+            organization_url,
+            pat,
+            work_item_type,
+            fields,
+        })
+    }
+
+    fn organization_url(&self) -> AppResult<&str> {
+        self.organization_url.as_deref().ok_or_else(|| {
+            AppError::Configuration("Azure DevOps organization not configured".to_string())
+        })
+    }
+
+    fn authorization(&self) -> AppResult<String> {
+        let pat = self.pat.as_deref().ok_or_else(|| {
+            AppError::Configuration("Azure DevOps personal access token not configured".to_string())
+        })?;
+        Ok(format!(
+            "Basic {}",
+            BASE64_STANDARD.encode(format!(":{pat}"))
+        ))
+    }
+
+    /// `segments` under the organization (or collection) URL, with the API version set.
+    fn endpoint(&self, segments: &[&str]) -> AppResult<Url> {
+        endpoint_under(self.organization_url()?, segments)
+    }
+
+    fn edit_url(&self, project: &str, id: u64) -> Option<String> {
+        self.endpoint(&[project, "_workitems", "edit", &id.to_string()])
+            .ok()
+            .map(|mut url| {
+                url.set_query(None);
+                url.to_string()
+            })
+    }
+
+    /// A page of the web UI, without the API version.
+    fn web_url(&self, segments: &[&str]) -> AppResult<String> {
+        let mut url = self.endpoint(segments)?;
+        url.set_query(None);
+        Ok(url.to_string())
+    }
+
+    fn work_item_url(&self, id: u64) -> AppResult<String> {
+        let mut url = self.endpoint(&["_apis", "wit", "workItems", &id.to_string()])?;
+        url.set_query(None);
+        Ok(url.to_string())
+    }
+
+    #[instrument(level = "debug", name = "azure_devops.send", skip_all, fields(url = %url))]
+    async fn send(
+        &self,
+        method: Method,
+        url: &Url,
+        content_type: &str,
+        body: Option<Vec<u8>>,
+    ) -> AppResult<Response> {
+        let authorization = self.authorization()?;
+        let response = send_with_retry(&self.retry, "Azure DevOps", || {
+            let request = self
+                .http
+                .request(method.clone(), url.clone())
+                .header(AUTHORIZATION, authorization.as_str())
+                .header(ACCEPT, "application/json");
+            match &body {
+                Some(body) => request
+                    .header(CONTENT_TYPE, content_type)
+                    .body(body.clone()),
+                None => request,
+            }
+        })
+        .await
+        .map_err(|err| AppError::IssueTracker(err.to_string()))?;
+
+        // A rejected token gets the sign-in page with 203 rather than a 401.
+        if response.status() == StatusCode::NON_AUTHORITATIVE_INFORMATION {
+            return Err(AppError::IssueTracker(
+                "Azure DevOps did not accept the personal access token; check that it is \
+                 current and has the Work Items scope"
+                    .to_string(),
+            ));
+        }
+        Ok(response)
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, url: &Url) -> AppResult<T> {
+        let response = self.send(Method::GET, url, "", None).await?;
+        parse_response(response).await
+    }
+
+    async fn send_json<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        url: &Url,
+        content_type: &str,
+        body: &Value,
+    ) -> AppResult<T> {
+        let response = self
+            .send(
+                method,
+                url,
+                content_type,
+                Some(body.to_string().into_bytes()),
+            )
+            .await?;
+        parse_response(response).await
+    }
+
+    /// Creates a work item from JSON Patch operations.
+    async fn create_work_item(
+        &self,
+        project: &str,
+        work_item_type: &str,
+        operations: Vec<Value>,
+    ) -> AppResult<Ticket> {
+        let url = self.endpoint(&[
+            project,
+            "_apis",
+            "wit",
+            "workitems",
+            &format!("${work_item_type}"),
+        ])?;
+        let created: WorkItem = self
+            .send_json(
+                Method::POST,
+                &url,
+                "application/json-patch+json",
+                &Value::Array(operations),
+            )
+            .await?;
+        Ok(Ticket {
+            key: created.id.to_string(),
+            url: self.edit_url(project, created.id),
+        })
+    }
+
+    async fn update_work_item(&self, id: u64, operations: Vec<Value>) -> AppResult<WorkItem> {
+        let url = self.endpoint(&["_apis", "wit", "workitems", &id.to_string()])?;
+        self.send_json(
+            Method::PATCH,
+            &url,
+            "application/json-patch+json",
+            &Value::Array(operations),
+        )
+        .await
+    }
+
+    /// Runs a WIQL query, within `project` when given, and returns the matching IDs.
+    async fn query_ids(&self, project: Option<&str>, query: &str) -> AppResult<Vec<u64>> {
+        let url = match project {
+            Some(project) => self.endpoint(&[project, "_apis", "wit", "wiql"])?,
+            None => self.endpoint(&["_apis", "wit", "wiql"])?,
+        };
+        let result: WiqlResult = self
+            .send_json(
+                Method::POST,
+                &url,
+                "application/json",
+                &json!({ "query": query }),
+            )
+            .await?;
+        Ok(result.work_items.into_iter().map(|item| item.id).collect())
+    }
+
+    /// The given work items with `fields`, in the order of `ids`.
+    async fn work_items(&self, ids: &[u64], fields: &[&str]) -> AppResult<Vec<WorkItem>> {
+        let url = self.endpoint(&["_apis", "wit", "workitemsbatch"])?;
+        let mut items = Vec::new();
+        for batch in ids.chunks(BATCH_SIZE) {
+            let page: ValueList<WorkItem> = self
+                .send_json(
+                    Method::POST,
+                    &url,
+                    "application/json",
+                    &json!({ "ids": batch, "fields": fields }),
+                )
+                .await?;
+            items.extend(page.value);
+        }
+        Ok(items)
+    }
+
+    async fn work_item(&self, id: u64, fields: &[&str]) -> AppResult<WorkItem> {
+        let mut url = self.endpoint(&["_apis", "wit", "workitems", &id.to_string()])?;
+        url.query_pairs_mut()
+            .append_pair("fields", &fields.join(","));
+        self.get_json(&url).await
+    }
+
+    /// Moves the work item to the first state of its type's "Completed" category.
+    async fn close_work_item(&self, id: u64) -> AppResult<String> {
+        let item = self.work_item(id, &[TEAM_PROJECT, WORK_ITEM_TYPE]).await?;
+        let project = field_text(&item, TEAM_PROJECT).unwrap_or_default();
+        let work_item_type = field_text(&item, WORK_ITEM_TYPE).unwrap_or_default();
+        let url = self.endpoint(&[
+            &project,
+            "_apis",
+            "wit",
+            "workitemtypes",
+            &work_item_type,
+            "states",
+        ])?;
+        let states: ValueList<WorkItemState> = self.get_json(&url).await?;
+        let state = states
+            .value
+            .into_iter()
+            .find(|state| state.category == "Completed")
+            .ok_or_else(|| {
+                AppError::IssueTracker(format!(
+                    "work item {id} cannot be deleted and its type has no completed state"
+                ))
+            })?;
+        self.update_work_item(
+            id,
+            vec![add(&format!("/fields/{STATE}"), state.name.as_str())],
+        )
+        .await?;
+        Ok(state.name)
+    }
+
+    /// Fields set from the draft's estimate, the team's configured values, and the run's
+    /// options.
+    fn optional_fields(
+        &self,
+        project: &str,
+        draft: &TicketDraft,
+        options: &TicketOptions,
+    ) -> Vec<Value> {
+        let mut operations = Vec::new();
+        if let (Some(field), Some(estimate)) = (self.fields.story_points.as_deref(), draft.estimate)
+        {
+            operations.push(add(&format!("/fields/{field}"), estimate));
+        }
+
+        let placeholders = [
+            ("board", project.to_string()),
+            ("title", draft.title.trim().to_string()),
+            ("category", draft.branch_category.as_str().to_string()),
+            (
+                "date",
+                Local::now().date_naive().format("%Y-%m-%d").to_string(),
+            ),
+        ];
+        for (field, value) in self.fields.values.iter().chain(&options.custom_fields) {
+            operations.push(add(
+                &format!("/fields/{field}"),
+                fill_placeholders(value, &placeholders),
+            ));
+        }
+
+        if let Some(assignee) = &options.assignee {
+            operations.push(add("/fields/System.AssignedTo", assignee.as_str()));
+        }
+        if !options.labels.is_empty() {
+            operations.push(add(&format!("/fields/{TAGS}"), options.labels.join("; ")));
+        }
+        if let Some(priority) = options.priority.as_deref() {
+            match priority_number(priority) {
+                Some(number) => operations.push(add(&format!("/fields/{PRIORITY}"), number)),
+                None => warn!("priority '{priority}' has no Azure DevOps equivalent (1 to 4)."),
+            }
+        }
+        operations
+    }
+}
+
+#[async_trait]
+impl IssueTrackerService for AzureDevOpsClient {
+    /// Titles are at most 255 characters. HTML fields take far more than a ticket needs,
+    /// so descriptions get the same budget as in Jira.
+    fn text_limits(&self) -> TextLimits {
+        TextLimits {
+            title_chars: 255,
+            description_chars: 30_000,
+        }
+    }
+
+    /// Work item IDs are plain numbers, so they are looked for as a path segment.
+    fn ticket_key_in_branch(&self, branch: &str) -> Option<String> {
+        work_item_id_in(branch)
+    }
+
+    /// The organization-level editor, which needs no project.
+    fn ticket_url(&self, key: &str) -> AppResult<String> {
+        let id = work_item_id(key)?;
+        self.web_url(&["_workitems", "edit", &id.to_string()])
+    }
+
+    fn board_url(&self, board: &str) -> AppResult<String> {
+        self.web_url(&[board.trim(), "_boards"])
+    }
+
+    #[instrument(level = "info", name = "azure_devops.create_ticket", skip_all, fields(board = %board))]
+    async fn create_ticket(
+        &self,
+        board: &str,
+        draft: TicketDraft,
+        options: &TicketOptions,
+    ) -> AppResult<Ticket> {
+        let project = board.trim();
+        if project.is_empty() {
+            return Err(AppError::IssueTracker(
+                "Azure DevOps project must not be empty".to_string(),
+            ));
+        }
+        if draft.title.trim().is_empty() {
+            return Err(AppError::LanguageModel(
+                "language model returned an empty title".to_string(),
+            ));
+        }
+        if draft.branch_summary.trim().is_empty() {
+            return Err(AppError::LanguageModel(
+                "language model returned an empty branch summary".to_string(),
+            ));
+        }
+        if options.reporter.is_some() {
+            warn!("Azure DevOps records the token's owner as creator; --reporter is ignored.");
+        }
+        if options.due_date.is_some() {
+            warn!("due dates are not applied to Azure DevOps work items.");
+        }
+        if !options.components.is_empty() {
+            warn!("components are not applied to Azure DevOps work items; use area paths.");
+        }
+        if options.placement.is_some() {
+            warn!("sprint and backlog placement are not applied to Azure DevOps work items.");
+        }
+
+        let mut description = markdown_to_html(draft.description.trim());
+        let mut operations = vec![add(&format!("/fields/{TITLE}"), draft.title.trim())];
+        match self.fields.acceptance_criteria.as_deref() {
+            Some(field) if !draft.acceptance_criteria.is_empty() => {
+                operations.push(add(
+                    &format!("/fields/{field}"),
+                    html_list(&draft.acceptance_criteria),
+                ));
+            }
+            _ if !draft.acceptance_criteria.is_empty() => {
+                description.push_str("<h3>Acceptance Criteria</h3>");
+                description.push_str(&html_list(&draft.acceptance_criteria));
+            }
+            _ => {}
+        }
+        operations.push(add(&format!("/fields/{DESCRIPTION}"), description));
+        operations.extend(self.optional_fields(project, &draft, options));
+
+        if let Some(epic) = options.epic.as_deref().map(str::trim)
+            && !epic.is_empty()
+        {
+            operations.push(add(
+                "/relations/-",
+                json!({
+                    "rel": "System.LinkTypes.Hierarchy-Reverse",
+                    "url": self.work_item_url(work_item_id(epic)?)?,
+                }),
+            ));
+        }
+
+        let work_item_type = options
+            .issue_type
+            .as_deref()
+            .unwrap_or(&self.work_item_type);
+        self.create_work_item(project, work_item_type, operations)
+            .await
+    }
+
+    #[instrument(level = "info", name = "azure_devops.get_ticket", skip_all, fields(key = %key))]
+    async fn get_ticket(&self, key: &str) -> AppResult<TicketDetails> {
+        let id = work_item_id(key)?;
+        let item = self
+            .work_item(
+                id,
+                &[TITLE, WORK_ITEM_TYPE, STATE, TEAM_PROJECT, DESCRIPTION],
+            )
+            .await?;
+        Ok(TicketDetails {
+            key: item.id.to_string(),
+            title: field_text(&item, TITLE).unwrap_or_default(),
+            issue_type: field_text(&item, WORK_ITEM_TYPE),
+            status: field_text(&item, STATE),
+            url: field_text(&item, TEAM_PROJECT)
+                .and_then(|project| self.edit_url(&project, item.id)),
+            description: field_text(&item, DESCRIPTION).map(|html| html_to_markdown(&html)),
+        })
+    }
+
+    #[instrument(level = "info", name = "azure_devops.update_ticket", skip_all, fields(key = %key))]
+    async fn update_ticket(
+        &self,
+        key: &str,
+        description: &str,
+        acceptance_criteria: &[String],
+    ) -> AppResult<()> {
+        let id = work_item_id(key)?;
+        let mut description = markdown_to_html(description.trim());
+        let mut operations = Vec::new();
+        match self.fields.acceptance_criteria.as_deref() {
+            Some(field) if !acceptance_criteria.is_empty() => {
+                operations.push(add(
+                    &format!("/fields/{field}"),
+                    html_list(acceptance_criteria),
+                ));
+            }
+            _ if !acceptance_criteria.is_empty() => {
+                description.push_str("<h3>Acceptance Criteria</h3>");
+                description.push_str(&html_list(acceptance_criteria));
+            }
+            _ => {}
+        }
+        operations.push(add(&format!("/fields/{DESCRIPTION}"), description));
+        self.update_work_item(id, operations).await?;
+        Ok(())
+    }
+
+    /// Azure Boards has no worklog, so the hours go onto Completed Work (and off Remaining
+    /// Work, when set) with a discussion comment noting the entry.
+    #[instrument(level = "info", name = "azure_devops.log_work", skip_all, fields(key = %key))]
+    async fn log_work(
+        &self,
+        key: &str,
+        time_spent: TimeDelta,
+        started: DateTime<Utc>,
+        comment: Option<&str>,
+    ) -> AppResult<()> {
+        let id = work_item_id(key)?;
+        let item = self
+            .work_item(id, &[COMPLETED_WORK, REMAINING_WORK])
+            .await?;
+        let hours = time_spent.num_minutes() as f64 / 60.0;
+        let field_hours = |field| item.fields.get(field).and_then(Value::as_f64);
+
+        let completed = field_hours(COMPLETED_WORK).unwrap_or(0.0) + hours;
+        let mut operations = vec![add(&format!("/fields/{COMPLETED_WORK}"), completed)];
+        if let Some(remaining) = field_hours(REMAINING_WORK) {
+            operations.push(add(
+                &format!("/fields/{REMAINING_WORK}"),
+                (remaining - hours).max(0.0),
+            ));
+        }
+        let mut note = format!(
+            "Logged {} of work started {}.",
+            format_time_spent(time_spent),
+            started.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+        );
+        if let Some(comment) = comment.map(str::trim).filter(|comment| !comment.is_empty()) {
+            note.push_str(&format!("\n\n{comment}"));
+        }
+        operations.push(add(&format!("/fields/{HISTORY}"), markdown_to_html(&note)));
+        self.update_work_item(id, operations).await?;
+        Ok(())
+    }
+
+    #[instrument(level = "info", name = "azure_devops.add_comment", skip_all, fields(key = %key))]
+    async fn add_comment(&self, key: &str, comment: &str) -> AppResult<()> {
+        let id = work_item_id(key)?;
+        let operations = vec![add(
+            &format!("/fields/{HISTORY}"),
+            markdown_to_html(comment.trim()),
+        )];
+        self.update_work_item(id, operations).await?;
+        Ok(())
+    }
+
+    #[instrument(level = "info", name = "azure_devops.attach_file", skip_all, fields(key = %key))]
+    async fn attach_file(&self, key: &str, file_name: &str, contents: Vec<u8>) -> AppResult<()> {
+        let id = work_item_id(key)?;
+        let mut url = self.endpoint(&["_apis", "wit", "attachments"])?;
+        url.query_pairs_mut().append_pair("fileName", file_name);
+        let response = self
+            .send(
+                Method::POST,
+                &url,
+                "application/octet-stream",
+                Some(contents),
+            )
+            .await?;
+        let attachment: Attachment = parse_response(response).await?;
+        self.update_work_item(
+            id,
+            vec![add(
+                "/relations/-",
+                json!({ "rel": "AttachedFile", "url": attachment.url }),
+            )],
+        )
+        .await?;
+        Ok(())
+    }
+
+    #[instrument(level = "info", name = "azure_devops.create_epic", skip_all, fields(board = %board))]
+    async fn create_epic(
+        &self,
+        board: &str,
+        name: &str,
+        description: Option<&str>,
+    ) -> AppResult<Ticket> {
+        let project = board.trim();
+        let name = name.trim();
+        if project.is_empty() {
+            return Err(AppError::IssueTracker(
+                "Azure DevOps project must not be empty".to_string(),
+            ));
+        }
+        if name.is_empty() {
+            return Err(AppError::IssueTracker(
+                "epic name must not be empty".to_string(),
+            ));
+        }
+
+        let mut operations = vec![add(&format!("/fields/{TITLE}"), name)];
+        if let Some(description) = description.map(str::trim).filter(|text| !text.is_empty()) {
+            operations.push(add(
+                &format!("/fields/{DESCRIPTION}"),
+                markdown_to_html(description),
+            ));
+        }
+        self.create_work_item(project, EPIC_WORK_ITEM_TYPE, operations)
+            .await
+    }
+
+    #[instrument(level = "info", name = "azure_devops.list_boards", skip_all)]
+    async fn list_boards(&self, query: Option<&str>) -> AppResult<Vec<Board>> {
+        let mut boards = Vec::new();
+        loop {
+            let mut url = self.endpoint(&["_apis", "projects"])?;
+            url.query_pairs_mut()
+                .append_pair("$top", "100")
+                .append_pair("$skip", &boards.len().to_string());
+            let page: ValueList<Project> = self.get_json(&url).await?;
+            let fetched = page.value.len();
+            boards.extend(page.value.into_iter().map(|project| {
+                Board {
+                    key: project.name.clone(),
+                    name: project
+                        .description
+                        .filter(|description| !description.trim().is_empty())
+                        .unwrap_or(project.name),
+                    kind: None,
+                    team_managed: false,
+                    issue_types: Vec::new(),
+                }
+            }));
+            if fetched < 100 {
+                break;
+            }
+        }
+        if let Some(query) = query {
+            boards.retain(|board| board.matches(query));
+        }
+        Ok(boards)
+    }
+
+    async fn list_sprints(&self, _board: &str, _include_closed: bool) -> AppResult<Vec<Sprint>> {
+        Err(AppError::IssueTracker(
+            "sprints are not supported for Azure DevOps; plan iterations in Azure Boards"
+                .to_string(),
+        ))
+    }
+
+    #[instrument(level = "info", name = "azure_devops.completed_tickets", skip_all, fields(board = %board))]
+    async fn completed_tickets(
+        &self,
+        board: &str,
+        scope: &RetroScope,
+    ) -> AppResult<Vec<CompletedTicket>> {
+        let RetroScope::Period { from, to } = scope else {
+            return Err(AppError::IssueTracker(
+                "Azure DevOps retrospectives take a period (--since/--until), not a sprint"
+                    .to_string(),
+            ));
+        };
+        let end = to.succ_opt().unwrap_or(*to);
+        let query = format!(
+            "SELECT [System.Id] FROM WorkItems WHERE [{TEAM_PROJECT}] = @project \
+             AND [{CLOSED_DATE}] >= '{from}' AND [{CLOSED_DATE}] < '{end}' \
+             ORDER BY [{CLOSED_DATE}] ASC"
+        );
+        let ids = self.query_ids(Some(board.trim()), &query).await?;
+        let items = self
+            .work_items(
+                &ids,
+                &[TITLE, WORK_ITEM_TYPE, TAGS, CREATED_DATE, CLOSED_DATE],
+            )
+            .await?;
+        Ok(items
+            .into_iter()
+            .filter_map(|item| {
+                Some(CompletedTicket {
+                    key: item.id.to_string(),
+                    summary: field_text(&item, TITLE).unwrap_or_default(),
+                    issue_type: field_text(&item, WORK_ITEM_TYPE),
+                    labels: field_text(&item, TAGS)
+                        .map(|tags| split_tags(&tags))
+                        .unwrap_or_default(),
+                    created: field_time(&item, CREATED_DATE)?,
+                    done: field_time(&item, CLOSED_DATE)?,
+                })
+            })
+            .collect())
+    }
+
+    #[instrument(level = "info", name = "azure_devops.in_progress_tickets", skip_all)]
+    async fn in_progress_tickets(&self) -> AppResult<Vec<TicketDetails>> {
+        let states = IN_PROGRESS_STATES
+            .iter()
+            .map(|state| format!("'{state}'"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let query = format!(
+            "SELECT [System.Id] FROM WorkItems WHERE [System.AssignedTo] = @Me \
+             AND [{STATE}] IN ({states}) ORDER BY [System.ChangedDate] DESC"
+        );
+        let ids = self.query_ids(None, &query).await?;
+        let items = self
+            .work_items(&ids, &[TITLE, WORK_ITEM_TYPE, STATE, TEAM_PROJECT])
+            .await?;
+        Ok(items
+            .into_iter()
+            .map(|item| TicketDetails {
+                key: item.id.to_string(),
+                title: field_text(&item, TITLE).unwrap_or_default(),
+                issue_type: field_text(&item, WORK_ITEM_TYPE),
+                status: field_text(&item, STATE),
+                url: field_text(&item, TEAM_PROJECT)
+                    .and_then(|project| self.edit_url(&project, item.id)),
+                description: None,
+            })
+            .collect())
+    }
+
+    #[instrument(level = "debug", name = "azure_devops.search_users", skip_all)]
+    async fn search_users(&self, query: &str) -> AppResult<Vec<TrackerUser>> {
+        let mut url = endpoint_under(
+            &identity_base(self.organization_url()?),
+            &["_apis", "identities"],
+        )?;
+        url.query_pairs_mut()
+            .append_pair("searchFilter", "General")
+            .append_pair("filterValue", query)
+            .append_pair("queryMembership", "None");
+        let identities: ValueList<Identity> = self.get_json(&url).await?;
+        Ok(identities
+            .value
+            .into_iter()
+            .filter(|identity| identity.active)
+            .filter_map(Identity::into_user)
+            .collect())
+    }
+
+    #[instrument(level = "info", name = "azure_devops.current_user", skip_all)]
+    async fn current_user(&self) -> AppResult<TrackerUser> {
+        let url = self.endpoint(&["_apis", "connectionData"])?;
+        let connection: ConnectionData = self.get_json(&url).await?;
+        connection.authenticated_user.into_user().ok_or_else(|| {
+            AppError::IssueTracker("Azure DevOps did not name the token's owner".to_string())
+        })
+    }
+
+    #[instrument(level = "info", name = "azure_devops.delete_ticket", skip_all, fields(key = %key))]
+    async fn delete_ticket(&self, key: &str) -> AppResult<TicketRemoval> {
+        let id = work_item_id(key)?;
+        let url = self.endpoint(&["_apis", "wit", "workitems", &id.to_string()])?;
+        let response = self.send(Method::DELETE, &url, "", None).await?;
+
+        // Deleting needs the "Delete work items" permission; closing is the fallback.
+        if response.status() == StatusCode::FORBIDDEN {
+            warn!("not allowed to delete work item {id}; closing it instead.");
+            return Ok(TicketRemoval::Closed(self.close_work_item(id).await?));
+        }
+        parse_response::<Value>(response).await?;
+        Ok(TicketRemoval::Deleted)
+    }
+}
+
+async fn parse_response<T: DeserializeOwned>(response: Response) -> AppResult<T> {
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .unwrap_or_else(|_| "<unable to read response>".to_string());
+    if !status.is_success() {
+        return Err(AppError::IssueTracker(format!(
+            "Azure DevOps responded with {status}: {body}"
+        )));
+    }
+    serde_json::from_str(&body).map_err(|err| {
+        AppError::IssueTracker(format!("failed to parse Azure DevOps response: {err}"))
+    })
+}
+
+fn endpoint_under(base: &str, segments: &[&str]) -> AppResult<Url> {
+    let invalid =
+        |detail: String| AppError::Configuration(format!("invalid Azure DevOps URL: {detail}"));
+    let mut url = Url::parse(base).map_err(|err| invalid(err.to_string()))?;
+    url.path_segments_mut()
+        .map_err(|_| invalid(base.to_string()))?
+        .pop_if_empty()
+        .extend(segments);
+    url.query_pairs_mut()
+        .append_pair("api-version", API_VERSION);
+    Ok(url)
+}
+
+/// Identities live on a separate host in Azure DevOps Services; a server collection
+/// serves them itself.
+fn identity_base(organization_url: &str) -> String {
+    if let Some(rest) = organization_url.strip_prefix("https://dev.azure.com/") {
+        format!("https://vssps.dev.azure.com/{rest}")
+    } else if let Some((organization, rest)) = organization_url
+        .strip_prefix("https://")
+        .and_then(|rest| rest.split_once(".visualstudio.com"))
+    {
+        format!("https://{organization}.vssps.visualstudio.com{rest}")
+    } else {
+        organization_url.to_string()
+    }
+}
+
+fn add(path: &str, value: impl Into<Value>) -> Value {
+    json!({ "op": "add", "path": path, "value": value.into() })
+}
+
+/// Accepts `1234`, `#1234`, and the `AB#1234` form commit messages use.
+fn work_item_id(key: &str) -> AppResult<u64> {
+    let key = key.trim();
+    let digits = key.rsplit_once('#').map_or(key, |(_, id)| id);
+    digits
+        .parse()
+        .map_err(|_| AppError::IssueTracker(format!("'{key}' is not an Azure DevOps work item ID")))
+}
+
+/// Azure DevOps priorities run from 1 (highest) to 4; Jira-style names are mapped.
+fn priority_number(priority: &str) -> Option<u8> {
+    match priority.trim().to_lowercase().as_str() {
+        "1" | "highest" | "critical" | "blocker" => Some(1),
+        "2" | "high" | "major" => Some(2),
+        "3" | "medium" | "normal" => Some(3),
+        "4" | "low" | "lowest" | "minor" | "trivial" => Some(4),
+        _ => None,
+    }
+}
+
+fn field_text(item: &WorkItem, field: &str) -> Option<String> {
+    item.fields
+        .get(field)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+fn field_time(item: &WorkItem, field: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(&field_text(item, field)?)
+        .ok()
+        .map(|time| time.to_utc())
+}
+
+fn split_tags(tags: &str) -> Vec<String> {
+    tags.split(';')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Renders the Markdown subset drafts use (headings, bullet lists, paragraphs) as the
+/// HTML that Azure DevOps rich-text fields hold.
+fn markdown_to_html(markdown: &str) -> String {
+    let mut html = String::new();
+    for section in markdown.replace('\r', "").split("\n\n") {
+        let mut paragraph: Vec<&str> = Vec::new();
+        let mut bullets: Vec<String> = Vec::new();
+        for line in section
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+        {
+            let heading = markdown_heading(line);
+            let bullet = line.strip_prefix("- ").or_else(|| line.strip_prefix("* "));
+            if bullet.is_none() && !bullets.is_empty() {
+                html.push_str(&html_list(&bullets));
+                bullets.clear();
+            }
+            if (heading.is_some() || bullet.is_some()) && !paragraph.is_empty() {
+                html.push_str(&format!("<p>{}</p>", escape_html(&paragraph.join(" "))));
+                paragraph.clear();
+            }
+            match (heading, bullet) {
+                (Some((level, text)), _) => {
+                    html.push_str(&format!("<h{level}>{}</h{level}>", escape_html(text)));
+                }
+                (None, Some(item)) => bullets.push(item.trim().to_string()),
+                (None, None) => paragraph.push(line),
+            }
+        }
+        if !bullets.is_empty() {
+            html.push_str(&html_list(&bullets));
+        }
+        if !paragraph.is_empty() {
+            html.push_str(&format!("<p>{}</p>", escape_html(&paragraph.join(" "))));
+        }
+    }
+    html
+}
+
+/// Reads rich-text HTML back into the Markdown subset [`markdown_to_html`] writes;
+/// other tags are dropped and their text kept.
+fn html_to_markdown(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        let Some(length) = rest[start..].find('>') else {
+            break;
+        };
+        text.push_str(&rest[..start]);
+        let tag = rest[start + 1..start + length].trim().to_lowercase();
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        let heading = name
+            .strip_prefix('h')
+            .and_then(|level| level.parse::<usize>().ok())
+            .filter(|level| (1..=6).contains(level));
+        match (name.as_str(), heading, closing) {
+            (_, Some(level), false) => text.push_str(&format!("\n\n{} ", "#".repeat(level))),
+            ("li", _, false) => text.push_str("- "),
+            ("li", _, true) | ("br", _, _) => text.push('\n'),
+            ("p" | "div" | "ul" | "ol", _, _) | (_, Some(_), true) => text.push_str("\n\n"),
+            _ => {}
+        }
+        rest = &rest[start + length + 1..];
+    }
+    text.push_str(rest);
+
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    let mut markdown = String::new();
+    let mut blank = false;
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() {
+            blank = !markdown.is_empty();
+            continue;
+        }
+        if blank {
+            markdown.push('\n');
+            blank = false;
+        }
+        if !markdown.is_empty() {
+            markdown.push('\n');
+        }
+        markdown.push_str(line);
+    }
+    markdown
+}
+
+fn html_list(items: &[String]) -> String {
+    let items: String = items
+        .iter()
+        .map(|item| format!("<li>{}</li>", escape_html(item)))
+        .collect();
+    format!("<ul>{items}</ul>")
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_draft_markdown_as_html_and_reads_work_item_ids() {
+        assert_eq!(
+            markdown_to_html("## Scope\n- Refunds <= 10\n- Emails\n\nRound to the\nnearest cent."),
+            "<h2>Scope</h2><ul><li>Refunds &lt;= 10</li><li>Emails</li></ul>\
+             <p>Round to the nearest cent.</p>"
+        );
+        assert_eq!(
+            html_to_markdown(
+                "<h2>Scope</h2><ul><li>Refunds &lt;= 10</li><li>Emails</li></ul>\
+                 <div>Round to the<br>nearest cent.</div>"
+            ),
+            "## Scope\n\n- Refunds <= 10\n- Emails\n\nRound to the\nnearest cent."
+        );
+        assert_eq!(work_item_id("AB#1234").unwrap(), 1234);
+        assert_eq!(work_item_id(" 77 ").unwrap(), 77);
+        assert!(work_item_id("PAY-7").is_err());
+        assert_eq!(
+            identity_base("https://dev.azure.com/contoso"),
+            "https://vssps.dev.azure.com/contoso"
+        );
+        assert_eq!(
+            endpoint_under("https://tfs.corp/tfs/Default/", &["My Project", "_apis"])
+                .unwrap()
+                .as_str(),
+            "https://tfs.corp/tfs/Default/My%20Project/_apis?api-version=7.1"
+        );
+    }
+}
@@ -0,0 +1,430 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use tracing::instrument;
+
+use crate::domain::change::{ChangeSummary, FileDiffStat};
+use crate::error::{AppError, AppResult};
+use crate::infra::github::GitHubPullRequest;
+use crate::infra::http::HttpClientFactory;
+use crate::services::{ChangeSource, VersionControlService};
+
+/// Picks the source `ugh ticket --from` names: a pull request URL, an editor buffer dump
+/// (`*.json`), or otherwise a patch file (`-` reads the patch from stdin).
+pub fn change_source(
+    spec: &str,
+    root: &Path,
+    http: &HttpClientFactory,
+    github_token: Option<String>,
+) -> AppResult<Arc<dyn ChangeSource>> {
+    let spec = spec.trim();
+    if spec.starts_with("https://") || spec.starts_with("http://") {
+        return Ok(Arc::new(GitHubPullRequest::from_url(
+            http,
+            spec,
+            github_token,
+            root.to_path_buf(),
+        )?));
+    }
+    if spec == "-" {
+        return Ok(Arc::new(PatchFile {
+            path: None,
+            root: root.to_path_buf(),
+        }));
+    }
+
+    let path = PathBuf::from(spec);
+    if !path.is_file() {
+        return Err(AppError::Configuration(format!(
+            "--from {spec}: no such file (expected a patch, a buffer dump ending in .json, or a pull request URL)"
+        )));
+    }
+    let root = root.to_path_buf();
+    if path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+    {
+        Ok(Arc::new(BufferDump { path, root }))
+    } else {
+        Ok(Arc::new(PatchFile {
+            path: Some(path),
+            root,
+        }))
+    }
+}
+
+/// Uncommitted changes in the repository's working tree; the default source.
+pub struct WorkingTree {
+    version_control: Arc<dyn VersionControlService>,
+}
+
+impl WorkingTree {
+    pub fn new(version_control: Arc<dyn VersionControlService>) -> Self {
+        Self { version_control }
+    }
+}
+
+#[async_trait]
+impl ChangeSource for WorkingTree {
+    fn describe(&self) -> String {
+        "working tree".to_string()
+    }
+
+    async fn collect(&self, paths: &[PathBuf]) -> AppResult<ChangeSummary> {
+        self.version_control.summarize_changes(paths).await
+    }
+}
+
+/// Changes already summarized elsewhere, such as the working trees of a whole workspace.
+pub struct Summarized {
+    origin: String,
+    changes: ChangeSummary,
+}
+
+impl Summarized {
+    pub fn new(origin: String, changes: ChangeSummary) -> Self {
+        Self { origin, changes }
+    }
+}
+
+#[async_trait]
+impl ChangeSource for Summarized {
+    fn describe(&self) -> String {
+        self.origin.clone()
+    }
+
+    async fn collect(&self, _paths: &[PathBuf]) -> AppResult<ChangeSummary> {
+        Ok(self.changes.clone())
+    }
+}
+
+/// A unified diff as written by `git diff` or `diff -u`, read from a file or stdin.
+pub struct PatchFile {
+    path: Option<PathBuf>,
+    root: PathBuf,
+}
+
+#[async_trait]
+impl ChangeSource for PatchFile {
+    fn describe(&self) -> String {
+        match &self.path {
+            Some(path) => format!("patch {}", path.display()),
+            None => "patch on stdin".to_string(),
+        }
+    }
+
+    #[instrument(level = "info", name = "patch.collect", skip_all)]
+    async fn collect(&self, paths: &[PathBuf]) -> AppResult<ChangeSummary> {
+        let text = match &self.path {
+            Some(path) => fs::read_to_string(path)?,
+            None => {
+                let mut text = String::new();
+                std::io::stdin().read_to_string(&mut text)?;
+                text
+            }
+        };
+        let scope = scope_prefixes(&self.root, paths);
+        let stats: Vec<FileDiffStat> = parse_patch(&text)
+            .into_iter()
+            .filter(|stat| in_scope(&stat.path, &scope))
+            .collect();
+        let origin = match &self.path {
+            Some(path) => format!(
+                "Patch {}",
+                path.file_name()
+                    .unwrap_or(path.as_os_str())
+                    .to_string_lossy()
+            ),
+            None => "The patch on stdin".to_string(),
+        };
+        Ok(ChangeSummary::from_diff_stats(&origin, &stats))
+    }
+}
+
+/// Unsaved editor buffers dumped to JSON, either `[{"path", "text"}]` or the same list
+/// under `buffers`. Each buffer is compared with the file on disk.
+pub struct BufferDump {
+    path: PathBuf,
+    root: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct Buffer {
+    path: String,
+    text: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BufferDumpFile {
+    Wrapped { buffers: Vec<Buffer> },
+    List(Vec<Buffer>),
+}
+
+#[async_trait]
+impl ChangeSource for BufferDump {
+    fn describe(&self) -> String {
+        format!("editor buffers in {}", self.path.display())
+    }
+
+    #[instrument(level = "info", name = "buffers.collect", skip_all)]
+    async fn collect(&self, paths: &[PathBuf]) -> AppResult<ChangeSummary> {
+        let dump: BufferDumpFile =
+            serde_json::from_str(&fs::read_to_string(&self.path)?).map_err(|err| {
+                AppError::Configuration(format!(
+                    "{} is not a buffer dump: {err}",
+                    self.path.display()
+                ))
+            })?;
+        let buffers = match dump {
+            BufferDumpFile::Wrapped { buffers } | BufferDumpFile::List(buffers) => buffers,
+        };
+
+        let scope = scope_prefixes(&self.root, paths);
+        let mut stats = Vec::new();
+        for buffer in buffers {
+            let absolute = self.root.join(&buffer.path);
+            let Some(path) = repo_relative(&self.root, &absolute) else {
+                continue;
+            };
+            if !in_scope(&path, &scope) {
+                continue;
+            }
+            let saved = fs::read_to_string(&absolute).ok();
+            if saved.as_deref() == Some(buffer.text.as_str()) {
+                continue;
+            }
+            let (added, removed) = line_changes(saved.as_deref().unwrap_or_default(), &buffer.text);
+            stats.push(FileDiffStat {
+                path,
+                status: if saved.is_some() { 'M' } else { 'A' },
+                added,
+                removed,
+            });
+        }
+        Ok(ChangeSummary::from_diff_stats(
+            "Unsaved editor buffers",
+            &stats,
+        ))
+    }
+}
+
+/// Lines added and removed between two texts, counted as a multiset so moved lines do not
+/// count. Close enough to a diff for a summary.
+fn line_changes(before: &str, after: &str) -> (usize, usize) {
+    let mut counts: HashMap<&str, isize> = HashMap::new();
+    for line in after.lines() {
+        *counts.entry(line).or_insert(0) += 1;
+    }
+    for line in before.lines() {
+        *counts.entry(line).or_insert(0) -= 1;
+    }
+    counts.values().fold((0, 0), |(added, removed), &count| {
+        if count > 0 {
+            (added + count as usize, removed)
+        } else {
+            (added, removed + count.unsigned_abs())
+        }
+    })
+}
+
+/// Per-file line counts from a unified diff, with or without git's `diff --git` headers.
+pub(crate) fn parse_patch(text: &str) -> Vec<FileDiffStat> {
+    let mut stats = Vec::new();
+    let mut current: Option<FileDiffStat> = None;
+    // Whether the current file's `---` header was seen; plain `diff -u` output starts each
+    // file with one instead of `diff --git`.
+    let mut seen_old_header = false;
+    let (mut old_left, mut new_left) = (0usize, 0usize);
+
+    for line in text.lines() {
+        if old_left > 0 || new_left > 0 {
+            if let Some(stat) = current.as_mut() {
+                match line.chars().next() {
+                    Some('+') => {
+                        stat.added += 1;
+                        new_left = new_left.saturating_sub(1);
+                    }
+                    Some('-') => {
+                        stat.removed += 1;
+                        old_left = old_left.saturating_sub(1);
+                    }
+                    Some('\\') => {}
+                    _ => {
+                        old_left = old_left.saturating_sub(1);
+                        new_left = new_left.saturating_sub(1);
+                    }
+                }
+            }
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            stats.extend(current.take());
+            let path = rest
+                .rsplit_once(" b/")
+                .map_or(rest, |(_, path)| path)
+                .to_string();
+            current = Some(new_stat(path));
+            seen_old_header = false;
+        } else if let Some(rest) = line.strip_prefix("--- ") {
+            if current.is_none() || seen_old_header {
+                stats.extend(current.take());
+                current = Some(new_stat(String::new()));
+            }
+            seen_old_header = true;
+            let path = header_path(rest);
+            if let Some(stat) = current.as_mut() {
+                if path == "/dev/null" {
+                    stat.status = 'A';
+                } else if stat.path.is_empty() {
+                    stat.path = path;
+                }
+            }
+        } else if let Some(rest) = line.strip_prefix("+++ ") {
+            let path = header_path(rest);
+            if let Some(stat) = current.as_mut() {
+                if path == "/dev/null" {
+                    stat.status = 'D';
+                } else {
+                    stat.path = path;
+                }
+            }
+        } else if let Some(stat) = current.as_mut() {
+            if let Some(path) = line.strip_prefix("rename to ") {
+                stat.path = path.to_string();
+                stat.status = 'R';
+            } else if line.starts_with("new file mode") {
+                stat.status = 'A';
+            } else if line.starts_with("deleted file mode") {
+                stat.status = 'D';
+            } else if let Some(range) = line.strip_prefix("@@ ") {
+                (old_left, new_left) = hunk_lengths(range);
+            }
+        }
+    }
+    stats.extend(current);
+    stats.retain(|stat| !stat.path.is_empty());
+    stats
+}
+
+fn new_stat(path: String) -> FileDiffStat {
+    FileDiffStat {
+        path,
+        status: 'M',
+        added: 0,
+        removed: 0,
+    }
+}
+
+/// The path in a `---`/`+++` header, without git's `a/`/`b/` prefix or a trailing timestamp.
+fn header_path(rest: &str) -> String {
+    let path = rest
+        .split('\t')
+        .next()
+        .unwrap_or(rest)
+        .trim()
+        .trim_matches('"');
+    path.strip_prefix("a/")
+        .or_else(|| path.strip_prefix("b/"))
+        .unwrap_or(path)
+        .to_string()
+}
+
+/// Old and new line counts from a hunk header such as `-12,7 +12,9 @@`.
+fn hunk_lengths(range: &str) -> (usize, usize) {
+    let length = |prefix: char| {
+        range
+            .split_whitespace()
+            .find_map(|part| part.strip_prefix(prefix))
+            .map(|part| match part.split_once(',') {
+                Some((_, length)) => length.parse().unwrap_or(0),
+                None => 1,
+            })
+            .unwrap_or(0)
+    };
+    (length('-'), length('+'))
+}
+
+/// `paths` relative to `root`, with `/` separators; empty means the whole repository.
+pub(crate) fn scope_prefixes(root: &Path, paths: &[PathBuf]) -> Vec<String> {
+    paths
+        .iter()
+        .filter_map(|path| repo_relative(root, path))
+        .collect()
+}
+
+pub(crate) fn in_scope(path: &str, prefixes: &[String]) -> bool {
+    prefixes.is_empty()
+        || prefixes.iter().any(|prefix| {
+            prefix.is_empty()
+                || path == prefix
+                || path
+                    .strip_prefix(prefix.as_str())
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+}
+
+fn repo_relative(root: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?;
+    Some(relative.to_string_lossy().replace('\\', "/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_lines_per_file_in_git_and_plain_patches() {
+        let git = "diff --git a/src/refund.rs b/src/refund.rs\n\
+                   index 1..2 100644\n\
+                   --- a/src/refund.rs\n\
+                   +++ b/src/refund.rs\n\
+                   @@ -1,3 +1,3 @@\n\
+                   \x20fn refund() {\n\
+                   -    round(total)\n\
+                   +    round_cents(total)\n\
+                   \x20}\n\
+                   diff --git a/docs/old.md b/docs/new.md\n\
+                   similarity index 90%\n\
+                   rename from docs/old.md\n\
+                   rename to docs/new.md\n\
+                   diff --git a/tests/gone.rs b/tests/gone.rs\n\
+                   deleted file mode 100644\n\
+                   --- a/tests/gone.rs\n\
+                   +++ /dev/null\n\
+                   @@ -1,2 +0,0 @@\n\
+                   --- a heading that looks like a header\n\
+                   -#[test]\n";
+        let stats = parse_patch(git);
+        let summary: Vec<(&str, char, usize, usize)> = stats
+            .iter()
+            .map(|stat| (stat.path.as_str(), stat.status, stat.added, stat.removed))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("src/refund.rs", 'M', 1, 1),
+                ("docs/new.md", 'R', 0, 0),
+                ("tests/gone.rs", 'D', 0, 2),
+            ]
+        );
+
+        let plain = "--- web/cart.ts\t2025-01-01\n+++ web/cart.ts\t2025-01-02\n@@ -1 +1,2 @@\n a\n+b\n\
+                     --- /dev/null\n+++ web/new.ts\n@@ -0,0 +1 @@\n+c\n";
+        let stats = parse_patch(plain);
+        assert_eq!(stats.len(), 2);
+        assert_eq!(
+            (stats[1].path.as_str(), stats[1].status),
+            ("web/new.ts", 'A')
+        );
+        assert!(in_scope("web/cart.ts", &["web".to_string()]));
+        assert!(!in_scope("webapp/cart.ts", &["web".to_string()]));
+        assert_eq!(line_changes("a\nb\nc", "a\nc\nd\ne"), (2, 1));
+    }
+}
@@ -0,0 +1,170 @@
+use std::future::Future;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::{info, warn};
+
+use crate::domain::change::ChangeSummary;
+use crate::domain::changelog::{ChangelogFacts, ReleaseNotes};
+use crate::domain::retro::{RetroFacts, RetroNotes};
+use crate::domain::standup::{StandupFacts, StandupNotes};
+use crate::domain::ticket::{
+    DraftGuidance, SplitDraft, TicketAmendment, TicketDetails, TicketDraft,
+};
+use crate::error::AppResult;
+use crate::infra::heuristic::{heuristic_split, heuristic_ticket};
+use crate::services::LanguageModelService;
+
+/// Tries each configured provider in order and returns the first successful draft.
+///
+/// When every provider fails (down, rate-limited, timed out, or returned garbage), the
+/// heuristic draft is used so the workflow can still proceed.
+pub struct FallbackLanguageModel {
+    providers: Vec<(String, Arc<dyn LanguageModelService>)>,
+}
+
+impl FallbackLanguageModel {
+    pub fn new(providers: Vec<(String, Arc<dyn LanguageModelService>)>) -> Self {
+        Self { providers }
+    }
+
+    /// Runs `call` against each provider in order and returns the first success, or
+    /// `None` once every provider has failed. `produced` names the result in the log.
+    async fn try_providers<'a, T, F, Fut>(&'a self, produced: &str, call: F) -> Option<T>
+    where
+        F: Fn(&'a dyn LanguageModelService) -> Fut,
+        Fut: Future<Output = AppResult<T>>,
+    {
+        let mut remaining = self.providers.len();
+        for (name, provider) in &self.providers {
+            remaining -= 1;
+            match call(provider.as_ref()).await {
+                Ok(value) => {
+                    info!(provider = %name, "{produced} produced");
+                    return Some(value);
+                }
+                Err(err) if remaining > 0 => {
+                    warn!("LLM provider '{name}' failed ({err}); trying next provider.");
+                }
+                Err(err) => {
+                    warn!("LLM provider '{name}' failed ({err}).");
+                }
+            }
+        }
+        None
+    }
+}
+
+#[async_trait]
+impl LanguageModelService for FallbackLanguageModel {
+    fn fingerprint(&self) -> String {
+        self.providers
+            .iter()
+            .map(|(_, provider)| provider.fingerprint())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    async fn draft_ticket(
+        &self,
+        changes: &ChangeSummary,
+        guidance: &DraftGuidance,
+    ) -> AppResult<TicketDraft> {
+        if let Some(draft) = self
+            .try_providers("draft", |provider| provider.draft_ticket(changes, guidance))
+            .await
+        {
+            return Ok(draft);
+        }
+        warn!("no LLM provider produced a draft; using heuristic ticket.");
+        Ok(heuristic_ticket(changes))
+    }
+
+    async fn draft_tickets(
+        &self,
+        changes: &ChangeSummary,
+        guidance: &DraftGuidance,
+    ) -> AppResult<Vec<SplitDraft>> {
+        if let Some(drafts) = self
+            .try_providers("split drafts", |provider| {
+                provider.draft_tickets(changes, guidance)
+            })
+            .await
+        {
+            return Ok(drafts);
+        }
+        warn!("no LLM provider could split the changes; splitting them by directory.");
+        Ok(heuristic_split(changes))
+    }
+
+    async fn retrospective_notes(&self, facts: &RetroFacts) -> AppResult<RetroNotes> {
+        if let Some(notes) = self
+            .try_providers("retrospective notes", |provider| {
+                provider.retrospective_notes(facts)
+            })
+            .await
+        {
+            return Ok(notes);
+        }
+        warn!("no LLM provider wrote the retrospective; deriving notes from the numbers.");
+        Ok(facts.heuristic_notes())
+    }
+
+    async fn release_notes(&self, facts: &ChangelogFacts) -> AppResult<ReleaseNotes> {
+        if let Some(notes) = self
+            .try_providers("release notes", |provider| provider.release_notes(facts))
+            .await
+        {
+            return Ok(notes);
+        }
+        warn!("no LLM provider wrote the release notes; listing the entries instead.");
+        Ok(facts.heuristic_notes())
+    }
+
+    async fn enrich_draft(&self, draft: &TicketDraft) -> AppResult<TicketDraft> {
+        if let Some(enriched) = self
+            .try_providers("enriched draft", |provider| provider.enrich_draft(draft))
+            .await
+        {
+            return Ok(enriched);
+        }
+
+        warn!(
+            "no LLM provider could enrich '{}'; keeping it as written.",
+            draft.title
+        );
+        Ok(draft.clone())
+    }
+
+    async fn amend_ticket(
+        &self,
+        ticket: &TicketDetails,
+        changes: &ChangeSummary,
+        guidance: &DraftGuidance,
+    ) -> AppResult<TicketAmendment> {
+        if let Some(amendment) = self
+            .try_providers("amendment", |provider| {
+                provider.amend_ticket(ticket, changes, guidance)
+            })
+            .await
+        {
+            return Ok(amendment);
+        }
+        warn!(
+            "no LLM provider could amend {}; appending the changed files instead.",
+            ticket.key
+        );
+        Ok(TicketAmendment::heuristic(ticket, changes))
+    }
+
+    async fn standup_notes(&self, facts: &StandupFacts) -> AppResult<StandupNotes> {
+        if let Some(notes) = self
+            .try_providers("standup summary", |provider| provider.standup_notes(facts))
+            .await
+        {
+            return Ok(notes);
+        }
+        warn!("no LLM provider wrote the standup; listing commit subjects instead.");
+        Ok(facts.heuristic_notes())
+    }
+}
@@ -1,26 +1,51 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{ExitStatus, Stdio};
 
 use async_trait::async_trait;
+use chrono::{DateTime, TimeDelta, Utc};
 use tokio::process::Command;
+use tracing::{debug, instrument};
 
 use crate::domain::branch::BranchName;
 use crate::domain::change::ChangeSummary;
+use crate::domain::retro::CommitSummary;
+use crate::domain::standup::BranchCommit;
 use crate::error::{AppError, AppResult};
 use crate::services::VersionControlService;
 
 pub struct GitCli {
-    workspace_root: PathBuf,
+    repo_root: PathBuf,
 }
 
 impl GitCli {
-    pub fn new(workspace_root: PathBuf) -> Self {
-        Self { workspace_root }
+    pub fn new(repo_root: PathBuf) -> Self {
+        Self { repo_root }
     }
 
+    /// Toplevel of the repository containing `start`, via `git rev-parse --show-toplevel`.
+    /// Blocking, as it runs once while the context is built.
+    pub fn toplevel(start: &Path) -> AppResult<PathBuf> {
+        let output = std::process::Command::new("git")
+            .arg("-C")
+            .arg(start)
+            .args(["rev-parse", "--show-toplevel"])
+            .output()
+            .map_err(|err| AppError::VersionControl(format!("failed to run git: {err}")))?;
+        let toplevel = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if !output.status.success() || toplevel.is_empty() {
+            return Err(AppError::VersionControl(format!(
+                "{} is not inside a git work tree: {}",
+                start.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(PathBuf::from(toplevel))
+    }
+
+    #[instrument(level = "debug", name = "git", skip_all, fields(args = %args.join(" ")))]
     async fn exec_git(&self, args: &[&str]) -> AppResult<GitCommandOutput> {
         let mut command = Command::new("git");
-        command.current_dir(&self.workspace_root);
+        command.current_dir(&self.repo_root);
         command.args(args);
         command.stdout(Stdio::piped());
         command.stderr(Stdio::piped());
@@ -32,6 +57,7 @@ impl GitCli {
 
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        debug!(status = %output.status, "git finished");
 
         Ok(GitCommandOutput {
             stdout,
@@ -53,7 +79,7 @@ impl GitCli {
         }
     }
 
-    async fn current_branch(&self) -> Option<String> {
+    async fn head_branch(&self) -> Option<String> {
         let output = self.exec_git(&["rev-parse", "--abbrev-ref", "HEAD"]).await;
         match output {
             Ok(result) if result.status.success() => Some(result.stdout.trim().to_string()),
@@ -71,73 +97,57 @@ impl GitCli {
 
 #[async_trait]
 impl VersionControlService for GitCli {
-    async fn summarize_changes(&self) -> AppResult<ChangeSummary> {
-        let status_output = self.run_git_checked(&["status", "--short"]).await?;
-
-        let files_changed = status_output
-            .lines()
-            .filter(|line| !line.trim().is_empty())
-            .count();
-
-        let diff_stat = if files_changed == 0 {
-            String::new()
-        } else {
-            self.run_git_checked(&["diff", "--stat=200"])
-                .await
-                .unwrap_or_default()
+    #[instrument(level = "info", name = "git.summarize_changes", skip_all)]
+    async fn summarize_changes(&self, paths: &[PathBuf]) -> AppResult<ChangeSummary> {
+        let pathspecs: Vec<String> = paths
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect();
+        let with_pathspecs = |base: &[&'static str]| -> Vec<&str> {
+            let mut args = base.to_vec();
+            if !pathspecs.is_empty() {
+                args.push("--");
+                args.extend(pathspecs.iter().map(String::as_str));
+            }
+            args
         };
+        let status_args = with_pathspecs(&["status", "--short"]);
+        let diff_args = with_pathspecs(&["diff", "--stat=200"]);
+        let staged_args = with_pathspecs(&["diff", "--cached", "--stat=200"]);
 
-        let branch = self
-            .current_branch()
-            .await
-            .unwrap_or_else(|| "HEAD".to_string());
-
-        let summary = if files_changed == 0 {
-            format!("Branch {branch} has no uncommitted changes.")
-        } else {
-            let mut lines = Vec::new();
-            lines.push(format!(
-                "Branch {branch} has {files_changed} file(s) with local changes."
-            ));
-
-            for entry in status_output
-                .lines()
-                .filter(|line| !line.trim().is_empty())
-                .take(8)
-            {
-                lines.push(format!("  {entry}"));
-            }
+        // Independent read-only invocations; run them together instead of back to back.
+        let (status_output, diff_stat, staged_stat, branch) = tokio::join!(
+            self.run_git_checked(&status_args),
+            self.run_git_checked(&diff_args),
+            self.run_git_checked(&staged_args),
+            self.head_branch(),
+        );
+        let status_output = status_output?;
+        let diff_stat = diff_stat.unwrap_or_default();
+        let staged_stat = staged_stat.unwrap_or_default();
+        let branch = branch.unwrap_or_else(|| "HEAD".to_string());
 
-            if files_changed > 8 {
-                lines.push("  …".to_string());
-            }
+        let status_lines: Vec<&str> = status_output
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect();
+        let files_changed = status_lines.len();
 
-            let diff_stat_lines: Vec<&str> = diff_stat
-                .lines()
-                .filter(|line| !line.trim().is_empty())
-                .collect();
-
-            if !diff_stat_lines.is_empty() {
-                lines.push(String::new());
-                lines.push("Diff summary:".to_string());
-                lines.extend(
-                    diff_stat_lines
-                        .into_iter()
-                        .take(8)
-                        .map(|line| format!("  {line}")),
-                );
-            }
+        let summary = render_summary(&branch, &status_lines, &diff_stat, &staged_stat);
 
-            lines.join("\n")
-        };
+        let files = status_lines
+            .iter()
+            .filter_map(|line| status_path(line))
+            .collect::<Vec<_>>();
 
         Ok(ChangeSummary {
             files_changed,
             summary,
+            files,
         })
     }
 
-    async fn checkout_branch(&self, branch: &BranchName) -> AppResult<()> {
+    async fn checkout_branch(&self, branch: &BranchName, base: Option<&str>) -> AppResult<()> {
         if branch.as_str().is_empty() {
             return Err(AppError::VersionControl(
                 "branch name cannot be empty".to_string(),
@@ -147,12 +157,236 @@ impl VersionControlService for GitCli {
         if self.branch_exists(branch.as_str()).await? {
             self.run_git_checked(&["checkout", branch.as_str()]).await?;
         } else {
-            self.run_git_checked(&["checkout", "-b", branch.as_str()])
-                .await?;
+            let mut args = vec!["checkout", "-b", branch.as_str()];
+            args.extend(base);
+            self.run_git_checked(&args).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn create_branch(&self, branch: &BranchName, base: Option<&str>) -> AppResult<()> {
+        if branch.as_str().is_empty() {
+            return Err(AppError::VersionControl(
+                "branch name cannot be empty".to_string(),
+            ));
+        }
+        if !self.branch_exists(branch.as_str()).await? {
+            let mut args = vec!["branch", branch.as_str()];
+            args.extend(base);
+            self.run_git_checked(&args).await?;
+        }
+        Ok(())
+    }
+
+    async fn create_worktree(
+        &self,
+        branch: &BranchName,
+        base: Option<&str>,
+        path: &Path,
+    ) -> AppResult<()> {
+        if branch.as_str().is_empty() {
+            return Err(AppError::VersionControl(
+                "branch name cannot be empty".to_string(),
+            ));
+        }
+        let path = path.to_string_lossy();
+        let mut args = vec!["worktree", "add"];
+        if self.branch_exists(branch.as_str()).await? {
+            args.extend([path.as_ref(), branch.as_str()]);
+        } else {
+            args.extend(["-b", branch.as_str(), path.as_ref()]);
+            args.extend(base);
         }
+        self.run_git_checked(&args).await?;
+        Ok(())
+    }
+
+    async fn remove_worktree(&self, path: &Path) -> AppResult<()> {
+        let path = path.to_string_lossy();
+        self.run_git_checked(&["worktree", "remove", path.as_ref()])
+            .await?;
+        Ok(())
+    }
+
+    async fn current_branch(&self) -> AppResult<Option<String>> {
+        let output = self
+            .run_git_checked(&["rev-parse", "--abbrev-ref", "HEAD"])
+            .await?;
+        let name = output.trim();
+        Ok((!name.is_empty() && name != "HEAD").then(|| name.to_string()))
+    }
+
+    async fn switch_branch(&self, branch: &str) -> AppResult<()> {
+        self.run_git_checked(&["checkout", branch]).await?;
+        Ok(())
+    }
 
+    async fn delete_branch(&self, branch: &str, force: bool) -> AppResult<()> {
+        let flag = if force { "-D" } else { "-d" };
+        self.run_git_checked(&["branch", flag, branch]).await?;
         Ok(())
     }
+
+    async fn commits_between(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> AppResult<Vec<CommitSummary>> {
+        // `--until` is inclusive, so step back a second to keep the range half-open.
+        let since = format!("--since={}", since.to_rfc3339());
+        let until = format!("--until={}", (until - TimeDelta::seconds(1)).to_rfc3339());
+        let output = self
+            .run_git_checked(&["log", "--format=%h%x09%s", &since, &until])
+            .await?;
+        Ok(output
+            .lines()
+            .filter_map(|line| line.split_once('\t'))
+            .map(|(id, subject)| CommitSummary {
+                id: id.to_string(),
+                subject: subject.to_string(),
+            })
+            .collect())
+    }
+
+    #[instrument(level = "info", name = "git.commits_in_range", skip_all, fields(range = %range))]
+    async fn commits_in_range(&self, range: &str) -> AppResult<Vec<CommitSummary>> {
+        if range.starts_with('-') {
+            return Err(AppError::VersionControl(format!(
+                "'{range}' is not a revision range"
+            )));
+        }
+        let output = self
+            .run_git_checked(&["log", "--format=%h%x09%s", range, "--"])
+            .await?;
+        Ok(output
+            .lines()
+            .filter_map(|line| line.split_once('\t'))
+            .map(|(id, subject)| CommitSummary {
+                id: id.to_string(),
+                subject: subject.to_string(),
+            })
+            .collect())
+    }
+
+    #[instrument(level = "info", name = "git.recent_commits", skip_all)]
+    async fn recent_commits(&self, since: DateTime<Utc>) -> AppResult<Vec<BranchCommit>> {
+        let author = self.exec_git(&["config", "user.email"]).await?;
+        let author = author.stdout.trim();
+        let since = format!("--since={}", since.to_rfc3339());
+        let branches = self
+            .run_git_checked(&["for-each-ref", "--format=%(refname:short)", "refs/heads"])
+            .await?;
+
+        let mut commits = Vec::new();
+        for branch in branches.lines().map(str::trim).filter(|b| !b.is_empty()) {
+            let mut args = vec![
+                "log",
+                branch,
+                "--no-merges",
+                &since,
+                "--format=%h%x09%cI%x09%s",
+            ];
+            let author_arg = format!("--author={author}");
+            if !author.is_empty() {
+                args.extend(["--fixed-strings", author_arg.as_str()]);
+            }
+            let output = self.run_git_checked(&args).await?;
+            commits.extend(output.lines().filter_map(|line| {
+                let mut fields = line.splitn(3, '\t');
+                let id = fields.next()?;
+                let committed = DateTime::parse_from_rfc3339(fields.next()?).ok()?;
+                Some(BranchCommit {
+                    branch: branch.to_string(),
+                    id: id.to_string(),
+                    subject: fields.next().unwrap_or_default().to_string(),
+                    committed: committed.to_utc(),
+                })
+            }));
+        }
+        Ok(commits)
+    }
+
+    #[instrument(level = "info", name = "git.branch_started_at", skip_all, fields(branch = %branch))]
+    async fn branch_started_at(&self, branch: &str) -> AppResult<Option<DateTime<Utc>>> {
+        let local = format!("--exclude={branch}");
+        let remote = format!("--exclude=*/{branch}");
+        let output = self
+            .run_git_checked(&[
+                "log",
+                &format!("refs/heads/{branch}"),
+                "--format=%aI",
+                "--not",
+                &local,
+                "--branches",
+                &remote,
+                "--remotes",
+                "--",
+            ])
+            .await?;
+        Ok(output
+            .lines()
+            .filter_map(|line| DateTime::parse_from_rfc3339(line.trim()).ok())
+            .map(|time| time.to_utc())
+            .min())
+    }
+}
+
+/// The human-readable change summary sent to the LLM: branch, up to eight short-status
+/// lines, and the unstaged and staged diff stats.
+pub(crate) fn render_summary(
+    branch: &str,
+    status_lines: &[&str],
+    diff_stat: &str,
+    staged_stat: &str,
+) -> String {
+    let files_changed = status_lines.len();
+    if files_changed == 0 {
+        return format!("Branch {branch} has no uncommitted changes.");
+    }
+
+    let mut lines = Vec::new();
+    lines.push(format!(
+        "Branch {branch} has {files_changed} file(s) with local changes."
+    ));
+
+    for entry in status_lines.iter().take(8) {
+        lines.push(format!("  {entry}"));
+    }
+
+    if files_changed > 8 {
+        lines.push("  …".to_string());
+    }
+
+    for (heading, stat) in [
+        ("Diff summary:", diff_stat),
+        ("Staged diff summary:", staged_stat),
+    ] {
+        let stat_lines: Vec<&str> = stat
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect();
+        if !stat_lines.is_empty() {
+            lines.push(String::new());
+            lines.push(heading.to_string());
+            lines.extend(
+                stat_lines
+                    .into_iter()
+                    .take(8)
+                    .map(|line| format!("  {line}")),
+            );
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Extracts the path from a `git status --short` line, using the new name for renames.
+fn status_path(line: &str) -> Option<String> {
+    let path = line.get(3..)?.trim();
+    let path = path.rsplit(" -> ").next().unwrap_or(path);
+    let path = path.trim_matches('"');
+    (!path.is_empty()).then(|| path.to_string())
 }
 
 struct GitCommandOutput {
@@ -0,0 +1,531 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use git2::build::CheckoutBuilder;
+use git2::{
+    BranchType, DiffOptions, DiffStatsFormat, Repository, Sort, Status, StatusOptions, StatusShow,
+    WorktreeAddOptions, WorktreePruneOptions,
+};
+use tracing::instrument;
+
+use crate::domain::branch::BranchName;
+use crate::domain::change::ChangeSummary;
+use crate::domain::retro::CommitSummary;
+use crate::domain::standup::BranchCommit;
+use crate::error::{AppError, AppResult};
+use crate::infra::git::render_summary;
+use crate::services::VersionControlService;
+
+/// Same width `GitCli` passes as `--stat=200`.
+const STAT_WIDTH: usize = 200;
+
+/// `VersionControlService` on libgit2: no `git` binary needed, and nothing depends on
+/// the locale of porcelain output.
+///
+/// `git2::Repository` is not `Sync`, so every call opens the repository afresh on a
+/// blocking thread.
+pub struct Git2Repo {
+    repo_root: PathBuf,
+}
+
+impl Git2Repo {
+    pub fn new(repo_root: PathBuf) -> Self {
+        Self { repo_root }
+    }
+
+    /// Working directory of the repository containing `start`.
+    pub fn toplevel(start: &Path) -> AppResult<PathBuf> {
+        let repo = Repository::discover(start).map_err(|err| {
+            AppError::VersionControl(format!(
+                "{} is not inside a git work tree: {}",
+                start.display(),
+                err.message()
+            ))
+        })?;
+        let workdir = repo.workdir().ok_or_else(|| {
+            AppError::VersionControl(format!(
+                "{} is inside a bare repository, which has no work tree",
+                start.display()
+            ))
+        })?;
+        Ok(workdir
+            .canonicalize()
+            .unwrap_or_else(|_| workdir.to_path_buf()))
+    }
+
+    async fn with_repo<T, F>(&self, operation: F) -> AppResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Repository) -> Result<T, git2::Error> + Send + 'static,
+    {
+        let root = self.repo_root.clone();
+        tokio::task::spawn_blocking(move || {
+            let repo = Repository::discover(&root)?;
+            operation(&repo)
+        })
+        .await
+        .map_err(|err| AppError::VersionControl(format!("git task failed: {err}")))?
+        .map_err(|err| AppError::VersionControl(err.message().to_string()))
+    }
+}
+
+#[async_trait]
+impl VersionControlService for Git2Repo {
+    #[instrument(level = "info", name = "git2.summarize_changes", skip_all)]
+    async fn summarize_changes(&self, paths: &[PathBuf]) -> AppResult<ChangeSummary> {
+        let paths = paths.to_vec();
+        self.with_repo(move |repo| {
+            let pathspecs = repo_pathspecs(repo, &paths);
+
+            let mut status_options = StatusOptions::new();
+            status_options
+                .show(StatusShow::IndexAndWorkdir)
+                .include_untracked(true)
+                .recurse_untracked_dirs(true)
+                .renames_head_to_index(true);
+            for spec in &pathspecs {
+                status_options.pathspec(spec);
+            }
+            let statuses = repo.statuses(Some(&mut status_options))?;
+
+            let mut status_lines = Vec::new();
+            let mut files = Vec::new();
+            for entry in statuses.iter() {
+                let status = entry.status();
+                if status.is_ignored() {
+                    continue;
+                }
+                let path = entry
+                    .head_to_index()
+                    .and_then(|delta| delta.new_file().path().map(Path::to_path_buf))
+                    .or_else(|| entry.path().map(PathBuf::from))
+                    .map(|path| path.to_string_lossy().replace('\\', "/"))
+                    .unwrap_or_default();
+                if path.is_empty() {
+                    continue;
+                }
+                status_lines.push(format!("{} {path}", short_status(status)));
+                files.push(path);
+            }
+
+            let diff_stat = {
+                let mut options = diff_options(&pathspecs);
+                let diff = repo.diff_index_to_workdir(None, Some(&mut options))?;
+                stats_text(&diff)?
+            };
+            let staged_stat = {
+                let mut options = diff_options(&pathspecs);
+                let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+                let diff = repo.diff_tree_to_index(head_tree.as_ref(), None, Some(&mut options))?;
+                stats_text(&diff)?
+            };
+            let branch = head_name(repo).unwrap_or_else(|| "HEAD".to_string());
+
+            let lines: Vec<&str> = status_lines.iter().map(String::as_str).collect();
+            Ok(ChangeSummary {
+                files_changed: lines.len(),
+                summary: render_summary(&branch, &lines, &diff_stat, &staged_stat),
+                files,
+            })
+        })
+        .await
+    }
+
+    async fn checkout_branch(&self, branch: &BranchName, base: Option<&str>) -> AppResult<()> {
+        let name = non_empty_branch(branch)?;
+        let base = base.map(str::to_string);
+        self.with_repo(move |repo| {
+            if repo.find_branch(&name, BranchType::Local).is_err() {
+                let start = start_commit(repo, base.as_deref())?;
+                repo.branch(&name, &start, false)?;
+            }
+            switch_to(repo, &name)
+        })
+        .await
+    }
+
+    async fn create_branch(&self, branch: &BranchName, base: Option<&str>) -> AppResult<()> {
+        let name = non_empty_branch(branch)?;
+        let base = base.map(str::to_string);
+        self.with_repo(move |repo| {
+            if repo.find_branch(&name, BranchType::Local).is_err() {
+                let start = start_commit(repo, base.as_deref())?;
+                repo.branch(&name, &start, false)?;
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn create_worktree(
+        &self,
+        branch: &BranchName,
+        base: Option<&str>,
+        path: &Path,
+    ) -> AppResult<()> {
+        let name = non_empty_branch(branch)?;
+        let base = base.map(str::to_string);
+        let path = path.to_path_buf();
+        self.with_repo(move |repo| {
+            if repo.find_branch(&name, BranchType::Local).is_err() {
+                let start = start_commit(repo, base.as_deref())?;
+                repo.branch(&name, &start, false)?;
+            }
+            let reference = repo.find_branch(&name, BranchType::Local)?.into_reference();
+            // Worktree names must be unique and path-safe; the directory name is both.
+            let worktree_name = path
+                .file_name()
+                .map(|file| file.to_string_lossy().into_owned())
+                .ok_or_else(|| git2::Error::from_str("worktree path has no directory name"))?;
+            let mut options = WorktreeAddOptions::new();
+            options.reference(Some(&reference));
+            repo.worktree(&worktree_name, &path, Some(&options))?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn remove_worktree(&self, path: &Path) -> AppResult<()> {
+        let path = path.to_path_buf();
+        self.with_repo(move |repo| {
+            let target = path.canonicalize().unwrap_or_else(|_| path.clone());
+            for name in repo.worktrees()?.iter().flatten() {
+                let worktree = repo.find_worktree(name)?;
+                let location = worktree
+                    .path()
+                    .canonicalize()
+                    .unwrap_or_else(|_| worktree.path().to_path_buf());
+                if location != target {
+                    continue;
+                }
+                let checkout = Repository::open_from_worktree(&worktree)?;
+                let mut options = StatusOptions::new();
+                options.include_untracked(true);
+                if !checkout.statuses(Some(&mut options))?.is_empty() {
+                    return Err(git2::Error::from_str(&format!(
+                        "'{}' has uncommitted changes",
+                        path.display()
+                    )));
+                }
+                fs::remove_dir_all(&location)
+                    .map_err(|err| git2::Error::from_str(&err.to_string()))?;
+                return worktree.prune(Some(WorktreePruneOptions::new().valid(true)));
+            }
+            Err(git2::Error::from_str(&format!(
+                "'{}' is not a worktree of this repository",
+                path.display()
+            )))
+        })
+        .await
+    }
+
+    async fn current_branch(&self) -> AppResult<Option<String>> {
+        self.with_repo(|repo| {
+            if repo.head_detached()? {
+                return Ok(None);
+            }
+            Ok(head_name(repo))
+        })
+        .await
+    }
+
+    async fn switch_branch(&self, branch: &str) -> AppResult<()> {
+        let name = branch.to_string();
+        self.with_repo(move |repo| switch_to(repo, &name)).await
+    }
+
+    async fn delete_branch(&self, branch: &str, force: bool) -> AppResult<()> {
+        let name = branch.to_string();
+        self.with_repo(move |repo| {
+            let mut local = repo.find_branch(&name, BranchType::Local)?;
+            if !force {
+                // Mirrors `git branch -d`: refuse unless HEAD already contains the branch.
+                let tip = local.get().peel_to_commit()?.id();
+                let head = repo.head()?.peel_to_commit()?.id();
+                if tip != head && !repo.graph_descendant_of(head, tip)? {
+                    return Err(git2::Error::from_str(&format!(
+                        "the branch '{name}' is not fully merged"
+                    )));
+                }
+            }
+            local.delete()
+        })
+        .await
+    }
+
+    async fn commits_between(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> AppResult<Vec<CommitSummary>> {
+        let (since, until) = (since.timestamp(), until.timestamp());
+        self.with_repo(move |repo| {
+            // An unborn HEAD has no history yet.
+            if repo.head().is_err() {
+                return Ok(Vec::new());
+            }
+            let mut walk = repo.revwalk()?;
+            walk.set_sorting(Sort::TIME)?;
+            walk.push_head()?;
+
+            let mut commits = Vec::new();
+            for oid in walk {
+                let commit = repo.find_commit(oid?)?;
+                let time = commit.time().seconds();
+                if time < since {
+                    break;
+                }
+                if time >= until {
+                    continue;
+                }
+                let id = commit.as_object().short_id()?;
+                commits.push(CommitSummary {
+                    id: id.as_str().unwrap_or_default().to_string(),
+                    subject: commit.summary().unwrap_or_default().to_string(),
+                });
+            }
+            Ok(commits)
+        })
+        .await
+    }
+
+    #[instrument(level = "info", name = "git2.commits_in_range", skip_all, fields(range = %range))]
+    async fn commits_in_range(&self, range: &str) -> AppResult<Vec<CommitSummary>> {
+        let range = range.to_string();
+        self.with_repo(move |repo| {
+            let mut walk = repo.revwalk()?;
+            walk.set_sorting(Sort::TIME)?;
+            match range.split_once("..") {
+                Some((from, to)) => {
+                    let to = if to.is_empty() { "HEAD" } else { to };
+                    walk.push(repo.revparse_single(to)?.peel_to_commit()?.id())?;
+                    if !from.is_empty() {
+                        walk.hide(repo.revparse_single(from)?.peel_to_commit()?.id())?;
+                    }
+                }
+                None => walk.push(repo.revparse_single(&range)?.peel_to_commit()?.id())?,
+            }
+
+            let mut commits = Vec::new();
+            for oid in walk {
+                let commit = repo.find_commit(oid?)?;
+                let id = commit.as_object().short_id()?;
+                commits.push(CommitSummary {
+                    id: id.as_str().unwrap_or_default().to_string(),
+                    subject: commit.summary().unwrap_or_default().to_string(),
+                });
+            }
+            Ok(commits)
+        })
+        .await
+    }
+
+    #[instrument(level = "info", name = "git2.recent_commits", skip_all)]
+    async fn recent_commits(&self, since: DateTime<Utc>) -> AppResult<Vec<BranchCommit>> {
+        let since = since.timestamp();
+        self.with_repo(move |repo| {
+            let author = repo
+                .config()
+                .and_then(|config| config.get_string("user.email"))
+                .ok()
+                .filter(|email| !email.trim().is_empty());
+
+            let mut commits = Vec::new();
+            for branch in repo.branches(Some(BranchType::Local))? {
+                let (branch, _) = branch?;
+                let (Some(name), Some(tip)) = (branch.name()?, branch.get().target()) else {
+                    continue;
+                };
+                let mut walk = repo.revwalk()?;
+                walk.set_sorting(Sort::TIME)?;
+                walk.push(tip)?;
+                for oid in walk {
+                    let commit = repo.find_commit(oid?)?;
+                    let time = commit.time().seconds();
+                    if time < since {
+                        break;
+                    }
+                    let by_author = author
+                        .as_deref()
+                        .is_none_or(|email| commit.author().email() == Some(email));
+                    if commit.parent_count() > 1 || !by_author {
+                        continue;
+                    }
+                    let id = commit.as_object().short_id()?;
+                    commits.push(BranchCommit {
+                        branch: name.to_string(),
+                        id: id.as_str().unwrap_or_default().to_string(),
+                        subject: commit.summary().unwrap_or_default().to_string(),
+                        committed: DateTime::from_timestamp(time, 0).unwrap_or_default(),
+                    });
+                }
+            }
+            Ok(commits)
+        })
+        .await
+    }
+
+    #[instrument(level = "info", name = "git2.branch_started_at", skip_all, fields(branch = %branch))]
+    async fn branch_started_at(&self, branch: &str) -> AppResult<Option<DateTime<Utc>>> {
+        let branch = branch.to_string();
+        self.with_repo(move |repo| {
+            let tip = repo
+                .find_branch(&branch, BranchType::Local)?
+                .get()
+                .peel_to_commit()?;
+            let mut walk = repo.revwalk()?;
+            walk.push(tip.id())?;
+            for other in repo.branches(None)? {
+                let (other, kind) = other?;
+                let Some(name) = other.name()? else {
+                    continue;
+                };
+                let same = match kind {
+                    BranchType::Local => name == branch,
+                    BranchType::Remote => {
+                        name.split_once('/').map(|(_, rest)| rest) == Some(&branch)
+                    }
+                };
+                if let (false, Some(target)) = (same, other.get().target()) {
+                    walk.hide(target)?;
+                }
+            }
+
+            let mut oldest: Option<i64> = None;
+            for oid in walk {
+                let time = repo.find_commit(oid?)?.author().when().seconds();
+                oldest = Some(oldest.map_or(time, |oldest| oldest.min(time)));
+            }
+            Ok(oldest.and_then(|time| DateTime::from_timestamp(time, 0)))
+        })
+        .await
+    }
+}
+
+fn non_empty_branch(branch: &BranchName) -> AppResult<String> {
+    if branch.as_str().is_empty() {
+        return Err(AppError::VersionControl(
+            "branch name cannot be empty".to_string(),
+        ));
+    }
+    Ok(branch.as_str().to_string())
+}
+
+/// Scope paths are absolute; libgit2 wants them relative to the working directory.
+fn repo_pathspecs(repo: &Repository, paths: &[PathBuf]) -> Vec<String> {
+    let Some(workdir) = repo.workdir() else {
+        return Vec::new();
+    };
+    let workdir = workdir
+        .canonicalize()
+        .unwrap_or_else(|_| workdir.to_path_buf());
+    paths
+        .iter()
+        .filter_map(|path| {
+            let path = path.canonicalize().unwrap_or_else(|_| path.clone());
+            let relative = path.strip_prefix(&workdir).ok()?;
+            let spec = relative.to_string_lossy().replace('\\', "/");
+            Some(if spec.is_empty() {
+                ".".to_string()
+            } else {
+                spec
+            })
+        })
+        .collect()
+}
+
+fn diff_options(pathspecs: &[String]) -> DiffOptions {
+    let mut options = DiffOptions::new();
+    for spec in pathspecs {
+        options.pathspec(spec);
+    }
+    options
+}
+
+fn stats_text(diff: &git2::Diff<'_>) -> Result<String, git2::Error> {
+    let stats = diff.stats()?;
+    // `git diff --stat` prints nothing for an empty diff.
+    if stats.files_changed() == 0 {
+        return Ok(String::new());
+    }
+    let buf = stats.to_buf(DiffStatsFormat::FULL, STAT_WIDTH)?;
+    Ok(buf.as_str().unwrap_or_default().to_string())
+}
+
+fn head_name(repo: &Repository) -> Option<String> {
+    repo.head()
+        .ok()
+        .and_then(|head| head.shorthand().map(str::to_string))
+}
+
+fn start_commit<'r>(
+    repo: &'r Repository,
+    base: Option<&str>,
+) -> Result<git2::Commit<'r>, git2::Error> {
+    match base {
+        Some(base) => repo.revparse_single(base)?.peel_to_commit(),
+        None => repo.head()?.peel_to_commit(),
+    }
+}
+
+/// Checks out a local branch like `git checkout <branch>`: a safe checkout keeps
+/// uncommitted changes and refuses to overwrite them.
+fn switch_to(repo: &Repository, name: &str) -> Result<(), git2::Error> {
+    let reference = repo.find_branch(name, BranchType::Local)?.into_reference();
+    let target = reference.peel(git2::ObjectType::Commit)?;
+    repo.checkout_tree(&target, Some(CheckoutBuilder::new().safe()))?;
+    let refname = reference
+        .name()
+        .ok_or_else(|| git2::Error::from_str("branch name is not valid UTF-8"))?;
+    repo.set_head(refname)
+}
+
+/// Two-letter code in the style of `git status --short`.
+fn short_status(status: Status) -> String {
+    if status.is_wt_new() && !status.is_index_new() {
+        return "??".to_string();
+    }
+    let index = if status.is_index_new() {
+        'A'
+    } else if status.is_index_modified() {
+        'M'
+    } else if status.is_index_deleted() {
+        'D'
+    } else if status.is_index_renamed() {
+        'R'
+    } else if status.is_index_typechange() {
+        'T'
+    } else {
+        ' '
+    };
+    let worktree = if status.is_wt_modified() {
+        'M'
+    } else if status.is_wt_deleted() {
+        'D'
+    } else if status.is_wt_renamed() {
+        'R'
+    } else if status.is_wt_typechange() {
+        'T'
+    } else {
+        ' '
+    };
+    format!("{index}{worktree}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_status_matches_porcelain_codes() {
+        assert_eq!(short_status(Status::WT_NEW), "??");
+        assert_eq!(short_status(Status::INDEX_NEW), "A ");
+        assert_eq!(
+            short_status(Status::INDEX_MODIFIED | Status::WT_MODIFIED),
+            "MM"
+        );
+        assert_eq!(short_status(Status::WT_DELETED), " D");
+    }
+}
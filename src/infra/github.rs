@@ -0,0 +1,225 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::header::{ACCEPT, AUTHORIZATION, USER_AGENT};
+use reqwest::{Client, RequestBuilder, StatusCode, Url};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use tracing::instrument;
+
+use crate::domain::change::{ChangeSummary, FileDiffStat};
+use crate::error::{AppError, AppResult};
+use crate::infra::change_source::{in_scope, scope_prefixes};
+use crate::infra::http::{HttpClientFactory, RetryPolicy, send_with_retry};
+use crate::services::ChangeSource;
+
+/// GitHub lists at most 3000 files per pull request, 100 per page.
+const FILES_PER_PAGE: usize = 100;
+const MAX_FILE_PAGES: usize = 30;
+/// Lines of the pull request description kept in the change summary.
+const MAX_DESCRIPTION_LINES: usize = 20;
+
+/// The changes in a GitHub (or GitHub Enterprise) pull request, read through the REST API.
+pub struct GitHubPullRequest {
+    http: Client,
+    retry: RetryPolicy,
+    api_base: String,
+    owner: String,
+    repo: String,
+    number: u64,
+    token: Option<String>,
+    root: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct PullRequest {
+    title: String,
+    #[serde(default)]
+    body: Option<String>,
+    head: BranchRef,
+    base: BranchRef,
+}
+
+#[derive(Deserialize)]
+struct BranchRef {
+    #[serde(rename = "ref")]
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct PullRequestFile {
+    filename: String,
+    status: String,
+    additions: usize,
+    deletions: usize,
+}
+
+impl GitHubPullRequest {
+    /// Takes a URL such as `https://github.com/acme/shop/pull/42`. Other hosts are treated
+    /// as GitHub Enterprise, whose API lives under `/api/v3`.
+    pub fn from_url(
+        http: &HttpClientFactory,
+        url: &str,
+        token: Option<String>,
+        root: PathBuf,
+    ) -> AppResult<Self> {
+        let (api_base, owner, repo, number) = parse_pull_url(url)?;
+        Ok(Self {
+            http: http.build(Some(Duration::from_secs(20)))?,
+            retry: http.retry_policy(),
+            api_base,
+            owner,
+            repo,
+            number,
+            token,
+            root,
+        })
+    }
+
+    fn get(&self, url: &str) -> RequestBuilder {
+        let request = self
+            .http
+            .get(url)
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, concat!("ugh/", env!("CARGO_PKG_VERSION")));
+        match &self.token {
+            Some(token) => request.header(AUTHORIZATION, format!("Bearer {token}")),
+            None => request,
+        }
+    }
+
+    async fn fetch<T: DeserializeOwned>(&self, url: &str) -> AppResult<T> {
+        let response = send_with_retry(&self.retry, "GitHub", || self.get(url))
+            .await
+            .map_err(|err| AppError::VersionControl(err.to_string()))?;
+        let status = response.status();
+        if !status.is_success() {
+            let hint = match status {
+                StatusCode::NOT_FOUND | StatusCode::UNAUTHORIZED if self.token.is_none() => {
+                    " (private repositories need github_token or UGH_GITHUB_TOKEN)"
+                }
+                _ => "",
+            };
+            return Err(AppError::VersionControl(format!(
+                "GitHub returned {status} for {url}{hint}"
+            )));
+        }
+        response.json().await.map_err(|err| {
+            AppError::VersionControl(format!("unexpected GitHub response from {url}: {err}"))
+        })
+    }
+}
+
+#[async_trait]
+impl ChangeSource for GitHubPullRequest {
+    fn describe(&self) -> String {
+        format!("pull request {}/{}#{}", self.owner, self.repo, self.number)
+    }
+
+    #[instrument(level = "info", name = "github.collect", skip_all, fields(number = self.number))]
+    async fn collect(&self, paths: &[PathBuf]) -> AppResult<ChangeSummary> {
+        let pull_url = format!(
+            "{}/repos/{}/{}/pulls/{}",
+            self.api_base, self.owner, self.repo, self.number
+        );
+        let pull: PullRequest = self.fetch(&pull_url).await?;
+
+        let mut files: Vec<PullRequestFile> = Vec::new();
+        for page in 1..=MAX_FILE_PAGES {
+            let batch: Vec<PullRequestFile> = self
+                .fetch(&format!(
+                    "{pull_url}/files?per_page={FILES_PER_PAGE}&page={page}"
+                ))
+                .await?;
+            let last = batch.len() < FILES_PER_PAGE;
+            files.extend(batch);
+            if last {
+                break;
+            }
+        }
+
+        let scope = scope_prefixes(&self.root, paths);
+        let stats: Vec<FileDiffStat> = files
+            .into_iter()
+            .filter(|file| in_scope(&file.filename, &scope))
+            .map(|file| FileDiffStat {
+                status: match file.status.as_str() {
+                    "added" => 'A',
+                    "removed" => 'D',
+                    "renamed" => 'R',
+                    _ => 'M',
+                },
+                path: file.filename,
+                added: file.additions,
+                removed: file.deletions,
+            })
+            .collect();
+
+        let origin = format!(
+            "Pull request {}/{}#{} ({} into {})",
+            self.owner, self.repo, self.number, pull.head.name, pull.base.name
+        );
+        let mut changes = ChangeSummary::from_diff_stats(&origin, &stats);
+        changes
+            .summary
+            .push_str(&format!("\n\nTitle: {}", pull.title.trim()));
+        let body = pull.body.unwrap_or_default();
+        if !body.trim().is_empty() {
+            changes.summary.push_str("\nDescription:");
+            for line in body.trim().lines().take(MAX_DESCRIPTION_LINES) {
+                changes.summary.push_str(&format!("\n  {line}"));
+            }
+        }
+        Ok(changes)
+    }
+}
+
+/// API base, owner, repository, and number from a pull request's web URL.
+fn parse_pull_url(url: &str) -> AppResult<(String, String, String, u64)> {
+    let invalid = || {
+        AppError::Configuration(format!(
+            "{url} is not a pull request URL like https://github.com/OWNER/REPO/pull/NUMBER"
+        ))
+    };
+    let parsed = Url::parse(url).map_err(|_| invalid())?;
+    let host = parsed.host_str().ok_or_else(invalid)?;
+    let segments: Vec<&str> = parsed
+        .path_segments()
+        .map(|segments| segments.filter(|segment| !segment.is_empty()).collect())
+        .unwrap_or_default();
+    let [owner, repo, "pull", number, ..] = segments.as_slice() else {
+        return Err(invalid());
+    };
+    let number = number.parse().map_err(|_| invalid())?;
+    let api_base = if host.eq_ignore_ascii_case("github.com") {
+        "https://api.github.com".to_string()
+    } else {
+        let port = parsed
+            .port()
+            .map(|port| format!(":{port}"))
+            .unwrap_or_default();
+        format!("{}://{host}{port}/api/v3", parsed.scheme())
+    };
+    Ok((api_base, owner.to_string(), repo.to_string(), number))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_owner_repo_and_number_from_pull_urls() {
+        let (api, owner, repo, number) =
+            parse_pull_url("https://github.com/acme/shop/pull/42/files").unwrap();
+        assert_eq!(api, "https://api.github.com");
+        assert_eq!(
+            (owner.as_str(), repo.as_str(), number),
+            ("acme", "shop", 42)
+        );
+
+        let (api, ..) = parse_pull_url("https://git.corp.example/acme/shop/pull/7").unwrap();
+        assert_eq!(api, "https://git.corp.example/api/v3");
+        assert!(parse_pull_url("https://github.com/acme/shop/issues/7").is_err());
+    }
+}
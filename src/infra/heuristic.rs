@@ -0,0 +1,344 @@
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+
+use crate::domain::branch::BranchCategory;
+use crate::domain::change::{ChangeSummary, FileKind};
+use crate::domain::ticket::{DraftGuidance, SplitDraft, TicketDraft};
+use crate::error::AppResult;
+use crate::services::LanguageModelService;
+
+pub(crate) const HEURISTIC_PROVIDER: &str = "heuristic";
+
+/// Part of the fingerprint; bump whenever the drafting rules change.
+const HEURISTIC_VERSION: u32 = 1;
+
+/// Path segments too generic to say what the work is about.
+const GENERIC_SEGMENTS: &[&str] = &[
+    "src", "lib", "app", "apps", "crates", "packages", "pkg", "internal", "cmd", "test", "tests",
+    "spec", "specs", "docs", "doc", "main", "java", "kotlin", "mod", "index", "init", "__init__",
+    "readme",
+];
+
+/// Words kept in a branch slug drawn from paths.
+const MAX_TOPIC_WORDS: usize = 4;
+/// Files listed per kind in a heuristic description.
+const MAX_LISTED_FILES: usize = 10;
+
+/// Drafts tickets from the changed paths and git's summary alone. Used when language models
+/// are turned off (`--no-llm`, `llm_provider = "none"`) and when every provider failed.
+pub struct HeuristicDrafter;
+
+#[async_trait]
+impl LanguageModelService for HeuristicDrafter {
+    fn fingerprint(&self) -> String {
+        format!("{HEURISTIC_PROVIDER}:v{HEURISTIC_VERSION}")
+    }
+
+    async fn draft_ticket(
+        &self,
+        changes: &ChangeSummary,
+        _guidance: &DraftGuidance,
+    ) -> AppResult<TicketDraft> {
+        Ok(heuristic_ticket(changes))
+    }
+
+    async fn draft_tickets(
+        &self,
+        changes: &ChangeSummary,
+        _guidance: &DraftGuidance,
+    ) -> AppResult<Vec<SplitDraft>> {
+        Ok(heuristic_split(changes))
+    }
+}
+
+pub(crate) fn heuristic_ticket(changes: &ChangeSummary) -> TicketDraft {
+    let branch_category = heuristic_category(changes);
+    let branch_summary = heuristic_summary(changes);
+    let topic = branch_summary.replace('-', " ");
+    let title = match branch_category {
+        BranchCategory::Feature => format!("Update {topic}"),
+        BranchCategory::Fix => format!("Fix {topic}"),
+        BranchCategory::Quality => format!("Improve {topic}"),
+    };
+
+    let mut acceptance_criteria = Vec::new();
+    let counts = changes.kind_counts();
+    if counts.contains_key(&FileKind::Test) {
+        acceptance_criteria.push("The updated tests pass.".to_string());
+    }
+    if counts.contains_key(&FileKind::Docs) {
+        acceptance_criteria.push("The documentation describes the new behavior.".to_string());
+    }
+
+    TicketDraft {
+        title,
+        description: heuristic_description(changes),
+        branch_category,
+        branch_summary,
+        acceptance_criteria,
+        estimate: None,
+        provider: Some(HEURISTIC_PROVIDER.to_string()),
+        rationale: None,
+    }
+}
+
+/// One ticket per area of the tree (the first descriptive path segment), so unrelated
+/// packages end up apart; tests and docs join the area they sit under.
+pub(crate) fn heuristic_split(changes: &ChangeSummary) -> Vec<SplitDraft> {
+    let mut areas: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for file in &changes.files {
+        let area = topic_segments(file).into_iter().next().unwrap_or_default();
+        areas.entry(area).or_default().push(file.clone());
+    }
+    // Files with no descriptive segment (e.g. `src/main.rs`) join the largest area.
+    if areas.len() > 1
+        && let Some(loose) = areas.remove("")
+        && let Some(largest) = areas.values_mut().max_by_key(|files| files.len())
+    {
+        largest.extend(loose);
+    }
+    if areas.len() <= 1 {
+        return vec![SplitDraft {
+            draft: heuristic_ticket(changes),
+            files: changes.files.clone(),
+        }];
+    }
+
+    areas
+        .into_values()
+        .map(|files| {
+            let part = ChangeSummary {
+                files_changed: files.len(),
+                summary: String::new(),
+                files,
+            };
+            SplitDraft {
+                draft: heuristic_ticket(&part),
+                files: part.files,
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn heuristic_category(changes: &ChangeSummary) -> BranchCategory {
+    let lower = changes.summary.to_lowercase();
+    let touches_code = changes
+        .tagged_files()
+        .any(|(_, kind)| matches!(kind, FileKind::Source | FileKind::Test));
+    if !changes.files.is_empty() && !touches_code {
+        BranchCategory::Quality
+    } else if lower.contains("fix") || lower.contains("bug") || lower.contains("error") {
+        BranchCategory::Fix
+    } else if lower.contains("refactor")
+        || lower.contains("cleanup")
+        || lower.contains("docs")
+        || lower.contains("chore")
+    {
+        BranchCategory::Quality
+    } else {
+        BranchCategory::Feature
+    }
+}
+
+/// A slug naming what the changed files have in common, e.g. `payments-refund` for
+/// `src/payments/refund.rs`.
+pub(crate) fn heuristic_summary(changes: &ChangeSummary) -> String {
+    let words = topic_words(&primary_files(changes));
+    if !words.is_empty() {
+        words.join("-")
+    } else if changes.files_changed == 0 {
+        "pending-update".to_string()
+    } else {
+        format!("update-{}-files", changes.files_changed)
+    }
+}
+
+/// The files that carry the work: everything but tests and generated files, unless
+/// nothing else changed.
+fn primary_files(changes: &ChangeSummary) -> Vec<&str> {
+    let primary: Vec<&str> = changes
+        .tagged_files()
+        .filter(|(_, kind)| !matches!(kind, FileKind::Test | FileKind::Generated))
+        .map(|(file, _)| file)
+        .collect();
+    if primary.is_empty() {
+        changes.files.iter().map(String::as_str).collect()
+    } else {
+        primary
+    }
+}
+
+fn topic_words(files: &[&str]) -> Vec<String> {
+    let paths: Vec<Vec<String>> = files
+        .iter()
+        .map(|file| topic_segments(file))
+        .filter(|segments| !segments.is_empty())
+        .collect();
+    let segments: Vec<String> = match paths.as_slice() {
+        [] => return Vec::new(),
+        [only] => only.iter().rev().take(2).rev().cloned().collect(),
+        [first, rest @ ..] => {
+            let shared = first
+                .iter()
+                .enumerate()
+                .take_while(|(index, segment)| {
+                    rest.iter().all(|path| path.get(*index) == Some(segment))
+                })
+                .count();
+            if shared > 0 {
+                first[..shared]
+                    .iter()
+                    .rev()
+                    .take(2)
+                    .rev()
+                    .cloned()
+                    .collect()
+            } else {
+                most_common_areas(&paths)
+            }
+        }
+    };
+
+    let mut words: Vec<String> = Vec::new();
+    for word in segments
+        .iter()
+        .flat_map(|segment| segment.split(|c: char| !c.is_ascii_alphanumeric()))
+        .filter(|word| !word.is_empty())
+    {
+        if !words.iter().any(|seen| seen == word) {
+            words.push(word.to_string());
+        }
+    }
+    words.truncate(MAX_TOPIC_WORDS);
+    words
+}
+
+/// The two areas (first descriptive segments) with the most changed files.
+fn most_common_areas(paths: &[Vec<String>]) -> Vec<String> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for path in paths {
+        *counts.entry(path[0].as_str()).or_insert(0) += 1;
+    }
+    let mut ranked: Vec<(&str, usize)> = counts.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    ranked
+        .into_iter()
+        .take(2)
+        .map(|(area, _)| area.to_string())
+        .collect()
+}
+
+/// Lower-cased path segments with the file extension and generic names dropped.
+fn topic_segments(path: &str) -> Vec<String> {
+    let path = path.trim().trim_end_matches('/').to_lowercase();
+    let segments: Vec<&str> = path.split('/').collect();
+    let last = segments.len().saturating_sub(1);
+    segments
+        .iter()
+        .enumerate()
+        .map(|(index, segment)| {
+            let segment = segment.trim_start_matches('.');
+            if index == last {
+                segment.split('.').next().unwrap_or(segment)
+            } else {
+                segment
+            }
+        })
+        .filter(|segment| !segment.is_empty() && !GENERIC_SEGMENTS.contains(segment))
+        .map(str::to_string)
+        .collect()
+}
+
+fn heuristic_description(changes: &ChangeSummary) -> String {
+    if changes.files.is_empty() {
+        return if changes.summary.trim().is_empty() {
+            "Summarize the local modifications before creating the ticket.".to_string()
+        } else {
+            format!("Summary of uncommitted work:\n{}", changes.summary.trim())
+        };
+    }
+
+    let mut by_kind: BTreeMap<FileKind, Vec<&str>> = BTreeMap::new();
+    for (file, kind) in changes.tagged_files() {
+        by_kind.entry(kind).or_default().push(file);
+    }
+    let mut description = format!("Changes {} file(s).\n", changes.files.len());
+    for (kind, files) in by_kind {
+        description.push_str(&format!("\n**{}**\n", kind_heading(kind)));
+        for file in files.iter().take(MAX_LISTED_FILES) {
+            description.push_str(&format!("- `{file}`\n"));
+        }
+        if files.len() > MAX_LISTED_FILES {
+            description.push_str(&format!(
+                "- … and {} more\n",
+                files.len() - MAX_LISTED_FILES
+            ));
+        }
+    }
+    if !changes.summary.trim().is_empty() {
+        description.push_str(&format!("\n```\n{}\n```", changes.summary.trim()));
+    }
+    description.trim_end().to_string()
+}
+
+fn kind_heading(kind: FileKind) -> &'static str {
+    match kind {
+        FileKind::Source => "Source",
+        FileKind::Test => "Tests",
+        FileKind::Config => "Configuration",
+        FileKind::Docs => "Documentation",
+        FileKind::Ci => "CI",
+        FileKind::Generated => "Generated",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changes(files: &[&str]) -> ChangeSummary {
+        ChangeSummary {
+            files_changed: files.len(),
+            summary: String::new(),
+            files: files.iter().map(|file| file.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn names_the_work_after_the_changed_paths_and_splits_by_area() {
+        let single = heuristic_ticket(&changes(&[
+            "src/payments/refund.rs",
+            "tests/refund_test.rs",
+        ]));
+        assert_eq!(single.branch_summary, "payments-refund");
+        assert_eq!(single.title, "Update payments refund");
+        assert_eq!(single.acceptance_criteria, ["The updated tests pass."]);
+        assert!(
+            single
+                .description
+                .contains("**Tests**\n- `tests/refund_test.rs`")
+        );
+
+        let shared = changes(&["crates/billing/src/invoice.rs", "crates/billing/src/tax.rs"]);
+        assert_eq!(heuristic_summary(&shared), "billing");
+        assert_eq!(heuristic_summary(&changes(&[])), "pending-update");
+
+        let split = heuristic_split(&changes(&[
+            "src/payments/refund.rs",
+            "src/main.rs",
+            "src/payments/ledger.rs",
+            "web/checkout.ts",
+        ]));
+        assert_eq!(split.len(), 2);
+        assert_eq!(
+            split[0].files,
+            [
+                "src/payments/refund.rs",
+                "src/payments/ledger.rs",
+                "src/main.rs"
+            ]
+        );
+        assert_eq!(split[1].draft.branch_summary, "web-checkout");
+    }
+}
@@ -0,0 +1,803 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, Utc};
+use reqwest::header::{ETAG, HeaderName, HeaderValue, IF_NONE_MATCH, RETRY_AFTER};
+use reqwest::{Certificate, Client, Method, Proxy, Request, RequestBuilder, Response, StatusCode};
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::{digest, hmac};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{debug, warn};
+
+use crate::config::{GatewaySettings, RateLimitSettings, config_directory};
+use crate::error::{AppError, AppResult};
+use crate::infra::rate_limit::RateLimiter;
+use crate::storage::{read_json_file, update_json_file};
+
+/// Upper bound for a single wait, even when the server asks for longer via `Retry-After`.
+const MAX_RETRY_WAIT: Duration = Duration::from_secs(60);
+
+const RESPONSE_CACHE_FILE_NAME: &str = "http_cache.json";
+/// Entries kept in the response cache; the least recently stored go first.
+const MAX_CACHED_RESPONSES: usize = 256;
+/// Larger bodies are not worth the disk and parse time they would save.
+const MAX_CACHED_BODY_BYTES: usize = 1024 * 1024;
+
+/// Network settings shared by every outbound HTTP client.
+#[derive(Debug, Clone, Default)]
+pub struct HttpSettings {
+    /// Proxy URL applied to all schemes (e.g. `http://proxy.corp:3128`).
+    pub proxy: Option<String>,
+    /// PEM file with one or more additional root certificates to trust.
+    pub ca_bundle: Option<PathBuf>,
+    pub retry: RetryPolicy,
+    /// Revalidate GETs against `http_cache.json` with `If-None-Match`.
+    pub response_cache: bool,
+    pub gateway: GatewaySettings,
+    pub rate_limit: RateLimitSettings,
+}
+
+/// Exponential backoff settings for transient failures (429, 5xx, timeouts), the gateway
+/// headers each attempt carries, and the pace attempts are held to.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total attempts including the first request; `1` disables retries.
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    /// Set by [`HttpClientFactory`] when a gateway is configured.
+    pub gateway: Option<Arc<GatewaySigner>>,
+    /// Set by [`HttpClientFactory`] when rate limits are configured.
+    pub limiter: Option<Arc<RateLimiter>>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(8),
+            gateway: None,
+            limiter: None,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Full-jitter backoff: a random wait between half and all of the exponential delay.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exponential = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_delay);
+        let half = exponential / 2;
+        // Random per call, so clients that failed together do not retry together.
+        let mut random = [0u8; 8];
+        let _ = SystemRandom::new().fill(&mut random);
+        let jitter_range = half.as_millis().max(1) as u64;
+        half + Duration::from_millis(u64::from_le_bytes(random) % jitter_range)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum RetryError {
+    #[error("{service} is rate limiting requests; gave up after {attempts} attempt(s): {body}")]
+    RateLimited {
+        service: String,
+        attempts: u32,
+        body: String,
+    },
+    #[error("{service} kept failing with {status} after {attempts} attempt(s): {body}")]
+    ServerError {
+        service: String,
+        status: StatusCode,
+        attempts: u32,
+        body: String,
+    },
+    #[error("could not reach {service} after {attempts} attempt(s): {source}")]
+    Transport {
+        service: String,
+        attempts: u32,
+        source: reqwest::Error,
+    },
+}
+
+/// Sends the request built by `build`, retrying transient failures according to `policy`.
+///
+/// Only idempotent methods are retried after a timeout or a 5xx, since the server may have
+/// acted on the request; a POST is retried only when it provably did not run (the
+/// connection failed, or the server answered 429). Non-retryable responses (including
+/// 4xx other than 429) are returned as-is so callers can surface their own error messages.
+pub async fn send_with_retry<F>(
+    policy: &RetryPolicy,
+    service: &str,
+    mut build: F,
+) -> Result<Response, RetryError>
+where
+    F: FnMut() -> RequestBuilder,
+{
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        let exhausted = attempt >= max_attempts;
+
+        let (client, request) = build().build_split();
+        let request = match request {
+            Ok(request) => request,
+            Err(source) => {
+                return Err(RetryError::Transport {
+                    service: service.to_string(),
+                    attempts: attempt,
+                    source,
+                });
+            }
+        };
+        let idempotent = is_idempotent(request.method());
+
+        let response = match send_once(policy, client, request).await {
+            Ok(response) => response,
+            Err(err) if is_transient(&err) && (idempotent || err.is_connect()) && !exhausted => {
+                let wait = policy.backoff(attempt);
+                warn!(
+                    "{service} request failed ({err}); retrying in {:.1}s.",
+                    wait.as_secs_f32()
+                );
+                tokio::time::sleep(wait).await;
+                continue;
+            }
+            Err(source) => {
+                return Err(RetryError::Transport {
+                    service: service.to_string(),
+                    attempts: attempt,
+                    source,
+                });
+            }
+        };
+
+        let status = response.status();
+        debug!(%service, %status, attempt, "HTTP response received");
+        if !is_retryable_status(status) || !(idempotent || status == StatusCode::TOO_MANY_REQUESTS)
+        {
+            return Ok(response);
+        }
+
+        if exhausted {
+            let body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "<no body>".to_string());
+            return Err(if status == StatusCode::TOO_MANY_REQUESTS {
+                RetryError::RateLimited {
+                    service: service.to_string(),
+                    attempts: attempt,
+                    body,
+                }
+            } else {
+                RetryError::ServerError {
+                    service: service.to_string(),
+                    status,
+                    attempts: attempt,
+                    body,
+                }
+            });
+        }
+
+        let wait = retry_after(&response)
+            .map(|requested| requested.max(policy.backoff(attempt)))
+            .unwrap_or_else(|| policy.backoff(attempt))
+            .min(MAX_RETRY_WAIT);
+        warn!(
+            "{service} responded with {status}; retrying in {:.1}s (attempt {}/{max_attempts}).",
+            wait.as_secs_f32(),
+            attempt + 1
+        );
+        tokio::time::sleep(wait).await;
+    }
+}
+
+async fn send_once(
+    policy: &RetryPolicy,
+    client: Client,
+    mut request: Request,
+) -> reqwest::Result<Response> {
+    // Wait for the slot before signing, so the signed timestamp is the send time.
+    let _permit = match &policy.limiter {
+        Some(limiter) => Some(
+            limiter
+                .acquire(request.url().host_str().unwrap_or_default())
+                .await,
+        ),
+        None => None,
+    };
+    if let Some(gateway) = &policy.gateway {
+        gateway.sign(&mut request);
+    }
+    client.execute(request).await
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || matches!(status.as_u16(), 500 | 502 | 503 | 504)
+}
+
+/// Methods a server must treat the same however often they arrive.
+fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+    )
+}
+
+fn is_transient(err: &reqwest::Error) -> bool {
+    err.is_timeout() || err.is_connect() || err.is_request()
+}
+
+fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_retry_after)
+}
+
+/// Parses the delta-seconds form of `Retry-After`; HTTP dates fall back to backoff.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    value.trim().parse::<u64>().ok().map(Duration::from_secs)
+}
+
+/// Whether lower-cased `host` is `pattern`, or lies under it when `pattern` starts with a dot
+/// (`.corp.example` covers `corp.example` and every subdomain).
+pub(crate) fn host_matches(pattern: &str, host: &str) -> bool {
+    match pattern.strip_prefix('.') {
+        Some(domain) => host == domain || host.ends_with(pattern),
+        None => host == pattern,
+    }
+}
+
+/// Adds the configured gateway headers to requests for the gateway's hosts. Each attempt
+/// gets a new request ID and timestamp, so retries are signed afresh.
+pub struct GatewaySigner {
+    hosts: Vec<String>,
+    headers: Vec<(HeaderName, HeaderValue)>,
+    request_id_header: Option<HeaderName>,
+    /// Signature and timestamp headers with the HMAC key.
+    signature: Option<(HeaderName, HeaderName, hmac::Key)>,
+    random: SystemRandom,
+}
+
+impl std::fmt::Debug for GatewaySigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GatewaySigner")
+            .field("hosts", &self.hosts)
+            .field("headers", &self.headers.len())
+            .field("request_id_header", &self.request_id_header)
+            .field("signed", &self.signature.is_some())
+            .finish()
+    }
+}
+
+impl GatewaySigner {
+    /// `None` when no gateway headers are configured.
+    pub fn new(settings: &GatewaySettings) -> AppResult<Option<Self>> {
+        if settings.is_empty() {
+            return Ok(None);
+        }
+        let hosts: Vec<String> = settings
+            .hosts
+            .iter()
+            .map(|host| host.trim().to_lowercase())
+            .filter(|host| !host.is_empty())
+            .collect();
+        // Headers and the signature would otherwise reach webhooks and model providers too.
+        if hosts.is_empty() {
+            return Err(AppError::Configuration(
+                "gateway.hosts must list the hosts that get the gateway headers".to_string(),
+            ));
+        }
+        let header_name = |name: &str| {
+            HeaderName::from_bytes(name.trim().as_bytes()).map_err(|err| {
+                AppError::Configuration(format!("invalid gateway header name '{name}': {err}"))
+            })
+        };
+        let mut headers = Vec::new();
+        for (name, value) in &settings.headers {
+            let value = HeaderValue::from_str(value).map_err(|err| {
+                AppError::Configuration(format!("invalid value for gateway header {name}: {err}"))
+            })?;
+            headers.push((header_name(name)?, value));
+        }
+        let signature = match (&settings.signature_header, &settings.signing_secret) {
+            (None, _) => None,
+            (Some(name), Some(secret)) => Some((
+                header_name(name)?,
+                header_name(
+                    settings
+                        .timestamp_header
+                        .as_deref()
+                        .unwrap_or("X-Timestamp"),
+                )?,
+                hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes()),
+            )),
+            (Some(_), None) => {
+                return Err(AppError::Configuration(
+                    "gateway.signature_header is set but no signing secret is configured \
+                     (gateway.signing_secret or UGH_GATEWAY_SIGNING_SECRET)"
+                        .to_string(),
+                ));
+            }
+        };
+        Ok(Some(Self {
+            hosts,
+            headers,
+            request_id_header: settings
+                .request_id_header
+                .as_deref()
+                .map(header_name)
+                .transpose()?,
+            signature,
+            random: SystemRandom::new(),
+        }))
+    }
+
+    fn applies_to(&self, host: &str) -> bool {
+        let host = host.to_lowercase();
+        self.hosts
+            .iter()
+            .any(|pattern| host_matches(pattern, &host))
+    }
+
+    fn sign(&self, request: &mut Request) {
+        if !request
+            .url()
+            .host_str()
+            .is_some_and(|host| self.applies_to(host))
+        {
+            return;
+        }
+        let headers = request.headers_mut();
+        for (name, value) in &self.headers {
+            headers.insert(name.clone(), value.clone());
+        }
+        if let Some(name) = &self.request_id_header
+            && let Ok(value) = HeaderValue::from_str(&self.request_id())
+        {
+            headers.insert(name.clone(), value);
+        }
+        if let Some((signature_header, timestamp_header, key)) = &self.signature {
+            let timestamp = Utc::now().timestamp();
+            let signature = hmac::sign(key, signing_string(request, timestamp).as_bytes());
+            let headers = request.headers_mut();
+            headers.insert(timestamp_header.clone(), HeaderValue::from(timestamp));
+            if let Ok(value) = HeaderValue::from_str(&hex(signature.as_ref())) {
+                headers.insert(signature_header.clone(), value);
+            }
+        }
+    }
+
+    /// A random (version 4) UUID.
+    fn request_id(&self) -> String {
+        let mut bytes = [0u8; 16];
+        if self.random.fill(&mut bytes).is_err() {
+            // Unique enough for tracing requests when the OS has no entropy to give.
+            let nanos = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos();
+            bytes = nanos.to_le_bytes();
+        }
+        bytes[6] = (bytes[6] & 0x0f) | 0x40;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+        let hex = hex(&bytes);
+        format!(
+            "{}-{}-{}-{}-{}",
+            &hex[..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..]
+        )
+    }
+}
+
+/// `METHOD\npath?query\ntimestamp\nhex(SHA-256(body))`; streamed bodies such as file
+/// uploads are hashed as empty.
+fn signing_string(request: &Request, timestamp: i64) -> String {
+    let url = request.url();
+    let target = match url.query() {
+        Some(query) => format!("{}?{query}", url.path()),
+        None => url.path().to_string(),
+    };
+    let body = request
+        .body()
+        .and_then(|body| body.as_bytes())
+        .unwrap_or_default();
+    let body_hash = digest::digest(&digest::SHA256, body);
+    format!(
+        "{}\n{target}\n{timestamp}\n{}",
+        request.method(),
+        hex(body_hash.as_ref())
+    )
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// A response body, possibly served from the response cache after a `304 Not Modified`.
+pub struct TextResponse {
+    pub status: StatusCode,
+    pub body: String,
+}
+
+/// Sends the GET built by `build` like [`send_with_retry`], revalidating against `cache`.
+///
+/// A body stored earlier for the same URL and `scope` is offered through `If-None-Match`;
+/// on `304 Not Modified` it is returned as a `200`. Successful responses carrying an
+/// `ETag` are stored. `scope` separates callers that see different data at the same URL,
+/// such as two accounts; only its hash is kept.
+pub async fn send_get_cached<F>(
+    cache: Option<&ResponseCache>,
+    policy: &RetryPolicy,
+    service: &str,
+    url: &str,
+    scope: &str,
+    mut build: F,
+) -> Result<TextResponse, RetryError>
+where
+    F: FnMut() -> RequestBuilder,
+{
+    let key = ResponseCache::key(url, scope);
+    let cached = cache.and_then(|cache| cache.lookup(&key));
+    let response = send_with_retry(policy, service, || match &cached {
+        Some(entry) => build().header(IF_NONE_MATCH, entry.etag.as_str()),
+        None => build(),
+    })
+    .await?;
+
+    let status = response.status();
+    if status == StatusCode::NOT_MODIFIED
+        && let Some(entry) = cached
+    {
+        debug!(%service, %url, "served from the response cache");
+        return Ok(TextResponse {
+            status: StatusCode::OK,
+            body: entry.body,
+        });
+    }
+
+    let etag = response
+        .headers()
+        .get(ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let body = response
+        .text()
+        .await
+        .map_err(|source| RetryError::Transport {
+            service: service.to_string(),
+            attempts: 1,
+            source,
+        })?;
+    if status.is_success()
+        && let (Some(cache), Some(etag)) = (cache, etag)
+        && body.len() <= MAX_CACHED_BODY_BYTES
+    {
+        cache.store(key, etag, &body);
+    }
+    Ok(TextResponse { status, body })
+}
+
+/// GET responses with their `ETag`, persisted in `http_cache.json` next to the config so
+/// later runs can revalidate instead of refetching. Loaded on first use.
+#[derive(Debug)]
+pub struct ResponseCache {
+    file_path: PathBuf,
+    entries: Mutex<Option<BTreeMap<String, CachedResponse>>>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct ResponseCacheFile {
+    #[serde(default)]
+    entries: BTreeMap<String, CachedResponse>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedResponse {
+    etag: String,
+    body: String,
+    stored_at: DateTime<Utc>,
+}
+
+impl ResponseCache {
+    pub fn open() -> AppResult<Self> {
+        Ok(Self {
+            file_path: config_directory()?.join(RESPONSE_CACHE_FILE_NAME),
+            entries: Mutex::new(None),
+        })
+    }
+
+    fn key(url: &str, scope: &str) -> String {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(scope.as_bytes());
+        hasher.update(b"\n");
+        hasher.update(url.as_bytes());
+        hasher.finalize().to_hex()[..32].to_string()
+    }
+
+    fn lookup(&self, key: &str) -> Option<CachedResponse> {
+        let mut entries = self.entries.lock().ok()?;
+        let entries = entries.get_or_insert_with(|| {
+            read_json_file::<ResponseCacheFile>(&self.file_path, "HTTP cache")
+                .map(|file| file.entries)
+                .unwrap_or_else(|err| {
+                    warn!("could not load the HTTP response cache ({err}); starting empty.");
+                    BTreeMap::new()
+                })
+        });
+        entries.get(key).cloned()
+    }
+
+    fn store(&self, key: String, etag: String, body: &str) {
+        let entry = CachedResponse {
+            etag,
+            body: body.to_string(),
+            stored_at: Utc::now(),
+        };
+        if let Ok(mut entries) = self.entries.lock()
+            && let Some(entries) = entries.as_mut()
+        {
+            entries.insert(key.clone(), entry.clone());
+        }
+        let saved = update_json_file(
+            &self.file_path,
+            "HTTP cache",
+            |file: &mut ResponseCacheFile| {
+                file.entries.insert(key, entry);
+                evict_oldest(&mut file.entries, MAX_CACHED_RESPONSES);
+            },
+        );
+        if let Err(err) = saved {
+            warn!("failed to persist the HTTP response cache ({err}).");
+        }
+    }
+}
+
+fn evict_oldest(entries: &mut BTreeMap<String, CachedResponse>, max: usize) {
+    while entries.len() > max {
+        let Some(oldest) = entries
+            .iter()
+            .min_by_key(|(_, entry)| entry.stored_at)
+            .map(|(key, _)| key.clone())
+        else {
+            break;
+        };
+        entries.remove(&oldest);
+    }
+}
+
+/// Builds `reqwest` clients that honor the configured proxy and custom CA.
+#[derive(Debug, Clone)]
+pub struct HttpClientFactory {
+    settings: HttpSettings,
+    response_cache: Option<Arc<ResponseCache>>,
+}
+
+impl HttpClientFactory {
+    /// Fails when the gateway or rate limit settings are invalid.
+    pub fn new(mut settings: HttpSettings) -> AppResult<Self> {
+        settings.retry.gateway = GatewaySigner::new(&settings.gateway)?.map(Arc::new);
+        settings.rate_limit.validate()?;
+        settings.retry.limiter = RateLimiter::new(&settings.rate_limit).map(Arc::new);
+        let response_cache = if settings.response_cache {
+            match ResponseCache::open() {
+                Ok(cache) => Some(Arc::new(cache)),
+                Err(err) => {
+                    warn!("HTTP response cache unavailable ({err}).");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        Ok(Self {
+            settings,
+            response_cache,
+        })
+    }
+
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.settings.retry.clone()
+    }
+
+    /// Shared by every client from this factory; `None` when disabled.
+    pub fn response_cache(&self) -> Option<Arc<ResponseCache>> {
+        self.response_cache.clone()
+    }
+
+    pub fn build(&self, timeout: Option<Duration>) -> AppResult<Client> {
+        let mut builder = Client::builder();
+
+        if let Some(timeout) = timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        if let Some(proxy_url) = self.settings.proxy.as_deref().map(str::trim)
+            && !proxy_url.is_empty()
+        {
+            let proxy = Proxy::all(proxy_url).map_err(|err| {
+                AppError::Configuration(format!("invalid HTTP proxy '{proxy_url}': {err}"))
+            })?;
+            builder = builder.proxy(proxy);
+        }
+
+        if let Some(path) = &self.settings.ca_bundle {
+            let pem = fs::read(path).map_err(|err| {
+                AppError::Configuration(format!(
+                    "failed to read CA bundle {}: {err}",
+                    path.display()
+                ))
+            })?;
+            let certificates = Certificate::from_pem_bundle(&pem).map_err(|err| {
+                AppError::Configuration(format!("invalid CA bundle {}: {err}", path.display()))
+            })?;
+            if certificates.is_empty() {
+                return Err(AppError::Configuration(format!(
+                    "CA bundle {} contains no certificates",
+                    path.display()
+                )));
+            }
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+
+        builder
+            .build()
+            .map_err(|err| AppError::Configuration(format!("failed to build HTTP client: {err}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn posts_are_not_retried_after_a_server_error() {
+        use std::io::{Read, Write};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/issue", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+        let served = Arc::clone(&requests);
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                served.fetch_add(1, Ordering::SeqCst);
+                let _ = stream.read(&mut [0u8; 4096]);
+                let _ = stream.write_all(
+                    b"HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\n\
+                      Connection: close\r\n\r\n",
+                );
+            }
+        });
+        let policy = RetryPolicy {
+            base_delay: Duration::from_millis(1),
+            ..RetryPolicy::default()
+        };
+        let client = Client::new();
+
+        let response = send_with_retry(&policy, "Jira", || client.post(&url).body("{}"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        let result = send_with_retry(&policy, "Jira", || client.get(&url)).await;
+        assert!(matches!(
+            result,
+            Err(RetryError::ServerError { attempts: 3, .. })
+        ));
+        assert_eq!(requests.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn parses_retry_after_seconds() {
+        assert_eq!(parse_retry_after(" 7 "), Some(Duration::from_secs(7)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+    }
+
+    #[test]
+    fn backoff_grows_and_stays_capped() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(400),
+            max_delay: Duration::from_secs(1),
+            gateway: None,
+            limiter: None,
+        };
+        let first = policy.backoff(1);
+        assert!(first >= Duration::from_millis(200) && first <= Duration::from_millis(400));
+        let late = policy.backoff(6);
+        assert!(late >= Duration::from_millis(500) && late <= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn gateway_signs_matching_hosts_only() {
+        let settings = GatewaySettings {
+            hosts: vec![".corp.example".to_string()],
+            headers: BTreeMap::from([("X-Team".to_string(), "payments".to_string())]),
+            request_id_header: Some("X-Request-Id".to_string()),
+            signature_header: Some("X-Signature".to_string()),
+            timestamp_header: None,
+            signing_secret: Some("s3cret".to_string()),
+        };
+        let signer = GatewaySigner::new(&settings).unwrap().unwrap();
+        let client = Client::new();
+
+        let mut request = client
+            .post("https://jira.corp.example/rest/api/3/issue?expand=names")
+            .body("{}")
+            .build()
+            .unwrap();
+        signer.sign(&mut request);
+        let headers = request.headers();
+        assert_eq!(headers["X-Team"], "payments");
+        assert_eq!(headers["X-Request-Id"].len(), 36);
+        let timestamp: i64 = headers["X-Timestamp"].to_str().unwrap().parse().unwrap();
+        let expected = hmac::sign(
+            &hmac::Key::new(hmac::HMAC_SHA256, b"s3cret"),
+            signing_string(&request, timestamp).as_bytes(),
+        );
+        assert_eq!(headers["X-Signature"], hex(expected.as_ref()).as_str());
+        assert!(
+            signing_string(&request, timestamp)
+                .starts_with("POST\n/rest/api/3/issue?expand=names\n")
+        );
+
+        let mut other = client.get("https://hooks.slack.com/x").build().unwrap();
+        signer.sign(&mut other);
+        assert!(other.headers().is_empty());
+
+        let unscoped = GatewaySettings {
+            hosts: Vec::new(),
+            ..settings.clone()
+        };
+        assert!(GatewaySigner::new(&unscoped).is_err());
+        let unsigned = GatewaySettings {
+            signing_secret: None,
+            ..settings
+        };
+        assert!(GatewaySigner::new(&unsigned).is_err());
+        assert!(
+            GatewaySigner::new(&GatewaySettings::default())
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn response_cache_keys_are_scoped_and_evict_oldest() {
+        let url = "https://acme.atlassian.net/rest/api/3/project/search";
+        assert_ne!(
+            ResponseCache::key(url, "Basic a"),
+            ResponseCache::key(url, "Basic b")
+        );
+
+        let entry = |minute: i64| CachedResponse {
+            etag: format!("\"{minute}\""),
+            body: "{}".to_string(),
+            stored_at: DateTime::from_timestamp(minute * 60, 0).unwrap(),
+        };
+        let mut entries = BTreeMap::from([
+            ("b".to_string(), entry(1)),
+            ("a".to_string(), entry(2)),
+            ("c".to_string(), entry(3)),
+        ]);
+        evict_oldest(&mut entries, 2);
+        assert_eq!(entries.keys().collect::<Vec<_>>(), ["a", "c"]);
+    }
+}
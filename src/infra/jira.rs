@@ -1,53 +1,114 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use base64::prelude::{BASE64_STANDARD, Engine as _};
+use chrono::{DateTime, Local, TimeDelta, Utc};
+use reqwest::multipart::{Form, Part};
 use reqwest::{
-    Client,
+    Client, Method, StatusCode, Url,
     header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE},
 };
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{instrument, warn};
 
-use crate::domain::ticket::{Ticket, TicketDraft};
+use crate::config::{JiraFields, ServiceDeskBoard};
+use crate::domain::board::Board;
+use crate::domain::retro::{CompletedTicket, RetroScope};
+use crate::domain::sprint::{Placement, Sprint};
+use crate::domain::ticket::{
+    TextLimits, Ticket, TicketDetails, TicketDraft, TicketOptions, TicketRemoval,
+};
+use crate::domain::user::TrackerUser;
 use crate::error::{AppError, AppResult};
+use crate::infra::atlassian_oauth::OAuthSession;
+use crate::infra::http::{
+    HttpClientFactory, ResponseCache, RetryPolicy, send_get_cached, send_with_retry,
+};
+use crate::infra::template::render_template;
 use crate::services::IssueTrackerService;
 
+/// How requests to Jira are authenticated.
+pub enum JiraAuth {
+    /// Email + API token basic auth against the site URL.
+    ApiToken {
+        email: Option<String>,
+        token: Option<String>,
+    },
+    /// OAuth 2.0 (3LO) bearer tokens through the Atlassian API gateway.
+    OAuth(Box<OAuthSession>),
+    /// OAuth is configured but `ugh config login jira` has not been run.
+    OAuthLoggedOut,
+}
+
 pub struct JiraClient {
     http: Client,
+    retry: RetryPolicy,
+    response_cache: Option<Arc<ResponseCache>>,
     base_url: Option<String>,
-    email: Option<String>,
-    token: Option<String>,
+    auth: JiraAuth,
     issue_type: String,
+    service_desks: BTreeMap<String, ServiceDeskBoard>,
+    fields: JiraFields,
 }
 
 impl JiraClient {
     pub fn new(
+        http: &HttpClientFactory,
         base_url: Option<String>,
-        email: Option<String>,
-        token: Option<String>,
+        auth: JiraAuth,
         issue_type: String,
-    ) -> Self {
-        Self {
-            http: Client::new(),
+        service_desks: BTreeMap<String, ServiceDeskBoard>,
+        fields: JiraFields,
+    ) -> AppResult<Self> {
+        Ok(Self {
+            http: http.build(None)?,
+            retry: http.retry_policy(),
+            response_cache: http.response_cache(),
             base_url,
-            email,
-            token,
+            auth,
             issue_type,
+            service_desks,
+            fields,
+        })
+    }
+
+    /// Root that REST paths are appended to.
+    fn api_base(&self) -> AppResult<&str> {
+        match &self.auth {
+            JiraAuth::OAuth(session) => Ok(session.api_base()),
+            _ => self.site_url(),
         }
     }
 
-    fn api_details(&self) -> AppResult<(&str, &str, &str)> {
-        let base_url = self
-            .base_url
-            .as_deref()
-            .ok_or_else(|| AppError::Configuration("Jira base URL not configured".to_string()))?;
-        let email = self
-            .email
-            .as_deref()
-            .ok_or_else(|| AppError::Configuration("Jira email not configured".to_string()))?;
-        let token = self
-            .token
-            .as_deref()
-            .ok_or_else(|| AppError::Configuration("Jira API token not configured".to_string()))?;
-        Ok((base_url, email, token))
+    /// The site users browse to, for ticket links.
+    fn site_url(&self) -> AppResult<&str> {
+        match &self.auth {
+            JiraAuth::OAuth(session) => Ok(session.site_url()),
+            _ => self
+                .base_url
+                .as_deref()
+                .ok_or_else(|| AppError::Configuration("Jira base URL not configured".to_string())),
+        }
+    }
+
+    async fn authorization(&self) -> AppResult<String> {
+        match &self.auth {
+            JiraAuth::ApiToken { email, token } => {
+                let email = email.as_deref().ok_or_else(|| {
+                    AppError::Configuration("Jira email not configured".to_string())
+                })?;
+                let token = token.as_deref().ok_or_else(|| {
+                    AppError::Configuration("Jira API token not configured".to_string())
+                })?;
+                Ok(Self::auth_header(email, token))
+            }
+            JiraAuth::OAuth(session) => session.authorization().await,
+            JiraAuth::OAuthLoggedOut => Err(AppError::Configuration(
+                "Jira OAuth is enabled but not logged in; run `ugh config login jira`".to_string(),
+            )),
+        }
     }
 
     fn auth_header(email: &str, token: &str) -> String {
@@ -57,20 +118,393 @@ impl JiraClient {
     }
 
     fn issue_endpoint(base_url: &str) -> String {
-        format!(
-            "{}/rest/api/3/issue",
-            base_url.trim_end_matches('/').to_string()
-        )
+        format!("{}/rest/api/3/issue", base_url.trim_end_matches('/'))
+    }
+
+    fn issue_url(base_url: &str, key: &str) -> String {
+        format!("{}/{key}", Self::issue_endpoint(base_url))
     }
 
     fn browse_url(base_url: &str, key: &str) -> String {
         format!("{}/browse/{}", base_url.trim_end_matches('/'), key)
     }
+
+    /// Checks the configured credentials and returns the authenticated user's display name.
+    pub async fn verify_credentials(&self) -> AppResult<String> {
+        let base_url = self.api_base()?;
+        let url = format!("{}/rest/api/3/myself", base_url.trim_end_matches('/'));
+        let myself: JiraUser = self.get_json(&url).await?;
+        Ok(myself.display_name)
+    }
+
+    /// Configured custom field values with per-run overrides applied and string
+    /// placeholders filled in from the draft.
+    fn custom_field_values(
+        &self,
+        board: &str,
+        draft: &TicketDraft,
+        options: &TicketOptions,
+    ) -> BTreeMap<String, Value> {
+        let placeholders = [
+            ("board", board.to_string()),
+            ("title", draft.title.trim().to_string()),
+            ("category", draft.branch_category.as_str().to_string()),
+            (
+                "date",
+                Local::now().date_naive().format("%Y-%m-%d").to_string(),
+            ),
+        ];
+        self.fields
+            .values
+            .iter()
+            .chain(&options.custom_fields)
+            .map(|(field, value)| (field.clone(), fill_placeholders(value, &placeholders)))
+            .collect()
+    }
+
+    /// Looks up the epic custom fields for company-managed projects. Team-managed
+    /// ("next-gen") projects return `None`; they use the standard `parent` field.
+    async fn classic_epic_fields(
+        &self,
+        base_url: &str,
+        project_key: &str,
+    ) -> AppResult<Option<ClassicEpicFields>> {
+        let base = base_url.trim_end_matches('/');
+        let project: JiraProjectDetails = self
+            .get_json(&format!("{base}/rest/api/3/project/{project_key}"))
+            .await?;
+        if project.style.as_deref() == Some("next-gen") {
+            return Ok(None);
+        }
+
+        let fields: Vec<JiraFieldSummary> =
+            self.get_json(&format!("{base}/rest/api/3/field")).await?;
+        let find = |custom_type: &str| {
+            fields
+                .iter()
+                .find(|field| {
+                    field
+                        .schema
+                        .as_ref()
+                        .and_then(|schema| schema.custom.as_deref())
+                        == Some(custom_type)
+                })
+                .map(|field| field.id.clone())
+        };
+        Ok(Some(ClassicEpicFields {
+            name: find(EPIC_NAME_FIELD_TYPE),
+            link: find(EPIC_LINK_FIELD_TYPE),
+        }))
+    }
+
+    async fn create_issue(&self, request: &JiraCreateIssueRequest) -> AppResult<Ticket> {
+        let base_url = self.api_base()?;
+        let endpoint = Self::issue_endpoint(base_url);
+        let payload: JiraCreateIssueResponse = self.post_json(&endpoint, request).await?;
+
+        let url = Self::browse_url(self.site_url()?, &payload.key);
+        Ok(Ticket {
+            key: payload.key,
+            url: Some(url),
+        })
+    }
+
+    /// Moves an issue into a sprint or back to the backlog through the Agile API.
+    async fn place_issue(&self, base_url: &str, key: &str, placement: Placement) -> AppResult<()> {
+        let base = base_url.trim_end_matches('/');
+        let url = match placement {
+            Placement::Backlog => format!("{base}/rest/agile/1.0/backlog/issue"),
+            Placement::Sprint(id) => format!("{base}/rest/agile/1.0/sprint/{id}/issue"),
+        };
+        let body = serde_json::json!({ "issues": [key] });
+        let response = self.send_raw(Method::POST, &url, Some(&body)).await?;
+        Self::expect_success(response).await
+    }
+
+    fn service_desk_endpoint(base_url: &str, path: &str) -> String {
+        format!(
+            "{}/rest/servicedeskapi/{}",
+            base_url.trim_end_matches('/'),
+            path.trim_start_matches('/')
+        )
+    }
+
+    #[instrument(level = "debug", name = "jira.get", skip_all, fields(url = %url))]
+    async fn get_json<T: for<'de> Deserialize<'de>>(&self, url: &str) -> AppResult<T> {
+        let authorization = self.authorization().await?;
+        // Scoped by credentials: two accounts may see different projects at the same URL.
+        let response = send_get_cached(
+            self.response_cache.as_deref(),
+            &self.retry,
+            "Jira",
+            url,
+            &authorization,
+            || {
+                self.http
+                    .get(url)
+                    .header(AUTHORIZATION, authorization.as_str())
+                    .header(ACCEPT, "application/json")
+            },
+        )
+        .await
+        .map_err(|err| AppError::IssueTracker(err.to_string()))?;
+
+        if !response.status.is_success() {
+            return Err(AppError::IssueTracker(format!(
+                "Jira responded with {}: {}",
+                response.status, response.body
+            )));
+        }
+        serde_json::from_str(&response.body)
+            .map_err(|err| AppError::IssueTracker(format!("failed to parse Jira response: {err}")))
+    }
+
+    #[instrument(level = "debug", name = "jira.post", skip_all, fields(url = %url))]
+    async fn post_json<B: Serialize, T: for<'de> Deserialize<'de>>(
+        &self,
+        url: &str,
+        body: &B,
+    ) -> AppResult<T> {
+        let authorization = self.authorization().await?;
+        let response = send_with_retry(&self.retry, "Jira", || {
+            self.http
+                .post(url)
+                .header(AUTHORIZATION, authorization.as_str())
+                .header(ACCEPT, "application/json")
+                .header(CONTENT_TYPE, "application/json")
+                .json(body)
+        })
+        .await
+        .map_err(|err| AppError::IssueTracker(err.to_string()))?;
+        Self::parse_response(response).await
+    }
+
+    /// Sends a request whose success response carries no body (deletes, transitions) and
+    /// returns the raw response so callers can branch on the status.
+    async fn send_raw(
+        &self,
+        method: Method,
+        url: &str,
+        body: Option<&Value>,
+    ) -> AppResult<reqwest::Response> {
+        let authorization = self.authorization().await?;
+        send_with_retry(&self.retry, "Jira", || {
+            let request = self
+                .http
+                .request(method.clone(), url)
+                .header(AUTHORIZATION, authorization.as_str())
+                .header(ACCEPT, "application/json");
+            match body {
+                Some(body) => request.json(body),
+                None => request,
+            }
+        })
+        .await
+        .map_err(|err| AppError::IssueTracker(err.to_string()))
+    }
+
+    async fn close_issue(&self, base_url: &str, key: &str) -> AppResult<String> {
+        let url = format!("{}/transitions", Self::issue_url(base_url, key));
+        let available: JiraTransitions = self.get_json(&url).await?;
+        let transition = available
+            .transitions
+            .into_iter()
+            .find(|transition| transition.to.status_category.key == "done")
+            .ok_or_else(|| {
+                AppError::IssueTracker(format!(
+                    "{key} cannot be deleted and has no transition to a done status"
+                ))
+            })?;
+
+        let body = serde_json::json!({ "transition": { "id": transition.id } });
+        let response = self.send_raw(Method::POST, &url, Some(&body)).await?;
+        Self::expect_success(response).await?;
+        Ok(transition.to.name)
+    }
+
+    async fn expect_success(response: reqwest::Response) -> AppResult<()> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "<unable to read response>".to_string());
+        Err(AppError::IssueTracker(format!(
+            "Jira responded with {status}: {body}"
+        )))
+    }
+
+    async fn parse_response<T: for<'de> Deserialize<'de>>(
+        response: reqwest::Response,
+    ) -> AppResult<T> {
+        let status = response.status();
+        if !status.is_success() {
+            let body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "<unable to read response>".to_string());
+            return Err(AppError::IssueTracker(format!(
+                "Jira responded with {status}: {body}"
+            )));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|err| AppError::IssueTracker(format!("failed to parse Jira response: {err}")))
+    }
+
+    async fn resolve_service_desk_id(
+        &self,
+        base_url: &str,
+        board_key: &str,
+        desk: &ServiceDeskBoard,
+    ) -> AppResult<String> {
+        if let Some(id) = desk.service_desk_id.as_deref().map(str::trim)
+            && !id.is_empty()
+        {
+            return Ok(id.to_string());
+        }
+
+        let url = Self::service_desk_endpoint(base_url, "servicedesk?limit=100");
+        let page: ServiceDeskPage<ServiceDeskSummary> = self.get_json(&url).await?;
+        page.values
+            .into_iter()
+            .find(|summary| summary.project_key.eq_ignore_ascii_case(board_key))
+            .map(|summary| summary.id)
+            .ok_or_else(|| {
+                AppError::IssueTracker(format!("no service desk found for project {board_key}"))
+            })
+    }
+
+    async fn resolve_request_type_id(
+        &self,
+        base_url: &str,
+        service_desk_id: &str,
+        request_type: &str,
+    ) -> AppResult<String> {
+        let wanted = request_type.trim();
+        if !wanted.is_empty() && wanted.chars().all(|c| c.is_ascii_digit()) {
+            return Ok(wanted.to_string());
+        }
+
+        let url = Self::service_desk_endpoint(
+            base_url,
+            &format!("servicedesk/{service_desk_id}/requesttype?limit=100"),
+        );
+        let page: ServiceDeskPage<RequestTypeSummary> = self.get_json(&url).await?;
+        page.values
+            .into_iter()
+            .find(|candidate| candidate.name.eq_ignore_ascii_case(wanted))
+            .map(|candidate| candidate.id)
+            .ok_or_else(|| {
+                AppError::IssueTracker(format!(
+                    "request type '{wanted}' not found in service desk {service_desk_id}"
+                ))
+            })
+    }
+
+    async fn create_service_request(
+        &self,
+        board_key: &str,
+        desk: &ServiceDeskBoard,
+        draft: &TicketDraft,
+        options: &TicketOptions,
+    ) -> AppResult<Ticket> {
+        let base_url = self.api_base()?;
+        let service_desk_id = self
+            .resolve_service_desk_id(base_url, board_key, desk)
+            .await?;
+        let request_type_id = self
+            .resolve_request_type_id(base_url, &service_desk_id, &desk.request_type)
+            .await?;
+
+        let description = draft.description.trim();
+        let visible_description = if desk.customer_visible {
+            JiraDescription::from_markdown(description)
+                .with_acceptance_criteria(&draft.acceptance_criteria)
+        } else {
+            JiraDescription::from_markdown(INTERNAL_REQUEST_PLACEHOLDER)
+        };
+
+        let body = ServiceDeskCreateRequest {
+            service_desk_id,
+            request_type_id,
+            request_field_values: ServiceDeskFieldValues {
+                summary: draft.title.trim().to_string(),
+                description: visible_description,
+                custom_fields: self.custom_field_values(board_key, draft, options),
+            },
+            is_adf_request: true,
+            raise_on_behalf_of: options.reporter.clone(),
+        };
+        let url = Self::service_desk_endpoint(base_url, "request");
+        let created: ServiceDeskCreateResponse = self.post_json(&url, &body).await?;
+
+        if !desk.customer_visible {
+            let comment_url = Self::service_desk_endpoint(
+                base_url,
+                &format!("request/{}/comment", created.issue_key),
+            );
+            let mut body = description.to_string();
+            if !draft.acceptance_criteria.is_empty() {
+                body.push_str("\n\nAcceptance criteria:");
+                for criterion in &draft.acceptance_criteria {
+                    body.push_str(&format!("\n- {criterion}"));
+                }
+            }
+            let comment = ServiceDeskComment {
+                body,
+                public: false,
+            };
+            let _: serde_json::Value = self.post_json(&comment_url, &comment).await?;
+        }
+
+        let url = Self::browse_url(self.site_url()?, &created.issue_key);
+        Ok(Ticket {
+            key: created.issue_key,
+            url: Some(url),
+        })
+    }
 }
 
+const EPIC_ISSUE_TYPE: &str = "Epic";
+const EPIC_NAME_FIELD_TYPE: &str = "com.pyxis.greenhopper.jira:gh-epic-label";
+const EPIC_LINK_FIELD_TYPE: &str = "com.pyxis.greenhopper.jira:gh-epic-link";
+
+const INTERNAL_REQUEST_PLACEHOLDER: &str =
+    "Request raised by engineering. Implementation details are tracked internally.";
+
 #[async_trait]
 impl IssueTrackerService for JiraClient {
-    async fn create_ticket(&self, board: &str, draft: TicketDraft) -> AppResult<Ticket> {
+    /// Summaries are a single line of at most 255 characters. Rich-text fields hold
+    /// 32,767 characters, and the ADF markup around each paragraph counts against that,
+    /// so descriptions keep some headroom.
+    fn text_limits(&self) -> TextLimits {
+        TextLimits {
+            title_chars: 255,
+            description_chars: 30_000,
+        }
+    }
+
+    fn ticket_url(&self, key: &str) -> AppResult<String> {
+        Ok(Self::browse_url(self.site_url()?, key))
+    }
+
+    /// A project key under `/browse` opens the project's board.
+    fn board_url(&self, board: &str) -> AppResult<String> {
+        Ok(Self::browse_url(self.site_url()?, board))
+    }
+
+    #[instrument(level = "info", name = "jira.create_ticket", skip_all, fields(board = %board))]
+    async fn create_ticket(
+        &self,
+        board: &str,
+        draft: TicketDraft,
+        options: &TicketOptions,
+    ) -> AppResult<Ticket> {
         let board_key = board.trim();
         if board_key.is_empty() {
             return Err(AppError::IssueTracker(
@@ -88,47 +522,573 @@ impl IssueTrackerService for JiraClient {
             ));
         }
 
-        let (base_url, email, token) = self.api_details()?;
-        let request_body = JiraCreateIssueRequest::new(
+        if let Some(desk) = self.service_desks.get(board_key) {
+            if options.epic.is_some() {
+                warn!("epic links are not applied to service desk requests ({board_key}).");
+            }
+            if options.assignee.is_some() || options.due_date.is_some() {
+                warn!(
+                    "assignee and due date are not applied to service desk requests ({board_key})."
+                );
+            }
+            if options.placement.is_some() {
+                warn!(
+                    "sprint and backlog placement do not apply to service desk requests ({board_key})."
+                );
+            }
+            if options.issue_type.is_some()
+                || !options.labels.is_empty()
+                || !options.components.is_empty()
+                || options.priority.is_some()
+            {
+                warn!(
+                    "issue type, labels, components, and priority are not applied to service \
+                     desk requests ({board_key}); the request type decides them."
+                );
+            }
+            return self
+                .create_service_request(board_key, desk, &draft, options)
+                .await;
+        }
+
+        let base_url = self.api_base()?;
+        let issue_type = options.issue_type.as_deref().unwrap_or(&self.issue_type);
+        let mut request =
+            JiraCreateIssueRequest::new(board_key, issue_type, draft.title.trim(), None);
+        let description = JiraDescription::from_markdown(draft.description.trim());
+        request.fields.description = Some(match self.fields.acceptance_criteria.as_deref() {
+            Some(field) if !draft.acceptance_criteria.is_empty() => {
+                let criteria = JiraDescription::bullet_list(&draft.acceptance_criteria);
+                request.fields.custom_fields.insert(
+                    field.to_string(),
+                    serde_json::to_value(criteria).map_err(|err| {
+                        AppError::IssueTracker(format!(
+                            "failed to encode acceptance criteria: {err}"
+                        ))
+                    })?,
+                );
+                description
+            }
+            _ => description.with_acceptance_criteria(&draft.acceptance_criteria),
+        });
+
+        if let Some(epic) = options.epic.as_deref().map(str::trim)
+            && !epic.is_empty()
+        {
+            let epic_link = self
+                .classic_epic_fields(base_url, board_key)
+                .await?
+                .and_then(|fields| fields.link);
+            match epic_link {
+                Some(field) => {
+                    request
+                        .fields
+                        .custom_fields
+                        .insert(field, Value::String(epic.to_string()));
+                }
+                None => {
+                    request.fields.parent = Some(JiraIssueRef {
+                        key: epic.to_string(),
+                    });
+                }
+            }
+        }
+
+        if let (Some(field), Some(estimate)) = (self.fields.story_points.as_deref(), draft.estimate)
+        {
+            request
+                .fields
+                .custom_fields
+                .insert(field.to_string(), Value::from(estimate));
+        }
+
+        request
+            .fields
+            .custom_fields
+            .extend(self.custom_field_values(board_key, &draft, options));
+        request.fields.assignee = options.assignee.as_deref().map(JiraAccountRef::new);
+        request.fields.reporter = options.reporter.as_deref().map(JiraAccountRef::new);
+        request.fields.duedate = options
+            .due_date
+            .map(|date| date.format("%Y-%m-%d").to_string());
+        request.fields.labels = options.labels.clone();
+        request.fields.components = options
+            .components
+            .iter()
+            .map(|name| JiraNamed { name: name.clone() })
+            .collect();
+        request.fields.priority = options
+            .priority
+            .as_ref()
+            .map(|name| JiraNamed { name: name.clone() });
+
+        let ticket = self.create_issue(&request).await?;
+        if let Some(placement) = options.placement
+            && let Err(err) = self.place_issue(base_url, &ticket.key, placement).await
+        {
+            // The ticket exists either way; failing here would only orphan it.
+            warn!("created {} but could not move it ({err}).", ticket.key);
+        }
+        Ok(ticket)
+    }
+
+    #[instrument(level = "info", name = "jira.create_epic", skip_all, fields(board = %board))]
+    async fn create_epic(
+        &self,
+        board: &str,
+        name: &str,
+        description: Option<&str>,
+    ) -> AppResult<Ticket> {
+        let board_key = board.trim();
+        let name = name.trim();
+        if board_key.is_empty() {
+            return Err(AppError::IssueTracker(
+                "board key must not be empty".to_string(),
+            ));
+        }
+        if name.is_empty() {
+            return Err(AppError::IssueTracker(
+                "epic name must not be empty".to_string(),
+            ));
+        }
+
+        let base_url = self.api_base()?;
+        let mut request = JiraCreateIssueRequest::new(
             board_key,
-            &self.issue_type,
-            draft.title.trim(),
-            draft.description.trim(),
+            EPIC_ISSUE_TYPE,
+            name,
+            description.map(str::trim).filter(|text| !text.is_empty()),
         );
 
+        // Company-managed projects reject epics without the separate "Epic Name" field.
+        if let Some(field) = self
+            .classic_epic_fields(base_url, board_key)
+            .await?
+            .and_then(|fields| fields.name)
+        {
+            request
+                .fields
+                .custom_fields
+                .insert(field, Value::String(name.to_string()));
+        }
+
+        self.create_issue(&request).await
+    }
+
+    #[instrument(level = "debug", name = "jira.search_users", skip_all)]
+    async fn search_users(&self, query: &str) -> AppResult<Vec<TrackerUser>> {
+        let base_url = self.api_base()?;
+        let endpoint = format!("{}/rest/api/3/user/search", base_url.trim_end_matches('/'));
+        let url = Url::parse_with_params(&endpoint, &[("query", query), ("maxResults", "20")])
+            .map_err(|err| AppError::Configuration(format!("invalid Jira base URL: {err}")))?;
+
+        let users: Vec<JiraUser> = self.get_json(url.as_str()).await?;
+        Ok(users
+            .into_iter()
+            .filter(|user| user.active && user.account_type.as_deref() != Some("app"))
+            .map(|user| TrackerUser {
+                account_id: user.account_id,
+                display_name: user.display_name,
+                email: user.email_address.filter(|email| !email.is_empty()),
+            })
+            .collect())
+    }
+
+    #[instrument(level = "info", name = "jira.list_boards", skip_all)]
+    async fn list_boards(&self, query: Option<&str>) -> AppResult<Vec<Board>> {
+        let base_url = self.api_base()?;
+        let endpoint = format!(
+            "{}/rest/api/3/project/search",
+            base_url.trim_end_matches('/')
+        );
+
+        let mut boards = Vec::new();
+        let mut start_at = 0;
+        loop {
+            let start = start_at.to_string();
+            let mut params = vec![
+                ("expand", "issueTypes"),
+                ("maxResults", "50"),
+                ("startAt", start.as_str()),
+            ];
+            if let Some(query) = query {
+                params.push(("query", query));
+            }
+            let url = Url::parse_with_params(&endpoint, &params)
+                .map_err(|err| AppError::Configuration(format!("invalid Jira base URL: {err}")))?;
+
+            let page: JiraProjectPage = self.get_json(url.as_str()).await?;
+            let fetched = page.values.len();
+            boards.extend(page.values.into_iter().map(|project| {
+                Board {
+                    key: project.key,
+                    name: project.name,
+                    kind: project.project_type_key,
+                    team_managed: project.style.as_deref() == Some("next-gen"),
+                    issue_types: project
+                        .issue_types
+                        .into_iter()
+                        .filter(|issue_type| !issue_type.subtask)
+                        .map(|issue_type| issue_type.name)
+                        .collect(),
+                }
+            }));
+
+            start_at += fetched;
+            if page.is_last || fetched == 0 {
+                break;
+            }
+        }
+        Ok(boards)
+    }
+
+    #[instrument(level = "info", name = "jira.list_sprints", skip_all, fields(board = %board))]
+    async fn list_sprints(&self, board: &str, include_closed: bool) -> AppResult<Vec<Sprint>> {
+        let base = self.api_base()?.trim_end_matches('/').to_string();
+        let board_endpoint = format!("{base}/rest/agile/1.0/board");
+
+        let mut board_ids = Vec::new();
+        let mut start_at = 0;
+        loop {
+            let start = start_at.to_string();
+            let url = Url::parse_with_params(
+                &board_endpoint,
+                [
+                    ("projectKeyOrId", board.trim()),
+                    ("type", "scrum"),
+                    ("startAt", start.as_str()),
+                ],
+            )
+            .map_err(|err| AppError::Configuration(format!("invalid Jira base URL: {err}")))?;
+            let page: JiraAgilePage<JiraAgileBoard> = self.get_json(url.as_str()).await?;
+            let fetched = page.values.len();
+            board_ids.extend(page.values.into_iter().map(|agile_board| agile_board.id));
+            start_at += fetched;
+            if page.is_last || fetched == 0 {
+                break;
+            }
+        }
+
+        // Boards can share sprints, so collect them by ID.
+        let states = if include_closed {
+            "active,future,closed"
+        } else {
+            "active,future"
+        };
+        let mut sprints = BTreeMap::new();
+        for board_id in board_ids {
+            let mut start_at = 0;
+            loop {
+                let url =
+                    format!("{board_endpoint}/{board_id}/sprint?state={states}&startAt={start_at}");
+                let page: JiraAgilePage<JiraSprint> = self.get_json(&url).await?;
+                let fetched = page.values.len();
+                for sprint in page.values {
+                    let start = sprint.start_date.as_deref().and_then(parse_jira_time);
+                    let end = sprint
+                        .complete_date
+                        .or(sprint.end_date)
+                        .as_deref()
+                        .and_then(parse_jira_time);
+                    sprints.entry(sprint.id).or_insert(Sprint {
+                        id: sprint.id,
+                        name: sprint.name,
+                        state: sprint.state,
+                        start,
+                        end,
+                    });
+                }
+                start_at += fetched;
+                if page.is_last || fetched == 0 {
+                    break;
+                }
+            }
+        }
+        Ok(sprints.into_values().collect())
+    }
+
+    #[instrument(level = "info", name = "jira.completed_tickets", skip_all, fields(board = %board))]
+    async fn completed_tickets(
+        &self,
+        board: &str,
+        scope: &RetroScope,
+    ) -> AppResult<Vec<CompletedTicket>> {
+        let base_url = self.api_base()?;
+        let url = format!("{}/rest/api/3/search/jql", base_url.trim_end_matches('/'));
+        let jql = completed_jql(board, scope);
+
+        let mut tickets = Vec::new();
+        let mut next_page_token: Option<String> = None;
+        loop {
+            let body = serde_json::json!({
+                "jql": jql,
+                "fields": ["summary", "issuetype", "labels", "created", "resolutiondate",
+                           "statuscategorychangedate", "status"],
+                "expand": "changelog",
+                "maxResults": 100,
+                "nextPageToken": next_page_token,
+            });
+            let page: JiraSearchPage = self.post_json(&url, &body).await?;
+            tickets.extend(page.issues.into_iter().filter_map(completed_ticket));
+            match page.next_page_token {
+                Some(token) if !page.is_last => next_page_token = Some(token),
+                _ => break,
+            }
+        }
+        Ok(tickets)
+    }
+
+    #[instrument(level = "info", name = "jira.in_progress_tickets", skip_all)]
+    async fn in_progress_tickets(&self) -> AppResult<Vec<TicketDetails>> {
+        let base_url = self.api_base()?;
+        let url = format!("{}/rest/api/3/search/jql", base_url.trim_end_matches('/'));
+        let site_url = self.site_url()?;
+
+        let mut tickets = Vec::new();
+        let mut next_page_token: Option<String> = None;
+        loop {
+            let body = serde_json::json!({
+                "jql": "assignee = currentUser() AND statusCategory = \"In Progress\" ORDER BY updated DESC",
+                "fields": ["summary", "issuetype", "created", "status"],
+                "maxResults": 100,
+                "nextPageToken": next_page_token,
+            });
+            let page: JiraSearchPage = self.post_json(&url, &body).await?;
+            tickets.extend(page.issues.into_iter().map(|issue| TicketDetails {
+                url: Some(Self::browse_url(site_url, &issue.key)),
+                key: issue.key,
+                title: issue.fields.summary,
+                issue_type: issue.fields.issuetype.map(|named| named.name),
+                status: issue.fields.status.and_then(|status| status.name),
+                description: None,
+            }));
+            match page.next_page_token {
+                Some(token) if !page.is_last => next_page_token = Some(token),
+                _ => break,
+            }
+        }
+        Ok(tickets)
+    }
+
+    #[instrument(level = "info", name = "jira.get_ticket", skip_all, fields(key = %key))]
+    async fn get_ticket(&self, key: &str) -> AppResult<TicketDetails> {
+        let base_url = self.api_base()?;
+        let url = format!(
+            "{}?fields=summary,issuetype,status,description",
+            Self::issue_url(base_url, key)
+        );
+        let issue: JiraIssueDetails = self.get_json(&url).await?;
+        Ok(TicketDetails {
+            url: Some(Self::browse_url(self.site_url()?, &issue.key)),
+            key: issue.key,
+            title: issue.fields.summary,
+            issue_type: issue.fields.issuetype.map(|named| named.name),
+            status: issue.fields.status.map(|named| named.name),
+            description: issue.fields.description.as_ref().map(adf_to_markdown),
+        })
+    }
+
+    #[instrument(level = "info", name = "jira.update_ticket", skip_all, fields(key = %key))]
+    async fn update_ticket(
+        &self,
+        key: &str,
+        description: &str,
+        acceptance_criteria: &[String],
+    ) -> AppResult<()> {
+        let base_url = self.api_base()?;
+        let encode = |doc: JiraDescription| {
+            serde_json::to_value(doc).map_err(|err| {
+                AppError::IssueTracker(format!("failed to encode the description: {err}"))
+            })
+        };
+        let description = JiraDescription::from_markdown(description.trim());
+        let mut fields = serde_json::Map::new();
+        match self.fields.acceptance_criteria.as_deref() {
+            Some(field) if !acceptance_criteria.is_empty() => {
+                fields.insert(
+                    field.to_string(),
+                    encode(JiraDescription::bullet_list(acceptance_criteria))?,
+                );
+                fields.insert("description".to_string(), encode(description)?);
+            }
+            _ => {
+                fields.insert(
+                    "description".to_string(),
+                    encode(description.with_acceptance_criteria(acceptance_criteria))?,
+                );
+            }
+        }
+
+        let body = serde_json::json!({ "fields": fields });
         let response = self
-            .http
-            .post(Self::issue_endpoint(base_url))
-            .header(AUTHORIZATION, Self::auth_header(email, token))
-            .header(ACCEPT, "application/json")
-            .header(CONTENT_TYPE, "application/json")
-            .json(&request_body)
-            .send()
-            .await
-            .map_err(|err| AppError::IssueTracker(format!("failed to call Jira: {err}")))?;
+            .send_raw(Method::PUT, &Self::issue_url(base_url, key), Some(&body))
+            .await?;
+        Self::expect_success(response).await
+    }
 
-        let status = response.status();
-        if !status.is_success() {
-            let body = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "<unable to read response>".to_string());
-            return Err(AppError::IssueTracker(format!(
-                "Jira responded with {status}: {body}"
-            )));
+    #[instrument(level = "info", name = "jira.log_work", skip_all, fields(key = %key))]
+    async fn log_work(
+        &self,
+        key: &str,
+        time_spent: TimeDelta,
+        started: DateTime<Utc>,
+        comment: Option<&str>,
+    ) -> AppResult<()> {
+        let base_url = self.api_base()?;
+        let url = format!("{}/worklog", Self::issue_url(base_url, key));
+        let mut body = serde_json::json!({
+            "timeSpentSeconds": time_spent.num_seconds(),
+            // Jira rejects RFC 3339 offsets with a colon.
+            "started": started.format("%Y-%m-%dT%H:%M:%S%.3f%z").to_string(),
+        });
+        if let Some(comment) = comment.map(str::trim).filter(|comment| !comment.is_empty()) {
+            body["comment"] = serde_json::to_value(JiraDescription::from_markdown(comment))
+                .map_err(|err| {
+                    AppError::IssueTracker(format!("failed to encode the worklog comment: {err}"))
+                })?;
         }
+        let response = self.send_raw(Method::POST, &url, Some(&body)).await?;
+        Self::expect_success(response).await
+    }
 
-        let payload: JiraCreateIssueResponse = response.json().await.map_err(|err| {
-            AppError::IssueTracker(format!("failed to parse Jira response: {err}"))
-        })?;
+    #[instrument(level = "info", name = "jira.add_comment", skip_all, fields(key = %key))]
+    async fn add_comment(&self, key: &str, comment: &str) -> AppResult<()> {
+        let base_url = self.api_base()?;
+        let url = format!("{}/comment", Self::issue_url(base_url, key));
+        let body = serde_json::json!({ "body": JiraDescription::from_markdown(comment.trim()) });
+        let response = self.send_raw(Method::POST, &url, Some(&body)).await?;
+        Self::expect_success(response).await
+    }
 
-        let key = payload.key;
-        let url = Self::browse_url(base_url, &key);
+    #[instrument(level = "info", name = "jira.current_user", skip_all)]
+    async fn current_user(&self) -> AppResult<TrackerUser> {
+        let base_url = self.api_base()?;
+        let url = format!("{}/rest/api/3/myself", base_url.trim_end_matches('/'));
+        let myself: JiraUser = self.get_json(&url).await?;
+        Ok(TrackerUser {
+            account_id: myself.account_id,
+            display_name: myself.display_name,
+            email: myself.email_address.filter(|email| !email.is_empty()),
+        })
+    }
 
-        Ok(Ticket {
-            key,
-            url: Some(url),
+    #[instrument(level = "info", name = "jira.attach_file", skip_all, fields(key = %key))]
+    async fn attach_file(&self, key: &str, file_name: &str, contents: Vec<u8>) -> AppResult<()> {
+        let url = format!("{}/attachments", Self::issue_url(self.api_base()?, key));
+        let authorization = self.authorization().await?;
+        let response = send_with_retry(&self.retry, "Jira", || {
+            let part = Part::bytes(contents.clone()).file_name(file_name.to_string());
+            self.http
+                .post(&url)
+                .header(AUTHORIZATION, authorization.as_str())
+                .header(ACCEPT, "application/json")
+                // Jira rejects multipart uploads without this as a CSRF precaution.
+                .header("X-Atlassian-Token", "no-check")
+                .multipart(Form::new().part("file", part))
         })
+        .await
+        .map_err(|err| AppError::IssueTracker(err.to_string()))?;
+        Self::expect_success(response).await
+    }
+
+    #[instrument(level = "info", name = "jira.delete_ticket", skip_all, fields(key = %key))]
+    async fn delete_ticket(&self, key: &str) -> AppResult<TicketRemoval> {
+        let base_url = self.api_base()?;
+        let response = self
+            .send_raw(Method::DELETE, &Self::issue_url(base_url, key), None)
+            .await?;
+
+        // Many projects withhold the "Delete issues" permission; closing is the fallback.
+        if response.status() == StatusCode::FORBIDDEN {
+            warn!("not allowed to delete {key}; closing it instead.");
+            let status = self.close_issue(base_url, key).await?;
+            return Ok(TicketRemoval::Closed(status));
+        }
+
+        Self::expect_success(response).await?;
+        Ok(TicketRemoval::Deleted)
+    }
+}
+
+/// Done tickets of `board` in `scope`. Periods filter on the status category change so
+/// workflows that never set a resolution are still covered.
+fn completed_jql(board: &str, scope: &RetroScope) -> String {
+    let mut jql = format!(
+        "project = \"{}\" AND statusCategory = Done",
+        board.trim().replace('"', "\\\"")
+    );
+    match scope {
+        RetroScope::Sprint(sprint) => jql.push_str(&format!(" AND sprint = {}", sprint.id)),
+        RetroScope::Period { from, to } => {
+            let end = to.succ_opt().unwrap_or(*to);
+            jql.push_str(&format!(
+                " AND statusCategoryChangedDate >= \"{from}\" AND statusCategoryChangedDate < \"{end}\""
+            ));
+        }
+    }
+    jql.push_str(" ORDER BY statusCategoryChangedDate ASC");
+    jql
+}
+
+/// The done time comes from the changelog: the last move into the issue's current status.
+/// Issues whose history is not in the response fall back to the resolution date.
+fn completed_ticket(issue: JiraSearchIssue) -> Option<CompletedTicket> {
+    let fields = issue.fields;
+    let status_id = fields.status.as_ref().map(|status| status.id.as_str());
+    let moved_to_status = issue
+        .changelog
+        .map(|changelog| changelog.histories)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|history| {
+            history.items.iter().any(|item| {
+                item.field == "status" && item.to.as_deref().is_some_and(|to| Some(to) == status_id)
+            })
+        })
+        .filter_map(|history| parse_jira_time(&history.created))
+        .max();
+    let done = moved_to_status.or_else(|| {
+        fields
+            .resolutiondate
+            .or(fields.statuscategorychangedate)
+            .as_deref()
+            .and_then(parse_jira_time)
+    })?;
+
+    Some(CompletedTicket {
+        key: issue.key,
+        summary: fields.summary,
+        issue_type: fields.issuetype.map(|issue_type| issue_type.name),
+        labels: fields.labels,
+        created: parse_jira_time(&fields.created)?,
+        done,
+    })
+}
+
+/// Jira timestamps look like `2025-03-14T09:30:00.000+0000`; the Agile API uses RFC 3339.
+fn parse_jira_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .or_else(|_| DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z"))
+        .ok()
+        .map(|time| time.to_utc())
+}
+
+/// Fills placeholders in every string inside `value`, leaving other JSON untouched.
+pub(crate) fn fill_placeholders(value: &Value, placeholders: &[(&str, String)]) -> Value {
+    match value {
+        Value::String(text) => Value::String(render_template(text, placeholders)),
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| fill_placeholders(item, placeholders))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, item)| (key.clone(), fill_placeholders(item, placeholders)))
+                .collect(),
+        ),
+        other => other.clone(),
     }
 }
 
@@ -138,17 +1098,25 @@ struct JiraCreateIssueRequest {
 }
 
 impl JiraCreateIssueRequest {
-    fn new(project_key: &str, issue_type: &str, summary: &str, description: &str) -> Self {
+    fn new(project_key: &str, issue_type: &str, summary: &str, description: Option<&str>) -> Self {
         Self {
             fields: JiraCreateIssueFields {
                 project: JiraProject {
                     key: project_key.to_string(),
                 },
                 summary: summary.to_string(),
-                description: JiraDescription::from_markdown(description),
-                issuetype: JiraIssueType {
+                description: description.map(JiraDescription::from_markdown),
+                issuetype: JiraNamed {
                     name: issue_type.to_string(),
                 },
+                parent: None,
+                assignee: None,
+                reporter: None,
+                duedate: None,
+                labels: Vec::new(),
+                components: Vec::new(),
+                priority: None,
+                custom_fields: BTreeMap::new(),
             },
         }
     }
@@ -158,17 +1126,55 @@ impl JiraCreateIssueRequest {
 struct JiraCreateIssueFields {
     project: JiraProject,
     summary: String,
-    description: JiraDescription,
-    issuetype: JiraIssueType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<JiraDescription>,
+    issuetype: JiraNamed,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent: Option<JiraIssueRef>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    assignee: Option<JiraAccountRef>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reporter: Option<JiraAccountRef>,
+    /// ISO `YYYY-MM-DD`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duedate: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    labels: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    components: Vec<JiraNamed>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    priority: Option<JiraNamed>,
+    /// `customfield_*` values keyed by field ID.
+    #[serde(flatten)]
+    custom_fields: BTreeMap<String, Value>,
 }
 
 #[derive(Serialize)]
-struct JiraProject {
+struct JiraIssueRef {
     key: String,
 }
 
 #[derive(Serialize)]
-struct JiraIssueType {
+#[serde(rename_all = "camelCase")]
+struct JiraAccountRef {
+    account_id: String,
+}
+
+impl JiraAccountRef {
+    fn new(account_id: &str) -> Self {
+        Self {
+            account_id: account_id.to_string(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JiraProject {
+    key: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JiraNamed {
     name: String,
 }
 
@@ -193,13 +1199,30 @@ impl JiraDescription {
             sections.push("Describe the planned work.");
         }
 
-        let content = sections
-            .into_iter()
-            .map(|section| {
-                let paragraph_text = section.replace('\n', " ").trim().to_string();
-                JiraDocNode::paragraph(paragraph_text)
-            })
-            .collect();
+        // Markdown headings (template sections such as `## Scope`) and bullet lists keep
+        // their structure; other lines are joined into paragraphs.
+        let mut content = Vec::new();
+        for section in sections {
+            let mut paragraph: Vec<&str> = Vec::new();
+            let mut bullets: Vec<String> = Vec::new();
+            for line in section.lines().map(str::trim) {
+                let heading = markdown_heading(line);
+                let bullet = line.strip_prefix("- ").or_else(|| line.strip_prefix("* "));
+                if bullet.is_none() {
+                    push_bullets(&mut content, &mut bullets);
+                }
+                if heading.is_some() || bullet.is_some() {
+                    push_paragraph(&mut content, &mut paragraph);
+                }
+                match (heading, bullet) {
+                    (Some((level, text)), _) => content.push(JiraDocNode::heading(level, text)),
+                    (None, Some(item)) => bullets.push(item.trim().to_string()),
+                    (None, None) => paragraph.push(line),
+                }
+            }
+            push_bullets(&mut content, &mut bullets);
+            push_paragraph(&mut content, &mut paragraph);
+        }
 
         Self {
             doc_type: "doc",
@@ -207,38 +1230,400 @@ impl JiraDescription {
             content,
         }
     }
+
+    /// Appends an "Acceptance Criteria" heading and bullet list; no-op without criteria.
+    fn with_acceptance_criteria(mut self, criteria: &[String]) -> Self {
+        if !criteria.is_empty() {
+            self.content
+                .push(JiraDocNode::heading(3, "Acceptance Criteria"));
+            self.content.push(JiraDocNode::bullet_list(criteria));
+        }
+        self
+    }
+
+    /// A document holding only a bullet list, for rich-text custom fields.
+    fn bullet_list(items: &[String]) -> Self {
+        Self {
+            doc_type: "doc",
+            version: 1,
+            content: vec![JiraDocNode::bullet_list(items)],
+        }
+    }
+}
+
+/// Level and text of a Markdown `#` heading line.
+pub(crate) fn markdown_heading(line: &str) -> Option<(u8, &str)> {
+    let text = line.trim_start_matches('#');
+    let level = line.len() - text.len();
+    (level > 0 && text.starts_with(' ')).then(|| (level.min(6) as u8, text.trim()))
+}
+
+/// Renders an ADF document back into the Markdown subset `from_markdown` reads:
+/// headings, bullet items, and paragraphs separated by blank lines.
+fn adf_to_markdown(doc: &Value) -> String {
+    fn inline(node: &Value) -> String {
+        match node["type"].as_str() {
+            Some("text") => node["text"].as_str().unwrap_or_default().to_string(),
+            Some("hardBreak") => "\n".to_string(),
+            _ => children(node).iter().map(inline).collect(),
+        }
+    }
+    fn children(node: &Value) -> &[Value] {
+        node["content"]
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+    fn block(node: &Value, blocks: &mut Vec<String>) {
+        match node["type"].as_str() {
+            Some("heading") => {
+                let level = node["attrs"]["level"].as_u64().unwrap_or(1).clamp(1, 6) as usize;
+                blocks.push(format!("{} {}", "#".repeat(level), inline(node).trim()));
+            }
+            Some("bulletList") | Some("orderedList") => blocks.push(
+                children(node)
+                    .iter()
+                    .map(|item| format!("- {}", inline(item).trim()))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+            Some("paragraph") | Some("codeBlock") => blocks.push(inline(node)),
+            _ => children(node).iter().for_each(|child| block(child, blocks)),
+        }
+    }
+
+    let mut blocks = Vec::new();
+    block(doc, &mut blocks);
+    blocks
+        .into_iter()
+        .map(|block| block.trim().to_string())
+        .filter(|block| !block.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn push_paragraph(content: &mut Vec<JiraDocNode>, lines: &mut Vec<&str>) {
+    if !lines.is_empty() {
+        content.push(JiraDocNode::paragraph(&lines.join(" ")));
+        lines.clear();
+    }
 }
 
+fn push_bullets(content: &mut Vec<JiraDocNode>, items: &mut Vec<String>) {
+    if !items.is_empty() {
+        content.push(JiraDocNode::bullet_list(items));
+        items.clear();
+    }
+}
+
+/// An ADF node; leaf `text` nodes carry `text`, block nodes carry `content`.
 #[derive(Serialize)]
 struct JiraDocNode {
     #[serde(rename = "type")]
     node_type: &'static str,
-    content: Vec<JiraDocText>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attrs: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    content: Vec<JiraDocNode>,
 }
 
 impl JiraDocNode {
-    fn paragraph(text: String) -> Self {
+    fn node(node_type: &'static str, content: Vec<JiraDocNode>) -> Self {
         Self {
-            node_type: "paragraph",
-            content: vec![JiraDocText::text(text)],
+            node_type,
+            attrs: None,
+            text: None,
+            content,
         }
     }
-}
 
-#[derive(Serialize)]
-struct JiraDocText {
-    #[serde(rename = "type")]
-    text_type: &'static str,
-    text: String,
-}
+    fn text(text: &str) -> Self {
+        Self {
+            node_type: "text",
+            attrs: None,
+            text: Some(text.to_string()),
+            content: Vec::new(),
+        }
+    }
 
-impl JiraDocText {
-    fn text(text: String) -> Self {
+    fn paragraph(text: &str) -> Self {
+        Self::node("paragraph", vec![Self::text(text)])
+    }
+
+    fn heading(level: u8, text: &str) -> Self {
         Self {
-            text_type: "text",
-            text,
+            attrs: Some(serde_json::json!({ "level": level })),
+            ..Self::node("heading", vec![Self::text(text)])
         }
     }
+
+    fn bullet_list(items: &[String]) -> Self {
+        Self::node(
+            "bulletList",
+            items
+                .iter()
+                .map(|item| Self::node("listItem", vec![Self::paragraph(item)]))
+                .collect(),
+        )
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ServiceDeskCreateRequest {
+    service_desk_id: String,
+    request_type_id: String,
+    request_field_values: ServiceDeskFieldValues,
+    is_adf_request: bool,
+    /// Account ID of the customer the request is raised for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    raise_on_behalf_of: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ServiceDeskFieldValues {
+    summary: String,
+    description: JiraDescription,
+    #[serde(flatten)]
+    custom_fields: BTreeMap<String, Value>,
+}
+
+#[derive(Serialize)]
+struct ServiceDeskComment {
+    body: String,
+    public: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ServiceDeskCreateResponse {
+    issue_key: String,
+}
+
+#[derive(Deserialize)]
+struct ServiceDeskPage<T> {
+    #[serde(default = "Vec::new")]
+    values: Vec<T>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ServiceDeskSummary {
+    id: String,
+    project_key: String,
+}
+
+#[derive(Deserialize)]
+struct RequestTypeSummary {
+    id: String,
+    name: String,
+}
+
+/// Custom field IDs backing "Epic Name" and "Epic Link" in company-managed projects.
+struct ClassicEpicFields {
+    name: Option<String>,
+    link: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct JiraProjectDetails {
+    /// `classic` (company-managed) or `next-gen` (team-managed).
+    style: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct JiraFieldSummary {
+    id: String,
+    schema: Option<JiraFieldSchema>,
+}
+
+#[derive(Deserialize)]
+struct JiraFieldSchema {
+    custom: Option<String>,
+}
+
+/// One page of an Agile API listing (`/rest/agile/1.0/...`).
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraAgilePage<T> {
+    #[serde(default = "Vec::new")]
+    values: Vec<T>,
+    #[serde(default = "default_true")]
+    is_last: bool,
+}
+
+#[derive(Deserialize)]
+struct JiraAgileBoard {
+    id: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraSprint {
+    id: u64,
+    name: String,
+    state: String,
+    #[serde(default)]
+    start_date: Option<String>,
+    #[serde(default)]
+    end_date: Option<String>,
+    #[serde(default)]
+    complete_date: Option<String>,
+}
+
+/// One page of `POST /rest/api/3/search/jql`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraSearchPage {
+    #[serde(default)]
+    issues: Vec<JiraSearchIssue>,
+    #[serde(default)]
+    next_page_token: Option<String>,
+    #[serde(default = "default_true")]
+    is_last: bool,
+}
+
+#[derive(Deserialize)]
+struct JiraSearchIssue {
+    key: String,
+    fields: JiraSearchFields,
+    #[serde(default)]
+    changelog: Option<JiraChangelog>,
+}
+
+#[derive(Deserialize)]
+struct JiraSearchFields {
+    #[serde(default)]
+    summary: String,
+    #[serde(default)]
+    issuetype: Option<JiraNamed>,
+    #[serde(default)]
+    labels: Vec<String>,
+    created: String,
+    #[serde(default)]
+    resolutiondate: Option<String>,
+    #[serde(default)]
+    statuscategorychangedate: Option<String>,
+    #[serde(default)]
+    status: Option<JiraStatusRef>,
+}
+
+#[derive(Deserialize)]
+struct JiraIssueDetails {
+    key: String,
+    fields: JiraIssueDetailFields,
+}
+
+#[derive(Deserialize)]
+struct JiraIssueDetailFields {
+    #[serde(default)]
+    summary: String,
+    #[serde(default)]
+    issuetype: Option<JiraNamed>,
+    #[serde(default)]
+    status: Option<JiraNamed>,
+    /// An ADF document.
+    #[serde(default)]
+    description: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct JiraStatusRef {
+    id: String,
+    #[serde(default)]
+    name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct JiraChangelog {
+    #[serde(default)]
+    histories: Vec<JiraHistory>,
+}
+
+#[derive(Deserialize)]
+struct JiraHistory {
+    created: String,
+    #[serde(default)]
+    items: Vec<JiraHistoryItem>,
+}
+
+#[derive(Deserialize)]
+struct JiraHistoryItem {
+    field: String,
+    #[serde(default)]
+    to: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraProjectPage {
+    #[serde(default)]
+    values: Vec<JiraProjectSummary>,
+    #[serde(default = "default_true")]
+    is_last: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraProjectSummary {
+    key: String,
+    name: String,
+    #[serde(default)]
+    project_type_key: Option<String>,
+    #[serde(default)]
+    style: Option<String>,
+    #[serde(default)]
+    issue_types: Vec<JiraIssueTypeSummary>,
+}
+
+#[derive(Deserialize)]
+struct JiraIssueTypeSummary {
+    name: String,
+    #[serde(default)]
+    subtask: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraUser {
+    account_id: String,
+    display_name: String,
+    #[serde(default)]
+    email_address: Option<String>,
+    #[serde(default = "default_true")]
+    active: bool,
+    #[serde(default)]
+    account_type: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+struct JiraTransitions {
+    #[serde(default)]
+    transitions: Vec<JiraTransition>,
+}
+
+#[derive(Deserialize)]
+struct JiraTransition {
+    id: String,
+    to: JiraStatus,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraStatus {
+    name: String,
+    status_category: JiraStatusCategory,
+}
+
+#[derive(Deserialize)]
+struct JiraStatusCategory {
+    key: String,
 }
 
 #[derive(Deserialize)]
@@ -247,3 +1632,124 @@ struct JiraCreateIssueResponse {
     #[serde(rename = "self")]
     _self_url: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn epic_link_serializes_as_flat_custom_field() {
+        let mut request = JiraCreateIssueRequest::new("PAY", "Task", "Add refunds", None);
+        request
+            .fields
+            .custom_fields
+            .insert("customfield_10014".to_string(), Value::from("PAY-1"));
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["fields"]["customfield_10014"], "PAY-1");
+        assert!(json["fields"].get("parent").is_none());
+        assert!(json["fields"].get("description").is_none());
+    }
+
+    #[test]
+    fn fills_placeholders_inside_nested_field_values() {
+        let value = serde_json::json!({ "value": "{board} team", "ids": [3, "{category}"] });
+        let placeholders = [
+            ("board", "PAY".to_string()),
+            ("category", "fix".to_string()),
+        ];
+        assert_eq!(
+            fill_placeholders(&value, &placeholders),
+            serde_json::json!({ "value": "PAY team", "ids": [3, "fix"] })
+        );
+    }
+
+    #[test]
+    fn acceptance_criteria_render_as_adf_bullet_list() {
+        let criteria = vec!["Refunds post to the ledger".to_string()];
+        let doc =
+            JiraDescription::from_markdown("Support refunds.").with_acceptance_criteria(&criteria);
+
+        let json = serde_json::to_value(&doc).unwrap();
+        let content = json["content"].as_array().unwrap();
+        assert_eq!(content.len(), 3);
+        assert_eq!(content[1]["type"], "heading");
+        assert_eq!(content[1]["attrs"]["level"], 3);
+        assert_eq!(content[2]["type"], "bulletList");
+        let item = &content[2]["content"][0];
+        assert_eq!(item["type"], "listItem");
+        assert_eq!(
+            item["content"][0]["content"][0]["text"],
+            "Refunds post to the ledger"
+        );
+    }
+
+    #[test]
+    fn adf_descriptions_read_back_as_markdown() {
+        let doc =
+            JiraDescription::from_markdown("## Scope\n- Refunds\n- Emails\n\nRound to the\ncent.");
+        let json = serde_json::to_value(&doc).unwrap();
+        assert_eq!(
+            adf_to_markdown(&json),
+            "## Scope\n\n- Refunds\n- Emails\n\nRound to the cent."
+        );
+    }
+
+    #[test]
+    fn template_sections_render_as_adf_headings() {
+        let doc = JiraDescription::from_markdown(
+            "## Background\nRefunds round to the\nwrong cent.\n\n## Scope\n- Ledger\n- Emails\n\n## Out of scope\nNone.",
+        );
+
+        let json = serde_json::to_value(&doc).unwrap();
+        let types: Vec<&str> = json["content"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|node| node["type"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            types,
+            [
+                "heading",
+                "paragraph",
+                "heading",
+                "bulletList",
+                "heading",
+                "paragraph"
+            ]
+        );
+        assert_eq!(json["content"][0]["attrs"]["level"], 2);
+        assert_eq!(json["content"][0]["content"][0]["text"], "Background");
+        assert_eq!(
+            json["content"][1]["content"][0]["text"],
+            "Refunds round to the wrong cent."
+        );
+        assert_eq!(json["content"][3]["content"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn done_time_is_the_last_move_into_the_current_status() {
+        let issue: JiraSearchIssue = serde_json::from_value(serde_json::json!({
+            "key": "PAY-7",
+            "fields": {
+                "summary": "Add refunds",
+                "created": "2025-03-03T09:00:00.000+0000",
+                "resolutiondate": "2025-03-09T09:00:00.000+0000",
+                "status": {"id": "10002"}
+            },
+            "changelog": {"histories": [
+                {"created": "2025-03-05T09:00:00.000+0000",
+                 "items": [{"field": "status", "to": "10002"}]},
+                {"created": "2025-03-06T09:00:00.000+0000",
+                 "items": [{"field": "status", "to": "3"}]},
+                {"created": "2025-03-07T09:00:00.000+0000",
+                 "items": [{"field": "status", "to": "10002"}]}
+            ]}
+        }))
+        .unwrap();
+
+        let ticket = completed_ticket(issue).unwrap();
+        assert_eq!(ticket.cycle_time(), chrono::TimeDelta::days(4));
+    }
+}
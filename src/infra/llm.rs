@@ -1,78 +1,240 @@
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{debug, instrument};
 
+use crate::config::LlmSettings;
 use crate::domain::branch::BranchCategory;
 use crate::domain::change::ChangeSummary;
-use crate::domain::ticket::TicketDraft;
+use crate::domain::changelog::{ChangelogFacts, ReleaseNotes};
+use crate::domain::estimate::snap_to_scale;
+use crate::domain::retro::{RetroFacts, RetroNotes, format_duration};
+use crate::domain::romanize::romanize;
+use crate::domain::standup::{StandupFacts, StandupNotes};
+use crate::domain::ticket::{
+    DraftGuidance, SplitDraft, TicketAmendment, TicketDetails, TicketDraft,
+};
 use crate::error::{AppError, AppResult};
+use crate::infra::heuristic::{heuristic_category, heuristic_summary};
+use crate::infra::http::{HttpClientFactory, RetryPolicy, send_with_retry};
+use crate::infra::stream::{StreamProgress, read_lines, sse_data};
 use crate::services::LanguageModelService;
 
-const GEMINI_SYSTEM_PROMPT: &str = r#"
+/// Bump whenever the prompts or the prompt builders change meaningfully, so drafts
+/// cached under the old wording are not reused.
+pub(crate) const PROMPT_VERSION: u32 = 7;
+
+pub(crate) const DRAFT_SYSTEM_PROMPT: &str = r#"
 You are an assistant for a developer CLI. Given local git change summaries, draft a Jira ticket
 and git branch metadata. Respond with VALID JSON only, no markdown, no commentary.
 
 Rules:
-- Keys: title, description, branch_category, branch_summary.
+- Keys: title, description, branch_category, branch_summary, acceptance_criteria, rationale.
 - branch_category must be one of: "feature", "fix", "quality".
-- branch_summary must be a short, lower-case slug (hyphen-separated words <= 6 words).
+- branch_summary must be a short, lower-case English slug in ASCII (hyphen-separated words <= 6 words), whatever language the ticket is in.
 - description should be concise Markdown (bullets or short paragraphs) that references the planned work.
+- acceptance_criteria is an array of 2-5 short, independently verifiable statements; do not repeat them in the description.
+- rationale is one or two plain sentences for the developer, not the ticket: what you understood the changes to do and why you chose the category.
 - Keep title under 80 characters and actionable.
-- Ignore test changes if non-test changes exist.
+- Each changed file is tagged with its kind: source, test, config, docs, ci, or generated.
+- Ignore files tagged [test] when files of other kinds changed, and treat [generated] files as side effects, not work.
 - Never invent work unrelated to the provided changes.
 "#;
 
+pub(crate) const SPLIT_SYSTEM_PROMPT: &str = r#"
+You are an assistant for a developer CLI. Given local git changes that may mix unrelated
+work, cluster them into separate Jira tickets, each with its own git branch metadata.
+Respond with VALID JSON only, no markdown, no commentary.
+
+Rules:
+- Top-level key: tickets, an array of objects.
+- Ticket keys: title, description, branch_category, branch_summary, acceptance_criteria, rationale, files.
+- acceptance_criteria is an array of 2-5 short, independently verifiable statements.
+- rationale is one sentence for the developer, not the ticket: why these files belong together.
+- files lists the changed paths exactly as given, without their [kind] tags; every path belongs to exactly one ticket.
+- branch_category must be one of: "feature", "fix", "quality".
+- branch_summary must be a short, lower-case English slug in ASCII (hyphen-separated words <= 6 words), whatever language the ticket is in.
+- Keep titles under 80 characters and actionable.
+- Never invent work unrelated to the provided changes.
+"#;
+
+pub(crate) const RETRO_SYSTEM_PROMPT: &str = r#"
+You are an assistant for a developer CLI. Given facts about the tickets a team completed in a
+sprint or period, draft the notes of a retrospective. Respond with VALID JSON only, no markdown,
+no commentary.
+
+Rules:
+- Keys: went_well, went_poorly, action_items; each an array of 2-5 short Markdown strings.
+- Base every point on the facts given and cite ticket keys or commit IDs where they apply.
+- Long cycle times, reverts, and hotfixes are signals to discuss, never blame on people.
+- Action items are concrete, achievable in the next sprint, and not assigned to anyone.
+- Never invent tickets, numbers, or events that are not in the facts.
+"#;
+
+pub(crate) const RELEASE_NOTES_SYSTEM_PROMPT: &str = r#"
+You are an assistant for a developer CLI. Given the tickets and commits that went into a release,
+write its release notes for the people using the software. Respond with VALID JSON only, no
+markdown fences, no commentary.
+
+Rules:
+- Keys: summary (one or two sentences on the release as a whole), features, fixes, quality,
+  other; each of the last four an array of short Markdown strings, possibly empty.
+- One item per ticket, starting with its key; put each under the category given for it, or
+  decide from its title when none is given. Ticketless commits may be merged into one item.
+- Describe what changed for users, not how; leave out merges, typo fixes, and version bumps.
+- Never invent changes, tickets, or numbers that are not in the facts.
+"#;
+
+pub(crate) const ENRICH_SYSTEM_PROMPT: &str = r#"
+You are an assistant for a developer CLI. Given a ticket someone wrote by hand for their backlog,
+expand it into a ticket ready for planning. Respond with VALID JSON only, no markdown, no
+commentary.
+
+Rules:
+- Keys: title, description, acceptance_criteria.
+- Keep the title's meaning; only fix wording, and keep it under 80 characters.
+- The description explains the goal and scope in 2-4 short paragraphs, building on what is given.
+- acceptance_criteria is an array of 2-5 short, verifiable conditions.
+- Never invent requirements, systems, or numbers the ticket does not suggest.
+"#;
+
+pub(crate) const AMEND_SYSTEM_PROMPT: &str = r#"
+You are an assistant for a developer CLI. Given an existing ticket and new uncommitted changes made
+for it, bring the ticket up to date. Respond with VALID JSON only, no markdown, no commentary.
+
+Rules:
+- Keys: description, acceptance_criteria, comment.
+- description is the full updated description: keep what still holds, keep its headings, and
+  work the new changes in where they belong. Do not list changed files.
+- acceptance_criteria is an array of 2-5 short, verifiable conditions covering the updated scope,
+  or an empty array when the current ones still fit.
+- comment is a short follow-up note (1-3 sentences or a few bullets) on what the new changes add.
+- Never invent requirements, systems, or numbers the ticket or changes do not suggest.
+"#;
+
+pub(crate) const STANDUP_SYSTEM_PROMPT: &str = r#"
+You are an assistant for a developer CLI. Given a developer's recent commits grouped by ticket,
+write what they would say at a daily standup about each group. Respond with VALID JSON only, no
+markdown, no commentary.
+
+Rules:
+- Keys: summaries, an object mapping each group label exactly as given to one or two sentences.
+- Write in the first person past tense ("Added ...", "Fixed ..."), plain and specific.
+- Merge related commits into one point; leave out typo fixes and merges.
+- Never invent work that the commits do not show.
+"#;
+
+/// Most commits listed per group in a standup prompt; the rest are only counted.
+const MAX_STANDUP_COMMITS: usize = 20;
+
+/// Most entries listed in a release notes prompt; the rest are only counted.
+const MAX_RELEASE_ENTRIES: usize = 80;
+
+/// Most commit subjects quoted per release notes entry.
+const MAX_RELEASE_COMMITS_PER_ENTRY: usize = 5;
+
+/// Most tickets listed individually in a retrospective prompt; the rest are only counted.
+const MAX_RETRO_TICKETS: usize = 60;
+
+/// Upper bound on tickets a single `--split` run proposes.
+const MAX_SPLIT_TICKETS: usize = 5;
+
 pub struct GeminiClient {
     http: Client,
+    retry: RetryPolicy,
     api_key: Option<String>,
     model: String,
+    generation: Option<GenerationConfig>,
+    stream: bool,
 }
 
 impl GeminiClient {
-    pub fn new(api_key: Option<String>, model: String) -> Self {
-        let http = Client::builder()
-            .timeout(Duration::from_secs(20))
-            .build()
-            .expect("failed to build HTTP client");
-        Self {
-            http,
+    pub fn new(
+        http: &HttpClientFactory,
+        api_key: Option<String>,
+        model: String,
+        settings: &LlmSettings,
+    ) -> AppResult<Self> {
+        settings.validate()?;
+        let generation = GenerationConfig {
+            temperature: settings.temperature,
+            top_p: settings.top_p,
+            max_output_tokens: settings.max_output_tokens,
+        };
+        Ok(Self {
+            http: http.build(Some(settings.timeout_or(Duration::from_secs(20))))?,
+            retry: http.retry_policy(),
             api_key,
             model,
-        }
+            generation: (generation != GenerationConfig::default()).then_some(generation),
+            stream: settings.streaming(),
+        })
     }
 }
 
-#[async_trait]
-impl LanguageModelService for GeminiClient {
-    async fn draft_ticket(&self, changes: &ChangeSummary) -> AppResult<TicketDraft> {
+impl GeminiClient {
+    /// Confirms the API key can see the configured model.
+    pub async fn verify_api_key(&self) -> AppResult<()> {
         let api_key = self
             .api_key
             .as_ref()
             .ok_or_else(|| AppError::Configuration("Gemini API key not configured".to_string()))?;
+        let url = format!(
+            "https://generativelanguage.googleapis.com/v1beta/models/{}?key={}",
+            self.model, api_key
+        );
+        let response = send_with_retry(&self.retry, "Gemini", || self.http.get(&url))
+            .await
+            .map_err(|err| AppError::LanguageModel(err.to_string()))?;
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err(AppError::LanguageModel(format!(
+                "Gemini rejected the key or model ({status})"
+            )))
+        }
+    }
+}
 
-        let baseline_category = heuristic_category(changes);
-        let baseline_summary = heuristic_summary(changes);
-        let user_prompt = build_user_prompt(changes, &baseline_category, &baseline_summary);
+impl GeminiClient {
+    /// Sends one system + user prompt pair and returns the first non-empty candidate text.
+    /// While a progress display is watching, the reply is streamed over server-sent events.
+    async fn generate(&self, system_prompt: &str, user_prompt: String) -> AppResult<String> {
+        let api_key = self
+            .api_key
+            .as_ref()
+            .ok_or_else(|| AppError::Configuration("Gemini API key not configured".to_string()))?;
 
         let request = GenerateContentRequest {
-            system_instruction: Some(Instruction::new(GEMINI_SYSTEM_PROMPT)),
+            system_instruction: Some(Instruction::new(system_prompt)),
             contents: vec![Content::user(user_prompt)],
+            generation_config: self.generation,
         };
 
+        let progress = self
+            .stream
+            .then(|| StreamProgress::start("Gemini"))
+            .flatten();
+        let method = if progress.is_some() {
+            "streamGenerateContent?alt=sse&"
+        } else {
+            "generateContent?"
+        };
         let url = format!(
-            "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
+            "https://generativelanguage.googleapis.com/v1beta/models/{}:{method}key={}",
             self.model, api_key
         );
 
-        let response = match self.http.post(&url).json(&request).send().await {
-            Ok(resp) => resp,
-            Err(err) => {
-                eprintln!("Warning: Gemini request failed ({err}); using heuristic ticket.");
-                return Ok(heuristic_ticket(changes));
-            }
-        };
+        let response = send_with_retry(&self.retry, "Gemini", || {
+            self.http.post(&url).json(&request)
+        })
+        .await
+        .map_err(|err| AppError::LanguageModel(err.to_string()))?;
 
         let status = response.status();
         if !status.is_success() {
@@ -80,21 +242,48 @@ impl LanguageModelService for GeminiClient {
                 .text()
                 .await
                 .unwrap_or_else(|_| "<no body>".to_string());
-            eprintln!(
-                "Warning: Gemini request returned {status}; falling back to heuristic ticket. Body: {body}"
-            );
-            return Ok(heuristic_ticket(changes));
+            return Err(AppError::LanguageModel(format!(
+                "Gemini request returned {status}: {body}"
+            )));
         }
 
-        let payload: GenerateContentResponse = match response.json().await {
-            Ok(payload) => payload,
-            Err(err) => {
-                eprintln!(
-                    "Warning: failed to parse Gemini response ({err}); using heuristic ticket."
-                );
-                return Ok(heuristic_ticket(changes));
+        if let Some(progress) = &progress {
+            let mut text = String::new();
+            read_lines("Gemini", response, |line| {
+                let Some(data) = sse_data(line) else {
+                    return Ok(());
+                };
+                let payload: GenerateContentResponse =
+                    serde_json::from_str(data).map_err(|err| {
+                        AppError::LanguageModel(format!("failed to parse Gemini stream: {err}"))
+                    })?;
+                let chunk: String = payload
+                    .candidates
+                    .into_iter()
+                    .next()
+                    .and_then(|candidate| candidate.content)
+                    .into_iter()
+                    .flat_map(|content| content.parts)
+                    .filter_map(|part| part.text)
+                    .collect();
+                progress.text(&chunk);
+                text.push_str(&chunk);
+                Ok(())
+            })
+            .await?;
+            let text = text.trim();
+            if text.is_empty() {
+                return Err(AppError::LanguageModel(
+                    "Gemini returned an empty response".to_string(),
+                ));
             }
-        };
+            debug!(response = %text, "Gemini response streamed");
+            return Ok(text.to_string());
+        }
+
+        let payload: GenerateContentResponse = response.json().await.map_err(|err| {
+            AppError::LanguageModel(format!("failed to parse Gemini response: {err}"))
+        })?;
 
         let candidate_text = payload
             .candidates
@@ -108,152 +297,726 @@ impl LanguageModelService for GeminiClient {
                 AppError::LanguageModel("Gemini returned an empty response".to_string())
             })?;
 
-        let normalized = normalize_json_blob(&candidate_text);
-        let draft: GeminiDraft = match serde_json::from_str(&normalized) {
-            Ok(draft) => draft,
-            Err(err) => {
-                eprintln!(
-                    "Warning: Gemini produced invalid JSON ({err}); using heuristic ticket. Payload: {}",
-                    candidate_text
-                );
-                return Ok(heuristic_ticket(changes));
-            }
-        };
+        debug!(response = %candidate_text, "Gemini response received");
+        Ok(candidate_text)
+    }
+}
 
-        let branch_category = match BranchCategory::from_str(&draft.branch_category) {
-            Some(category) => category,
-            None => {
-                eprintln!(
-                    "Warning: Gemini returned invalid branch_category '{}'; using heuristic ticket.",
-                    draft.branch_category
-                );
-                return Ok(heuristic_ticket(changes));
-            }
-        };
+#[async_trait]
+impl LanguageModelService for GeminiClient {
+    fn fingerprint(&self) -> String {
+        format!("gemini:{}:p{PROMPT_VERSION}", self.model)
+    }
 
-        let branch_summary = if draft.branch_summary.trim().is_empty() {
-            baseline_summary
-        } else {
-            draft.branch_summary.trim().to_lowercase()
-        };
+    #[instrument(level = "info", name = "gemini.draft_ticket", skip_all, fields(model = %self.model))]
+    async fn draft_ticket(
+        &self,
+        changes: &ChangeSummary,
+        guidance: &DraftGuidance,
+    ) -> AppResult<TicketDraft> {
+        let text = self
+            .generate(DRAFT_SYSTEM_PROMPT, build_user_prompt(changes, guidance))
+            .await?;
+        parse_model_draft("Gemini", &text, changes, &guidance.estimate_scale)
+    }
 
-        let title = draft.title.trim();
-        if title.is_empty() {
-            eprintln!("Warning: Gemini returned empty title; using heuristic ticket.");
-            return Ok(heuristic_ticket(changes));
-        }
+    #[instrument(level = "info", name = "gemini.draft_tickets", skip_all, fields(model = %self.model))]
+    async fn draft_tickets(
+        &self,
+        changes: &ChangeSummary,
+        guidance: &DraftGuidance,
+    ) -> AppResult<Vec<SplitDraft>> {
+        let text = self
+            .generate(SPLIT_SYSTEM_PROMPT, build_split_prompt(changes, guidance))
+            .await?;
+        parse_split_drafts("Gemini", &text, changes, &guidance.estimate_scale)
+    }
+
+    #[instrument(level = "info", name = "gemini.retrospective_notes", skip_all, fields(model = %self.model))]
+    async fn retrospective_notes(&self, facts: &RetroFacts) -> AppResult<RetroNotes> {
+        let text = self
+            .generate(RETRO_SYSTEM_PROMPT, build_retro_prompt(facts))
+            .await?;
+        parse_retro_notes("Gemini", &text)
+    }
+
+    #[instrument(level = "info", name = "gemini.release_notes", skip_all, fields(model = %self.model))]
+    async fn release_notes(&self, facts: &ChangelogFacts) -> AppResult<ReleaseNotes> {
+        let text = self
+            .generate(
+                RELEASE_NOTES_SYSTEM_PROMPT,
+                build_release_notes_prompt(facts),
+            )
+            .await?;
+        parse_release_notes("Gemini", &text)
+    }
+
+    #[instrument(level = "info", name = "gemini.enrich_draft", skip_all, fields(model = %self.model))]
+    async fn enrich_draft(&self, draft: &TicketDraft) -> AppResult<TicketDraft> {
+        let text = self
+            .generate(ENRICH_SYSTEM_PROMPT, build_enrich_prompt(draft))
+            .await?;
+        parse_enriched_draft("Gemini", &text, draft)
+    }
+
+    #[instrument(level = "info", name = "gemini.amend_ticket", skip_all, fields(model = %self.model))]
+    async fn amend_ticket(
+        &self,
+        ticket: &TicketDetails,
+        changes: &ChangeSummary,
+        guidance: &DraftGuidance,
+    ) -> AppResult<TicketAmendment> {
+        let text = self
+            .generate(
+                AMEND_SYSTEM_PROMPT,
+                build_amend_prompt(ticket, changes, guidance),
+            )
+            .await?;
+        parse_amendment("Gemini", &text, ticket, changes)
+    }
 
-        let description = draft.description.trim();
-        if description.is_empty() {
-            eprintln!("Warning: Gemini returned empty description; using heuristic ticket.");
-            return Ok(heuristic_ticket(changes));
+    #[instrument(level = "info", name = "gemini.standup_notes", skip_all, fields(model = %self.model))]
+    async fn standup_notes(&self, facts: &StandupFacts) -> AppResult<StandupNotes> {
+        let text = self
+            .generate(STANDUP_SYSTEM_PROMPT, build_standup_prompt(facts))
+            .await?;
+        parse_standup_notes("Gemini", &text, facts)
+    }
+}
+
+/// Validates the JSON text returned by a model and converts it into a draft.
+pub(crate) fn parse_model_draft(
+    provider: &str,
+    text: &str,
+    changes: &ChangeSummary,
+    estimate_scale: &[u32],
+) -> AppResult<TicketDraft> {
+    let normalized = normalize_json_blob(text);
+    let draft: ModelDraft = serde_json::from_str(&normalized).map_err(|err| {
+        AppError::LanguageModel(format!(
+            "{provider} produced invalid JSON ({err}). Payload: {text}"
+        ))
+    })?;
+    validate_model_draft(provider, draft, changes, estimate_scale)
+}
+
+/// Parses a `{"tickets": [...]}` clustering response. Files the model invented are
+/// dropped, and clusters left without any real file are discarded.
+pub(crate) fn parse_split_drafts(
+    provider: &str,
+    text: &str,
+    changes: &ChangeSummary,
+    estimate_scale: &[u32],
+) -> AppResult<Vec<SplitDraft>> {
+    let normalized = normalize_json_blob(text);
+    let split: ModelSplit = serde_json::from_str(&normalized).map_err(|err| {
+        AppError::LanguageModel(format!(
+            "{provider} produced invalid JSON ({err}). Payload: {text}"
+        ))
+    })?;
+
+    let mut drafts = Vec::new();
+    for ticket in split.tickets {
+        let files: Vec<String> = ticket
+            .files
+            .into_iter()
+            .map(|file| file.trim().to_string())
+            .filter(|file| changes.files.contains(file))
+            .collect();
+        if files.is_empty() && !changes.files.is_empty() {
+            continue;
         }
+        drafts.push(SplitDraft {
+            draft: validate_model_draft(provider, ticket.draft, changes, estimate_scale)?,
+            files,
+        });
+    }
 
-        Ok(TicketDraft {
-            title: title.to_string(),
-            description: description.to_string(),
-            branch_category,
-            branch_summary,
-        })
+    if drafts.is_empty() {
+        return Err(AppError::LanguageModel(format!(
+            "{provider} did not group the changes into any ticket"
+        )));
     }
+    Ok(drafts)
+}
+
+/// `text` romanized into a slug of at most `max_words` words, or `None` when it cannot be
+/// spelled in ASCII (e.g. a slug in kanji).
+fn ascii_slug(text: &str, max_words: usize) -> Option<String> {
+    let romanized = romanize(text.trim())?;
+    let words: Vec<&str> = romanized
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .take(max_words)
+        .collect();
+    (!words.is_empty()).then(|| words.join("-").to_lowercase())
 }
 
-fn build_user_prompt(
+fn validate_model_draft(
+    provider: &str,
+    draft: ModelDraft,
     changes: &ChangeSummary,
-    baseline_category: &BranchCategory,
-    baseline_summary: &str,
-) -> String {
+    estimate_scale: &[u32],
+) -> AppResult<TicketDraft> {
+    let branch_category = BranchCategory::from_str(&draft.branch_category).ok_or_else(|| {
+        AppError::LanguageModel(format!(
+            "{provider} returned invalid branch_category '{}'",
+            draft.branch_category
+        ))
+    })?;
+
+    // A slug the model wrote in another script is romanized; one that cannot be (kanji)
+    // gives way to the romanized title, then to the path-based slug.
+    let branch_summary = if draft.branch_summary.trim().is_empty() {
+        heuristic_summary(changes)
+    } else {
+        ascii_slug(&draft.branch_summary, usize::MAX)
+            .or_else(|| ascii_slug(&draft.title, 6))
+            .unwrap_or_else(|| heuristic_summary(changes))
+    };
+
+    let title = draft.title.trim();
+    if title.is_empty() {
+        return Err(AppError::LanguageModel(format!(
+            "{provider} returned an empty title"
+        )));
+    }
+
+    let description = draft.description.trim();
+    if description.is_empty() {
+        return Err(AppError::LanguageModel(format!(
+            "{provider} returned an empty description"
+        )));
+    }
+
+    let acceptance_criteria = clean_items(&draft.acceptance_criteria);
+
+    // Models sometimes quote numbers; anything off the scale snaps to its nearest value.
+    let estimate = draft
+        .estimate
+        .and_then(|value| match value {
+            Value::Number(number) => number.as_f64(),
+            Value::String(text) => text.trim().parse().ok(),
+            _ => None,
+        })
+        .and_then(|value| snap_to_scale(value, estimate_scale));
+
+    Ok(TicketDraft {
+        title: title.to_string(),
+        description: description.to_string(),
+        branch_category,
+        branch_summary,
+        acceptance_criteria,
+        estimate,
+        provider: Some(provider.to_ascii_lowercase()),
+        rationale: draft
+            .rationale
+            .map(|rationale| rationale.trim().to_string())
+            .filter(|rationale| !rationale.is_empty()),
+    })
+}
+
+pub(crate) fn build_split_prompt(changes: &ChangeSummary, guidance: &DraftGuidance) -> String {
+    let mut prompt = format!(
+        concat!(
+            "Git status summary:\n{}\n\n",
+            "Changed files, tagged with their kind:\n{}\n\n",
+            "Group these files into the smallest number of coherent, independently reviewable ",
+            "tickets (at most {}). Unrelated work belongs in separate tickets; do not split one ",
+            "logical change. Every file must appear in exactly one ticket.\n",
+            "Return only JSON: {{\"tickets\": [{{\"title\", \"description\", ",
+            "\"branch_category\", \"branch_summary\", \"acceptance_criteria\": [strings], ",
+            "\"rationale\", \"files\": [paths]}}]}}.\n",
+            "branch_category must be feature, fix, or quality.\n",
+            "branch_summary must be a short hyphenated slug (<=6 words).\n",
+            "Use concise Markdown in each description and do not list the files in it."
+        ),
+        changes.summary.trim(),
+        tagged_file_list(changes),
+        MAX_SPLIT_TICKETS
+    );
+
+    push_estimate_request(&mut prompt, &guidance.estimate_scale);
+    push_language_request(&mut prompt, guidance.language.as_deref());
+    push_sections_request(&mut prompt, &guidance.sections);
+
+    push_corrections(&mut prompt, &guidance.corrections);
+
+    prompt
+}
+
+/// Passes on what was wrong with earlier drafts, as rated with `ugh feedback bad`.
+fn push_corrections(prompt: &mut String, corrections: &[String]) {
+    if !corrections.is_empty() {
+        prompt.push_str("\n\nFeedback on earlier drafts that you must address:");
+        for correction in corrections {
+            prompt.push_str(&format!("\n- {}", correction.trim()));
+        }
+    }
+}
+
+/// Asks for the ticket text in `language` while keeping the branch slug English.
+fn push_language_request(prompt: &mut String, language: Option<&str>) {
+    if let Some(language) = language {
+        prompt.push_str(&format!(
+            "\n\nWrite the title, description, and acceptance_criteria in {language}. \
+             branch_summary must still be an English slug of ASCII letters, digits, and hyphens."
+        ));
+    }
+}
+
+/// Asks for the description to follow the team's ticket template.
+fn push_sections_request(prompt: &mut String, sections: &[String]) {
+    if sections.is_empty() {
+        return;
+    }
+    prompt.push_str(
+        "\n\nOrganize the description under these Markdown headings, in this order, \
+         writing \"None.\" under any that do not apply:",
+    );
+    for section in sections {
+        prompt.push_str(&format!("\n## {section}"));
+    }
+}
+
+pub(crate) fn build_user_prompt(changes: &ChangeSummary, guidance: &DraftGuidance) -> String {
+    let baseline_category = heuristic_category(changes);
+    let baseline_summary = heuristic_summary(changes);
     let summary = if changes.summary.trim().is_empty() {
         "(no diff summary provided)".to_string()
     } else {
         changes.summary.trim().to_string()
     };
 
-    format!(
+    let mut prompt = format!(
         concat!(
             "Git status summary:\n{}\n\n",
-            "Files changed: {}\n\n",
-            "Return only JSON with keys: title, description, branch_category, branch_summary.\n",
+            "Files changed: {} ({})\n{}\n\n",
+            "Return only JSON with keys: title, description, branch_category, branch_summary, acceptance_criteria, rationale.\n",
+            "acceptance_criteria must be an array of 2-5 short, testable statements of done.\n",
+            "rationale is one or two sentences on how you read the changes and why you chose the category; it is not filed.\n",
             "branch_category must be feature, fix, or quality.\n",
             "branch_summary must be a short hyphenated slug (<=6 words).\n",
             "Use concise Markdown in the description. Do not list changed files in the description.\n",
             "The description should be a backward engineered Jira ticket, not a changelog.\n",
-            "Ignore pure test-only changes when other files are touched; mention tests as follow-up if needed.\n",
+            "{}\n",
             "Heuristic hint -> category: {}, summary: {}.\n",
             "If information is missing, make conservative assumptions and mention follow-up items."
         ),
         summary,
         changes.files_changed,
+        kind_counts(changes),
+        tagged_file_list(changes),
+        if changes.has_non_test_changes() {
+            "Files other than tests changed: describe those and ignore the [test] files, \
+             mentioning tests only as follow-up if needed."
+        } else {
+            "Only [test] files changed: the ticket is about the tests themselves."
+        },
         baseline_category.as_str(),
         baseline_summary
-    )
+    );
+
+    if !guidance.examples.is_empty() {
+        prompt.push_str(
+            "\n\nExamples of drafts the developer rated highly (match their tone and depth):",
+        );
+        for example in &guidance.examples {
+            let draft_json = serde_json::json!({
+                "title": example.draft.title,
+                "description": example.draft.description,
+                "branch_category": example.draft.branch_category.as_str(),
+                "branch_summary": example.draft.branch_summary,
+                "acceptance_criteria": example.draft.acceptance_criteria,
+            });
+            prompt.push_str(&format!(
+                "\n\nChanges:\n{}\nDraft:\n{}",
+                example.change_summary.trim(),
+                draft_json
+            ));
+        }
+    }
+
+    push_estimate_request(&mut prompt, &guidance.estimate_scale);
+    push_language_request(&mut prompt, guidance.language.as_deref());
+    push_sections_request(&mut prompt, &guidance.sections);
+
+    push_corrections(&mut prompt, &guidance.corrections);
+
+    prompt
 }
 
-fn heuristic_category(changes: &ChangeSummary) -> BranchCategory {
-    let lower = changes.summary.to_lowercase();
-    if lower.contains("fix") || lower.contains("bug") || lower.contains("error") {
-        BranchCategory::Fix
-    } else if lower.contains("refactor")
-        || lower.contains("cleanup")
-        || lower.contains("docs")
-        || lower.contains("chore")
-    {
-        BranchCategory::Quality
+pub(crate) fn build_retro_prompt(facts: &RetroFacts) -> String {
+    let mut prompt = format!(
+        "Board: {}\nPeriod: {}\nCompleted tickets: {}\n",
+        facts.board,
+        facts.period,
+        facts.tickets.len()
+    );
+    if let Some(median) = facts.median_cycle_time() {
+        prompt.push_str(&format!(
+            "Median cycle time (created to done): {}\n",
+            format_duration(median)
+        ));
+    }
+
+    if !facts.tickets.is_empty() {
+        prompt.push_str("\nTickets with their cycle times:\n");
+        for ticket in facts.tickets.iter().take(MAX_RETRO_TICKETS) {
+            prompt.push_str(&format!(
+                "- {} [{}] {} ({}){}\n",
+                ticket.key,
+                ticket.issue_type.as_deref().unwrap_or("issue"),
+                ticket.summary.trim(),
+                format_duration(ticket.cycle_time()),
+                if ticket.is_revert_or_hotfix() {
+                    " [revert/hotfix]"
+                } else {
+                    ""
+                }
+            ));
+        }
+        if facts.tickets.len() > MAX_RETRO_TICKETS {
+            prompt.push_str(&format!(
+                "- … and {} more\n",
+                facts.tickets.len() - MAX_RETRO_TICKETS
+            ));
+        }
+    }
+
+    let slow: Vec<&str> = facts
+        .slow_tickets()
+        .iter()
+        .map(|ticket| ticket.key.as_str())
+        .collect();
+    if !slow.is_empty() {
+        prompt.push_str(&format!(
+            "\nTook more than twice the median: {}\n",
+            slow.join(", ")
+        ));
+    }
+
+    match &facts.commits {
+        Some(commits) => {
+            prompt.push_str(&format!("\nCommits in the period: {}\n", commits.len()));
+            let notable: Vec<String> = facts
+                .notable_commits()
+                .map(|commit| format!("- {} {}", commit.id, commit.subject))
+                .collect();
+            if !notable.is_empty() {
+                prompt.push_str("Reverts and hotfixes in the git history:\n");
+                prompt.push_str(&notable.join("\n"));
+                prompt.push('\n');
+            }
+        }
+        None => prompt.push_str("\nThe git history was not available.\n"),
+    }
+
+    prompt.push_str(
+        "\nReturn only JSON: {\"went_well\": [strings], \"went_poorly\": [strings], \
+         \"action_items\": [strings]}.",
+    );
+    prompt
+}
+
+pub(crate) fn build_release_notes_prompt(facts: &ChangelogFacts) -> String {
+    let mut prompt = format!(
+        "Range: {}\nEntries: {}\n\n",
+        facts.range,
+        facts.entries.len()
+    );
+    for entry in facts.entries.iter().take(MAX_RELEASE_ENTRIES) {
+        let category = entry
+            .category
+            .as_ref()
+            .map_or("uncategorized", |category| category.as_str());
+        match (&entry.ticket, &entry.details) {
+            (Some(key), Some(details)) => prompt.push_str(&format!(
+                "- {key} [{category}, {}, {}] {}\n",
+                details.issue_type.as_deref().unwrap_or("issue"),
+                details.status.as_deref().unwrap_or("unknown status"),
+                details.title.trim()
+            )),
+            (Some(key), None) => prompt.push_str(&format!("- {key} [{category}]\n")),
+            (None, _) => prompt.push_str(&format!("- no ticket [{category}]\n")),
+        }
+        for commit in entry.commits.iter().take(MAX_RELEASE_COMMITS_PER_ENTRY) {
+            prompt.push_str(&format!("  - {} {}\n", commit.id, commit.subject.trim()));
+        }
+        if entry.commits.len() > MAX_RELEASE_COMMITS_PER_ENTRY {
+            prompt.push_str(&format!(
+                "  - … and {} more commits\n",
+                entry.commits.len() - MAX_RELEASE_COMMITS_PER_ENTRY
+            ));
+        }
+    }
+    if facts.entries.len() > MAX_RELEASE_ENTRIES {
+        prompt.push_str(&format!(
+            "- … and {} more entries\n",
+            facts.entries.len() - MAX_RELEASE_ENTRIES
+        ));
+    }
+    prompt.push_str(
+        "\nReturn only JSON: {\"summary\": string, \"features\": [strings], \
+         \"fixes\": [strings], \"quality\": [strings], \"other\": [strings]}.",
+    );
+    prompt
+}
+
+pub(crate) fn build_enrich_prompt(draft: &TicketDraft) -> String {
+    let mut prompt = format!(
+        "Category: {}\nTitle: {}\n",
+        draft.branch_category.as_str(),
+        draft.title
+    );
+    if draft.description.trim().is_empty() {
+        prompt.push_str("Description: (none)\n");
     } else {
-        BranchCategory::Feature
+        prompt.push_str(&format!("Description:\n{}\n", draft.description.trim()));
+    }
+    if !draft.acceptance_criteria.is_empty() {
+        prompt.push_str("Acceptance criteria so far:\n");
+        for criterion in &draft.acceptance_criteria {
+            prompt.push_str(&format!("- {criterion}\n"));
+        }
+    }
+    prompt.push_str(
+        "\nReturn only JSON: {\"title\": string, \"description\": string, \
+         \"acceptance_criteria\": [strings]}.",
+    );
+    prompt
+}
+
+/// Applies a model's expansion to `draft`; blank fields keep what was written by hand.
+pub(crate) fn parse_enriched_draft(
+    provider: &str,
+    text: &str,
+    draft: &TicketDraft,
+) -> AppResult<TicketDraft> {
+    let normalized = normalize_json_blob(text);
+    let enriched: ModelEnrichedDraft = serde_json::from_str(&normalized).map_err(|err| {
+        AppError::LanguageModel(format!(
+            "{provider} produced invalid JSON ({err}). Payload: {text}"
+        ))
+    })?;
+    let mut result = draft.clone();
+    if !enriched.title.trim().is_empty() {
+        result.title = enriched.title.trim().to_string();
+    }
+    if !enriched.description.trim().is_empty() {
+        result.description = enriched.description.trim().to_string();
+    }
+    let criteria = clean_items(&enriched.acceptance_criteria);
+    if !criteria.is_empty() {
+        result.acceptance_criteria = criteria;
+    }
+    result.provider = Some(provider.to_lowercase());
+    Ok(result)
+}
+
+pub(crate) fn build_amend_prompt(
+    ticket: &TicketDetails,
+    changes: &ChangeSummary,
+    guidance: &DraftGuidance,
+) -> String {
+    let description = ticket
+        .description
+        .as_deref()
+        .map(str::trim)
+        .filter(|description| !description.is_empty())
+        .unwrap_or("(none)");
+    let mut prompt = format!(
+        "Ticket {}: {}\nCurrent description:\n{}\n\nNew changes:\n{}\n\nFiles changed: {} ({})\n{}\n",
+        ticket.key,
+        ticket.title,
+        description,
+        changes.summary.trim(),
+        changes.files_changed,
+        kind_counts(changes),
+        tagged_file_list(changes),
+    );
+    if let Some(language) = guidance.language.as_deref() {
+        prompt.push_str(&format!(
+            "\nWrite the description, acceptance_criteria, and comment in {language}.\n"
+        ));
     }
+    prompt.push_str(
+        "\nReturn only JSON: {\"description\": string, \"acceptance_criteria\": [strings], \
+         \"comment\": string}.",
+    );
+    prompt
 }
 
-fn heuristic_summary(changes: &ChangeSummary) -> String {
-    let summary = changes.summary.trim();
-    if summary.is_empty() {
-        return if changes.files_changed == 0 {
-            "pending-update".to_string()
+/// Parses a model's amendment; a blank description or comment falls back to the
+/// heuristic one so the ticket never loses its text.
+pub(crate) fn parse_amendment(
+    provider: &str,
+    text: &str,
+    ticket: &TicketDetails,
+    changes: &ChangeSummary,
+) -> AppResult<TicketAmendment> {
+    let normalized = normalize_json_blob(text);
+    let amended: ModelAmendment = serde_json::from_str(&normalized).map_err(|err| {
+        AppError::LanguageModel(format!(
+            "{provider} produced invalid JSON ({err}). Payload: {text}"
+        ))
+    })?;
+    let fallback = TicketAmendment::heuristic(ticket, changes);
+    let or_fallback = |text: String, fallback: String| {
+        let text = text.trim();
+        if text.is_empty() {
+            fallback
         } else {
-            format!("update-{}-files", changes.files_changed)
-        };
+            text.to_string()
+        }
+    };
+    Ok(TicketAmendment {
+        description: or_fallback(amended.description, fallback.description),
+        acceptance_criteria: clean_items(&amended.acceptance_criteria),
+        comment: or_fallback(amended.comment, fallback.comment),
+        provider: Some(provider.to_lowercase()),
+    })
+}
+
+pub(crate) fn build_standup_prompt(facts: &StandupFacts) -> String {
+    let mut prompt = String::from("Commits by group (label, then branches):\n");
+    for group in &facts.groups {
+        prompt.push_str(&format!(
+            "\n{} ({})\n",
+            group.label(),
+            group.branches.join(", ")
+        ));
+        for commit in group.commits.iter().take(MAX_STANDUP_COMMITS) {
+            prompt.push_str(&format!("- {}\n", commit.subject.trim()));
+        }
+        if group.commits.len() > MAX_STANDUP_COMMITS {
+            prompt.push_str(&format!(
+                "- … and {} more\n",
+                group.commits.len() - MAX_STANDUP_COMMITS
+            ));
+        }
     }
+    prompt.push_str("\nReturn only JSON: {\"summaries\": {\"<label>\": string}}.");
+    prompt
+}
 
-    let words: Vec<String> = summary
-        .split_whitespace()
-        .take(8)
-        .map(|word| {
-            word.chars()
-                .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
-                .collect::<String>()
-                .to_lowercase()
+/// Parses per-group standup summaries, keeping only labels that exist in `facts`.
+pub(crate) fn parse_standup_notes(
+    provider: &str,
+    text: &str,
+    facts: &StandupFacts,
+) -> AppResult<StandupNotes> {
+    let normalized = normalize_json_blob(text);
+    let notes: ModelStandupNotes = serde_json::from_str(&normalized).map_err(|err| {
+        AppError::LanguageModel(format!(
+            "{provider} produced invalid JSON ({err}). Payload: {text}"
+        ))
+    })?;
+    let summaries: BTreeMap<String, String> = notes
+        .summaries
+        .into_iter()
+        .map(|(label, summary)| (label.trim().to_string(), summary.trim().to_string()))
+        .filter(|(label, summary)| {
+            !summary.is_empty() && facts.groups.iter().any(|group| group.label() == label)
         })
-        .filter(|word| !word.is_empty())
         .collect();
-
-    if words.is_empty() {
-        "pending-update".to_string()
-    } else {
-        words.join("-")
+    if summaries.is_empty() && !facts.groups.is_empty() {
+        return Err(AppError::LanguageModel(format!(
+            "{provider} returned no standup summaries"
+        )));
     }
+    Ok(StandupNotes { summaries })
 }
 
-fn heuristic_ticket(changes: &ChangeSummary) -> TicketDraft {
-    let branch_category = heuristic_category(changes);
-    let branch_summary = heuristic_summary(changes);
-    let description = if changes.summary.is_empty() {
-        "Summarize the local modifications before creating the ticket.".to_string()
-    } else {
-        format!("Summary of uncommitted work:\n{}", changes.summary)
+/// Parses JSON release notes, dropping blank items.
+/// List entries from the model without list markers or blank lines.
+fn clean_items(items: &[String]) -> Vec<String> {
+    items
+        .iter()
+        .map(|item| item.trim().trim_start_matches(['-', '*']).trim())
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+pub(crate) fn parse_release_notes(provider: &str, text: &str) -> AppResult<ReleaseNotes> {
+    let normalized = normalize_json_blob(text);
+    let notes: ModelReleaseNotes = serde_json::from_str(&normalized).map_err(|err| {
+        AppError::LanguageModel(format!(
+            "{provider} produced invalid JSON ({err}). Payload: {text}"
+        ))
+    })?;
+    let notes = ReleaseNotes {
+        summary: notes.summary.trim().to_string(),
+        features: clean_items(&notes.features),
+        fixes: clean_items(&notes.fixes),
+        quality: clean_items(&notes.quality),
+        other: clean_items(&notes.other),
     };
+    if notes.features.is_empty()
+        && notes.fixes.is_empty()
+        && notes.quality.is_empty()
+        && notes.other.is_empty()
+    {
+        return Err(AppError::LanguageModel(format!(
+            "{provider} returned empty release notes"
+        )));
+    }
+    Ok(notes)
+}
 
-    let title = match branch_category {
-        BranchCategory::Feature => format!("Add {}", branch_summary.replace('-', " ")),
-        BranchCategory::Fix => format!("Fix {}", branch_summary.replace('-', " ")),
-        BranchCategory::Quality => format!("Improve {}", branch_summary.replace('-', " ")),
+/// Parses the JSON notes of a retrospective, dropping blank entries.
+pub(crate) fn parse_retro_notes(provider: &str, text: &str) -> AppResult<RetroNotes> {
+    let normalized = normalize_json_blob(text);
+    let notes: ModelRetroNotes = serde_json::from_str(&normalized).map_err(|err| {
+        AppError::LanguageModel(format!(
+            "{provider} produced invalid JSON ({err}). Payload: {text}"
+        ))
+    })?;
+    let notes = RetroNotes {
+        went_well: clean_items(&notes.went_well),
+        went_poorly: clean_items(&notes.went_poorly),
+        action_items: clean_items(&notes.action_items),
     };
+    if notes.went_well.is_empty() && notes.went_poorly.is_empty() {
+        return Err(AppError::LanguageModel(format!(
+            "{provider} returned an empty retrospective"
+        )));
+    }
+    Ok(notes)
+}
 
-    TicketDraft {
-        title,
-        description,
-        branch_category,
-        branch_summary,
+/// `- path [kind]` per changed file.
+fn tagged_file_list(changes: &ChangeSummary) -> String {
+    changes
+        .tagged_files()
+        .map(|(file, kind)| format!("- {file} [{}]", kind.as_str()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// e.g. `3 source, 1 test`.
+fn kind_counts(changes: &ChangeSummary) -> String {
+    changes
+        .kind_counts()
+        .into_iter()
+        .map(|(kind, count)| format!("{count} {}", kind.as_str()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn push_estimate_request(prompt: &mut String, scale: &[u32]) {
+    if scale.is_empty() {
+        return;
     }
+    let values = scale
+        .iter()
+        .map(u32::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    prompt.push_str(&format!(
+        "\n\nAlso return an estimate key: story points from [{values}] sized to the scope of the changes."
+    ));
 }
 
 #[derive(Serialize)]
@@ -261,6 +1024,19 @@ struct GenerateContentRequest {
     #[serde(rename = "system_instruction")]
     system_instruction: Option<Instruction>,
     contents: Vec<Content>,
+    #[serde(rename = "generationConfig", skip_serializing_if = "Option::is_none")]
+    generation_config: Option<GenerationConfig>,
+}
+
+#[derive(Serialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct GenerationConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_output_tokens: Option<u32>,
 }
 
 #[derive(Serialize)]
@@ -321,11 +1097,81 @@ struct CandidatePart {
 }
 
 #[derive(Deserialize)]
-struct GeminiDraft {
+struct ModelDraft {
     title: String,
     description: String,
     branch_category: String,
     branch_summary: String,
+    #[serde(default)]
+    acceptance_criteria: Vec<String>,
+    #[serde(default)]
+    estimate: Option<Value>,
+    #[serde(default)]
+    rationale: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ModelEnrichedDraft {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    acceptance_criteria: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ModelAmendment {
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    acceptance_criteria: Vec<String>,
+    #[serde(default)]
+    comment: String,
+}
+
+#[derive(Deserialize)]
+struct ModelStandupNotes {
+    #[serde(default)]
+    summaries: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct ModelReleaseNotes {
+    #[serde(default)]
+    summary: String,
+    #[serde(default)]
+    features: Vec<String>,
+    #[serde(default)]
+    fixes: Vec<String>,
+    #[serde(default)]
+    quality: Vec<String>,
+    #[serde(default)]
+    other: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ModelRetroNotes {
+    #[serde(default)]
+    went_well: Vec<String>,
+    #[serde(default)]
+    went_poorly: Vec<String>,
+    #[serde(default)]
+    action_items: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ModelSplit {
+    #[serde(default)]
+    tickets: Vec<ModelSplitTicket>,
+}
+
+#[derive(Deserialize)]
+struct ModelSplitTicket {
+    #[serde(flatten)]
+    draft: ModelDraft,
+    #[serde(default)]
+    files: Vec<String>,
 }
 
 fn normalize_json_blob(input: &str) -> String {
@@ -349,3 +1195,33 @@ fn normalize_json_blob(input: &str) -> String {
         trimmed.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_drops_unknown_files_and_empty_clusters() {
+        let changes = ChangeSummary {
+            files_changed: 2,
+            summary: "Branch main has 2 file(s) with local changes.".to_string(),
+            files: vec!["src/pay.rs".to_string(), "README.md".to_string()],
+        };
+        let text = r#"{"tickets": [
+            {"title": "Add refunds", "description": "Support refunds.", "branch_category": "feature",
+             "branch_summary": "add-refunds", "rationale": " Both touch the refund flow. ",
+             "files": ["src/pay.rs", "src/made_up.rs"]},
+            {"title": "Ghost", "description": "Nothing real.", "branch_category": "fix",
+             "branch_summary": "ghost", "files": ["nope.rs"]}
+        ]}"#;
+
+        let drafts = parse_split_drafts("Test", text, &changes, &[]).unwrap();
+        assert_eq!(drafts.len(), 1);
+        assert_eq!(drafts[0].files, vec!["src/pay.rs".to_string()]);
+        assert_eq!(drafts[0].draft.branch_summary, "add-refunds");
+        assert_eq!(
+            drafts[0].draft.rationale.as_deref(),
+            Some("Both touch the refund flow.")
+        );
+    }
+}
@@ -1,3 +1,16 @@
+pub mod atlassian_oauth;
+pub mod azure_devops;
+pub mod change_source;
+pub mod fallback;
 pub mod git;
+pub mod git2_repo;
+pub mod github;
+pub mod heuristic;
+pub mod http;
 pub mod jira;
 pub mod llm;
+pub mod ollama;
+pub mod rate_limit;
+pub mod stream;
+pub mod template;
+pub mod webhook;
@@ -0,0 +1,279 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument};
+
+use crate::config::LlmSettings;
+use crate::domain::change::ChangeSummary;
+use crate::domain::changelog::{ChangelogFacts, ReleaseNotes};
+use crate::domain::retro::{RetroFacts, RetroNotes};
+use crate::domain::standup::{StandupFacts, StandupNotes};
+use crate::domain::ticket::{
+    DraftGuidance, SplitDraft, TicketAmendment, TicketDetails, TicketDraft,
+};
+use crate::error::{AppError, AppResult};
+use crate::infra::http::{HttpClientFactory, RetryPolicy, send_with_retry};
+use crate::infra::llm::{
+    AMEND_SYSTEM_PROMPT, DRAFT_SYSTEM_PROMPT, ENRICH_SYSTEM_PROMPT, PROMPT_VERSION,
+    RELEASE_NOTES_SYSTEM_PROMPT, RETRO_SYSTEM_PROMPT, SPLIT_SYSTEM_PROMPT, STANDUP_SYSTEM_PROMPT,
+    build_amend_prompt, build_enrich_prompt, build_release_notes_prompt, build_retro_prompt,
+    build_split_prompt, build_standup_prompt, build_user_prompt, parse_amendment,
+    parse_enriched_draft, parse_model_draft, parse_release_notes, parse_retro_notes,
+    parse_split_drafts, parse_standup_notes,
+};
+use crate::infra::stream::{StreamProgress, read_lines};
+use crate::services::LanguageModelService;
+
+pub struct OllamaClient {
+    http: Client,
+    retry: RetryPolicy,
+    base_url: String,
+    model: String,
+    options: Option<ChatOptions>,
+    stream: bool,
+}
+
+impl OllamaClient {
+    pub fn new(
+        http: &HttpClientFactory,
+        base_url: String,
+        model: String,
+        settings: &LlmSettings,
+    ) -> AppResult<Self> {
+        settings.validate()?;
+        let options = ChatOptions {
+            temperature: settings.temperature,
+            top_p: settings.top_p,
+            num_predict: settings.max_output_tokens,
+        };
+        Ok(Self {
+            http: http.build(Some(settings.timeout_or(Duration::from_secs(60))))?,
+            retry: http.retry_policy(),
+            base_url,
+            model,
+            options: (options != ChatOptions::default()).then_some(options),
+            stream: settings.streaming(),
+        })
+    }
+
+    /// Confirms the Ollama server is reachable.
+    pub async fn verify_connection(&self) -> AppResult<()> {
+        let url = format!("{}/api/tags", self.base_url.trim_end_matches('/'));
+        let response = send_with_retry(&self.retry, "Ollama", || self.http.get(&url))
+            .await
+            .map_err(|err| AppError::LanguageModel(format!("Ollama is not reachable: {err}")))?;
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err(AppError::LanguageModel(format!(
+                "Ollama responded with {status}"
+            )))
+        }
+    }
+
+    fn chat_endpoint(&self) -> String {
+        format!("{}/api/chat", self.base_url.trim_end_matches('/'))
+    }
+}
+
+impl OllamaClient {
+    /// Runs one system + user chat turn in JSON mode and returns the reply text. While a
+    /// progress display is watching, the reply is streamed as newline-delimited JSON.
+    async fn chat(&self, system_prompt: &str, user_prompt: String) -> AppResult<String> {
+        let progress = self
+            .stream
+            .then(|| StreamProgress::start("Ollama"))
+            .flatten();
+        let request = ChatRequest {
+            model: &self.model,
+            messages: vec![
+                ChatMessage {
+                    role: "system",
+                    content: system_prompt.to_string(),
+                },
+                ChatMessage {
+                    role: "user",
+                    content: user_prompt,
+                },
+            ],
+            stream: progress.is_some(),
+            format: "json",
+            options: self.options,
+        };
+
+        let endpoint = self.chat_endpoint();
+        let response = send_with_retry(&self.retry, "Ollama", || {
+            self.http.post(&endpoint).json(&request)
+        })
+        .await
+        .map_err(|err| AppError::LanguageModel(err.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "<no body>".to_string());
+            return Err(AppError::LanguageModel(format!(
+                "Ollama request returned {status}: {body}"
+            )));
+        }
+
+        let content = match &progress {
+            Some(progress) => {
+                let mut content = String::new();
+                read_lines("Ollama", response, |line| {
+                    let payload: ChatResponse = serde_json::from_str(line).map_err(|err| {
+                        AppError::LanguageModel(format!("failed to parse Ollama stream: {err}"))
+                    })?;
+                    progress.text(&payload.message.content);
+                    content.push_str(&payload.message.content);
+                    Ok(())
+                })
+                .await?;
+                content
+            }
+            None => {
+                let payload: ChatResponse = response.json().await.map_err(|err| {
+                    AppError::LanguageModel(format!("failed to parse Ollama response: {err}"))
+                })?;
+                payload.message.content
+            }
+        };
+
+        let text = content.trim();
+        if text.is_empty() {
+            return Err(AppError::LanguageModel(
+                "Ollama returned an empty response".to_string(),
+            ));
+        }
+
+        debug!(response = %text, "Ollama response received");
+        Ok(text.to_string())
+    }
+}
+
+#[async_trait]
+impl LanguageModelService for OllamaClient {
+    fn fingerprint(&self) -> String {
+        format!("ollama:{}:p{PROMPT_VERSION}", self.model)
+    }
+
+    #[instrument(level = "info", name = "ollama.draft_ticket", skip_all, fields(model = %self.model))]
+    async fn draft_ticket(
+        &self,
+        changes: &ChangeSummary,
+        guidance: &DraftGuidance,
+    ) -> AppResult<TicketDraft> {
+        let text = self
+            .chat(DRAFT_SYSTEM_PROMPT, build_user_prompt(changes, guidance))
+            .await?;
+        parse_model_draft("Ollama", &text, changes, &guidance.estimate_scale)
+    }
+
+    #[instrument(level = "info", name = "ollama.draft_tickets", skip_all, fields(model = %self.model))]
+    async fn draft_tickets(
+        &self,
+        changes: &ChangeSummary,
+        guidance: &DraftGuidance,
+    ) -> AppResult<Vec<SplitDraft>> {
+        let text = self
+            .chat(SPLIT_SYSTEM_PROMPT, build_split_prompt(changes, guidance))
+            .await?;
+        parse_split_drafts("Ollama", &text, changes, &guidance.estimate_scale)
+    }
+
+    #[instrument(level = "info", name = "ollama.retrospective_notes", skip_all, fields(model = %self.model))]
+    async fn retrospective_notes(&self, facts: &RetroFacts) -> AppResult<RetroNotes> {
+        let text = self
+            .chat(RETRO_SYSTEM_PROMPT, build_retro_prompt(facts))
+            .await?;
+        parse_retro_notes("Ollama", &text)
+    }
+
+    #[instrument(level = "info", name = "ollama.release_notes", skip_all, fields(model = %self.model))]
+    async fn release_notes(&self, facts: &ChangelogFacts) -> AppResult<ReleaseNotes> {
+        let text = self
+            .chat(
+                RELEASE_NOTES_SYSTEM_PROMPT,
+                build_release_notes_prompt(facts),
+            )
+            .await?;
+        parse_release_notes("Ollama", &text)
+    }
+
+    #[instrument(level = "info", name = "ollama.enrich_draft", skip_all, fields(model = %self.model))]
+    async fn enrich_draft(&self, draft: &TicketDraft) -> AppResult<TicketDraft> {
+        let text = self
+            .chat(ENRICH_SYSTEM_PROMPT, build_enrich_prompt(draft))
+            .await?;
+        parse_enriched_draft("Ollama", &text, draft)
+    }
+
+    #[instrument(level = "info", name = "ollama.amend_ticket", skip_all, fields(model = %self.model))]
+    async fn amend_ticket(
+        &self,
+        ticket: &TicketDetails,
+        changes: &ChangeSummary,
+        guidance: &DraftGuidance,
+    ) -> AppResult<TicketAmendment> {
+        let text = self
+            .chat(
+                AMEND_SYSTEM_PROMPT,
+                build_amend_prompt(ticket, changes, guidance),
+            )
+            .await?;
+        parse_amendment("Ollama", &text, ticket, changes)
+    }
+
+    #[instrument(level = "info", name = "ollama.standup_notes", skip_all, fields(model = %self.model))]
+    async fn standup_notes(&self, facts: &StandupFacts) -> AppResult<StandupNotes> {
+        let text = self
+            .chat(STANDUP_SYSTEM_PROMPT, build_standup_prompt(facts))
+            .await?;
+        parse_standup_notes("Ollama", &text, facts)
+    }
+}
+
+#[derive(Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage>,
+    stream: bool,
+    format: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    options: Option<ChatOptions>,
+}
+
+#[derive(Serialize, Clone, Copy, Default, PartialEq)]
+struct ChatOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    /// Ollama's name for the output token limit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_predict: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct ChatMessage {
+    role: &'static str,
+    content: String,
+}
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    /// Absent from the last line of a stream on some versions.
+    #[serde(default)]
+    message: ChatResponseMessage,
+}
+
+#[derive(Deserialize, Default)]
+struct ChatResponseMessage {
+    #[serde(default)]
+    content: String,
+}
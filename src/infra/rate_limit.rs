@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+use tracing::debug;
+
+use crate::config::RateLimitSettings;
+use crate::infra::http::host_matches;
+
+/// Spaces out requests to each host and caps how many are in flight at once. One limiter
+/// is shared by every client built from the same `HttpClientFactory`, so batch commands
+/// (`--split`, `ugh import`) stay under a tracker's or model provider's quota however
+/// many requests they issue together.
+#[derive(Debug)]
+pub struct RateLimiter {
+    default_interval: Option<Duration>,
+    /// Host patterns (`jira.corp.example`, `.corp.example`) with their own pace.
+    host_intervals: Vec<(String, Duration)>,
+    max_concurrent: Option<usize>,
+    hosts: Mutex<HashMap<String, HostState>>,
+}
+
+#[derive(Debug, Default)]
+struct HostState {
+    /// Earliest time the next request may start.
+    next_slot: Option<Instant>,
+    in_flight: Option<Arc<Semaphore>>,
+}
+
+/// Held while a request is in flight; dropping it frees the host's slot.
+pub struct RatePermit {
+    _in_flight: Option<OwnedSemaphorePermit>,
+}
+
+impl RateLimiter {
+    /// `None` when no limit is configured.
+    pub fn new(settings: &RateLimitSettings) -> Option<Self> {
+        let interval = |per_second: f64| Duration::from_secs_f64(1.0 / per_second);
+        let limiter = Self {
+            default_interval: settings.requests_per_second.map(interval),
+            host_intervals: settings
+                .hosts
+                .iter()
+                .map(|(host, per_second)| (host.trim().to_lowercase(), interval(*per_second)))
+                .filter(|(host, _)| !host.is_empty())
+                .collect(),
+            max_concurrent: settings.max_concurrent,
+            hosts: Mutex::new(HashMap::new()),
+        };
+        (limiter.default_interval.is_some()
+            || !limiter.host_intervals.is_empty()
+            || limiter.max_concurrent.is_some())
+        .then_some(limiter)
+    }
+
+    /// The pace for `host`: an exact host entry, else the longest matching domain entry,
+    /// else the default.
+    fn interval_for(&self, host: &str) -> Option<Duration> {
+        self.host_intervals
+            .iter()
+            .filter(|(pattern, _)| host_matches(pattern, host))
+            .max_by_key(|(pattern, _)| (!pattern.starts_with('.'), pattern.len()))
+            .map(|(_, interval)| *interval)
+            .or(self.default_interval)
+    }
+
+    /// Waits for a free slot on `host` and for its turn under the pace.
+    pub async fn acquire(&self, host: &str) -> RatePermit {
+        let host = host.to_lowercase();
+        let semaphore = self.max_concurrent.and_then(|limit| {
+            let mut hosts = self.hosts.lock().ok()?;
+            let state = hosts.entry(host.clone()).or_default();
+            Some(
+                state
+                    .in_flight
+                    .get_or_insert_with(|| Arc::new(Semaphore::new(limit.max(1))))
+                    .clone(),
+            )
+        });
+        let permit = match semaphore {
+            Some(semaphore) => semaphore.acquire_owned().await.ok(),
+            None => None,
+        };
+
+        if let Some(interval) = self.interval_for(&host) {
+            let slot = self.hosts.lock().ok().map(|mut hosts| {
+                let state = hosts.entry(host.clone()).or_default();
+                let now = Instant::now();
+                let slot = state.next_slot.map_or(now, |next| next.max(now));
+                state.next_slot = Some(slot + interval);
+                slot
+            });
+            if let Some(slot) = slot
+                && slot > Instant::now()
+            {
+                debug!(%host, wait_ms = (slot - Instant::now()).as_millis() as u64, "rate limited");
+                tokio::time::sleep_until(slot).await;
+            }
+        }
+        RatePermit { _in_flight: permit }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_hosts_beat_domains_which_beat_the_default() {
+        let settings = RateLimitSettings {
+            requests_per_second: Some(10.0),
+            max_concurrent: None,
+            hosts: [
+                (".corp.example".to_string(), 4.0),
+                ("jira.corp.example".to_string(), 2.0),
+            ]
+            .into(),
+        };
+        let limiter = RateLimiter::new(&settings).unwrap();
+        assert_eq!(
+            limiter.interval_for("jira.corp.example"),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(
+            limiter.interval_for("wiki.corp.example"),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(
+            limiter.interval_for("generativelanguage.googleapis.com"),
+            Some(Duration::from_millis(100))
+        );
+        assert!(RateLimiter::new(&RateLimitSettings::default()).is_none());
+    }
+}
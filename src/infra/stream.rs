@@ -0,0 +1,105 @@
+use std::sync::{Arc, RwLock};
+
+use reqwest::Response;
+
+use crate::error::{AppError, AppResult};
+
+/// Told about a model reply while it streams in, so a command can show that work is
+/// happening instead of sitting silent.
+pub trait StreamObserver: Send + Sync {
+    /// A provider started answering.
+    fn started(&self, provider: &str);
+    /// Another piece of the reply arrived.
+    fn text(&self, chunk: &str);
+    /// The reply is complete or was abandoned.
+    fn finished(&self);
+}
+
+static OBSERVER: RwLock<Option<Arc<dyn StreamObserver>>> = RwLock::new(None);
+
+/// Installs the observer for the rest of the process, or removes it with `None`. Providers
+/// only stream while one is installed.
+pub fn set_observer(observer: Option<Arc<dyn StreamObserver>>) {
+    if let Ok(mut slot) = OBSERVER.write() {
+        *slot = observer;
+    }
+}
+
+fn observer() -> Option<Arc<dyn StreamObserver>> {
+    OBSERVER.read().ok().and_then(|slot| slot.clone())
+}
+
+/// One streamed reply: reports `started` when created and `finished` when dropped, so an
+/// error midway still ends the progress display.
+pub(crate) struct StreamProgress(Arc<dyn StreamObserver>);
+
+impl StreamProgress {
+    /// `None` when nobody is watching, in which case providers skip streaming.
+    pub(crate) fn start(provider: &str) -> Option<Self> {
+        let observer = observer()?;
+        observer.started(provider);
+        Some(Self(observer))
+    }
+
+    pub(crate) fn text(&self, chunk: &str) {
+        self.0.text(chunk);
+    }
+}
+
+impl Drop for StreamProgress {
+    fn drop(&mut self) {
+        self.0.finished();
+    }
+}
+
+/// Reads a streamed body line by line (server-sent events or newline-delimited JSON),
+/// handing each non-empty line to `on_line` as it arrives.
+pub(crate) async fn read_lines(
+    service: &str,
+    mut response: Response,
+    mut on_line: impl FnMut(&str) -> AppResult<()>,
+) -> AppResult<()> {
+    let mut pending: Vec<u8> = Vec::new();
+    loop {
+        let chunk = response.chunk().await.map_err(|err| {
+            AppError::LanguageModel(format!("{service} stream was interrupted: {err}"))
+        })?;
+        let Some(chunk) = chunk else {
+            break;
+        };
+        pending.extend_from_slice(&chunk);
+        while let Some(end) = pending.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = pending.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim();
+            if !line.is_empty() {
+                on_line(line)?;
+            }
+        }
+    }
+    let rest = String::from_utf8_lossy(&pending);
+    if !rest.trim().is_empty() {
+        on_line(rest.trim())?;
+    }
+    Ok(())
+}
+
+/// The payload of a server-sent `data:` line; other SSE fields and comments are `None`.
+pub(crate) fn sse_data(line: &str) -> Option<&str> {
+    line.strip_prefix("data:")
+        .map(str::trim)
+        .filter(|data| !data.is_empty() && *data != "[DONE]")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_only_data_lines_from_server_sent_events() {
+        assert_eq!(sse_data("data: {\"a\":1}"), Some("{\"a\":1}"));
+        assert_eq!(sse_data("data:[DONE]"), None);
+        assert_eq!(sse_data(": keep-alive"), None);
+        assert_eq!(sse_data("event: message"), None);
+    }
+}
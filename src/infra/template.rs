@@ -0,0 +1,133 @@
+use std::collections::BTreeSet;
+
+use async_trait::async_trait;
+use chrono::Local;
+
+use crate::config::DraftTemplate;
+use crate::domain::branch::BranchCategory;
+use crate::domain::change::ChangeSummary;
+use crate::domain::ticket::{DraftGuidance, TicketDraft};
+use crate::error::{AppError, AppResult};
+use crate::services::LanguageModelService;
+
+pub(crate) const TEMPLATE_PROVIDER: &str = "template";
+
+/// Fills configured templates with structured change data. Used where generative output
+/// is not allowed: no model is called and nothing is inferred from the diff text.
+pub struct TemplateDrafter {
+    template: DraftTemplate,
+    branch_category: BranchCategory,
+}
+
+impl TemplateDrafter {
+    pub fn new(template: DraftTemplate) -> AppResult<Self> {
+        let branch_category =
+            BranchCategory::from_str(&template.branch_category).ok_or_else(|| {
+                AppError::Configuration(format!(
+                    "draft_template.branch_category must be feature, fix, or quality (got '{}')",
+                    template.branch_category
+                ))
+            })?;
+        Ok(Self {
+            template,
+            branch_category,
+        })
+    }
+}
+
+#[async_trait]
+impl LanguageModelService for TemplateDrafter {
+    fn fingerprint(&self) -> String {
+        "template".to_string()
+    }
+
+    async fn draft_ticket(
+        &self,
+        changes: &ChangeSummary,
+        _guidance: &DraftGuidance,
+    ) -> AppResult<TicketDraft> {
+        let values = template_values(changes);
+        let title = render_template(&self.template.title, &values);
+        let description = render_template(&self.template.description, &values);
+        let branch_summary = render_template(&self.template.branch_summary, &values);
+
+        if title.trim().is_empty() {
+            return Err(AppError::Configuration(
+                "draft_template.title rendered an empty title".to_string(),
+            ));
+        }
+
+        Ok(TicketDraft {
+            title: title.trim().to_string(),
+            description: description.trim().to_string(),
+            branch_category: self.branch_category.clone(),
+            branch_summary: branch_summary.trim().to_lowercase(),
+            acceptance_criteria: self
+                .template
+                .acceptance_criteria
+                .iter()
+                .map(|criterion| render_template(criterion, &values).trim().to_string())
+                .filter(|criterion| !criterion.is_empty())
+                .collect(),
+            estimate: None,
+            provider: Some(TEMPLATE_PROVIDER.to_string()),
+            rationale: None,
+        })
+    }
+}
+
+fn template_values(changes: &ChangeSummary) -> Vec<(&'static str, String)> {
+    let directories = changes
+        .files
+        .iter()
+        .map(|file| match file.split_once('/') {
+            Some((top, _)) => top.to_string(),
+            None => "root".to_string(),
+        })
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    vec![
+        (
+            "files",
+            changes
+                .files
+                .iter()
+                .map(|file| format!("- {file}"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ),
+        ("files_changed", changes.files_changed.to_string()),
+        ("directories", directories),
+        ("summary", changes.summary.trim().to_string()),
+        ("date", Local::now().format("%Y-%m-%d").to_string()),
+    ]
+}
+
+/// Replaces `{name}` placeholders; unknown placeholders are left untouched.
+pub(crate) fn render_template(template: &str, values: &[(&str, String)]) -> String {
+    values
+        .iter()
+        .fold(template.to_string(), |rendered, (name, value)| {
+            rendered.replace(&format!("{{{name}}}"), value)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_known_placeholders_only() {
+        let values = vec![
+            ("files_changed", "2".to_string()),
+            ("directories", "api, web".to_string()),
+        ];
+        assert_eq!(
+            render_template("Update {directories} ({files_changed}) {unknown}", &values),
+            "Update api, web (2) {unknown}"
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::{Value, json};
+use tracing::instrument;
+
+use crate::domain::ticket::TicketNotice;
+use crate::error::{AppError, AppResult};
+use crate::infra::http::{HttpClientFactory, RetryPolicy, send_with_retry};
+use crate::services::NotificationService;
+
+/// Posts to a Slack incoming webhook.
+pub struct SlackWebhook {
+    poster: WebhookPoster,
+}
+
+impl SlackWebhook {
+    pub fn new(http: &HttpClientFactory, url: String) -> AppResult<Self> {
+        Ok(Self {
+            poster: WebhookPoster::new(http, url)?,
+        })
+    }
+}
+
+#[async_trait]
+impl NotificationService for SlackWebhook {
+    fn name(&self) -> &str {
+        "Slack"
+    }
+
+    #[instrument(level = "info", name = "slack.notify", skip_all, fields(key = %notice.key))]
+    async fn notify(&self, notice: &TicketNotice) -> AppResult<()> {
+        self.poster.post("Slack", &slack_payload(notice)).await
+    }
+
+    async fn post_text(&self, text: &str) -> AppResult<()> {
+        self.poster.post("Slack", &json!({ "text": text })).await
+    }
+}
+
+/// Posts to a Microsoft Teams incoming webhook as a message card.
+pub struct TeamsWebhook {
+    poster: WebhookPoster,
+}
+
+impl TeamsWebhook {
+    pub fn new(http: &HttpClientFactory, url: String) -> AppResult<Self> {
+        Ok(Self {
+            poster: WebhookPoster::new(http, url)?,
+        })
+    }
+}
+
+#[async_trait]
+impl NotificationService for TeamsWebhook {
+    fn name(&self) -> &str {
+        "Teams"
+    }
+
+    #[instrument(level = "info", name = "teams.notify", skip_all, fields(key = %notice.key))]
+    async fn notify(&self, notice: &TicketNotice) -> AppResult<()> {
+        self.poster.post("Teams", &teams_payload(notice)).await
+    }
+
+    async fn post_text(&self, text: &str) -> AppResult<()> {
+        let card = json!({
+            "@type": "MessageCard",
+            "@context": "https://schema.org/extensions",
+            "summary": text.lines().next().unwrap_or_default(),
+            "text": text,
+        });
+        self.poster.post("Teams", &card).await
+    }
+}
+
+struct WebhookPoster {
+    http: Client,
+    retry: RetryPolicy,
+    url: String,
+}
+
+impl WebhookPoster {
+    fn new(http: &HttpClientFactory, url: String) -> AppResult<Self> {
+        Ok(Self {
+            http: http.build(Some(Duration::from_secs(15)))?,
+            retry: http.retry_policy(),
+            url,
+        })
+    }
+
+    async fn post(&self, service: &str, payload: &Value) -> AppResult<()> {
+        let response = send_with_retry(&self.retry, service, || {
+            self.http.post(&self.url).json(payload)
+        })
+        .await
+        .map_err(|err| AppError::Notification(err.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "<no body>".to_string());
+            return Err(AppError::Notification(format!(
+                "{service} webhook returned {status}: {body}"
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn slack_payload(notice: &TicketNotice) -> Value {
+    let key = match &notice.url {
+        Some(url) => format!("<{url}|{}>", notice.key),
+        None => notice.key.clone(),
+    };
+    json!({
+        "text": format!(
+            "{key} created, branch `{}` ready: {}",
+            notice.branch, notice.title
+        ),
+    })
+}
+
+fn teams_payload(notice: &TicketNotice) -> Value {
+    let key = match &notice.url {
+        Some(url) => format!("[{}]({url})", notice.key),
+        None => notice.key.clone(),
+    };
+    json!({
+        "@type": "MessageCard",
+        "@context": "https://schema.org/extensions",
+        "summary": notice.summary(),
+        "text": format!(
+            "{key} created, branch `{}` ready: {}",
+            notice.branch, notice.title
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payloads_link_the_ticket_and_name_the_branch() {
+        let notice = TicketNotice {
+            key: "PAY-123".to_string(),
+            url: Some("https://example.atlassian.net/browse/PAY-123".to_string()),
+            title: "Add refunds".to_string(),
+            branch: "feature/PAY-123/add-refunds".to_string(),
+        };
+
+        assert_eq!(
+            slack_payload(&notice)["text"],
+            "<https://example.atlassian.net/browse/PAY-123|PAY-123> created, branch \
+             `feature/PAY-123/add-refunds` ready: Add refunds"
+        );
+        let teams = teams_payload(&notice);
+        assert_eq!(
+            teams["summary"],
+            "PAY-123 created, branch feature/PAY-123/add-refunds ready"
+        );
+        assert!(
+            teams["text"]
+                .as_str()
+                .unwrap()
+                .starts_with("[PAY-123](https://example.atlassian.net/browse/PAY-123)")
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use std::env;
+
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::fmt::format::FmtSpan;
+
+const LOG_ENV_VAR: &str = "UGH_LOG";
+
+/// Installs the stderr logger.
+///
+/// `UGH_LOG` (an `EnvFilter` directive such as `ugh=debug`) takes precedence over the
+/// `-v` count. With one or more `-v`, span close events report how long git, Jira, and
+/// LLM calls took.
+pub fn init(verbosity: u8) {
+    let default_directive = match verbosity {
+        0 => "warn",
+        1 => "warn,ugh=info",
+        2 => "warn,ugh=debug",
+        _ => "info,ugh=trace",
+    };
+
+    let filter = env::var(LOG_ENV_VAR)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .and_then(|value| EnvFilter::try_new(value).ok())
+        .unwrap_or_else(|| EnvFilter::new(default_directive));
+
+    let span_events = if verbosity > 0 || env::var(LOG_ENV_VAR).is_ok() {
+        FmtSpan::CLOSE
+    } else {
+        FmtSpan::NONE
+    };
+
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_span_events(span_events)
+        .with_target(verbosity > 0)
+        .without_time()
+        .try_init();
+}
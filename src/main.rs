@@ -1,30 +1,70 @@
 mod cache;
 mod cmd;
 mod config;
+mod config_file;
 mod context;
 mod domain;
+mod env_file;
 mod error;
+mod feedback;
+mod history;
 mod infra;
+mod logging;
+mod metadata;
+mod plugins;
+mod repo_config;
+mod repo_state;
 mod services;
+mod storage;
 mod workflow;
 
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
 
-use clap::{Args, Parser, Subcommand};
+use chrono::{Local, NaiveDate};
+use clap::{ArgAction, Args, CommandFactory, Parser, Subcommand};
+use serde_json::Value;
+use tracing::warn;
 
+use crate::cmd::agents::{self as agents_cmd, AgentsArgs};
+use crate::cmd::board::{self as board_cmd, BoardArgs};
+use crate::cmd::branch_name::{self as branch_name_cmd, BranchNameArgs};
+use crate::cmd::cache::{self as cache_cmd, CacheArgs};
+use crate::cmd::changelog::{self as changelog_cmd, ChangelogArgs};
+use crate::cmd::completions::{self as completions_cmd, CompletionsArgs};
 use crate::cmd::config::{self as config_cmd, ConfigArgs, ConfigCommand};
-use crate::cmd::ticket::{self, TicketCommandArgs};
-use crate::config::{AppConfig, LlmProvider};
+use crate::cmd::digest::{self as digest_cmd, DigestArgs};
+use crate::cmd::epic::{self as epic_cmd, EpicArgs};
+use crate::cmd::feedback::{self as feedback_cmd, FeedbackArgs};
+use crate::cmd::history::{self as history_cmd, HistoryArgs};
+use crate::cmd::hooks::{self as hooks_cmd, HooksArgs};
+use crate::cmd::import::{self as import_cmd, ImportArgs};
+use crate::cmd::onboarding;
+use crate::cmd::open::{self as open_cmd, OpenArgs};
+use crate::cmd::retro::{self as retro_cmd, RetroArgs};
+use crate::cmd::standup::{self as standup_cmd, StandupArgs};
+use crate::cmd::ticket::{self, AmendField, PrintField, TicketCommandArgs};
+use crate::cmd::undo::{self as undo_cmd, UndoArgs};
+use crate::cmd::worklog::{self as worklog_cmd, WorklogArgs};
+use crate::config::{
+    AppConfig, DraftingMode, JiraAuthMode, LlmProvider, TrackerKind, is_first_run,
+};
 use crate::context::AppContext;
+use crate::domain::due::parse_due_date;
 use crate::error::{AppError, AppResult};
-use crate::infra::git::GitCli;
-use crate::infra::jira::JiraClient;
-use crate::infra::llm::GeminiClient;
-use crate::services::LanguageModelService;
 
 #[derive(Parser)]
 #[command(name = "ugh", author, version, about = "Multi-agent developer CLI")]
 struct Cli {
+    /// Increase log verbosity (-v for timings, -vv for debug detail). `UGH_LOG` overrides.
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+    /// Never prompt: missing settings and confirmations become errors. Also `UGH_CI=1`.
+    #[arg(long, global = true)]
+    non_interactive: bool,
+    /// Make no language model calls; drafts and notes come from heuristics. Same as
+    /// `llm_provider = "none"`.
+    #[arg(long, global = true)]
+    no_llm: bool,
     #[command(subcommand)]
     command: Commands,
 }
@@ -33,8 +73,46 @@ struct Cli {
 enum Commands {
     /// Generate a ticket from local changes and create a matching branch.
     Ticket(TicketArgs),
+    /// Draft a ticket for local changes and print only its branch name, for scripts.
+    BranchName(BranchNameArgs),
+    /// Create tickets in bulk from a YAML or CSV backlog file.
+    Import(ImportArgs),
+    /// Create a Jira epic and file this repository's future tickets under it.
+    Epic(EpicArgs),
+    /// Browse the tracker's boards (projects).
+    Board(BoardArgs),
+    /// Open the current branch's ticket (or `board`, or a given key) in the browser.
+    Open(OpenArgs),
+    /// Log time spent on the current branch's ticket, e.g. `ugh worklog --time 2h`.
+    Worklog(WorklogArgs),
     /// Manage CLI configuration.
     Config(ConfigArgs),
+    /// Approve or revoke the hooks and webhooks in this repository's `.ugh.toml`.
+    Hooks(HooksArgs),
+    /// Rate the last generated draft to improve future drafts.
+    Feedback(FeedbackArgs),
+    /// List previously created tickets and their branches.
+    History(HistoryArgs),
+    /// Roll back the last run: remove its ticket and branch.
+    Undo(UndoArgs),
+    /// Draft a retrospective of a sprint or period from the tickets completed in it.
+    #[command(alias = "retro")]
+    Retrospective(RetroArgs),
+    /// Write Markdown release notes for a revision range, e.g. `v1.2.0..HEAD`.
+    Changelog(ChangelogArgs),
+    /// Summarize your recent commits across local branches, grouped by ticket.
+    Standup(StandupArgs),
+    /// Summarize today's tickets and branches across repositories, plus work in progress.
+    Digest(DigestArgs),
+    /// Inspect or prune the cache of drafted tickets.
+    Cache(CacheArgs),
+    /// Print a shell completion script, e.g. `ugh completions zsh > ~/.zfunc/_ugh`.
+    Completions(CompletionsArgs),
+    /// Show the agents a ticket run passes through and how each is configured.
+    Agents(AgentsArgs),
+    /// Print the roff man page.
+    #[command(hide = true)]
+    Man,
 }
 
 #[derive(Args)]
@@ -42,6 +120,103 @@ struct TicketArgs {
     /// Override the default board configured in the CLI.
     #[arg(short, long)]
     board: Option<String>,
+    /// Send change details to the LLM without scrubbing secrets.
+    #[arg(long)]
+    no_redact: bool,
+    /// File the ticket under this epic instead of the repository's default.
+    #[arg(long, conflicts_with = "no_epic")]
+    epic: Option<String>,
+    /// Do not link the ticket to the repository's default epic.
+    #[arg(long)]
+    no_epic: bool,
+    /// Assign the ticket to the Jira user matching this name or email.
+    #[arg(long, value_name = "QUERY")]
+    assignee: Option<String>,
+    /// File the ticket on behalf of the Jira user matching this name or email.
+    #[arg(long, value_name = "QUERY")]
+    reporter: Option<String>,
+    /// Due date, e.g. 2025-03-14, "tomorrow", "next friday", or "in 3 days".
+    #[arg(long, value_parser = parse_due_arg)]
+    due: Option<NaiveDate>,
+    /// Cluster unrelated changes into several tickets and choose which to create.
+    #[arg(long)]
+    split: bool,
+    /// Only consider changes under this directory (repeatable). Defaults to the enclosing
+    /// Cargo or npm package when run from inside one.
+    #[arg(long = "path", value_name = "DIR")]
+    paths: Vec<PathBuf>,
+    /// Set a Jira custom field (repeatable), e.g. customfield_10010=Platform. JSON values
+    /// such as '{"value":"Platform"}' are sent as-is.
+    #[arg(long = "field", value_name = "ID=VALUE", value_parser = parse_field_arg)]
+    fields: Vec<(String, Value)>,
+    /// Apply a named preset from the config's `presets` (issue type, labels, priority,
+    /// branch template, base branch).
+    #[arg(long, value_name = "NAME")]
+    preset: Option<String>,
+    /// Move the new ticket to the board's backlog.
+    #[arg(long, conflicts_with = "sprint")]
+    backlog: bool,
+    /// Move the new ticket into this sprint: an ID, or a name matched loosely against the
+    /// board's active and future sprints.
+    #[arg(long, value_name = "NAME|ID")]
+    sprint: Option<String>,
+    /// Check the new branch out in a new git worktree instead of this tree (default path:
+    /// a sibling directory named after the branch).
+    #[arg(long, value_name = "PATH", num_args = 0..=1)]
+    worktree: Option<Option<PathBuf>>,
+    /// Show the draft beside the changed files, flag claims no file supports, and confirm
+    /// before filing.
+    #[arg(long, conflicts_with = "split")]
+    preview: bool,
+    /// Don't post the configured Slack or Teams notification for this run.
+    #[arg(long)]
+    no_notify: bool,
+    /// Review the draft beside the change summary in a terminal UI: edit the title,
+    /// category, slug, description, and criteria, regenerate, then file or cancel.
+    #[arg(long, conflicts_with_all = ["split", "preview"])]
+    tui: bool,
+    /// Open the draft's title, category, slug, description, and criteria in $VISUAL or
+    /// $EDITOR as Markdown, and file what you save.
+    #[arg(long, conflicts_with_all = ["split", "tui"])]
+    edit: bool,
+    /// Draft from these changes instead of the working tree: a patch file (`-` for stdin),
+    /// a GitHub pull request URL, or an editor's unsaved-buffer dump (`*.json`).
+    #[arg(long, value_name = "SOURCE")]
+    from: Option<String>,
+    /// File one ticket for the changes in every repository listed in `workspace_repos`
+    /// and create its branch, under the same name, in each one that has changes.
+    #[arg(long, conflicts_with_all = ["split", "from", "paths"])]
+    workspace: bool,
+    /// Update the ticket the current branch was created for instead of filing one: rewrite
+    /// its description from the new changes (default) or add a follow-up comment.
+    #[arg(
+        long,
+        value_enum,
+        value_name = "WHAT",
+        num_args = 0..=1,
+        default_missing_value = "description",
+        conflicts_with_all = [
+            "board", "epic", "no_epic", "assignee", "reporter", "due", "split", "fields",
+            "preset", "backlog", "sprint", "worktree", "tui", "edit", "from", "workspace",
+            "explain", "print"
+        ]
+    )]
+    amend: Option<AmendField>,
+    /// After filing, list which agent wrote the draft and what each later one changed.
+    #[arg(long)]
+    explain: bool,
+    /// Print nothing but errors and warnings.
+    #[arg(short, long)]
+    quiet: bool,
+    /// Print only this value of the new ticket, e.g. `git push origin "$(ugh ticket --print
+    /// branch)"`.
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FIELD",
+        conflicts_with_all = ["quiet", "explain", "split", "preview", "tui", "edit"]
+    )]
+    print: Option<PrintField>,
 }
 
 #[tokio::main]
@@ -54,24 +229,58 @@ async fn main() {
 
 async fn run() -> AppResult<()> {
     let cli = Cli::parse();
+    logging::init(cli.verbose);
+    env_file::load(&std::env::current_dir()?)?;
+    config_cmd::set_non_interactive(cli.non_interactive || env_flag("UGH_CI"));
 
     match cli.command {
-        Commands::Config(args) => {
-            config_cmd::run(args.command)?;
-            Ok(())
-        }
-        Commands::Ticket(args) => run_ticket(args).await,
+        Commands::Config(args) => config_cmd::run(args.command).await,
+        Commands::Hooks(args) => hooks_cmd::run(args),
+        Commands::Feedback(args) => feedback_cmd::run(args),
+        Commands::History(args) => history_cmd::run(args),
+        Commands::Cache(args) => cache_cmd::run(&AppConfig::load(&std::env::current_dir()?)?, args),
+        Commands::Undo(args) => undo_cmd::run(&load_context(cli.no_llm)?, args).await,
+        Commands::Ticket(args) => run_ticket(args, cli.no_llm).await,
+        Commands::Epic(args) => run_epic(args).await,
+        Commands::BranchName(args) => branch_name_cmd::run(&load_context(cli.no_llm)?, args).await,
+        Commands::Import(args) => import_cmd::run(&load_context(cli.no_llm)?, args).await,
+        Commands::Board(args) => board_cmd::run(&load_context(cli.no_llm)?, args).await,
+        Commands::Open(args) => open_cmd::run(&load_context(cli.no_llm)?, args).await,
+        Commands::Worklog(args) => worklog_cmd::run(&load_context(cli.no_llm)?, args).await,
+        Commands::Retrospective(args) => retro_cmd::run(&load_context(cli.no_llm)?, args).await,
+        Commands::Changelog(args) => changelog_cmd::run(&load_context(cli.no_llm)?, args).await,
+        Commands::Standup(args) => standup_cmd::run(&load_context(cli.no_llm)?, args).await,
+        Commands::Digest(args) => digest_cmd::run(&load_context(cli.no_llm)?, args).await,
+        Commands::Agents(args) => agents_cmd::run(&load_context(cli.no_llm)?, args),
+        Commands::Completions(args) => completions_cmd::run(Cli::command(), args),
+        Commands::Man => completions_cmd::man_page(Cli::command()),
     }
 }
 
-async fn run_ticket(args: TicketArgs) -> AppResult<()> {
+async fn run_ticket(args: TicketArgs, no_llm: bool) -> AppResult<()> {
     let cwd = std::env::current_dir()?;
-    let mut config = AppConfig::load(&cwd)?;
+    let mut config = load_config(&cwd, no_llm)?;
 
     if let Some(missing) = missing_required_settings(&config, args.board.as_ref()) {
+        if config_cmd::is_non_interactive() {
+            return Err(AppError::Configuration(format!(
+                "Required settings missing ({missing}). Set them in the config file or via \
+                 UGH_* environment variables; setup cannot run non-interactively."
+            )));
+        }
+        // Interactive setup only knows Jira.
+        if config.tracker == TrackerKind::AzureDevOps {
+            return Err(AppError::Configuration(format!(
+                "Required settings missing ({missing}). Set them in the azure_devops section \
+                 of the config file or via UGH_AZURE_DEVOPS_* environment variables."
+            )));
+        }
+        if is_first_run()? {
+            return onboarding::run(&cwd).await;
+        }
         eprintln!("Configuration incomplete ({missing}). Launching setup...");
-        config_cmd::run(ConfigCommand::Init)?;
-        config = AppConfig::load(&cwd)?;
+        config_cmd::run(ConfigCommand::Init).await?;
+        config = load_config(&cwd, no_llm)?;
         if let Some(missing_after) = missing_required_settings(&config, args.board.as_ref()) {
             return Err(AppError::Configuration(format!(
                 "Required settings still missing after setup ({missing_after}). \
@@ -80,63 +289,187 @@ async fn run_ticket(args: TicketArgs) -> AppResult<()> {
         }
     }
 
-    let gemini_api_key = config.gemini_api_key.clone();
-    let gemini_model = config.gemini_model.clone();
-    let jira_base_url = config.jira_base_url.clone();
-    let jira_email = config.jira_email.clone();
-    let jira_token = config.jira_token.clone();
-    let jira_issue_type = config.jira_issue_type.clone();
+    if config.tracker == TrackerKind::Jira {
+        if config.jira_base_url.is_none() {
+            warn!("Jira base URL not configured; ticket creation and links may fail.");
+        }
+        if config.jira_auth == JiraAuthMode::ApiToken {
+            if config.jira_email.is_none() {
+                warn!("Jira email not configured; ticket creation may fail.");
+            }
+            if config.jira_token.is_none() {
+                warn!("Jira token not configured; ticket creation may fail.");
+            }
+        }
+    }
+    if config.drafting == DraftingMode::Llm
+        && config.llm_providers.contains(&LlmProvider::Gemini)
+        && config.gemini_api_key.is_none()
+    {
+        warn!("Gemini API key not configured; ticket drafting may fail.");
+    }
+
+    let context = AppContext::from_config(config)?;
 
-    if jira_base_url.is_none() {
-        eprintln!("Warning: Jira base URL not configured; ticket creation and links may fail.");
+    let explain = args.explain;
+    let quiet = args.quiet;
+    let workspace = args.workspace;
+    let print = args.print;
+    let amend = args.amend;
+    let command_args = TicketCommandArgs {
+        board: args.board,
+        no_redact: args.no_redact,
+        epic: args.epic,
+        no_epic: args.no_epic,
+        assignee: args.assignee,
+        reporter: args.reporter,
+        due_date: args.due,
+        split: args.split,
+        paths: args.paths,
+        fields: args.fields,
+        preset: args.preset,
+        backlog: args.backlog,
+        sprint: args.sprint,
+        worktree: args.worktree,
+        preview: args.preview,
+        no_notify: args.no_notify,
+        tui: args.tui,
+        edit: args.edit,
+        from: args.from,
+        progress: !quiet,
+        workspace: args.workspace,
+    };
+    if let Some(field) = amend {
+        if let Some(ticket) = ticket::amend(&context, command_args, field).await?
+            && !quiet
+        {
+            match field {
+                AmendField::Description => println!("Updated {}.", ticket.key),
+                AmendField::Comment => println!("Commented on {}.", ticket.key),
+            }
+            if let Some(url) = &ticket.url {
+                println!("View ticket: {url}");
+            }
+        }
+        return Ok(());
     }
-    if jira_email.is_none() {
-        eprintln!("Warning: Jira email not configured; ticket creation may fail.");
+    let outcomes = ticket::run(&context, command_args).await?;
+
+    if let Some(field) = print {
+        // A workspace run reports one ticket through several outcomes.
+        let mut printed = Vec::new();
+        for outcome in &outcomes {
+            match field.value(outcome) {
+                Some(value) if printed.contains(&value) => {}
+                Some(value) => {
+                    println!("{value}");
+                    printed.push(value);
+                }
+                None => warn!("the tracker returned no URL for {}.", outcome.ticket.key),
+            }
+        }
+        return Ok(());
     }
-    if jira_token.is_none() {
-        eprintln!("Warning: Jira token not configured; ticket creation may fail.");
+    if quiet {
+        return Ok(());
     }
-    if config.gemini_api_key.is_none() {
-        eprintln!("Warning: Gemini API key not configured; ticket drafting may fail.");
+    if workspace && let Some(first) = outcomes.first() {
+        println!("Ticket {} created.", first.ticket.key);
+        if let Some(url) = &first.ticket.url {
+            println!("View ticket: {url}");
+        }
+        for outcome in &outcomes {
+            println!(
+                "Branch ready in {}: {}",
+                outcome.repo.display(),
+                outcome.branch.as_str()
+            );
+            if let Some(worktree) = &outcome.worktree {
+                println!("Worktree ready: cd {}", worktree.display());
+            }
+        }
+        if explain {
+            print!(
+                "Agents that shaped {}:\n{}",
+                first.ticket.key,
+                first.agents.render()
+            );
+        }
+        return Ok(());
     }
-
-    let language_model: Arc<dyn LanguageModelService> = match &config.llm_provider {
-        LlmProvider::Gemini => Arc::new(GeminiClient::new(
-            gemini_api_key.clone(),
-            gemini_model.clone(),
-        )),
-        LlmProvider::Custom(provider) => {
-            eprintln!(
-                "Warning: custom LLM provider '{provider}' not yet implemented, using Gemini fallback."
+    for outcome in &outcomes {
+        println!(
+            "Ticket {} created. Branch ready: {}",
+            outcome.ticket.key,
+            outcome.branch.as_str()
+        );
+        if let Some(url) = &outcome.ticket.url {
+            println!("View ticket: {url}");
+        }
+        if let Some(worktree) = &outcome.worktree {
+            println!("Worktree ready: cd {}", worktree.display());
+        }
+        if explain {
+            print!(
+                "Agents that shaped {}:\n{}",
+                outcome.ticket.key,
+                outcome.agents.render()
             );
-            Arc::new(GeminiClient::new(
-                gemini_api_key.clone(),
-                gemini_model.clone(),
-            ))
         }
-    };
+    }
 
-    let git = Arc::new(GitCli::new(config.workspace_root.clone()));
-    let issue_tracker = Arc::new(JiraClient::new(
-        jira_base_url,
-        jira_email,
-        jira_token,
-        jira_issue_type,
-    ));
+    Ok(())
+}
+
+/// `1`, `true`, or `yes` (any case) enable a boolean environment switch.
+fn env_flag(name: &str) -> bool {
+    env_file::var(name)
+        .is_some_and(|value| matches!(value.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+}
 
-    let context = AppContext::new(config, git, issue_tracker, language_model);
+fn parse_due_arg(value: &str) -> Result<NaiveDate, String> {
+    parse_due_date(value, Local::now().date_naive())
+}
+
+fn parse_field_arg(value: &str) -> Result<(String, Value), String> {
+    let (field, raw) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected ID=VALUE, got '{value}'"))?;
+    let field = field.trim();
+    if field.is_empty() {
+        return Err("field ID must not be empty".to_string());
+    }
+    let value = serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()));
+    Ok((field.to_string(), value))
+}
 
-    let outcome = ticket::run(&context, TicketCommandArgs { board: args.board }).await?;
+/// Context for commands that skip the ticket flow's setup checks.
+fn load_context(no_llm: bool) -> AppResult<AppContext> {
+    let cwd = std::env::current_dir()?;
+    AppContext::from_config(load_config(&cwd, no_llm)?)
+}
 
-    println!(
-        "Ticket {} created. Branch ready: {}",
-        outcome.ticket.key,
-        outcome.branch.as_str()
-    );
-    if let Some(url) = &outcome.ticket.url {
-        println!("View ticket: {url}");
+fn load_config(cwd: &Path, no_llm: bool) -> AppResult<AppConfig> {
+    let mut config = AppConfig::load(cwd)?;
+    if no_llm {
+        config.disable_llm();
     }
+    Ok(config)
+}
 
+async fn run_epic(args: EpicArgs) -> AppResult<()> {
+    let context = load_context(false)?;
+
+    let set_default = !args.no_default;
+    let epic = epic_cmd::run(&context, args).await?;
+
+    println!("Epic {} created.", epic.key);
+    if set_default {
+        println!("New tickets from this repository will be filed under it.");
+    }
+    if let Some(url) = &epic.url {
+        println!("View epic: {url}");
+    }
     Ok(())
 }
 
@@ -145,19 +478,38 @@ fn missing_required_settings(
     board_override: Option<&String>,
 ) -> Option<String> {
     let mut missing = Vec::new();
-    if config.jira_base_url.is_none() {
-        missing.push("Jira base URL");
-    }
-    if config.jira_email.is_none() {
-        missing.push("Jira email");
-    }
-    if config.jira_token.is_none() {
-        missing.push("Jira API token");
-    }
-    if board_override.is_none() && config.default_board.is_none() {
-        missing.push("default Jira board");
+    let no_board =
+        board_override.is_none() && config.default_board.is_none() && config.path_boards.is_empty();
+    match config.tracker {
+        TrackerKind::Jira => {
+            if config.jira_base_url.is_none() {
+                missing.push("Jira base URL");
+            }
+            if config.jira_auth == JiraAuthMode::ApiToken {
+                if config.jira_email.is_none() {
+                    missing.push("Jira email");
+                }
+                if config.jira_token.is_none() {
+                    missing.push("Jira API token");
+                }
+            }
+            if no_board {
+                missing.push("default Jira board");
+            }
+        }
+        TrackerKind::AzureDevOps => {
+            if config.azure_devops.organization_url().is_none() {
+                missing.push("Azure DevOps organization");
+            }
+            if config.azure_devops.pat.is_none() {
+                missing.push("Azure DevOps personal access token");
+            }
+            if no_board {
+                missing.push("Azure DevOps project");
+            }
+        }
     }
-    if config.gemini_api_key.is_none() {
+    if requires_gemini_key(config) && config.gemini_api_key.is_none() {
         missing.push("Gemini API key");
     }
 
@@ -167,3 +519,28 @@ fn missing_required_settings(
         Some(missing.join(", "))
     }
 }
+
+/// Gemini credentials are only mandatory when no other usable provider is in the chain.
+fn requires_gemini_key(config: &AppConfig) -> bool {
+    config.drafting == DraftingMode::Llm
+        && config.llm_providers.contains(&LlmProvider::Gemini)
+        && !config.llm_providers.contains(&LlmProvider::Ollama)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cli_definition_is_consistent_and_documented() {
+        let command = Cli::command();
+        command.clone().debug_assert();
+        for sub in command.get_subcommands() {
+            assert!(
+                sub.get_about().is_some(),
+                "`{}` has no help text",
+                sub.get_name()
+            );
+        }
+    }
+}
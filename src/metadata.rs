@@ -0,0 +1,82 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::config::config_directory;
+use crate::error::AppResult;
+use crate::storage::{read_json_file, update_json_file};
+
+const METADATA_FILE_NAME: &str = "metadata_cache.json";
+
+#[derive(Default, Serialize, Deserialize)]
+struct MetadataFile {
+    #[serde(default)]
+    entries: BTreeMap<String, MetadataEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct MetadataEntry {
+    fetched_at: DateTime<Utc>,
+    value: Value,
+}
+
+/// Tracker lookups (users, fields, ...) that rarely change, kept for a caller-chosen TTL.
+/// Like the draft cache, writes are replayed onto the latest file contents under a lock.
+pub struct MetadataCache {
+    file_path: PathBuf,
+    file: MetadataFile,
+    pending: Vec<(String, MetadataEntry)>,
+}
+
+impl MetadataCache {
+    pub fn load() -> AppResult<Self> {
+        let path = config_directory()?.join(METADATA_FILE_NAME);
+        let file = read_json_file(&path, "metadata cache")?;
+        Ok(Self {
+            file_path: path,
+            file,
+            pending: Vec::new(),
+        })
+    }
+
+    /// Returns the cached value for `key` if it is younger than `ttl` and still parses.
+    pub fn get<T: DeserializeOwned>(&self, key: &str, ttl: Duration) -> Option<T> {
+        let entry = self.file.entries.get(key)?;
+        let age = Utc::now().signed_duration_since(entry.fetched_at);
+        if age.to_std().is_ok_and(|age| age > ttl) {
+            return None;
+        }
+        serde_json::from_value(entry.value.clone()).ok()
+    }
+
+    pub fn insert<T: Serialize>(&mut self, key: &str, value: &T) {
+        let Ok(value) = serde_json::to_value(value) else {
+            return;
+        };
+        let entry = MetadataEntry {
+            fetched_at: Utc::now(),
+            value,
+        };
+        self.file.entries.insert(key.to_string(), entry.clone());
+        self.pending.push((key.to_string(), entry));
+    }
+
+    pub fn save(&mut self) -> AppResult<()> {
+        let pending = std::mem::take(&mut self.pending);
+        self.file = update_json_file(
+            &self.file_path,
+            "metadata cache",
+            |file: &mut MetadataFile| {
+                for (key, entry) in pending {
+                    file.entries.insert(key, entry);
+                }
+            },
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::config::AppConfig;
+use crate::context::AppContext;
+use crate::domain::ticket::TicketDraft;
+use crate::error::{AppError, AppResult};
+use crate::workflow::steps::WorkflowStep;
+
+pub const STEP_NAME: &str = "require-acceptance-criteria";
+
+/// Refuses to file tickets whose draft has no acceptance criteria.
+pub struct AcceptanceCriteriaCheck;
+
+impl AcceptanceCriteriaCheck {
+    pub fn build(_config: &AppConfig) -> AppResult<Arc<dyn WorkflowStep>> {
+        Ok(Arc::new(Self))
+    }
+}
+
+#[async_trait]
+impl WorkflowStep for AcceptanceCriteriaCheck {
+    fn name(&self) -> &str {
+        STEP_NAME
+    }
+
+    fn description(&self) -> &str {
+        "rejects drafts without acceptance criteria"
+    }
+
+    async fn review_draft(
+        &self,
+        _ctx: &AppContext,
+        board: &str,
+        draft: &mut TicketDraft,
+    ) -> AppResult<()> {
+        if draft.acceptance_criteria.is_empty() {
+            return Err(AppError::WorkflowStep(format!(
+                "{STEP_NAME}: the draft for board {board} has no acceptance criteria"
+            )));
+        }
+        Ok(())
+    }
+}
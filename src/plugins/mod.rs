@@ -0,0 +1,16 @@
+//! Workflow steps compiled into the binary.
+//!
+//! To add a step, implement [`WorkflowStep`](crate::workflow::steps::WorkflowStep) in a
+//! module here and register a factory for it below. Users enable it by listing its name
+//! under `workflow_steps` in the config.
+
+mod compliance;
+
+use crate::workflow::steps::StepRegistry;
+
+pub fn register(registry: &mut StepRegistry) {
+    registry.register(
+        compliance::STEP_NAME,
+        compliance::AcceptanceCriteriaCheck::build,
+    );
+}
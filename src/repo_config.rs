@@ -0,0 +1,160 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use ring::digest;
+use serde::Deserialize;
+use tracing::warn;
+
+use crate::error::{AppError, AppResult};
+use crate::repo_state::{RepoStateStore, main_worktree};
+
+/// Checked-in settings shared by everyone working in a repository.
+pub const REPO_CONFIG_FILE_NAME: &str = ".ugh.toml";
+
+/// The contents of a repository's `.ugh.toml`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RepoConfig {
+    #[serde(default)]
+    pub hooks: HookCommands,
+    #[serde(default)]
+    pub notify: NotifySettings,
+}
+
+/// Where this repository's new tickets are announced, overriding the global webhooks.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NotifySettings {
+    /// `false` keeps this repository quiet even when global webhooks are configured.
+    pub enabled: Option<bool>,
+    pub slack_webhook_url: Option<String>,
+    pub teams_webhook_url: Option<String>,
+}
+
+/// Shell commands run at fixed points of `ugh ticket`, from the repository root.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HookCommands {
+    /// Sees the finished draft before it is filed; may veto it or print a replacement.
+    pub pre_draft: Option<String>,
+    /// Runs once the ticket exists; a failure stops the run before the branch is created.
+    pub post_ticket: Option<String>,
+    /// Runs once the branch exists; failures are only reported.
+    pub post_branch: Option<String>,
+}
+
+impl HookCommands {
+    pub fn is_empty(&self) -> bool {
+        self.pre_draft.is_none() && self.post_ticket.is_none() && self.post_branch.is_none()
+    }
+}
+
+/// A repository's `.ugh.toml` as found on disk.
+pub struct RepoConfigFile {
+    pub path: PathBuf,
+    pub config: RepoConfig,
+    /// Hex SHA-256 of the file, recorded by `ugh hooks allow`.
+    pub digest: String,
+}
+
+impl RepoConfig {
+    /// Reads `.ugh.toml` from `repo_root`, or from the main worktree when `repo_root` is a
+    /// linked worktree without its own copy (e.g. an untracked file).
+    pub fn find(repo_root: &Path) -> AppResult<Option<RepoConfigFile>> {
+        let mut path = repo_root.join(REPO_CONFIG_FILE_NAME);
+        if !path.exists()
+            && let Some(main) = main_worktree(repo_root)
+        {
+            path = main.join(REPO_CONFIG_FILE_NAME);
+        }
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let config = toml::from_str(&contents)
+            .map_err(|err| AppError::Configuration(format!("invalid {}: {err}", path.display())))?;
+        let digest = digest::digest(&digest::SHA256, contents.as_bytes())
+            .as_ref()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        Ok(Some(RepoConfigFile {
+            path,
+            config,
+            digest,
+        }))
+    }
+
+    /// The repository's settings, with its hooks and webhook URLs left out unless the user
+    /// approved this exact file with `ugh hooks allow`: a cloned repository must not run
+    /// commands or redirect notifications on its own. A repository without one gets the
+    /// defaults.
+    pub fn load_approved(repo_root: &Path) -> AppResult<Self> {
+        let Some(file) = Self::find(repo_root)? else {
+            return Ok(Self::default());
+        };
+        if !file.config.needs_approval() {
+            return Ok(file.config);
+        }
+        let approved = RepoStateStore::open(repo_root)?
+            .load()?
+            .approved_repo_config;
+        if approved.as_deref() == Some(file.digest.as_str()) {
+            return Ok(file.config);
+        }
+        warn!(
+            "ignoring the hooks and webhooks in {} until you review it and run `ugh hooks allow`.",
+            file.path.display()
+        );
+        Ok(file.config.without_hooks_and_webhooks())
+    }
+
+    /// True when the file runs commands or sends notifications somewhere.
+    pub fn needs_approval(&self) -> bool {
+        !self.hooks.is_empty()
+            || self.notify.slack_webhook_url.is_some()
+            || self.notify.teams_webhook_url.is_some()
+    }
+
+    /// Keeps only what is safe without approval; `enabled = false` can only quiet things.
+    fn without_hooks_and_webhooks(self) -> Self {
+        Self {
+            hooks: HookCommands::default(),
+            notify: NotifySettings {
+                enabled: self.notify.enabled,
+                slack_webhook_url: None,
+                teams_webhook_url: None,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hooks_and_rejects_unknown_keys() {
+        let config: RepoConfig = toml::from_str(
+            "[hooks]\npre_draft = \"scripts/check-title.sh\"\npost_ticket = \"./notify.sh\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.hooks.pre_draft.as_deref(),
+            Some("scripts/check-title.sh")
+        );
+        assert!(config.hooks.post_branch.is_none());
+        assert!(!config.hooks.is_empty());
+
+        assert!(toml::from_str::<RepoConfig>("[hooks]\npre_ticket = \"x\"\n").is_err());
+
+        assert!(config.needs_approval());
+        let unapproved = config.without_hooks_and_webhooks();
+        assert!(unapproved.hooks.is_empty());
+        assert!(!unapproved.needs_approval());
+        let quiet: RepoConfig = toml::from_str("[notify]\nenabled = false\n").unwrap();
+        assert!(!quiet.needs_approval());
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::config_directory;
+use crate::error::AppResult;
+use crate::storage::{read_json_file, update_json_file};
+
+const STATE_FILE_NAME: &str = "repos.json";
+
+#[derive(Default, Serialize, Deserialize)]
+struct RepoStateFile {
+    #[serde(default)]
+    repos: BTreeMap<String, RepoState>,
+}
+
+/// Per-repository settings remembered between runs (as opposed to global config).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RepoState {
+    /// Epic that new tickets from this repository are filed under.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_epic: Option<String>,
+    /// SHA-256 of the `.ugh.toml` approved with `ugh hooks allow`; its hooks and webhooks
+    /// are ignored while the file's contents differ.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approved_repo_config: Option<String>,
+}
+
+/// Stable identifier for a repository: the canonical path of its main worktree, so linked
+/// worktrees share state and history with it, or the path as given when it cannot be
+/// resolved.
+pub fn repo_identity(repo_root: &Path) -> String {
+    let root = main_worktree(repo_root).unwrap_or_else(|| repo_root.to_path_buf());
+    fs::canonicalize(&root)
+        .unwrap_or(root)
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// The main worktree (or bare repository directory) behind `repo_root` when it is a
+/// linked worktree made by `git worktree add`; `None` for a main worktree.
+///
+/// A linked worktree's `.git` is a file pointing at its private git dir, whose
+/// `commondir` names the git dir shared by all worktrees. Submodules also use a `.git`
+/// file but have no `commondir`, so they stay repositories of their own.
+pub fn main_worktree(repo_root: &Path) -> Option<PathBuf> {
+    let pointer = fs::read_to_string(repo_root.join(".git")).ok()?;
+    let git_dir = repo_root.join(pointer.trim().strip_prefix("gitdir:")?.trim());
+    let common_dir = fs::read_to_string(git_dir.join("commondir")).ok()?;
+    let common_dir = fs::canonicalize(git_dir.join(common_dir.trim())).ok()?;
+    if common_dir.file_name()? == ".git" {
+        common_dir.parent().map(Path::to_path_buf)
+    } else {
+        Some(common_dir)
+    }
+}
+
+/// Remembered state for the repository at `repo_root`, keyed by its [`repo_identity`].
+pub struct RepoStateStore {
+    file_path: PathBuf,
+    repo_key: String,
+}
+
+impl RepoStateStore {
+    pub fn open(repo_root: &Path) -> AppResult<Self> {
+        Ok(Self {
+            file_path: config_directory()?.join(STATE_FILE_NAME),
+            repo_key: repo_identity(repo_root),
+        })
+    }
+
+    pub fn load(&self) -> AppResult<RepoState> {
+        let file: RepoStateFile = read_json_file(&self.file_path, "repository state")?;
+        Ok(file.repos.get(&self.repo_key).cloned().unwrap_or_default())
+    }
+
+    /// Applies `update` to this repository's entry under the state file lock.
+    pub fn update<F>(&self, update: F) -> AppResult<RepoState>
+    where
+        F: FnOnce(&mut RepoState),
+    {
+        let file = update_json_file(
+            &self.file_path,
+            "repository state",
+            |file: &mut RepoStateFile| {
+                update(file.repos.entry(self.repo_key.clone()).or_default());
+            },
+        )?;
+        Ok(file.repos.get(&self.repo_key).cloned().unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linked_worktrees_share_the_main_worktree_identity() {
+        let dir = std::env::temp_dir().join(format!("ugh-worktrees-{}", std::process::id()));
+        let main = dir.join("app");
+        let linked = dir.join("app-fix-rounding");
+        let private_git_dir = main.join(".git/worktrees/app-fix-rounding");
+        fs::create_dir_all(&private_git_dir).unwrap();
+        fs::create_dir_all(&linked).unwrap();
+        fs::write(private_git_dir.join("commondir"), "../..\n").unwrap();
+        fs::write(
+            linked.join(".git"),
+            format!("gitdir: {}\n", private_git_dir.display()),
+        )
+        .unwrap();
+
+        assert_eq!(main_worktree(&main), None);
+        assert_eq!(
+            main_worktree(&linked),
+            Some(fs::canonicalize(&main).unwrap())
+        );
+        assert_eq!(repo_identity(&linked), repo_identity(&main));
+
+        let submodule = dir.join("vendored");
+        fs::create_dir_all(&submodule).unwrap();
+        fs::write(
+            submodule.join(".git"),
+            "gitdir: ../app/.git/modules/vendored\n",
+        )
+        .unwrap();
+        assert_eq!(main_worktree(&submodule), None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
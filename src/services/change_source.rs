@@ -0,0 +1,17 @@
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+
+use crate::domain::change::ChangeSummary;
+use crate::error::AppResult;
+
+/// Where the changes a ticket is drafted from come from: the working tree by default, or a
+/// patch, a pull request, or an editor's unsaved buffers.
+#[async_trait]
+pub trait ChangeSource: Send + Sync {
+    /// Short name used in logs, e.g. `patch fix.diff`.
+    fn describe(&self) -> String;
+    /// Summarizes the changes, limited to `paths` (absolute, inside the repository) unless
+    /// it is empty.
+    async fn collect(&self, paths: &[PathBuf]) -> AppResult<ChangeSummary>;
+}
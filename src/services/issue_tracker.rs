@@ -1,9 +1,99 @@
 use async_trait::async_trait;
+use chrono::{DateTime, TimeDelta, Utc};
 
-use crate::domain::ticket::{Ticket, TicketDraft};
+use crate::domain::board::Board;
+use crate::domain::branch::ticket_key_in;
+use crate::domain::retro::{CompletedTicket, RetroScope};
+use crate::domain::sprint::Sprint;
+use crate::domain::ticket::{
+    TextLimits, Ticket, TicketDetails, TicketDraft, TicketOptions, TicketRemoval,
+};
+use crate::domain::user::TrackerUser;
 use crate::error::AppResult;
 
 #[async_trait]
 pub trait IssueTrackerService: Send + Sync {
-    async fn create_ticket(&self, board: &str, draft: TicketDraft) -> AppResult<Ticket>;
+    /// The longest title and description this tracker accepts; drafts are cut to fit.
+    fn text_limits(&self) -> TextLimits;
+
+    /// The ticket key a branch name carries, e.g. `PAY-123` in `feature/PAY-123/refunds`.
+    fn ticket_key_in_branch(&self, branch: &str) -> Option<String> {
+        ticket_key_in(branch)
+    }
+
+    /// Where a person views the ticket in a browser.
+    fn ticket_url(&self, key: &str) -> AppResult<String>;
+
+    /// Where a person views the board (project) in a browser.
+    fn board_url(&self, board: &str) -> AppResult<String>;
+
+    async fn create_ticket(
+        &self,
+        board: &str,
+        draft: TicketDraft,
+        options: &TicketOptions,
+    ) -> AppResult<Ticket>;
+
+    /// Looks up an existing ticket by key.
+    async fn get_ticket(&self, key: &str) -> AppResult<TicketDetails>;
+
+    /// Replaces an existing ticket's Markdown description, and its acceptance criteria
+    /// unless `acceptance_criteria` is empty.
+    async fn update_ticket(
+        &self,
+        key: &str,
+        description: &str,
+        acceptance_criteria: &[String],
+    ) -> AppResult<()>;
+
+    /// Adds a Markdown comment to an existing ticket.
+    async fn add_comment(&self, key: &str, comment: &str) -> AppResult<()>;
+
+    /// Records `time_spent` of work on the ticket, begun at `started`, with an optional
+    /// note.
+    async fn log_work(
+        &self,
+        key: &str,
+        time_spent: TimeDelta,
+        started: DateTime<Utc>,
+        comment: Option<&str>,
+    ) -> AppResult<()>;
+
+    /// Attaches a file to an existing ticket.
+    async fn attach_file(&self, key: &str, file_name: &str, contents: Vec<u8>) -> AppResult<()>;
+
+    /// Creates an epic that later tickets can be filed under.
+    async fn create_epic(
+        &self,
+        board: &str,
+        name: &str,
+        description: Option<&str>,
+    ) -> AppResult<Ticket>;
+
+    /// Lists boards (projects) visible to the user; `query` narrows them by key or name.
+    async fn list_boards(&self, query: Option<&str>) -> AppResult<Vec<Board>>;
+
+    /// Active and future sprints of the scrum boards showing `board`'s issues, plus closed
+    /// ones when `include_closed` is set.
+    async fn list_sprints(&self, board: &str, include_closed: bool) -> AppResult<Vec<Sprint>>;
+
+    /// Tickets on `board` that reached a done status within `scope`.
+    async fn completed_tickets(
+        &self,
+        board: &str,
+        scope: &RetroScope,
+    ) -> AppResult<Vec<CompletedTicket>>;
+
+    /// Unfinished tickets assigned to the current user whose status is in progress, most
+    /// recently updated first.
+    async fn in_progress_tickets(&self) -> AppResult<Vec<TicketDetails>>;
+
+    /// Finds users whose name or email matches `query`.
+    async fn search_users(&self, query: &str) -> AppResult<Vec<TrackerUser>>;
+
+    /// The user the tracker credentials authenticate as.
+    async fn current_user(&self) -> AppResult<TrackerUser>;
+
+    /// Deletes the ticket, or closes it when the account may not delete issues.
+    async fn delete_ticket(&self, key: &str) -> AppResult<TicketRemoval>;
 }
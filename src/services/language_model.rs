@@ -1,10 +1,73 @@
 use async_trait::async_trait;
 
 use crate::domain::change::ChangeSummary;
-use crate::domain::ticket::TicketDraft;
+use crate::domain::changelog::{ChangelogFacts, ReleaseNotes};
+use crate::domain::retro::{RetroFacts, RetroNotes};
+use crate::domain::standup::{StandupFacts, StandupNotes};
+use crate::domain::ticket::{
+    DraftGuidance, SplitDraft, TicketAmendment, TicketDetails, TicketDraft,
+};
 use crate::error::AppResult;
 
 #[async_trait]
 pub trait LanguageModelService: Send + Sync {
-    async fn draft_ticket(&self, changes: &ChangeSummary) -> AppResult<TicketDraft>;
+    /// Identifies the model and prompt behind drafts (e.g. `gemini:gemini-2.5-flash:p3`);
+    /// it is part of the draft cache key, so changing either invalidates cached drafts.
+    fn fingerprint(&self) -> String;
+
+    async fn draft_ticket(
+        &self,
+        changes: &ChangeSummary,
+        guidance: &DraftGuidance,
+    ) -> AppResult<TicketDraft>;
+
+    /// Clusters a mixed changeset into several drafts. Models without clustering support
+    /// return a single draft covering every file.
+    async fn draft_tickets(
+        &self,
+        changes: &ChangeSummary,
+        guidance: &DraftGuidance,
+    ) -> AppResult<Vec<SplitDraft>> {
+        let draft = self.draft_ticket(changes, guidance).await?;
+        Ok(vec![SplitDraft {
+            draft,
+            files: changes.files.clone(),
+        }])
+    }
+
+    /// Writes the went-well / didn't / action-item notes of a retrospective. Drafters
+    /// that do not generate text derive them from the numbers.
+    async fn retrospective_notes(&self, facts: &RetroFacts) -> AppResult<RetroNotes> {
+        Ok(facts.heuristic_notes())
+    }
+
+    /// Writes release notes for the tickets and commits of a revision range. Drafters
+    /// that do not generate text list the entries under their categories.
+    async fn release_notes(&self, facts: &ChangelogFacts) -> AppResult<ReleaseNotes> {
+        Ok(facts.heuristic_notes())
+    }
+
+    /// Fleshes out a hand-written draft's description and acceptance criteria, keeping
+    /// its intent. Drafters that do not generate text return it unchanged.
+    async fn enrich_draft(&self, draft: &TicketDraft) -> AppResult<TicketDraft> {
+        Ok(draft.clone())
+    }
+
+    /// Rewrites an existing ticket's description to also cover new changes and writes a
+    /// follow-up comment about them. Drafters that do not generate text append the
+    /// changed files instead.
+    async fn amend_ticket(
+        &self,
+        ticket: &TicketDetails,
+        changes: &ChangeSummary,
+        _guidance: &DraftGuidance,
+    ) -> AppResult<TicketAmendment> {
+        Ok(TicketAmendment::heuristic(ticket, changes))
+    }
+
+    /// Summarizes each ticket's recent commits for a standup. Drafters that do not
+    /// generate text list the commit subjects.
+    async fn standup_notes(&self, facts: &StandupFacts) -> AppResult<StandupNotes> {
+        Ok(facts.heuristic_notes())
+    }
 }
@@ -1,7 +1,11 @@
+pub mod change_source;
 pub mod issue_tracker;
 pub mod language_model;
+pub mod notification;
 pub mod version_control;
 
+pub use change_source::ChangeSource;
 pub use issue_tracker::IssueTrackerService;
 pub use language_model::LanguageModelService;
+pub use notification::NotificationService;
 pub use version_control::VersionControlService;
@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+
+use crate::domain::ticket::TicketNotice;
+use crate::error::AppResult;
+
+/// A chat channel that hears about tickets once ugh has filed them and made their branch,
+/// and takes other summaries ugh writes.
+#[async_trait]
+pub trait NotificationService: Send + Sync {
+    /// Short name used in warnings, e.g. `Slack`.
+    fn name(&self) -> &str;
+    async fn notify(&self, notice: &TicketNotice) -> AppResult<()>;
+    /// Posts free-form text, e.g. a standup summary.
+    async fn post_text(&self, text: &str) -> AppResult<()>;
+}
@@ -1,11 +1,52 @@
+use std::path::{Path, PathBuf};
+
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 use crate::domain::branch::BranchName;
 use crate::domain::change::ChangeSummary;
+use crate::domain::retro::CommitSummary;
+use crate::domain::standup::BranchCommit;
 use crate::error::AppResult;
 
 #[async_trait]
 pub trait VersionControlService: Send + Sync {
-    async fn summarize_changes(&self) -> AppResult<ChangeSummary>;
-    async fn checkout_branch(&self, branch: &BranchName) -> AppResult<()>;
+    /// Summarizes uncommitted changes, limited to `paths` unless it is empty.
+    async fn summarize_changes(&self, paths: &[PathBuf]) -> AppResult<ChangeSummary>;
+    /// Checks `branch` out, creating it from `base` (default: the current commit) if needed.
+    async fn checkout_branch(&self, branch: &BranchName, base: Option<&str>) -> AppResult<()>;
+    /// Creates a branch from `base` (default: the current commit) without checking it out.
+    async fn create_branch(&self, branch: &BranchName, base: Option<&str>) -> AppResult<()>;
+    /// Checks `branch` out in a new worktree at `path`, creating the branch from `base`
+    /// (default: the current commit) if needed. The current tree is left untouched.
+    async fn create_worktree(
+        &self,
+        branch: &BranchName,
+        base: Option<&str>,
+        path: &Path,
+    ) -> AppResult<()>;
+    /// Removes a worktree; refused while it has uncommitted changes.
+    async fn remove_worktree(&self, path: &Path) -> AppResult<()>;
+    /// Name of the checked-out branch, or `None` for a detached HEAD.
+    async fn current_branch(&self) -> AppResult<Option<String>>;
+    /// Switches to an existing branch, carrying uncommitted changes along.
+    async fn switch_branch(&self, branch: &str) -> AppResult<()>;
+    /// Deletes a local branch; without `force`, unmerged branches are refused.
+    async fn delete_branch(&self, branch: &str, force: bool) -> AppResult<()>;
+    /// Commits reachable from HEAD that were committed in `[since, until)`, newest first.
+    async fn commits_between(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> AppResult<Vec<CommitSummary>>;
+    /// Commits in a revision range such as `v1.2.0..HEAD` (a single revision means all of
+    /// its history), merges included, newest first.
+    async fn commits_in_range(&self, range: &str) -> AppResult<Vec<CommitSummary>>;
+    /// Non-merge commits since `since` on every local branch, by the configured git user
+    /// (`user.email`; everyone when unset). A commit on several branches is listed once
+    /// per branch.
+    async fn recent_commits(&self, since: DateTime<Utc>) -> AppResult<Vec<BranchCommit>>;
+    /// Author time of the oldest commit on `branch` that no other local or remote branch
+    /// contains, or `None` while the branch has no commits of its own.
+    async fn branch_started_at(&self, branch: &str) -> AppResult<Option<DateTime<Utc>>>;
 }
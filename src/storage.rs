@@ -0,0 +1,177 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::error::{AppError, AppResult};
+
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(50);
+/// Locks older than this are assumed to belong to a crashed process.
+const STALE_LOCK_AGE: Duration = Duration::from_secs(30);
+
+/// Advisory lock implemented as a `<file>.lock` sidecar created exclusively.
+pub struct FileLock {
+    path: PathBuf,
+}
+
+impl FileLock {
+    pub fn acquire(target: &Path) -> AppResult<Self> {
+        Self::acquire_with_timeout(target, LOCK_TIMEOUT)
+    }
+
+    pub fn acquire_with_timeout(target: &Path, timeout: Duration) -> AppResult<Self> {
+        let path = lock_path(target);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let started = SystemTime::now();
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    let _ = writeln!(file, "{}", std::process::id());
+                    return Ok(Self { path });
+                }
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                    if is_stale(&path) {
+                        let _ = fs::remove_file(&path);
+                        continue;
+                    }
+                    if started.elapsed().unwrap_or_default() >= timeout {
+                        return Err(AppError::Configuration(format!(
+                            "timed out waiting for lock on {} (another ugh process may be running; \
+                             delete the lock file if it is stale)",
+                            target.display()
+                        )));
+                    }
+                    thread::sleep(LOCK_RETRY_INTERVAL);
+                }
+                Err(err) => return Err(AppError::Io(err)),
+            }
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn lock_path(target: &Path) -> PathBuf {
+    let mut name = target
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_default();
+    name.push(".lock");
+    target.with_file_name(name)
+}
+
+fn is_stale(lock: &Path) -> bool {
+    fs::metadata(lock)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age > STALE_LOCK_AGE)
+}
+
+/// Writes through a temporary file and renames it so readers never see partial content.
+pub fn write_atomic(path: &Path, data: &[u8]) -> io::Result<()> {
+    write_through_temp(path, data, false)
+}
+
+/// Like [`write_atomic`], but the file is readable only by its owner (on Unix).
+pub fn write_atomic_private(path: &Path, data: &[u8]) -> io::Result<()> {
+    write_through_temp(path, data, true)
+}
+
+fn write_through_temp(path: &Path, data: &[u8], private: bool) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut tmp_name = path
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_default();
+    tmp_name.push(format!(".{}.tmp", std::process::id()));
+    let tmp_path = path.with_file_name(tmp_name);
+
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    if private {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    #[cfg(not(unix))]
+    let _ = private;
+    options.open(&tmp_path)?.write_all(data)?;
+    fs::rename(&tmp_path, path).inspect_err(|_| {
+        let _ = fs::remove_file(&tmp_path);
+    })
+}
+
+/// Reads a JSON file, returning the default value when it does not exist yet.
+pub fn read_json_file<T>(path: &Path, label: &str) -> AppResult<T>
+where
+    T: Default + DeserializeOwned,
+{
+    match fs::read_to_string(path) {
+        Ok(contents) => serde_json::from_str(&contents)
+            .map_err(|err| AppError::Configuration(format!("invalid {label} file: {err}"))),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(T::default()),
+        Err(err) => Err(AppError::Io(err)),
+    }
+}
+
+/// Locks `path`, re-reads its latest contents, applies `update`, and writes the result
+/// atomically. Returns the value that was written.
+pub fn update_json_file<T, F>(path: &Path, label: &str, update: F) -> AppResult<T>
+where
+    T: Default + Serialize + DeserializeOwned,
+    F: FnOnce(&mut T),
+{
+    let _lock = FileLock::acquire(path)?;
+    let mut value: T = read_json_file(path, label)?;
+    update(&mut value);
+    let data = serde_json::to_string_pretty(&value)
+        .map_err(|err| AppError::Configuration(format!("failed to write {label} file: {err}")))?;
+    write_atomic(path, data.as_bytes())?;
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ugh-storage-{}-{name}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("state.json")
+    }
+
+    #[test]
+    fn second_lock_times_out_while_first_is_held() {
+        let path = scratch_file("contention");
+        let first = FileLock::acquire(&path).unwrap();
+        let second = FileLock::acquire_with_timeout(&path, Duration::from_millis(120));
+        assert!(second.is_err());
+        drop(first);
+        assert!(FileLock::acquire_with_timeout(&path, Duration::from_millis(120)).is_ok());
+    }
+
+    #[test]
+    fn update_merges_into_latest_contents() {
+        let path = scratch_file("merge");
+        let _ = fs::remove_file(&path);
+        update_json_file(&path, "test", |items: &mut Vec<u32>| items.push(1)).unwrap();
+        let merged = update_json_file(&path, "test", |items: &mut Vec<u32>| items.push(2)).unwrap();
+        assert_eq!(merged, vec![1, 2]);
+        assert!(!lock_path(&path).exists());
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::HashSet;
+
+use tracing::debug;
+
+use crate::domain::ticket::TicketDraft;
+
+/// Longest excerpt of a changed line quoted in an explanation.
+const EXCERPT_CHARS: usize = 60;
+
+/// What one pipeline agent did to the draft.
+#[derive(Debug, Clone)]
+struct AgentRecord {
+    agent: String,
+    /// Plain-language changes, e.g. `rewrote the title: "a" → "b"`; empty for none.
+    changes: Vec<String>,
+}
+
+/// The agents a draft passed through, in order, for `ugh ticket --explain`.
+#[derive(Debug, Clone, Default)]
+pub struct AgentTrail {
+    records: Vec<AgentRecord>,
+}
+
+impl AgentTrail {
+    /// Notes what `agent` changed between `before` and `after`; `before` is `None` for
+    /// the agent that wrote the draft. Both versions go to the debug log.
+    pub fn record(&mut self, agent: &str, before: Option<&TicketDraft>, after: &TicketDraft) {
+        debug!(agent, input = ?before, output = ?after, "agent finished");
+        self.records.push(AgentRecord {
+            agent: agent.to_string(),
+            changes: describe_changes(before, after),
+        });
+    }
+
+    /// One line per agent, in the order they ran.
+    pub fn render(&self) -> String {
+        self.records
+            .iter()
+            .map(|record| {
+                let changes = if record.changes.is_empty() {
+                    "no changes".to_string()
+                } else {
+                    record.changes.join("; ")
+                };
+                format!("  {}: {changes}\n", record.agent)
+            })
+            .collect()
+    }
+}
+
+fn describe_changes(before: Option<&TicketDraft>, after: &TicketDraft) -> Vec<String> {
+    let Some(before) = before else {
+        let mut wrote = vec![
+            format!("the title \"{}\"", excerpt(&after.title)),
+            "the description".to_string(),
+            format!(
+                "the branch {}/{}",
+                after.branch_category.as_str(),
+                after.branch_summary
+            ),
+        ];
+        if !after.acceptance_criteria.is_empty() {
+            wrote.push(format!(
+                "{} acceptance criteria",
+                after.acceptance_criteria.len()
+            ));
+        }
+        if let Some(estimate) = after.estimate {
+            wrote.push(format!("an estimate of {estimate}"));
+        }
+        return vec![format!("wrote {}", wrote.join(", "))];
+    };
+
+    let mut changes = Vec::new();
+    if before.title != after.title {
+        changes.push(format!(
+            "rewrote the title: \"{}\" → \"{}\"",
+            excerpt(&before.title),
+            excerpt(&after.title)
+        ));
+    }
+    if before.description != after.description {
+        let added = new_lines(&before.description, &after.description);
+        let removed = new_lines(&after.description, &before.description);
+        changes.push(match added.first() {
+            Some(first) => format!(
+                "changed the description (+{}/-{} lines, first new line \"{}\")",
+                added.len(),
+                removed.len(),
+                excerpt(first)
+            ),
+            None => format!("removed {} line(s) from the description", removed.len()),
+        });
+    }
+    if before.branch_category != after.branch_category
+        || before.branch_summary != after.branch_summary
+    {
+        changes.push(format!(
+            "changed the branch to {}/{}",
+            after.branch_category.as_str(),
+            after.branch_summary
+        ));
+    }
+    if before.acceptance_criteria != after.acceptance_criteria {
+        changes.push(format!(
+            "changed the acceptance criteria ({} → {})",
+            before.acceptance_criteria.len(),
+            after.acceptance_criteria.len()
+        ));
+    }
+    if before.estimate != after.estimate {
+        let show = |estimate: Option<u32>| estimate.map_or("none".to_string(), |e| e.to_string());
+        changes.push(format!(
+            "changed the estimate from {} to {}",
+            show(before.estimate),
+            show(after.estimate)
+        ));
+    }
+    changes
+}
+
+/// Non-blank lines of `after` that `before` does not have.
+fn new_lines<'a>(before: &str, after: &'a str) -> Vec<&'a str> {
+    let known: HashSet<&str> = before.lines().map(str::trim).collect();
+    after
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !known.contains(line))
+        .collect()
+}
+
+fn excerpt(text: &str) -> String {
+    if text.chars().count() <= EXCERPT_CHARS {
+        return text.to_string();
+    }
+    let head: String = text.chars().take(EXCERPT_CHARS - 1).collect();
+    format!("{}…", head.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::branch::BranchCategory;
+
+    #[test]
+    fn trail_names_the_agent_behind_each_change() {
+        let drafted = TicketDraft {
+            title: "add refunds".to_string(),
+            description: "Adds refunds to the ledger.".to_string(),
+            branch_category: BranchCategory::Feature,
+            branch_summary: "add-refunds".to_string(),
+            acceptance_criteria: vec!["Refunds post to the ledger".to_string()],
+            estimate: None,
+            provider: Some("gemini".to_string()),
+            rationale: None,
+        };
+        let mut reviewed = drafted.clone();
+        reviewed.title = "PAY: Add refunds".to_string();
+        reviewed
+            .description
+            .push_str("\n\n## Risks\nLedger totals may drift.");
+
+        let mut trail = AgentTrail::default();
+        trail.record("gemini", None, &drafted);
+        trail.record("hooks", Some(&drafted), &reviewed);
+        trail.record("limits", Some(&reviewed), &reviewed);
+
+        let rendered = trail.render();
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert!(lines[0].starts_with("  gemini: wrote the title \"add refunds\""));
+        assert_eq!(
+            lines[1],
+            "  hooks: rewrote the title: \"add refunds\" → \"PAY: Add refunds\"; changed the \
+             description (+2/-0 lines, first new line \"## Risks\")"
+        );
+        assert_eq!(lines[2], "  limits: no changes");
+    }
+}
@@ -0,0 +1,80 @@
+use tracing::info;
+
+use crate::context::AppContext;
+use crate::domain::change::ChangeSummary;
+use crate::domain::ticket::{DraftGuidance, TicketAmendment, TicketDetails};
+use crate::error::{AppError, AppResult};
+use crate::workflow::scope::ChangeScope;
+use crate::workflow::ticket::collect_changes;
+
+/// What `ugh ticket --amend` changes on the ticket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmendTarget {
+    /// Rewrite the description and, when the model gives new ones, the criteria.
+    Description,
+    /// Leave the ticket as it is and add a follow-up comment.
+    Comment,
+}
+
+/// An amendment that has not been sent yet, so it can be reviewed first.
+pub struct PreparedAmendment {
+    pub ticket: TicketDetails,
+    pub changes: ChangeSummary,
+    pub amendment: TicketAmendment,
+}
+
+/// Reads the ticket and the changes in `scope` and asks the language model how the
+/// ticket should change to cover them.
+pub async fn prepare_amendment(
+    ctx: &AppContext,
+    key: &str,
+    scope: &ChangeScope,
+    redact: bool,
+) -> AppResult<PreparedAmendment> {
+    let changes = collect_changes(ctx, scope, redact).await?;
+    if changes.files.is_empty() {
+        return Err(AppError::VersionControl(format!(
+            "no uncommitted changes to amend {key} with"
+        )));
+    }
+    let ticket = ctx.issue_tracker.get_ticket(key).await?;
+    let guidance = DraftGuidance {
+        language: ctx.config.ticket_language.clone(),
+        ..DraftGuidance::default()
+    };
+    let amendment = ctx
+        .language_model
+        .amend_ticket(&ticket, &changes, &guidance)
+        .await?;
+    info!(key = %ticket.key, provider = ?amendment.provider, "amendment drafted");
+    Ok(PreparedAmendment {
+        ticket,
+        changes,
+        amendment,
+    })
+}
+
+/// Sends the amendment to the tracker as a new description or as a comment.
+pub async fn apply_amendment(
+    ctx: &AppContext,
+    prepared: &PreparedAmendment,
+    target: AmendTarget,
+) -> AppResult<()> {
+    let amendment = &prepared.amendment;
+    match target {
+        AmendTarget::Description => {
+            ctx.issue_tracker
+                .update_ticket(
+                    &prepared.ticket.key,
+                    &amendment.description,
+                    &amendment.acceptance_criteria,
+                )
+                .await
+        }
+        AmendTarget::Comment => {
+            ctx.issue_tracker
+                .add_comment(&prepared.ticket.key, &amendment.comment)
+                .await
+        }
+    }
+}
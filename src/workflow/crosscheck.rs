@@ -0,0 +1,130 @@
+use std::collections::BTreeSet;
+
+/// Words too generic to tie a claim to a particular file.
+const STOP_WORDS: &[&str] = &[
+    "add", "added", "adds", "and", "are", "for", "from", "into", "its", "new", "now", "that",
+    "the", "their", "them", "this", "update", "updated", "updates", "use", "uses", "when", "with",
+];
+
+/// One statement from a draft description and the changed files it refers to.
+#[derive(Debug, Clone)]
+pub struct ClaimCheck {
+    pub claim: String,
+    pub files: Vec<String>,
+}
+
+impl ClaimCheck {
+    /// No changed path shares a word with the claim, so the model may have invented it.
+    pub fn is_unverified(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+/// Matches each bullet (or, without bullets, each sentence) of `description` against the
+/// changed paths by shared words, e.g. "Retry failed uploads" against `src/upload/retry.rs`.
+pub fn cross_check(description: &str, files: &[String]) -> Vec<ClaimCheck> {
+    let file_words: Vec<BTreeSet<String>> = files.iter().map(|file| words(file)).collect();
+    claims(description)
+        .into_iter()
+        .filter_map(|claim| {
+            let claim_words = words(&claim);
+            if claim_words.is_empty() {
+                return None;
+            }
+            let matched = files
+                .iter()
+                .zip(&file_words)
+                .filter(|(_, path_words)| {
+                    claim_words
+                        .iter()
+                        .any(|word| path_words.iter().any(|path_word| related(word, path_word)))
+                })
+                .map(|(file, _)| file.clone())
+                .collect();
+            Some(ClaimCheck {
+                claim,
+                files: matched,
+            })
+        })
+        .collect()
+}
+
+fn claims(description: &str) -> Vec<String> {
+    let bullets: Vec<String> = description
+        .lines()
+        .filter_map(|line| bullet_text(line.trim()))
+        .collect();
+    if !bullets.is_empty() {
+        return bullets;
+    }
+    description
+        .split_terminator(['.', '\n'])
+        .map(str::trim)
+        .filter(|sentence| !sentence.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn bullet_text(line: &str) -> Option<String> {
+    let rest = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| {
+            let (number, rest) = line.split_once(". ")?;
+            number.chars().all(|c| c.is_ascii_digit()).then_some(rest)
+        })?;
+    let rest = rest.trim();
+    (!rest.is_empty()).then(|| rest.to_string())
+}
+
+/// Lowercased words of at least three characters, splitting paths and camelCase identifiers.
+fn words(text: &str) -> BTreeSet<String> {
+    let mut words = BTreeSet::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+    for c in text.chars() {
+        if !c.is_alphanumeric() || (c.is_uppercase() && previous_lower) {
+            push_word(&mut words, &mut current);
+        }
+        if c.is_alphanumeric() {
+            current.extend(c.to_lowercase());
+        }
+        previous_lower = c.is_lowercase();
+    }
+    push_word(&mut words, &mut current);
+    words
+}
+
+fn push_word(words: &mut BTreeSet<String>, current: &mut String) {
+    let word = std::mem::take(current);
+    if word.len() >= 3 && !STOP_WORDS.contains(&word.as_str()) {
+        words.insert(word);
+    }
+}
+
+/// Same word, or one is a stem of the other ("retry" / "retries", "config" / "configuration").
+fn related(a: &str, b: &str) -> bool {
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    short == long || (short.len() >= 4 && long.starts_with(&short[..short.len() - 1]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_bullets_without_a_matching_changed_file() {
+        let files = vec![
+            "src/upload/retry.rs".to_string(),
+            "src/config.rs".to_string(),
+        ];
+        let description = "Summary line.\n\n- Retries failed uploads\n- Adds a configuration flag\n- Migrates the billing database";
+
+        let checks = cross_check(description, &files);
+
+        assert_eq!(checks.len(), 3);
+        assert_eq!(checks[0].files, vec!["src/upload/retry.rs".to_string()]);
+        assert_eq!(checks[1].files, vec!["src/config.rs".to_string()]);
+        assert!(checks[2].is_unverified());
+    }
+}
@@ -0,0 +1,242 @@
+use std::path::PathBuf;
+use std::process::Stdio;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tracing::info;
+
+use crate::context::AppContext;
+use crate::domain::branch::BranchCategory;
+use crate::domain::ticket::{Ticket, TicketDraft};
+use crate::error::{AppError, AppResult};
+use crate::repo_config::{HookCommands, REPO_CONFIG_FILE_NAME};
+use crate::workflow::steps::WorkflowStep;
+use crate::workflow::ticket::TicketWorkflowOutcome;
+
+pub const STEP_NAME: &str = "hooks";
+
+/// Runs the repository's `.ugh.toml` hooks as the last workflow step.
+///
+/// Each hook is a shell command run from the repository root with a JSON payload on
+/// stdin and `UGH_HOOK` set to its name. A non-zero exit vetoes the run; what the hook
+/// writes to stderr is shown to the user as is.
+pub struct HookRunner {
+    commands: HookCommands,
+    repo_root: PathBuf,
+}
+
+/// Fields a `pre_draft` hook may print to replace in the draft; the rest are kept.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DraftPatch {
+    title: Option<String>,
+    description: Option<String>,
+    branch_category: Option<String>,
+    branch_summary: Option<String>,
+    acceptance_criteria: Option<Vec<String>>,
+    estimate: Option<u32>,
+}
+
+impl HookRunner {
+    pub fn new(commands: HookCommands, repo_root: PathBuf) -> Self {
+        Self {
+            commands,
+            repo_root,
+        }
+    }
+
+    /// Runs `command` with `payload` on stdin and returns what it printed.
+    async fn run(&self, hook: &str, command: &str, payload: &Value) -> AppResult<String> {
+        info!(hook, command, "running hook");
+        let mut process = shell(command)
+            .current_dir(&self.repo_root)
+            .env("UGH_HOOK", hook)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|err| {
+                AppError::WorkflowStep(format!("{hook} hook `{command}` could not start: {err}"))
+            })?;
+
+        if let Some(mut stdin) = process.stdin.take() {
+            // A hook that exits without reading its input is fine; only its status counts.
+            let _ = stdin.write_all(payload.to_string().as_bytes()).await;
+        }
+        let output = process.wait_with_output().await?;
+        if !output.status.success() {
+            return Err(AppError::WorkflowStep(format!(
+                "{hook} hook `{command}` rejected the run ({})",
+                output.status
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+#[async_trait]
+impl WorkflowStep for HookRunner {
+    fn name(&self) -> &str {
+        STEP_NAME
+    }
+
+    fn description(&self) -> &str {
+        "runs the repository's .ugh.toml hooks (pre_draft, post_ticket, post_branch)"
+    }
+
+    async fn review_draft(
+        &self,
+        _ctx: &AppContext,
+        board: &str,
+        draft: &mut TicketDraft,
+    ) -> AppResult<()> {
+        let Some(command) = &self.commands.pre_draft else {
+            return Ok(());
+        };
+        let payload = json!({ "board": board, "draft": draft_json(draft) });
+        let stdout = self.run("pre_draft", command, &payload).await?;
+        apply_patch(draft, &stdout)
+    }
+
+    async fn after_ticket(
+        &self,
+        _ctx: &AppContext,
+        board: &str,
+        draft: &TicketDraft,
+        ticket: &Ticket,
+    ) -> AppResult<()> {
+        let Some(command) = &self.commands.post_ticket else {
+            return Ok(());
+        };
+        let payload = json!({
+            "board": board,
+            "draft": draft_json(draft),
+            "ticket": { "key": ticket.key, "url": ticket.url },
+        });
+        self.run("post_ticket", command, &payload)
+            .await
+            .map(drop)
+            .map_err(|err| {
+                AppError::WorkflowStep(format!(
+                    "{err}; {} was created but no branch was made for it",
+                    ticket.key
+                ))
+            })
+    }
+
+    async fn after_create(
+        &self,
+        _ctx: &AppContext,
+        outcome: &TicketWorkflowOutcome,
+    ) -> AppResult<()> {
+        let Some(command) = &self.commands.post_branch else {
+            return Ok(());
+        };
+        let payload = json!({
+            "ticket": { "key": outcome.ticket.key, "url": outcome.ticket.url },
+            "branch": outcome.branch.as_str(),
+            "worktree": outcome.worktree,
+        });
+        self.run("post_branch", command, &payload).await.map(drop)
+    }
+}
+
+fn draft_json(draft: &TicketDraft) -> Value {
+    json!({
+        "title": draft.title,
+        "description": draft.description,
+        "branch_category": draft.branch_category.as_str(),
+        "branch_summary": draft.branch_summary,
+        "acceptance_criteria": draft.acceptance_criteria,
+        "estimate": draft.estimate,
+    })
+}
+
+/// Applies the JSON object a `pre_draft` hook printed; printing nothing keeps the draft.
+fn apply_patch(draft: &mut TicketDraft, stdout: &str) -> AppResult<()> {
+    if stdout.trim().is_empty() {
+        return Ok(());
+    }
+    let patch: DraftPatch = serde_json::from_str(stdout).map_err(|err| {
+        AppError::WorkflowStep(format!(
+            "pre_draft hook printed something other than a draft object ({err}); see \
+             {REPO_CONFIG_FILE_NAME}"
+        ))
+    })?;
+
+    if let Some(category) = patch.branch_category {
+        draft.branch_category = BranchCategory::from_str(&category).ok_or_else(|| {
+            AppError::WorkflowStep(format!(
+                "pre_draft hook set an unknown branch category '{category}'"
+            ))
+        })?;
+    }
+    if let Some(title) = patch.title {
+        draft.title = title;
+    }
+    if let Some(description) = patch.description {
+        draft.description = description;
+    }
+    if let Some(summary) = patch.branch_summary {
+        draft.branch_summary = summary;
+    }
+    if let Some(criteria) = patch.acceptance_criteria {
+        draft.acceptance_criteria = criteria;
+    }
+    if patch.estimate.is_some() {
+        draft.estimate = patch.estimate;
+    }
+    Ok(())
+}
+
+fn shell(command: &str) -> Command {
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    shell.arg(command);
+    shell
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patch_replaces_only_the_printed_fields() {
+        let mut draft = TicketDraft {
+            title: "add refunds".to_string(),
+            description: "Adds refunds.".to_string(),
+            branch_category: BranchCategory::Feature,
+            branch_summary: "add refunds".to_string(),
+            acceptance_criteria: vec!["Refunds post to the ledger".to_string()],
+            estimate: None,
+            provider: Some("gemini".to_string()),
+            rationale: None,
+        };
+
+        apply_patch(&mut draft, "").unwrap();
+        assert_eq!(draft.title, "add refunds");
+
+        apply_patch(
+            &mut draft,
+            r#"{"title": "PAY: Add refunds", "branch_category": "fix"}"#,
+        )
+        .unwrap();
+        assert_eq!(draft.title, "PAY: Add refunds");
+        assert_eq!(draft.branch_category, BranchCategory::Fix);
+        assert_eq!(draft.description, "Adds refunds.");
+        assert_eq!(draft.acceptance_criteria.len(), 1);
+
+        assert!(apply_patch(&mut draft, r#"{"branch_category": "chore"}"#).is_err());
+        assert!(apply_patch(&mut draft, "looks good").is_err());
+    }
+}
@@ -1 +1,10 @@
+pub mod agents;
+pub mod amend;
+pub mod crosscheck;
+pub mod hooks;
+pub mod redact;
+pub mod scope;
+pub mod steps;
 pub mod ticket;
+pub mod undo;
+pub mod workspace;
@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// The part of the repository a run is about. Empty `paths` means the whole repository.
+#[derive(Debug, Clone, Default)]
+pub struct ChangeScope {
+    /// Absolute paths used as git pathspecs.
+    pub paths: Vec<PathBuf>,
+    /// The same paths relative to the repository root, with `/` separators.
+    pub repo_relative: Vec<String>,
+}
+
+impl ChangeScope {
+    /// Uses the explicit `--path` values when given; otherwise, when run from inside a
+    /// package below the repository root (a Cargo crate or an npm package), scopes to it.
+    pub fn resolve(cwd: &Path, repo_root: Option<&Path>, explicit: &[PathBuf]) -> Self {
+        let paths: Vec<PathBuf> = if explicit.is_empty() {
+            repo_root
+                .and_then(|root| enclosing_package(cwd, root))
+                .into_iter()
+                .collect()
+        } else {
+            explicit
+                .iter()
+                .map(|path| normalize(&cwd.join(path)))
+                .collect()
+        };
+
+        let repo_relative = paths
+            .iter()
+            .filter_map(|path| {
+                let relative = path.strip_prefix(repo_root?).ok()?;
+                Some(
+                    relative
+                        .components()
+                        .map(|part| part.as_os_str().to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join("/"),
+                )
+            })
+            .collect();
+
+        Self {
+            paths,
+            repo_relative,
+        }
+    }
+
+    pub fn is_whole_repo(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    /// Board configured for the deepest `path_boards` prefix covering every scoped path.
+    pub fn board(&self, path_boards: &BTreeMap<String, String>) -> Option<String> {
+        let mut chosen: Option<(&str, &String)> = None;
+        for relative in &self.repo_relative {
+            let (prefix, board) = path_boards
+                .iter()
+                .filter(|(prefix, _)| path_has_prefix(relative, prefix))
+                .max_by_key(|(prefix, _)| prefix.len())?;
+            match chosen {
+                Some((_, existing)) if existing != board => return None,
+                Some((existing_prefix, _)) if existing_prefix.len() >= prefix.len() => {}
+                _ => chosen = Some((prefix, board)),
+            }
+        }
+        chosen.map(|(_, board)| board.clone())
+    }
+}
+
+fn path_has_prefix(path: &str, prefix: &str) -> bool {
+    let prefix = prefix.trim_matches('/');
+    prefix.is_empty()
+        || path == prefix
+        || path
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// Nearest package manifest directory between `start` and the repository root (exclusive).
+fn enclosing_package(start: &Path, repo_root: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .take_while(|dir| *dir != repo_root && dir.starts_with(repo_root))
+        .find(|dir| is_package_dir(dir))
+        .map(Path::to_path_buf)
+}
+
+fn is_package_dir(dir: &Path) -> bool {
+    if dir.join("package.json").is_file() {
+        return true;
+    }
+    fs::read_to_string(dir.join("Cargo.toml"))
+        .map(|manifest| manifest.lines().any(|line| line.trim() == "[package]"))
+        .unwrap_or(false)
+}
+
+/// Resolves `.` and `..` lexically so paths compare cleanly against the repository root.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scope(relative: &[&str]) -> ChangeScope {
+        ChangeScope {
+            paths: relative.iter().map(PathBuf::from).collect(),
+            repo_relative: relative.iter().map(|path| path.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn picks_deepest_matching_board() {
+        let boards = BTreeMap::from([
+            ("packages".to_string(), "PLAT".to_string()),
+            ("packages/billing".to_string(), "PAY".to_string()),
+        ]);
+        assert_eq!(
+            scope(&["packages/billing/src"]).board(&boards).as_deref(),
+            Some("PAY")
+        );
+        assert_eq!(
+            scope(&["packages/search"]).board(&boards).as_deref(),
+            Some("PLAT")
+        );
+        assert_eq!(scope(&["packages-old"]).board(&boards), None);
+        assert_eq!(
+            scope(&["packages/billing", "packages/search"]).board(&boards),
+            None
+        );
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::{info, instrument, warn};
+
+use crate::config::AppConfig;
+use crate::context::AppContext;
+use crate::domain::ticket::{Ticket, TicketDraft};
+use crate::error::AppResult;
+use crate::plugins;
+use crate::workflow::agents::AgentTrail;
+use crate::workflow::ticket::TicketWorkflowOutcome;
+
+/// A custom stage in the ticket pipeline, enabled by name through `workflow_steps`.
+///
+/// Steps run in the configured order. Every stage defaults to doing nothing, so a step
+/// only implements the ones it cares about.
+#[async_trait]
+pub trait WorkflowStep: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// One line for `ugh agents list` on what the step does.
+    fn description(&self) -> &str {
+        "custom workflow step"
+    }
+
+    /// Runs after drafting and before the ticket is created. The step may edit the draft;
+    /// returning an error aborts the run before anything is created.
+    async fn review_draft(
+        &self,
+        _ctx: &AppContext,
+        _board: &str,
+        _draft: &mut TicketDraft,
+    ) -> AppResult<()> {
+        Ok(())
+    }
+
+    /// Runs once the ticket exists and before its branch is created. Returning an error
+    /// stops the run there; the ticket itself stays.
+    async fn after_ticket(
+        &self,
+        _ctx: &AppContext,
+        _board: &str,
+        _draft: &TicketDraft,
+        _ticket: &Ticket,
+    ) -> AppResult<()> {
+        Ok(())
+    }
+
+    /// Runs once the ticket and its branch exist. Errors are reported but do not undo
+    /// the ticket.
+    async fn after_create(
+        &self,
+        _ctx: &AppContext,
+        _outcome: &TicketWorkflowOutcome,
+    ) -> AppResult<()> {
+        Ok(())
+    }
+}
+
+pub type StepFactory = fn(&AppConfig) -> AppResult<Arc<dyn WorkflowStep>>;
+
+/// Build-time registry of the steps this binary knows about.
+pub struct StepRegistry {
+    factories: BTreeMap<&'static str, StepFactory>,
+}
+
+impl StepRegistry {
+    /// Every step compiled into the binary, including those in [`crate::plugins`].
+    pub fn builtin() -> Self {
+        let mut registry = Self {
+            factories: BTreeMap::new(),
+        };
+        plugins::register(&mut registry);
+        registry
+    }
+
+    pub fn register(&mut self, name: &'static str, factory: StepFactory) {
+        self.factories.insert(name, factory);
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.factories.keys().copied()
+    }
+
+    /// Instantiates the enabled steps in order, skipping names nothing registered.
+    pub fn build(&self, config: &AppConfig) -> AppResult<Vec<Arc<dyn WorkflowStep>>> {
+        let mut steps = Vec::new();
+        for name in &config.workflow_steps {
+            match self.factories.get(name.as_str()) {
+                Some(factory) => steps.push(factory(config)?),
+                None => warn!("workflow step '{name}' is not registered, skipping it."),
+            }
+        }
+        Ok(steps)
+    }
+}
+
+#[instrument(level = "info", name = "steps.review_draft", skip_all)]
+pub(crate) async fn review_draft(
+    ctx: &AppContext,
+    board: &str,
+    draft: &mut TicketDraft,
+    trail: &mut AgentTrail,
+) -> AppResult<()> {
+    for step in ctx.workflow_steps.iter() {
+        info!(step = step.name(), "reviewing draft");
+        let before = draft.clone();
+        step.review_draft(ctx, board, draft).await?;
+        trail.record(step.name(), Some(&before), draft);
+    }
+    Ok(())
+}
+
+#[instrument(level = "info", name = "steps.after_ticket", skip_all)]
+pub(crate) async fn after_ticket(
+    ctx: &AppContext,
+    board: &str,
+    draft: &TicketDraft,
+    ticket: &Ticket,
+) -> AppResult<()> {
+    for step in ctx.workflow_steps.iter() {
+        step.after_ticket(ctx, board, draft, ticket).await?;
+    }
+    Ok(())
+}
+
+#[instrument(level = "info", name = "steps.after_create", skip_all)]
+pub(crate) async fn after_create(ctx: &AppContext, outcome: &TicketWorkflowOutcome) {
+    for step in ctx.workflow_steps.iter() {
+        if let Err(err) = step.after_create(ctx, outcome).await {
+            warn!(
+                "workflow step '{}' failed after creating {} ({err}).",
+                step.name(),
+                outcome.ticket.key
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::config::{BehaviorSection, StoredConfig};
+
+    #[test]
+    fn builds_enabled_steps_in_order_and_skips_unknown_names() {
+        let stored = StoredConfig {
+            behavior: BehaviorSection {
+                workflow_steps: Some(vec![
+                    "missing-step".to_string(),
+                    " Require-Acceptance-Criteria ".to_string(),
+                ]),
+                ..BehaviorSection::default()
+            },
+            ..StoredConfig::default()
+        };
+        let config = AppConfig::from_stored(&stored, Path::new(".")).unwrap();
+
+        let steps = StepRegistry::builtin().build(&config).unwrap();
+        let names: Vec<_> = steps.iter().map(|step| step.name().to_string()).collect();
+        assert_eq!(names, ["require-acceptance-criteria"]);
+    }
+}
@@ -1,61 +1,290 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{NaiveDate, Utc};
+use serde_json::Value;
+use tracing::{debug, info, warn};
+
 use crate::cache::TicketDraftCache;
+use crate::config::{DraftingMode, TicketPreset};
 use crate::context::AppContext;
-use crate::domain::branch::BranchName;
-use crate::domain::ticket::Ticket;
+use crate::domain::branch::{BranchName, DEFAULT_BRANCH_TEMPLATE};
+use crate::domain::change::ChangeSummary;
+use crate::domain::sprint::Placement;
+use crate::domain::ticket::{
+    DraftGuidance, OVERFLOW_ATTACHMENT_NAME, SplitDraft, Ticket, TicketDraft, TicketNotice,
+    TicketOptions,
+};
 use crate::error::{AppError, AppResult};
+use crate::feedback::FeedbackStore;
+use crate::history::{HistoryEntry, TicketHistory};
+use crate::repo_state::repo_identity;
+use crate::workflow::agents::AgentTrail;
+use crate::workflow::redact::Redactor;
+use crate::workflow::scope::ChangeScope;
+use crate::workflow::steps;
+use crate::workflow::workspace::WorkspaceRepo;
 
 pub struct TicketWorkflowOutcome {
     pub ticket: Ticket,
     pub branch: BranchName,
+    /// Repository the branch was created in.
+    pub repo: PathBuf,
+    /// Worktree the branch was checked out in, for `--worktree` runs.
+    pub worktree: Option<PathBuf>,
+    /// Which agents wrote and changed the draft, for `--explain`.
+    pub agents: AgentTrail,
 }
 
-pub async fn create_ticket_from_changes(
-    ctx: &AppContext,
-    board_override: Option<String>,
-) -> AppResult<TicketWorkflowOutcome> {
-    let board = board_override
-        .or_else(|| ctx.config.default_board.clone())
-        .ok_or_else(|| AppError::Configuration("no board configured".to_string()))?;
+/// Where the new ticket branch is checked out.
+#[derive(Debug, Clone, Default)]
+pub enum CheckoutTarget {
+    #[default]
+    CurrentTree,
+    /// A new worktree; without a path, a sibling of the repository named after the branch.
+    Worktree(Option<PathBuf>),
+}
 
-    let changes = ctx.version_control.summarize_changes().await?;
+#[derive(Debug, Clone)]
+pub struct TicketWorkflowOptions {
+    pub board_override: Option<String>,
+    /// Scrub credentials from the change summary before any language model sees it.
+    pub redact: bool,
+    /// Epic key the new ticket is filed under.
+    pub epic: Option<String>,
+    /// Account IDs for the assignee/reporter overrides.
+    pub assignee: Option<String>,
+    pub reporter: Option<String>,
+    /// Explicit due date; otherwise the category's configured lead time applies.
+    pub due_date: Option<NaiveDate>,
+    /// Limits the change summary (and board selection) to part of a monorepo.
+    pub scope: ChangeScope,
+    /// `--field` overrides for Jira custom fields.
+    pub custom_fields: BTreeMap<String, Value>,
+    /// Settings from `--preset`; empty when none was chosen.
+    pub preset: TicketPreset,
+    /// `--backlog` or a resolved `--sprint`.
+    pub placement: Option<Placement>,
+    pub checkout: CheckoutTarget,
+    /// Announce the new ticket through the configured notifiers.
+    pub notify: bool,
+}
 
-    let cache_key =
-        TicketDraftCache::compute_key(&changes.summary, changes.files_changed, Some(&board));
-    let mut cache = match TicketDraftCache::load() {
-        Ok(cache) => Some(cache),
-        Err(err) => {
-            eprintln!(
-                "Warning: could not load ticket draft cache ({err}). Continuing without cache."
-            );
-            None
+/// Drafts proposed for a mixed changeset, awaiting the user's selection.
+pub struct SplitPlan {
+    pub board: String,
+    pub drafts: Vec<SplitDraft>,
+}
+
+/// A drafted ticket that has not been filed yet, so it can be reviewed first.
+pub struct PreparedTicket {
+    pub board: String,
+    pub changes: ChangeSummary,
+    pub draft: TicketDraft,
+    /// Description headings from the ticket template, kept for regenerating.
+    sections: Vec<String>,
+    cache_hit: bool,
+    agents: AgentTrail,
+}
+
+impl PreparedTicket {
+    /// Replaces the draft with the user's edits, noting them in the agent trail.
+    pub fn edit_draft(&mut self, edited: TicketDraft) {
+        self.agents.record("you", Some(&self.draft), &edited);
+        self.draft = edited;
+    }
+}
+
+/// Collects the changes and drafts a ticket for them, from the cache when possible.
+pub async fn prepare_ticket(
+    ctx: &AppContext,
+    options: &TicketWorkflowOptions,
+) -> AppResult<PreparedTicket> {
+    let board = resolve_board(ctx, options)?;
+    let changes = collect_changes(ctx, &options.scope, options.redact).await?;
+    let sections = template_sections(ctx, options, &board);
+
+    let cache_key = TicketDraftCache::compute_key(
+        &changes.summary,
+        changes.files_changed,
+        Some(&board),
+        &draft_fingerprint(ctx, &sections),
+    );
+    // Template and heuristic output is deterministic, and a cached model draft must never
+    // stand in for it.
+    let mut cache = if ctx.config.drafting == DraftingMode::Template
+        || ctx.config.llm_disabled()
+        || ctx.config.draft_cache_ttl.is_zero()
+    {
+        None
+    } else {
+        match TicketDraftCache::load() {
+            Ok(cache) => Some(cache),
+            Err(err) => {
+                warn!("could not load ticket draft cache ({err}). Continuing without cache.");
+                None
+            }
         }
     };
 
-    let draft = match cache.as_mut().and_then(|c| c.get(&cache_key)) {
-        Some(cached) => cached,
+    let mut feedback = load_feedback();
+
+    let cached = cache
+        .as_mut()
+        .and_then(|c| c.get(&cache_key, ctx.config.draft_cache_ttl));
+    let cache_hit = cached.is_some();
+    let mut agents = AgentTrail::default();
+    let draft = match cached {
+        Some(cached) => {
+            info!(key = %cache_key, "using cached draft");
+            agents.record(&format!("cache ({})", drafter_name(&cached)), None, &cached);
+            cached
+        }
         None => {
-            let generated = ctx.language_model.draft_ticket(&changes).await?;
+            info!(key = %cache_key, "no cached draft; asking the language model");
+            let guidance = draft_guidance(ctx, feedback.as_ref(), &sections);
+            let generated = ctx.language_model.draft_ticket(&changes, &guidance).await?;
+            debug!(agent = drafter_name(&generated), input = %changes.summary, "drafter input");
+            agents.record(drafter_name(&generated), None, &generated);
             if let Some(cache_ref) = cache.as_mut() {
                 cache_ref.insert(cache_key.clone(), &generated);
                 if let Err(err) = cache_ref.save() {
-                    eprintln!("Warning: failed to persist ticket draft cache ({err}).");
+                    warn!("failed to persist ticket draft cache ({err}).");
                 }
             }
             generated
         }
     };
 
-    if draft.description.trim().is_empty() {
-        return Err(AppError::LanguageModel(
-            "language model returned an empty description".to_string(),
+    if let Some(store) = feedback.as_mut() {
+        store.record_draft(&cache_key, &changes.summary, &draft);
+        if let Err(err) = store.save() {
+            warn!("failed to record draft for feedback ({err}).");
+        }
+    }
+
+    Ok(PreparedTicket {
+        board,
+        changes,
+        draft,
+        sections,
+        cache_hit,
+        agents,
+    })
+}
+
+/// Asks the language model for a fresh draft of the same changes, bypassing the cache.
+pub async fn redraft(ctx: &AppContext, prepared: &mut PreparedTicket) -> AppResult<()> {
+    let guidance = draft_guidance(ctx, load_feedback().as_ref(), &prepared.sections);
+    let generated = ctx
+        .language_model
+        .draft_ticket(&prepared.changes, &guidance)
+        .await?;
+    prepared.agents.record(
+        &format!("{} (regenerated)", drafter_name(&generated)),
+        Some(&prepared.draft),
+        &generated,
+    );
+    prepared.draft = generated;
+    prepared.cache_hit = false;
+    Ok(())
+}
+
+/// The provider that wrote `draft`, as named in the agent trail.
+fn drafter_name(draft: &TicketDraft) -> &str {
+    draft.provider.as_deref().unwrap_or("drafter")
+}
+
+/// Files a prepared draft: creates the ticket, its branch, and the history entry.
+pub async fn submit_ticket(
+    ctx: &AppContext,
+    options: &TicketWorkflowOptions,
+    prepared: PreparedTicket,
+) -> AppResult<TicketWorkflowOutcome> {
+    let previous_branch = current_branch_for_undo(ctx).await;
+    file_ticket(
+        ctx,
+        &prepared.board,
+        prepared.draft,
+        options,
+        FilingContext {
+            previous_branch,
+            cache_hit: prepared.cache_hit,
+            checkout: true,
+            agents: prepared.agents,
+        },
+    )
+    .await
+}
+
+/// Asks the language model to cluster the working tree into several ticket drafts.
+pub async fn plan_split(ctx: &AppContext, options: &TicketWorkflowOptions) -> AppResult<SplitPlan> {
+    let board = resolve_board(ctx, options)?;
+    let changes = collect_changes(ctx, &options.scope, options.redact).await?;
+    if changes.files.is_empty() {
+        return Err(AppError::VersionControl(
+            "no uncommitted changes to split".to_string(),
         ));
     }
 
-    let ticket = ctx
-        .issue_tracker
-        .create_ticket(&board, draft.clone())
+    let sections = template_sections(ctx, options, &board);
+    let guidance = draft_guidance(ctx, load_feedback().as_ref(), &sections);
+    let drafts = ctx
+        .language_model
+        .draft_tickets(&changes, &guidance)
+        .await?;
+    Ok(SplitPlan { board, drafts })
+}
+
+/// Creates the selected drafts of a split. The first ticket's branch is checked out; the
+/// rest are created at the current commit so their files can be committed there later.
+pub async fn create_split_tickets(
+    ctx: &AppContext,
+    options: &TicketWorkflowOptions,
+    plan: SplitPlan,
+    selected: &[usize],
+) -> AppResult<Vec<TicketWorkflowOutcome>> {
+    let previous_branch = current_branch_for_undo(ctx).await;
+    let mut outcomes = Vec::new();
+    for (position, split) in plan
+        .drafts
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| selected.contains(index))
+        .map(|(_, split)| split)
+        .enumerate()
+    {
+        let mut agents = AgentTrail::default();
+        agents.record(
+            &format!("{} (split)", drafter_name(&split.draft)),
+            None,
+            &split.draft,
+        );
+        let outcome = file_ticket(
+            ctx,
+            &plan.board,
+            split.draft,
+            options,
+            FilingContext {
+                previous_branch: previous_branch.clone(),
+                cache_hit: false,
+                checkout: position == 0,
+                agents,
+            },
+        )
         .await?;
+        outcomes.push(outcome);
+    }
+    Ok(outcomes)
+}
 
+/// The branch a draft gets under the preset's template. An empty `key` drops the key
+/// segment, for naming a branch before its ticket exists.
+pub fn branch_name_for(
+    options: &TicketWorkflowOptions,
+    draft: &TicketDraft,
+    key: &str,
+) -> AppResult<BranchName> {
     let branch_summary = draft.branch_summary.trim();
     if branch_summary.is_empty() {
         return Err(AppError::LanguageModel(
@@ -63,12 +292,460 @@ pub async fn create_ticket_from_changes(
         ));
     }
 
-    let branch_name = BranchName::from_parts(&draft.branch_category, &ticket.key, branch_summary);
+    let template = options
+        .preset
+        .branch_template
+        .as_deref()
+        .unwrap_or(DEFAULT_BRANCH_TEMPLATE);
+    Ok(BranchName::from_template(
+        template,
+        &draft.branch_category,
+        key,
+        branch_summary,
+    ))
+}
+
+/// `--board`, then the deepest `path_boards` match for the scope, then the default board.
+pub fn resolve_board(ctx: &AppContext, options: &TicketWorkflowOptions) -> AppResult<String> {
+    options
+        .board_override
+        .clone()
+        .or_else(|| options.scope.board(&ctx.config.path_boards))
+        .or_else(|| ctx.config.default_board.clone())
+        .ok_or_else(|| AppError::Configuration("no board configured".to_string()))
+}
+
+pub(crate) async fn collect_changes(
+    ctx: &AppContext,
+    scope: &ChangeScope,
+    redact: bool,
+) -> AppResult<ChangeSummary> {
+    let changes = ctx.change_source.collect(&scope.paths).await?;
+    if redact {
+        Ok(Redactor::new(ctx.config.redact_exclude_files.clone()).redact(&changes))
+    } else {
+        Ok(changes)
+    }
+}
+
+fn load_feedback() -> Option<FeedbackStore> {
+    match FeedbackStore::load() {
+        Ok(store) => Some(store),
+        Err(err) => {
+            warn!("could not load draft feedback ({err}). Continuing without it.");
+            None
+        }
+    }
+}
+
+/// Feedback-derived guidance plus the team's estimate scale, when estimates have a field to
+/// go in, the ticket language, and the template's sections.
+fn draft_guidance(
+    ctx: &AppContext,
+    feedback: Option<&FeedbackStore>,
+    sections: &[String],
+) -> DraftGuidance {
+    let mut guidance = feedback.map(FeedbackStore::guidance).unwrap_or_default();
+    guidance.language = ctx.config.ticket_language.clone();
+    guidance.sections = sections.to_vec();
+    if ctx.config.jira_fields.story_points.is_some() {
+        guidance.estimate_scale = ctx.config.estimate_scale.clone();
+    }
+    guidance
+}
+
+/// Identifies the drafter and everything that shapes its output besides the changes, so a
+/// cached draft is not served once the team's language or template changes.
+fn draft_fingerprint(ctx: &AppContext, sections: &[String]) -> String {
+    let mut fingerprint = ctx.language_model.fingerprint();
+    if let Some(language) = &ctx.config.ticket_language {
+        fingerprint.push_str(&format!(":{language}"));
+    }
+    if !sections.is_empty() {
+        fingerprint.push_str(&format!(":{}", sections.join("|")));
+    }
+    fingerprint
+}
+
+/// The issue type the ticket will be filed as.
+fn issue_type<'a>(ctx: &'a AppContext, options: &'a TicketWorkflowOptions) -> &'a str {
+    options
+        .preset
+        .issue_type
+        .as_deref()
+        .unwrap_or(ctx.config.default_issue_type())
+}
+
+fn template_sections(
+    ctx: &AppContext,
+    options: &TicketWorkflowOptions,
+    board: &str,
+) -> Vec<String> {
+    ctx.config
+        .ticket_template(board, issue_type(ctx, options))
+        .map(|template| template.sections.clone())
+        .unwrap_or_default()
+}
+
+async fn current_branch_for_undo(ctx: &AppContext) -> Option<String> {
+    match ctx.version_control.current_branch().await {
+        Ok(branch) => branch,
+        Err(err) => {
+            warn!(
+                "could not determine the current branch ({err}); `ugh undo` will not switch back."
+            );
+            None
+        }
+    }
+}
+
+/// Creates the ticket's branch as `options` asks. The ticket exists by now, so when git
+/// rejects a name that breaks its ref-format rules (say, a template or key with `:` or `..`),
+/// the branch is retried once under a sanitized name instead of failing the run.
+async fn create_ticket_branch(
+    ctx: &AppContext,
+    options: &TicketWorkflowOptions,
+    repo_root: &Path,
+    checkout: bool,
+    branch_name: BranchName,
+) -> AppResult<(BranchName, Option<PathBuf>)> {
+    let err = match place_branch(ctx, options, repo_root, checkout, &branch_name).await {
+        Ok(worktree) => return Ok((branch_name, worktree)),
+        Err(err) => err,
+    };
+    let Some((sanitized, fixes)) = branch_name.sanitized() else {
+        return Err(err);
+    };
+    warn!(
+        "git rejected branch name '{}' ({err}); retrying as '{}' ({}).",
+        branch_name.as_str(),
+        sanitized.as_str(),
+        fixes.join(", ")
+    );
+    let worktree = place_branch(ctx, options, repo_root, checkout, &sanitized).await?;
+    Ok((sanitized, worktree))
+}
+
+/// Creates `branch_name` and checks it out here, in a new worktree, or not at all.
+async fn place_branch(
+    ctx: &AppContext,
+    options: &TicketWorkflowOptions,
+    repo_root: &Path,
+    checkout: bool,
+    branch_name: &BranchName,
+) -> AppResult<Option<PathBuf>> {
+    let base = options.preset.base_branch.as_deref();
+    match (&options.checkout, checkout) {
+        (CheckoutTarget::Worktree(requested), true) => {
+            let path = requested
+                .clone()
+                .unwrap_or_else(|| default_worktree_path(repo_root, branch_name));
+            ctx.version_control
+                .create_worktree(branch_name, base, &path)
+                .await?;
+            Ok(Some(path))
+        }
+        (CheckoutTarget::CurrentTree, true) => {
+            ctx.version_control
+                .checkout_branch(branch_name, base)
+                .await?;
+            Ok(None)
+        }
+        (_, false) => {
+            ctx.version_control.create_branch(branch_name, base).await?;
+            Ok(None)
+        }
+    }
+}
+
+/// `<repo>-<last branch segment>` next to the repository, e.g. `../ugh-add-retries`.
+fn default_worktree_path(repo_root: &Path, branch: &BranchName) -> PathBuf {
+    let repo_name = repo_root
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "worktree".to_string());
+    let leaf = branch
+        .as_str()
+        .rsplit('/')
+        .next()
+        .unwrap_or(branch.as_str());
+    let directory = format!("{repo_name}-{leaf}");
+    match repo_root.parent() {
+        Some(parent) => parent.join(directory),
+        None => repo_root.join(directory),
+    }
+}
 
-    ctx.version_control.checkout_branch(&branch_name).await?;
+struct FilingContext {
+    previous_branch: Option<String>,
+    cache_hit: bool,
+    /// Check the new branch out, rather than only creating it.
+    checkout: bool,
+    agents: AgentTrail,
+}
 
-    Ok(TicketWorkflowOutcome {
+/// Posts the new ticket to every configured channel; a failed post is only reported.
+async fn notify(ctx: &AppContext, draft: &TicketDraft, outcome: &TicketWorkflowOutcome) {
+    let notice = TicketNotice {
+        key: outcome.ticket.key.clone(),
+        url: outcome.ticket.url.clone(),
+        title: draft.title.clone(),
+        branch: outcome.branch.as_str().to_string(),
+    };
+    for notifier in ctx.notifiers.iter() {
+        if let Err(err) = notifier.notify(&notice).await {
+            warn!(
+                "could not notify {} about {} ({err}).",
+                notifier.name(),
+                notice.key
+            );
+        }
+    }
+}
+
+/// Creates the ticket for `draft`, its branch, and the history ledger entry.
+async fn file_ticket(
+    ctx: &AppContext,
+    board: &str,
+    draft: TicketDraft,
+    options: &TicketWorkflowOptions,
+    mut filing: FilingContext,
+) -> AppResult<TicketWorkflowOutcome> {
+    // Outside a repository there is nowhere to put the branch; stop before filing.
+    ctx.repo_root()?;
+    let (ticket, draft) = create_ticket(ctx, board, draft, options, &mut filing.agents).await?;
+    let branch_name = branch_name_for(options, &draft, &ticket.key)?;
+    record_pending(ctx, board, &draft, &ticket, &branch_name, &filing);
+    steps::after_ticket(ctx, board, &draft, &ticket).await?;
+    let outcome = branch_ticket(ctx, options, ticket, branch_name, filing).await?;
+    if options.notify {
+        notify(ctx, &draft, &outcome).await;
+    }
+    Ok(outcome)
+}
+
+/// Files one ticket for a workspace draft and creates its branch, under the same name, in
+/// every repository the changes came from. The ticket exists once the first branch is
+/// attempted, so a repository whose branch fails is reported and the others still get
+/// theirs.
+pub async fn submit_workspace_ticket(
+    ctx: &AppContext,
+    options: &TicketWorkflowOptions,
+    prepared: PreparedTicket,
+    repos: &[WorkspaceRepo],
+) -> AppResult<Vec<TicketWorkflowOutcome>> {
+    let mut agents = prepared.agents;
+    let (ticket, draft) =
+        create_ticket(ctx, &prepared.board, prepared.draft, options, &mut agents).await?;
+    let branch_name = branch_name_for(options, &draft, &ticket.key)?;
+    let mut filings = Vec::new();
+    for repo in repos {
+        let filing = FilingContext {
+            previous_branch: current_branch_for_undo(&repo.ctx).await,
+            cache_hit: prepared.cache_hit,
+            checkout: true,
+            agents: agents.clone(),
+        };
+        record_pending(
+            &repo.ctx,
+            &prepared.board,
+            &draft,
+            &ticket,
+            &branch_name,
+            &filing,
+        );
+        filings.push(filing);
+    }
+    steps::after_ticket(ctx, &prepared.board, &draft, &ticket).await?;
+
+    let mut outcomes = Vec::new();
+    let mut failure = None;
+    for (repo, filing) in repos.iter().zip(filings) {
+        match branch_ticket(
+            &repo.ctx,
+            options,
+            ticket.clone(),
+            branch_name.clone(),
+            filing,
+        )
+        .await
+        {
+            Ok(outcome) => outcomes.push(outcome),
+            Err(err) => {
+                warn!(
+                    "could not create branch {} in {} ({err}).",
+                    branch_name.as_str(),
+                    repo.name
+                );
+                failure = Some(err);
+            }
+        }
+    }
+    if let (None, Some(err)) = (outcomes.first(), failure) {
+        return Err(err);
+    }
+    if options.notify
+        && let Some(outcome) = outcomes.first()
+    {
+        notify(ctx, &draft, outcome).await;
+    }
+    Ok(outcomes)
+}
+
+/// Creates the ticket for `draft` with the options the run asked for. Returns the ticket
+/// with the draft as filed; the caller records it and runs the `after_ticket` steps.
+async fn create_ticket(
+    ctx: &AppContext,
+    board: &str,
+    draft: TicketDraft,
+    options: &TicketWorkflowOptions,
+    agents: &mut AgentTrail,
+) -> AppResult<(Ticket, TicketDraft)> {
+    let ticket_options = TicketOptions {
+        epic: options.epic.clone(),
+        assignee: options.assignee.clone(),
+        reporter: options.reporter.clone(),
+        due_date: options.due_date,
+        custom_fields: options.custom_fields.clone(),
+        issue_type: options.preset.issue_type.clone(),
+        labels: options.preset.labels.clone(),
+        components: Vec::new(),
+        priority: options.preset.priority.clone(),
+        placement: options.placement,
+    };
+    file_draft(ctx, board, draft, ticket_options, agents).await
+}
+
+/// Runs the review steps over `draft`, fits it to the tracker's limits, and creates it on
+/// `board`. The matching ticket template adds its labels and components to `options`, the
+/// category's default due date applies when none is set, and a description cut to fit is
+/// attached in full. Returns the ticket with the draft as filed.
+pub async fn file_draft(
+    ctx: &AppContext,
+    board: &str,
+    mut draft: TicketDraft,
+    mut options: TicketOptions,
+    agents: &mut AgentTrail,
+) -> AppResult<(Ticket, TicketDraft)> {
+    steps::review_draft(ctx, board, &mut draft, agents).await?;
+
+    if draft.description.trim().is_empty() {
+        return Err(AppError::WorkflowStep(format!(
+            "'{}' has an empty description",
+            draft.title
+        )));
+    }
+    let before_limits = draft.clone();
+    let overflow = draft.fit_to(&ctx.issue_tracker.text_limits());
+    agents.record("limits", Some(&before_limits), &draft);
+    if overflow.is_some() {
+        info!("description exceeds the tracker's limit; attaching the full text");
+    }
+
+    if options.due_date.is_none() {
+        options.due_date = ctx.config.default_due_date(draft.branch_category.as_str());
+    }
+    let issue_type = options
+        .issue_type
+        .as_deref()
+        .unwrap_or(ctx.config.default_issue_type());
+    if let Some(template) = ctx.config.ticket_template(board, issue_type).cloned() {
+        for label in template.labels {
+            if !options.labels.contains(&label) {
+                options.labels.push(label);
+            }
+        }
+        for component in template.components {
+            if !options.components.contains(&component) {
+                options.components.push(component);
+            }
+        }
+    }
+
+    let ticket = ctx
+        .issue_tracker
+        .create_ticket(board, draft.clone(), &options)
+        .await?;
+    if let Some(full) = overflow {
+        let contents = format!("# {}\n\n{full}\n", draft.title);
+        if let Err(err) = ctx
+            .issue_tracker
+            .attach_file(&ticket.key, OVERFLOW_ATTACHMENT_NAME, contents.into_bytes())
+            .await
+        {
+            warn!(
+                "{} was filed with a shortened description, but attaching the full text \
+                 failed ({err}).",
+                ticket.key
+            );
+        }
+    }
+    Ok((ticket, draft))
+}
+
+/// Records the new ticket in the history ledger of `ctx`'s repository before its branch
+/// exists, so `ugh undo` finds the ticket even when the branch is never made.
+fn record_pending(
+    ctx: &AppContext,
+    board: &str,
+    draft: &TicketDraft,
+    ticket: &Ticket,
+    branch_name: &BranchName,
+    filing: &FilingContext,
+) {
+    let recorded = ctx.repo_root().and_then(|repo_root| {
+        TicketHistory::open()?.append(&HistoryEntry {
+            key: ticket.key.clone(),
+            url: ticket.url.clone(),
+            branch: branch_name.as_str().to_string(),
+            branch_pending: true,
+            repo: repo_identity(repo_root),
+            board: board.to_string(),
+            created_at: Utc::now(),
+            provider: draft.provider.clone(),
+            cache_hit: filing.cache_hit,
+            rationale: draft.rationale.clone(),
+            previous_branch: filing.previous_branch.clone(),
+            worktree: None,
+            undone_at: None,
+        })
+    });
+    if let Err(err) = recorded {
+        warn!("failed to record ticket history ({err}).");
+    }
+}
+
+/// Creates the ticket's branch in `ctx`'s repository and fills it in on the history entry.
+async fn branch_ticket(
+    ctx: &AppContext,
+    options: &TicketWorkflowOptions,
+    ticket: Ticket,
+    branch_name: BranchName,
+    filing: FilingContext,
+) -> AppResult<TicketWorkflowOutcome> {
+    let repo_root = ctx.repo_root()?.to_path_buf();
+    let (branch_name, worktree) =
+        create_ticket_branch(ctx, options, &repo_root, filing.checkout, branch_name).await?;
+
+    let recorded = TicketHistory::open().and_then(|history| {
+        history.record_branch(
+            &ticket.key,
+            &repo_identity(&repo_root),
+            branch_name.as_str(),
+            worktree.clone(),
+        )
+    });
+    if let Err(err) = recorded {
+        warn!("failed to record ticket history ({err}).");
+    }
+
+    let outcome = TicketWorkflowOutcome {
         ticket,
         branch: branch_name,
-    })
+        repo: repo_root,
+        worktree,
+        agents: filing.agents,
+    };
+    steps::after_create(ctx, &outcome).await;
+    Ok(outcome)
 }
@@ -0,0 +1,108 @@
+use tracing::warn;
+
+use crate::context::AppContext;
+use crate::domain::ticket::TicketRemoval;
+use crate::error::{AppError, AppResult};
+use crate::history::{HistoryEntry, TicketHistory};
+use crate::repo_state::repo_identity;
+
+pub struct UndoOutcome {
+    pub removal: TicketRemoval,
+    /// Branch checked out after the rollback, when a switch happened.
+    pub switched_to: Option<String>,
+    pub branch_deleted: bool,
+}
+
+/// Most recent ticket created from the current repository that has not been undone.
+pub fn last_undoable_run(ctx: &AppContext) -> AppResult<Option<HistoryEntry>> {
+    let repo = repo_identity(ctx.repo_root()?);
+    Ok(TicketHistory::open()?
+        .entries()?
+        .into_iter()
+        .rev()
+        .find(|entry| entry.repo == repo && entry.undone_at.is_none()))
+}
+
+/// Removes the ticket, returns to the branch the run started from, and deletes the
+/// ticket branch. Once the ticket is gone the run counts as undone; a branch that cannot
+/// be left or has commits of its own is kept with a warning.
+pub async fn undo_run(ctx: &AppContext, entry: &HistoryEntry) -> AppResult<UndoOutcome> {
+    let removal = ctx.issue_tracker.delete_ticket(&entry.key).await?;
+    TicketHistory::open()?.mark_undone(&entry.key)?;
+
+    // The branch was never made, so there is nothing else to roll back.
+    if entry.branch_pending {
+        return Ok(UndoOutcome {
+            removal,
+            switched_to: None,
+            branch_deleted: false,
+        });
+    }
+
+    // A worktree run never touched this tree; drop the worktree so the branch is free.
+    if let Some(worktree) = &entry.worktree {
+        let removed = ctx.version_control.remove_worktree(worktree).await;
+        if let Err(err) = &removed {
+            warn!(
+                "kept worktree {} ({err}); remove it with `git worktree remove`.",
+                worktree.display()
+            );
+        }
+        let branch_deleted = removed.is_ok() && delete_ticket_branch(ctx, entry).await;
+        return Ok(UndoOutcome {
+            removal,
+            switched_to: None,
+            branch_deleted,
+        });
+    }
+
+    let (switched_to, branch_deleted) = match leave_ticket_branch(ctx, entry).await {
+        Ok(switched_to) => (switched_to, delete_ticket_branch(ctx, entry).await),
+        Err(err) => {
+            warn!(
+                "kept branch {} ({err}). Switch away from it and delete it manually.",
+                entry.branch
+            );
+            (None, false)
+        }
+    };
+
+    Ok(UndoOutcome {
+        removal,
+        switched_to,
+        branch_deleted,
+    })
+}
+
+/// Checks out the branch the run started from when the ticket branch is the current one,
+/// returning it.
+async fn leave_ticket_branch(ctx: &AppContext, entry: &HistoryEntry) -> AppResult<Option<String>> {
+    let current = ctx.version_control.current_branch().await?;
+    if current.as_deref() != Some(entry.branch.as_str()) {
+        return Ok(None);
+    }
+    let Some(previous) = &entry.previous_branch else {
+        return Err(AppError::VersionControl(
+            "the branch the run started from is unknown".to_string(),
+        ));
+    };
+    ctx.version_control.switch_branch(previous).await?;
+    Ok(Some(previous.clone()))
+}
+
+async fn delete_ticket_branch(ctx: &AppContext, entry: &HistoryEntry) -> bool {
+    match ctx
+        .version_control
+        .delete_branch(&entry.branch, false)
+        .await
+    {
+        Ok(()) => true,
+        Err(err) => {
+            warn!(
+                "kept branch {} ({err}). Delete it with `git branch -D {}` if it is no longer needed.",
+                entry.branch, entry.branch
+            );
+            false
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use tracing::info;
+
+use crate::context::AppContext;
+use crate::domain::change::ChangeSummary;
+use crate::error::{AppError, AppResult};
+use crate::infra::change_source::Summarized;
+use crate::workflow::ticket::{PreparedTicket, TicketWorkflowOptions, prepare_ticket};
+
+/// A repository of the workspace with uncommitted changes.
+pub struct WorkspaceRepo {
+    /// Directory name, which labels the repository's files for the drafter.
+    pub name: String,
+    /// Context bound to the repository, for creating its branch.
+    pub ctx: AppContext,
+    pub changes: ChangeSummary,
+}
+
+/// The `workspace_repos` that have uncommitted changes, in configured order except that
+/// the repository ugh runs in comes first.
+pub async fn changed_repositories(ctx: &AppContext) -> AppResult<Vec<WorkspaceRepo>> {
+    if ctx.config.workspace_repos.is_empty() {
+        return Err(AppError::Configuration(
+            "--workspace needs workspace_repos in the config (or UGH_WORKSPACE_REPOS)".to_string(),
+        ));
+    }
+
+    let mut roots: Vec<PathBuf> = Vec::new();
+    let mut repos = Vec::new();
+    for dir in &ctx.config.workspace_repos {
+        let repo_ctx = ctx.for_repository(dir)?;
+        let root = repo_ctx.repo_root()?.to_path_buf();
+        if roots.contains(&root) {
+            continue;
+        }
+        roots.push(root.clone());
+
+        let changes = repo_ctx.version_control.summarize_changes(&[]).await?;
+        if changes.files.is_empty() {
+            info!(repo = %root.display(), "no uncommitted changes; leaving it out");
+            continue;
+        }
+        repos.push(WorkspaceRepo {
+            name: repo_name(&root),
+            ctx: repo_ctx,
+            changes,
+        });
+    }
+
+    if let Ok(current) = ctx.repo_root()
+        && let Some(position) = repos
+            .iter()
+            .position(|repo| repo.ctx.repo_root().ok() == Some(current))
+    {
+        let repo = repos.remove(position);
+        repos.insert(0, repo);
+    }
+    if repos.is_empty() {
+        return Err(AppError::VersionControl(
+            "no uncommitted changes in any workspace repository".to_string(),
+        ));
+    }
+    Ok(repos)
+}
+
+/// Drafts one ticket covering the changes of every repository in `repos`.
+pub async fn prepare_workspace_ticket(
+    ctx: &AppContext,
+    options: &TicketWorkflowOptions,
+    repos: &[WorkspaceRepo],
+) -> AppResult<PreparedTicket> {
+    let parts: Vec<(&str, &ChangeSummary)> = repos
+        .iter()
+        .map(|repo| (repo.name.as_str(), &repo.changes))
+        .collect();
+    let names: Vec<&str> = parts.iter().map(|(name, _)| *name).collect();
+    let source = Summarized::new(
+        format!("workspace ({})", names.join(", ")),
+        ChangeSummary::combine(&parts),
+    );
+    let ctx = ctx.clone().with_change_source(Arc::new(source));
+    prepare_ticket(&ctx, options).await
+}
+
+fn repo_name(root: &Path) -> String {
+    root.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| root.display().to_string())
+}